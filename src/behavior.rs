@@ -6,7 +6,9 @@ pub trait Behavior<Item, Err>: Observer<Item, Err> {
   /// Example:
   /// ```
   /// use rxrust::prelude::*;
-  /// let mut behavior = BehaviorSubject::<i32, Subject::<_, _>>::new(0);
+  /// use std::convert::Infallible;
+  ///
+  /// let mut behavior = BehaviorSubject::<i32, Infallible>::new(0);
   /// behavior.clone()
   ///     .subscribe(|value| println!("{value}"));
   /// behavior.next(7);
@@ -25,7 +27,9 @@ pub trait Behavior<Item, Err>: Observer<Item, Err> {
   /// Example:
   /// ```
   /// use rxrust::prelude::*;
-  /// let mut behavior = BehaviorSubject::<i32, Subject::<_, _>>::new(0);
+  /// use std::convert::Infallible;
+  ///
+  /// let mut behavior = BehaviorSubject::<i32, Infallible>::new(0);
   /// behavior.clone()
   ///     .subscribe(|value| println!("{value}"));
   /// for i in 0..3 {
@@ -36,6 +40,7 @@ pub trait Behavior<Item, Err>: Observer<Item, Err> {
   /// // 0
   /// // 1
   /// // 2
+  /// // 3
   ///
   /// ```
   fn next_by(&mut self, f: impl FnOnce(Item) -> Item) {
@@ -1,11 +1,18 @@
 #![macro_use]
 
 mod trivial;
+use std::any::Any;
 use std::hash::*;
 use std::sync::Arc;
 pub use trivial::*;
 mod from_iter;
-pub use from_iter::{from_iter, repeat};
+pub use from_iter::{from_iter, repeat, repeat_value, repeat_value_forever};
+
+pub mod range;
+pub use range::{range, range_step};
+
+pub mod unfold;
+pub use unfold::unfold;
 
 pub mod of;
 pub use of::{of, of_fn, of_option, of_result};
@@ -18,65 +25,128 @@ pub(crate) mod from_stream_result;
 pub use from_stream::from_stream;
 pub use from_stream_result::from_stream_result;
 
+pub mod from_channel;
+pub use from_channel::from_channel;
+
+#[cfg(all(feature = "tokio-scheduler", not(target_arch = "wasm32")))]
+pub mod from_tokio_mpsc;
+#[cfg(all(feature = "tokio-scheduler", not(target_arch = "wasm32")))]
+pub use from_tokio_mpsc::from_tokio_mpsc;
+
 pub mod interval;
 pub use interval::{interval, interval_at};
 
 pub(crate) mod connectable_observable;
-pub use connectable_observable::ConnectableObservable;
+pub use connectable_observable::{
+  ConnectableObservable, ConnectionSubscription,
+};
 
 pub mod from_fn;
 pub use from_fn::*;
 
 pub mod timer;
-pub use timer::{timer, timer_at};
+pub use timer::{timer, timer_at, timer_at_interval};
 
 pub mod start;
 pub use start::start;
 
+pub mod amb;
+pub use amb::{amb, amb_threads};
+
+pub mod merge_all_iter;
+pub use merge_all_iter::{merge_all_iter, merge_all_iter_threads};
+
+pub mod concat_iter;
+pub use concat_iter::{concat_iter, concat_iter_threads};
+
+pub mod zip_all;
+pub use zip_all::{zip_iter, zip_iter_threads, zip_tuple, zip_tuple_threads};
+
+pub mod combine_latest_all;
+pub use combine_latest_all::{
+  combine_latest_iter, combine_latest_iter_threads, combine_latest_tuple,
+  combine_latest_tuple_threads,
+};
+
 mod subscribe_item;
 pub use subscribe_item::*;
 mod defer;
 pub use defer::*;
+pub mod using;
+pub use using::{using, using_threads};
+pub mod iif;
+pub use iif::{iif, iif_option};
 
 use crate::ops::collect::CollectOp;
 use crate::ops::combine_latest::CombineLatestOpThread;
 use crate::ops::complete_status::{CompleteStatus, StatusOp};
 use crate::ops::delay::{DelayOpThreads, DelaySubscriptionOp};
+use crate::ops::expand::{ExpandOp, ExpandOpThreads};
 use crate::ops::finalize::FinalizeOpThreads;
+use crate::ops::find::{FindIndexOp, FindOptOp};
 use crate::ops::future::{ObservableFuture, ObservableFutureObserver};
+use crate::ops::materialize::{DematerializeOp, MaterializeOp};
 use crate::ops::merge::MergeOpThreads;
 use crate::ops::merge_all::MergeAllOpThreads;
 use crate::ops::observe_on::ObserveOnOpThreads;
 use crate::ops::on_complete::OnCompleteOp;
 use crate::ops::on_error::OnErrorOp;
-use crate::ops::ref_count::{ShareOp, ShareOpThreads};
+use crate::ops::ref_count::{
+  ShareOp, ShareOpThreads, ShareReplayOp, ShareReplayOpThreads,
+};
 use crate::ops::sample::SampleOpThreads;
 use crate::ops::skip_until::SkipUntilOpThreads;
 use crate::ops::stream::{ObservableStream, ObservableStreamObserver};
 use crate::ops::take_until::TakeUntilOpThreads;
 use crate::ops::with_latest_from::WithLatestFromOpThreads;
-use crate::ops::zip::ZipOpThreads;
+use crate::ops::zip::{ZipOpThreads, ZipPairOp, ZipPairOpThreads};
 use crate::ops::FlatMapOpThreads;
 use crate::prelude::*;
 pub use ops::box_it::BoxIt;
 
-use crate::ops::default_if_empty::DefaultIfEmptyOp;
-use crate::ops::distinct::{DistinctKeyOp, DistinctUntilKeyChangedOp};
+use crate::ops::default_if_empty::{
+  DefaultIfEmptyOp, SwitchIfEmptyOp, SwitchIfEmptyOpThreads,
+};
+use crate::ops::distinct::{
+  DistinctKeyOp, DistinctUntilKeyChangedOp, PartialEqKey,
+};
+use crate::ops::min_max_by_key::MinMaxByKeyOp;
 use crate::ops::on_error_map::OnErrorMapOp;
+use crate::ops::on_error_resume_next::{
+  OnErrorResumeNextOp, OnErrorResumeNextOpThreads,
+};
 use crate::ops::pairwise::PairwiseOp;
-use crate::ops::tap::TapOp;
+use crate::ops::partition::PartitionOp;
+use crate::ops::race::{RaceOp, RaceOpThreads};
+use crate::ops::repeat::{RepeatOp, RepeatOpThreads};
+use crate::ops::repeat_when::{RepeatWhenOp, RepeatWhenOpThreads};
+use crate::ops::retry::{RetryOp, RetryOpThreads};
+use crate::ops::retry_when::{RetryWhenOp, RetryWhenOpThreads};
+use crate::ops::sequence_equal::{SequenceEqualOp, SequenceEqualOpThreads};
+use crate::ops::single::SingleOp;
+use crate::ops::tap::{Tap, TapComplete, TapError, TapNext, TapOp};
+use crate::ops::time_interval::TimeIntervalOp;
+use crate::ops::timeout::{ThrowTimeout, TimeoutOp};
+use crate::ops::timestamp::TimestampOp;
 use ops::{
-  buffer::{BufferWithCountOp, BufferWithCountOrTimerOp, BufferWithTimeOp},
+  audit::AuditOp,
+  buffer::{
+    BufferWhenOp, BufferWhenOpThreads, BufferWithCountOp,
+    BufferWithCountOrTimerOp, BufferWithTimeOp,
+  },
+  catch_unwind::CatchUnwindOp,
   combine_latest::CombineLatestOp,
-  contains::ContainsOp,
-  debounce::DebounceOp,
+  contains::{AnyItem, ContainsOp, Equals, Fails, MatchBy},
+  debounce::{DebounceOp, DebounceWhenOp, DebounceWhenOpThreads},
   delay::DelayOp,
-  distinct::DistinctOp,
   distinct::DistinctUntilChangedOp,
+  distinct::{DistinctOp, DistinctWithinDurationOp, DistinctWithinOp},
+  exhaust_map::{ExhaustMapOp, ExhaustMapOpThreads},
   filter::FilterOp,
   filter_map::FilterMapOp,
   finalize::FinalizeOp,
-  group_by::GroupByOp,
+  flat_map_iter::FlatMapIterOp,
+  group_by::{GroupByOp, GroupByUntilOp},
   last::LastOp,
   map::MapOp,
   map_to::MapToOp,
@@ -91,14 +161,17 @@ use ops::{
   skip_while::SkipWhileOp,
   start_with::StartWithOp,
   subscribe_on::SubscribeOnOP,
+  switch_on_next::{SwitchOnNextOp, SwitchOnNextOpThreads},
   take::TakeOp,
   take_last::TakeLastOp,
   take_until::TakeUntilOp,
   take_while::TakeWhileOp,
   throttle::{ThrottleEdge, ThrottleOp},
+  window::{WindowWithCountOp, WindowWithTimeOp},
   with_latest_from::WithLatestFromOp,
   zip::ZipOp,
-  Accum, AverageOp, CountOp, FlatMapOp, MinMaxOp, ReduceOp, SumOp,
+  Accum, AverageOp, CountByOp, CountOp, FlatMapOp, MinMaxOp, ReduceOp, SumOp,
+  SwitchMapOp, SwitchMapOpThreads,
 };
 use std::{
   ops::{Add, Mul},
@@ -107,11 +180,6 @@ use std::{
 #[cfg(test)]
 pub mod fake_timer;
 
-type ALLOp<S, F, Item> = DefaultIfEmptyOp<
-  TakeOp<FilterOp<MapOp<S, F, Item>, fn(&bool) -> bool>>,
-  bool,
->;
-
 pub trait Observable<Item, Err, O>
 where
   O: Observer<Item, Err>,
@@ -128,11 +196,86 @@ pub trait ObservableExt<Item, Err>: Sized {
     self.take(1)
   }
 
-  /// emit only the first item emitted by an Observable
+  /// emit only the first item emitted by an Observable, or `default` if it
+  /// completes without emitting.
   fn first_or(self, default: Item) -> DefaultIfEmptyOp<TakeOp<Self>, Item> {
     DefaultIfEmptyOp::new(self.first(), default)
   }
 
+  /// Emit only the first item that satisfies `predicate`, then completes and
+  /// stops observing the source.
+  ///
+  /// Completes without emitting if the source completes before any item
+  /// satisfies it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(1..)
+  ///   .first_with(|v| v % 7 == 0)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 7
+  /// ```
+  #[inline]
+  fn first_with<F>(self, predicate: F) -> TakeOp<FilterOp<Self, F>>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    self.filter(predicate).first()
+  }
+
+  /// Same as `first_with`, emits the first item that satisfies `predicate`
+  /// and stops observing the source.
+  #[inline]
+  fn find<F>(self, predicate: F) -> TakeOp<FilterOp<Self, F>>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    self.first_with(predicate)
+  }
+
+  /// Same as `find`, but emits `Some` of the found item, or `None` if the
+  /// source completes before any item satisfies `predicate`.
+  #[inline]
+  fn find_opt<F>(self, predicate: F) -> FindOptOp<Self, F, Item>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    self
+      .find(predicate)
+      .map(Some as fn(Item) -> Option<Item>)
+      .default_if_empty(None)
+  }
+
+  /// Emits the zero-based index of the first item that satisfies
+  /// `predicate`, then completes and stops observing the source.
+  ///
+  /// Completes without emitting if no item satisfies it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(["a", "b", "c"])
+  ///   .find_index(|v| *v == "b")
+  ///   .subscribe(|i| println!("{}", i));
+  ///
+  /// // print log:
+  /// // 1
+  /// ```
+  #[inline]
+  fn find_index<F>(self, predicate: F) -> FindIndexOp<Self, F, Item>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    FindIndexOp::new(self, predicate)
+  }
+
   /// Emit only the last final item emitted by a source observable or a
   /// default item given.
   ///
@@ -160,13 +303,56 @@ pub trait ObservableExt<Item, Err>: Sized {
   }
 
   /// Emit only item n (0-indexed) emitted by an Observable
+  ///
+  /// Completes right after emitting the item and stops observing the source.
+  /// Completes without emitting if the source completes before reaching the
+  /// item n.
   #[inline]
   fn element_at(self, nth: usize) -> TakeOp<SkipOp<Self>> {
     TakeOp::new(self.skip(nth), 1)
   }
 
+  /// Emit only item n (0-indexed) emitted by an Observable, or a default item
+  /// if the source completes before reaching the item n.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .element_at_or(5, -1)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // -1
+  /// ```
+  #[inline]
+  fn element_at_or(
+    self,
+    nth: usize,
+    default: Item,
+  ) -> DefaultIfEmptyOp<TakeOp<SkipOp<Self>>, Item> {
+    DefaultIfEmptyOp::new(self.element_at(nth), default)
+  }
+
   /// Do not emit any items from an Observable but mirror its termination
   /// notification
+  ///
+  /// Items are dropped as they arrive, so they don't need to be `Clone`. This
+  /// is useful to wait for a side-effecting stream to finish.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .tap(|v| println!("side effect {}", v))
+  ///   .ignore_elements()
+  ///   .on_complete(|| println!("done"))
+  ///   .subscribe(|_| unreachable!());
+  /// ```
   #[inline]
   fn ignore_elements(self) -> FilterOp<Self, fn(&Item) -> bool> {
     fn always_false<Item>(_: &Item) -> bool {
@@ -176,26 +362,62 @@ pub trait ObservableExt<Item, Err>: Sized {
   }
 
   /// Determine whether all items emitted by an Observable meet some criteria
+  ///
+  /// Emits `false` and completes as soon as an item fails `pred`, the source
+  /// is stopped at that moment. Otherwise emits `true` when the source
+  /// completes, also for an empty source.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .all(|v| v < 5)
+  ///   .subscribe(|b| println!("{}", b));
+  ///
+  /// // print log:
+  /// // false
+  /// ```
   #[inline]
-  fn all<F>(self, pred: F) -> ALLOp<Self, F, Item>
+  fn all<F>(self, pred: F) -> ContainsOp<Self, Fails<F>, Item>
   where
     F: Fn(Item) -> bool,
   {
-    fn not(b: &bool) -> bool {
-      !b
-    }
-    let map: MapOp<Self, F, Item> = MapOp::new(self, pred);
-    let filter_map = FilterOp {
-      source: map,
-      filter: not as fn(&bool) -> bool,
-    };
-    let take = TakeOp::new(filter_map, 1);
-    DefaultIfEmptyOp::new(take, true)
+    ContainsOp::new(self, Fails(pred), false)
   }
 
   /// Determine whether an Observable emits a particular item or not
-  fn contains(self, target: Item) -> ContainsOp<Self, Item> {
-    ContainsOp { source: self, target }
+  ///
+  /// Emits `true` and completes as soon as an item equals `target`, the source
+  /// is stopped at that moment. Otherwise emits `false` when the source
+  /// completes.
+  #[inline]
+  fn contains(self, target: Item) -> ContainsOp<Self, Equals<Item>, Item>
+  where
+    Item: PartialEq,
+  {
+    ContainsOp::new(self, Equals(target), true)
+  }
+
+  /// Same as `contains`, but looks for an item satisfying `predicate`, so the
+  /// items don't need to be `PartialEq`.
+  #[inline]
+  fn contains_by<F>(self, predicate: F) -> ContainsOp<Self, MatchBy<F>, Item>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    ContainsOp::new(self, MatchBy(predicate), true)
+  }
+
+  /// Emits `false` and completes at the first item, stopping the source, or
+  /// `true` if the source completes without emitting.
+  ///
+  /// Not named `is_empty`, which would shadow the `is_empty` of subjects
+  /// telling whether they have observers.
+  #[inline]
+  fn emits_nothing(self) -> ContainsOp<Self, AnyItem, Item> {
+    ContainsOp::new(self, AnyItem, false)
   }
 
   /// Emits only last final item emitted by a source observable.
@@ -221,19 +443,108 @@ pub trait ObservableExt<Item, Err>: Sized {
     LastOp { source: self, last: None }
   }
 
+  /// Emits only the last item that satisfies `predicate` when the source
+  /// completes.
+  ///
+  /// Completes without emitting if no item satisfies it. The kept item is
+  /// dropped if the source errors.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..100)
+  ///   .last_with(|v| v % 7 == 0)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 98
+  /// ```
+  #[inline]
+  fn last_with<F>(self, predicate: F) -> LastOp<FilterOp<Self, F>, Item>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    self.filter(predicate).last()
+  }
+
+  /// Emits the only item of the source when it completes.
+  ///
+  /// Errors with a [`SingleError`](crate::ops::single::SingleError) if the
+  /// source completes without an item, or as soon as it emits a second item,
+  /// the source is stopped at that moment.
+  ///
+  /// Like `timeout`, it requires `Err: From<SingleError>`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::{ops::single::SingleError, prelude::*};
+  ///
+  /// observable::from_iter(0..2)
+  ///   .on_error_map(|e| match e {})
+  ///   .single()
+  ///   .on_error(|e: SingleError| println!("{}", e))
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // the observable emitted more than one matching value
+  /// ```
+  #[inline]
+  fn single(self) -> SingleOp<Self, fn(&Item) -> bool> {
+    SingleOp { source: self, predicate: |_| true }
+  }
+
+  /// Same as `single`, but only considers the items that satisfy
+  /// `predicate`.
+  #[inline]
+  fn single_with<F>(self, predicate: F) -> SingleOp<Self, F>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    SingleOp { source: self, predicate }
+  }
+
   /// Call a function when observable completes, errors or is unsubscribed from.
+  ///
+  /// The function is called exactly once, after the downstream is notified of
+  /// the termination or after the source is unsubscribed, whichever happens
+  /// first.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut file = Subject::default();
+  /// let subscription = file
+  ///   .clone()
+  ///   .finalize(|| println!("close the file"))
+  ///   .subscribe(|line: &str| println!("{}", line));
+  ///
+  /// file.next("first line");
+  /// subscription.unsubscribe();
+  /// file.complete();
+  ///
+  /// // print log:
+  /// // first line
+  /// // close the file
+  /// ```
   #[inline]
   fn finalize<F>(self, f: F) -> FinalizeOp<Self, F>
   where
-    F: FnMut(),
+    F: FnOnce(),
   {
     FinalizeOp::new(self, f)
   }
 
-  /// A threads safe version of `finalize`
+  /// A threads safe version of `finalize`, the function is called once even
+  /// if the source terminates on a thread while unsubscribing on another.
+  #[inline]
   fn finalize_threads<F>(self, f: F) -> FinalizeOpThreads<Self, F>
   where
-    F: FnMut(),
+    F: FnOnce(),
   {
     FinalizeOpThreads::new(self, f)
   }
@@ -289,7 +600,7 @@ pub trait ObservableExt<Item, Err>: Sized {
     MapOp<Self, F, Item>: ObservableExt<V, Err>,
     V: ObservableExt<Item2, Err>,
   {
-    self.map(f).merge_all(usize::MAX)
+    self.map(f).merge_all()
   }
 
   #[inline]
@@ -302,336 +613,896 @@ pub trait ObservableExt<Item, Err>: Sized {
     MapOp<Self, F, Item>: ObservableExt<V, Err>,
     V: ObservableExt<Item2, Err>,
   {
-    self.map(f).merge_all_threads(usize::MAX)
+    self.map(f).merge_all_threads()
   }
 
-  /// Groups items emitted by the source Observable into Observables.
-  /// Each emitted Observable emits items matching the key returned
-  /// by the discriminator function.
+  /// Applies given function to each item emitted by this Observable, where
+  /// that function returns an iterator, and emits all the items of the
+  /// iterator synchronously. Lighter than `flat_map` when the expansion of an
+  /// item is a plain collection rather than an Observable.
+  ///
+  /// Stops draining the iterator once the downstream is unsubscribed.
   ///
   /// # Example
   ///
   /// ```
-  /// use rxrust::prelude::*;
+  /// # use rxrust::prelude::*;
+  /// let mut words = vec![];
+  /// observable::from_iter(["hello world", "rx"])
+  ///   .flat_map_iter(|line| line.split_whitespace())
+  ///   .subscribe(|w| words.push(w));
   ///
-  /// #[derive(Clone)]
-  /// struct Person {
-  ///   name: String,
-  ///   age: u32,
-  /// }
+  /// assert_eq!(words, ["hello", "world", "rx"]);
+  /// ```
+  #[inline]
+  fn flat_map_iter<F, I>(self, f: F) -> FlatMapIterOp<Self, F, Item>
+  where
+    F: FnMut(Item) -> I,
+    I: IntoIterator,
+  {
+    FlatMapIterOp::new(self, f)
+  }
+
+  /// Converts an Observable that emits Observables into an Observable that
+  /// emits the items emitted by the most recently emitted of those
+  /// Observables.
   ///
-  /// observable::from_iter([
-  ///   Person{ name: String::from("John"), age: 26 },
-  ///   Person{ name: String::from("Anne"), age: 28 },
-  ///   Person{ name: String::from("Gregory"), age: 24 },
-  ///   Person{ name: String::from("Alice"), age: 28 },
-  /// ])
-  /// .group_by::<_,_,Subject<_,_>>(|person: &Person| person.age)
-  /// .flat_map(|group| group.reduce(|acc, person: Person| format!("{} {}", acc, person.name)))
-  /// .subscribe(|result| println!("{}", result));
+  /// When a new inner Observable is emitted, the previous one is unsubscribed.
+  /// Completes after both the source and the last inner Observable complete.
   ///
-  /// // Prints:
-  /// //  John
-  /// //  Anne Alice
-  /// //  Gregory
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// let mut source = Subject::default();
+  /// let mut a = Subject::default();
+  /// let mut b = Subject::default();
+  ///
+  /// source
+  ///   .clone()
+  ///   .switch_on_next()
+  ///   .subscribe(|v: i32| println!("{} ", v));
+  ///
+  /// source.next(a.clone());
+  /// a.next(1);
+  /// source.next(b.clone());
+  /// // `a` is unsubscribed, only the values of `b` are emitted.
+  /// a.next(2);
+  /// b.next(3);
+  ///
+  /// // print logs:
+  /// // 1
+  /// // 3
   /// ```
   #[inline]
-  fn group_by<D, Key, Subject>(self, discr: D) -> GroupByOp<Self, D, Subject>
+  fn switch_on_next<'a, Item2>(self) -> SwitchOnNextOp<'a, Self, Item>
   where
-    D: FnMut(&Item) -> Key,
-    Key: Hash + Eq + Clone,
-    Subject: Clone + Default + Observer<Item, Err>,
+    Item: ObservableExt<Item2, Err>,
   {
-    GroupByOp::new(self, discr)
+    SwitchOnNextOp::new(self)
   }
 
-  /// Creates a new stream which calls a closure on each element and uses
-  /// its return as the value.
+  /// A threads safe version of `switch_on_next`
   #[inline]
-  fn map<B, F>(self, f: F) -> MapOp<Self, F, Item>
+  fn switch_on_next_threads<Item2>(self) -> SwitchOnNextOpThreads<Self, Item>
   where
-    F: FnMut(Item) -> B,
+    Item: ObservableExt<Item2, Err>,
   {
-    MapOp::new(self, f)
+    SwitchOnNextOpThreads::new(self)
   }
 
-  /// Creates a new stream which calls a closure on each error and uses
-  /// its return as emitted error.
+  /// Applies given function to each item emitted by this Observable, where
+  /// that function returns an Observable, and emits only the items of the
+  /// most recent of these Observables. The previous Observable is unsubscribed
+  /// as soon as a new item is emitted by this Observable.
   #[inline]
-  fn on_error_map<B, F>(self, f: F) -> OnErrorMapOp<Self, F, Err>
+  fn switch_map<'a, V, Item2, F>(
+    self,
+    f: F,
+  ) -> SwitchMapOp<'a, Self, V, F, Item>
   where
-    F: FnMut(Err) -> B,
+    F: Fn(Item) -> V,
+    MapOp<Self, F, Item>: ObservableExt<V, Err>,
+    V: ObservableExt<Item2, Err>,
   {
-    OnErrorMapOp::new(self, f)
+    self.map(f).switch_on_next()
   }
 
-  /// Maps emissions to a constant value.
+  /// A threads safe version of `switch_map`
   #[inline]
-  fn map_to<B>(self, value: B) -> MapToOp<Self, B, Item> {
-    MapToOp::new(self, value)
+  fn switch_map_threads<V, Item2, F>(
+    self,
+    f: F,
+  ) -> SwitchMapOpThreads<Self, V, F, Item>
+  where
+    F: Fn(Item) -> V,
+    MapOp<Self, F, Item>: ObservableExt<V, Err>,
+    V: ObservableExt<Item2, Err>,
+  {
+    self.map(f).switch_on_next_threads()
   }
 
-  /// combine two Observables into one by merging their emissions
+  /// Applies given function to each item emitted by this Observable, where
+  /// that function returns an Observable, and emits the items of that
+  /// Observable. Items emitted by this Observable while the previous returned
+  /// Observable is still active are ignored, and the function is not called
+  /// for them.
   ///
   /// # Example
   ///
   /// ```
   /// # use rxrust::prelude::*;
-  /// let numbers = Subject::default();
-  /// // create a even stream by filter
-  /// let even = numbers.clone().filter(|v| *v % 2 == 0);
-  /// // create an odd stream by filter
-  /// let odd = numbers.clone().filter(|v| *v % 2 != 0);
+  /// let mut clicks = Subject::default();
+  /// let mut request = Subject::default();
+  /// let c_request = request.clone();
   ///
-  /// // merge odd and even stream again
-  /// let merged = even.merge(odd);
+  /// clicks
+  ///   .clone()
+  ///   .exhaust_map(move |_| c_request.clone())
+  ///   .subscribe(|v: &str| println!("{}", v));
   ///
-  /// // attach observers
-  /// merged.subscribe(|v: &i32| println!("{} ", v));
+  /// clicks.next(());
+  /// // ignored, the first request is still in flight.
+  /// clicks.next(());
+  /// request.next("saved");
+  ///
+  /// // print logs:
+  /// // saved
   /// ```
   #[inline]
-  fn merge<S>(self, other: S) -> MergeOp<Self, S>
+  fn exhaust_map<'a, V, Item2, F>(self, f: F) -> ExhaustMapOp<'a, Self, F, Item>
   where
-    S: ObservableExt<Item, Err>,
+    F: FnMut(Item) -> V,
+    V: ObservableExt<Item2, Err>,
   {
-    MergeOp::new(self, other)
+    ExhaustMapOp::new(self, f)
   }
 
-  /// A threads safe version of `merge`
+  /// A threads safe version of `exhaust_map`
   #[inline]
-  fn merge_threads<S>(self, other: S) -> MergeOpThreads<Self, S>
+  fn exhaust_map_threads<V, Item2, F>(
+    self,
+    f: F,
+  ) -> ExhaustMapOpThreads<Self, F, Item>
   where
-    S: ObservableExt<Item, Err>,
+    F: FnMut(Item) -> V,
+    V: ObservableExt<Item2, Err>,
   {
-    MergeOpThreads::new(self, other)
+    ExhaustMapOpThreads::new(self, f)
   }
 
-  /// Converts a higher-order Observable into a first-order Observable which
-  /// concurrently delivers all values that are emitted on the inner
-  /// Observables.
+  /// Applies given function to each item emitted by this Observable, where
+  /// that function returns an Observable, and merges the emissions of at most
+  /// `max_concurrent` of these resulting Observables at once. The remaining
+  /// items are queued until one of the subscribed Observables completes.
   ///
-  /// # Example
+  /// `flat_map_concurrent(f, 1)` behaves like `concat_map(f)`, and
+  /// `flat_map_concurrent(f, usize::MAX)` like `flat_map(f)`.
   ///
-  /// ```
-  /// # use rxrust::prelude::*;
-  /// # use futures::executor::LocalPool;
-  /// # use std::time::Duration;
-  /// let mut local = LocalPool::new();
-  /// observable::from_iter(
-  ///   (0..3)
-  ///     .map(|_| interval(Duration::from_millis(1), local.spawner()).take(5)),
-  /// )
-  /// .merge_all(2)
-  /// .subscribe(move |i| println!("{}", i));
-  /// local.run();
-  /// ```
+  /// # Panics
+  ///
+  /// Panics if `max_concurrent` is zero.
   #[inline]
-  fn merge_all<'a, Item2>(self, concurrent: usize) -> MergeAllOp<'a, Self, Item>
+  fn flat_map_concurrent<'a, V, Item2, F>(
+    self,
+    f: F,
+    max_concurrent: usize,
+  ) -> FlatMapOp<'a, Self, V, F, Item>
   where
-    Item: ObservableExt<Item2, Err>,
+    F: Fn(Item) -> V,
+    MapOp<Self, F, Item>: ObservableExt<V, Err>,
+    V: ObservableExt<Item2, Err>,
   {
-    MergeAllOp::new(self, concurrent)
+    self.map(f).merge_all_concurrent(max_concurrent)
   }
 
-  /// A threads safe version of `merge_all`
+  /// A threads safe version of `flat_map_concurrent`
   #[inline]
-  fn merge_all_threads<Item2>(
+  fn flat_map_concurrent_threads<V, Item2, F>(
     self,
-    concurrent: usize,
-  ) -> MergeAllOpThreads<Self, Item>
+    f: F,
+    max_concurrent: usize,
+  ) -> FlatMapOpThreads<Self, V, F, Item>
   where
-    Item: ObservableExt<Item2, Err>,
+    F: Fn(Item) -> V,
+    MapOp<Self, F, Item>: ObservableExt<V, Err>,
+    V: ObservableExt<Item2, Err>,
   {
-    MergeAllOpThreads::new(self, concurrent)
+    self.map(f).merge_all_concurrent_threads(max_concurrent)
   }
 
-  /// Emit only those items from an Observable that pass a predicate test
+  /// Emits the items of this Observable, and projects every emitted item
+  /// into an Observable whose items are emitted and projected the same way,
+  /// recursively. Completes once this Observable and all the projected ones
+  /// have completed.
+  ///
+  /// The projected Observables are queued and subscribed one after another
+  /// when they emit synchronously, so a deep expansion doesn't recurse.
+  ///
   /// # Example
   ///
   /// ```
-  /// use rxrust:: prelude::*;
-  ///
-  /// let mut coll = vec![];
-  /// let coll_clone = coll.clone();
+  /// use rxrust::prelude::*;
   ///
-  /// observable::from_iter(0..10)
-  ///   .filter(|v| *v % 2 == 0)
-  ///   .subscribe(|v| { coll.push(v); });
+  /// observable::of(1)
+  ///   .expand(|v| observable::from_iter((*v < 8).then_some(v * 2)))
+  ///   .subscribe(|v| println!("{}", v));
   ///
-  /// // only even numbers received.
-  /// assert_eq!(coll, vec![0, 2, 4, 6, 8]);
+  /// // print logs:
+  /// // 1
+  /// // 2
+  /// // 4
+  /// // 8
   /// ```
   #[inline]
-  fn filter<F>(self, filter: F) -> FilterOp<Self, F>
+  fn expand<'a, F, R>(self, project: F) -> ExpandOp<'a, Self, F>
   where
-    F: Fn(&Item) -> bool,
+    F: FnMut(&Item) -> R,
+    R: ObservableExt<Item, Err>,
   {
-    FilterOp { source: self, filter }
+    ExpandOp::new(self, project, usize::MAX)
   }
 
-  /// The closure must return an Option<T>. filter_map creates an iterator which
-  /// calls this closure on each element. If the closure returns Some(element),
-  /// then that element is returned. If the closure returns None, it will try
-  /// again, and call the closure on the next element, seeing if it will return
-  /// Some.
+  /// A threads safe version of `expand`
+  #[inline]
+  fn expand_threads<F, R>(self, project: F) -> ExpandOpThreads<Self, F>
+  where
+    F: FnMut(&Item) -> R,
+    R: ObservableExt<Item, Err>,
+  {
+    ExpandOpThreads::new(self, project, usize::MAX)
+  }
+
+  /// Same as `expand`, but subscribes at most `concurrent` of the projected
+  /// Observables at once, like `flat_map_concurrent`. The remaining ones are
+  /// queued until one of the subscribed Observables completes.
   ///
-  /// Why filter_map and not just filter and map? The key is in this part:
+  /// # Panics
   ///
-  /// If the closure returns Some(element), then that element is returned.
+  /// Panics if `concurrent` is zero.
+  #[inline]
+  fn expand_concurrent<'a, F, R>(
+    self,
+    project: F,
+    concurrent: usize,
+  ) -> ExpandOp<'a, Self, F>
+  where
+    F: FnMut(&Item) -> R,
+    R: ObservableExt<Item, Err>,
+  {
+    assert!(concurrent > 0, "concurrent must be greater than 0");
+    ExpandOp::new(self, project, concurrent)
+  }
+
+  /// A threads safe version of `expand_concurrent`
+  #[inline]
+  fn expand_concurrent_threads<F, R>(
+    self,
+    project: F,
+    concurrent: usize,
+  ) -> ExpandOpThreads<Self, F>
+  where
+    F: FnMut(&Item) -> R,
+    R: ObservableExt<Item, Err>,
+  {
+    assert!(concurrent > 0, "concurrent must be greater than 0");
+    ExpandOpThreads::new(self, project, concurrent)
+  }
+
+  /// Applies given function to each item emitted by this Observable, where
+  /// that function returns an Observable, and subscribes to the resulting
+  /// Observables one at a time, in order. The next one is subscribed only
+  /// after the previous one completes, the pending items are queued.
   ///
-  /// In other words, it removes the Option<T> layer automatically. If your
-  /// mapping is already returning an Option<T> and you want to skip over Nones,
-  /// then filter_map is much, much nicer to use.
+  /// An error from the source or any of the resulting Observables drops the
+  /// queue and is propagated.
   ///
-  /// # Examples
+  /// # Example
   ///
   /// ```
-  ///  # use rxrust::prelude::*;
-  ///  let mut res: Vec<i32> = vec![];
-  ///   observable::from_iter(["1", "lol", "3", "NaN", "5"].iter())
-  ///   .filter_map(|s: &&str| s.parse().ok())
-  ///   .subscribe(|v| res.push(v));
+  /// # use rxrust::prelude::*;
+  /// observable::from_iter(0..3)
+  ///   .concat_map(|i| observable::from_iter(0..2).map(move |j| (i, j)))
+  ///   .subscribe(|v| println!("{:?}", v));
   ///
-  /// assert_eq!(res, [1, 3, 5]);
+  /// // print logs:
+  /// // (0, 0)
+  /// // (0, 1)
+  /// // (1, 0)
+  /// // (1, 1)
+  /// // (2, 0)
+  /// // (2, 1)
   /// ```
   #[inline]
-  fn filter_map<F, OutputItem>(self, f: F) -> FilterMapOp<Self, F, Item>
+  fn concat_map<'a, V, Item2, F>(self, f: F) -> FlatMapOp<'a, Self, V, F, Item>
   where
-    F: FnMut(Item) -> Option<OutputItem>,
+    F: Fn(Item) -> V,
+    MapOp<Self, F, Item>: ObservableExt<V, Err>,
+    V: ObservableExt<Item2, Err>,
   {
-    FilterMapOp::new(self, f)
+    self.map(f).concat_all()
   }
 
-  /// Ignore the first `count` values emitted by the source Observable.
-  ///
-  /// `skip` returns an Observable that ignore the first `count` values
-  /// emitted by the source Observable. If the source emits fewer than `count`
-  /// values then 0 of its values are emitted. After that, it completes,
-  /// regardless if the source completes.
+  /// A threads safe version of `concat_map`
+  #[inline]
+  fn concat_map_threads<V, Item2, F>(
+    self,
+    f: F,
+  ) -> FlatMapOpThreads<Self, V, F, Item>
+  where
+    F: Fn(Item) -> V,
+    MapOp<Self, F, Item>: ObservableExt<V, Err>,
+    V: ObservableExt<Item2, Err>,
+  {
+    self.map(f).concat_all_threads()
+  }
+
+  /// Groups items emitted by the source Observable into Observables.
+  /// Each emitted Observable emits items matching the key returned
+  /// by the discriminator function.
   ///
   /// # Example
-  /// Ignore the first 5 seconds of an infinite 1-second interval Observable
   ///
   /// ```
-  /// # use rxrust::prelude::*;
+  /// use rxrust::prelude::*;
   ///
-  /// observable::from_iter(0..10).skip(5).subscribe(|v| println!("{}", v));
-
-  /// // print logs:
-  /// // 6
-  /// // 7
-  /// // 8
-  /// // 9
-  /// // 10
+  /// #[derive(Clone)]
+  /// struct Person {
+  ///   name: String,
+  ///   age: u32,
+  /// }
+  ///
+  /// observable::from_iter([
+  ///   Person{ name: String::from("John"), age: 26 },
+  ///   Person{ name: String::from("Anne"), age: 28 },
+  ///   Person{ name: String::from("Gregory"), age: 24 },
+  ///   Person{ name: String::from("Alice"), age: 28 },
+  /// ])
+  /// .group_by::<_,_,Subject<_,_>>(|person: &Person| person.age)
+  /// .flat_map(|group| group.reduce(|acc, person: Person| format!("{} {}", acc, person.name)))
+  /// .subscribe(|result| println!("{}", result));
+  ///
+  /// // Prints:
+  /// //  John
+  /// //  Anne Alice
+  /// //  Gregory
   /// ```
   #[inline]
-  fn skip(self, count: usize) -> SkipOp<Self> {
-    SkipOp::new(self, count)
+  fn group_by<D, Key, Subject>(self, discr: D) -> GroupByOp<Self, D, Subject>
+  where
+    D: FnMut(&Item) -> Key,
+    Key: Hash + Eq + Clone,
+    Subject: Clone + Default + Observer<Item, Err>,
+  {
+    GroupByOp::new(self, discr)
   }
 
-  /// Discard items emitted by an Observable until a second Observable emits an item
+  /// Works like [`group_by`](ObservableExt::group_by), but every group is
+  /// closed when the observable returned by `duration_selector` for its key
+  /// emits or completes. A closed group is completed and removed, so a later
+  /// item with the same key opens a fresh group.
+  ///
+  /// An error of a duration observable is emitted by its group only. The
+  /// remaining groups are closed when the source completes or errors.
   ///
   /// # Example
-  /// Ignore the numbers in the 0-10 range until the Observer emits 5 and trigger
-  ///  the notify observable.
   ///
   /// ```
-  /// # use rxrust::prelude::*;
+  /// use rxrust::prelude::*;
   ///
-  /// let mut items = vec![];
-  /// let notifier = Subject::<(), ()>::default();
-  /// let mut c_notifier = notifier.clone();
-  /// observable::from_iter(0..10)
-  ///   .tap(move |v| {
-  ///     if v == &5 {
-  ///       c_notifier.next(());
-  ///     }
+  /// let mut source = Subject::default();
+  /// let mut flush = Subject::default();
+  /// let c_flush = flush.clone();
+  ///
+  /// source
+  ///   .clone()
+  ///   .group_by_until::<_, _, Subject<_, _>, _, _, _>(
+  ///     |v: &i32| v % 2,
+  ///     move |_| c_flush.clone(),
+  ///   )
+  ///   .flat_map(|group| {
+  ///     let key = group.key;
+  ///     group.to_vec().map(move |items| (key, items))
   ///   })
-  ///   .skip_until(notifier)
-  ///   .subscribe(|v| items.push(v));
+  ///   .subscribe(|(key, items)| println!("{key}: {items:?}"));
   ///
-  /// assert_eq!((5..10).collect::<Vec<i32>>(), items);
+  /// source.next(1);
+  /// source.next(3);
+  /// flush.next(());
+  /// source.next(5);
+  /// source.complete();
+  ///
+  /// // Prints:
+  /// // 1: [1, 3]
+  /// // 1: [5]
   /// ```
   #[inline]
-  fn skip_until<NotifyItem, NotifyErr, Other>(
+  fn group_by_until<D, Key, Subject, DS, N, NotifyItem>(
     self,
-    notifier: Other,
-  ) -> SkipUntilOp<Self, Other, NotifyItem, NotifyErr>
+    discr: D,
+    duration_selector: DS,
+  ) -> GroupByUntilOp<Self, D, DS, Subject, NotifyItem>
   where
-    Other: ObservableExt<NotifyItem, NotifyErr>,
+    D: FnMut(&Item) -> Key,
+    DS: FnMut(&Key) -> N,
+    Key: Hash + Eq + Clone,
+    Subject: Clone + Default + Observer<Item, Err>,
   {
-    SkipUntilOp::new(self, notifier)
+    GroupByUntilOp::new(self, discr, duration_selector)
   }
 
-  /// A threads safe version of `skip_until_threads`
+  /// Creates a new stream which calls a closure on each element and uses
+  /// its return as the value.
   #[inline]
-  fn skip_until_threads<NotifyItem, NotifyErr, Other>(
-    self,
-    notifier: Other,
-  ) -> SkipUntilOpThreads<Self, Other, NotifyItem, NotifyErr>
+  fn map<B, F>(self, f: F) -> MapOp<Self, F, Item>
   where
-    Other: ObservableExt<NotifyItem, NotifyErr>,
+    F: FnMut(Item) -> B,
   {
-    SkipUntilOpThreads::new(self, notifier)
+    MapOp::new(self, f)
   }
 
-  /// Discard items emitted by an Observable until a specified condition becomes false
+  /// Creates a new stream which calls a closure on each error and uses
+  /// its return as emitted error.
+  #[inline]
+  fn on_error_map<B, F>(self, f: F) -> OnErrorMapOp<Self, F, Err>
+  where
+    F: FnOnce(Err) -> B,
+  {
+    OnErrorMapOp::new(self, f)
+  }
+
+  /// Catches the panics of the downstream while it handles an item, and
+  /// emits the panic payload mapped by `mapper` as the error instead, so a
+  /// single bad element doesn't take down the whole thread. The upstream is
+  /// unsubscribed once a panic is caught.
   ///
+  /// Only the panics raised after this operator in the chain, e.g. in the
+  /// `map` closures following it or in the `subscribe` closure, are caught.
+  /// The downstream is treated as `UnwindSafe`: after a panic it only
+  /// receives the error. A panic while the downstream handles the error or
+  /// the completion is not caught.
   ///
   /// # Example
-  /// Suppress the first 5 items of an infinite 1-second interval Observable
   ///
   /// ```
-  /// # use rxrust::prelude::*;
+  /// use rxrust::prelude::*;
   ///
-  /// observable::from_iter(0..10)
-  ///   .skip_while(|v| v < &5)
+  /// let mut error = None;
+  /// observable::from_iter(["1", "x", "3"])
+  ///   .map_err(|e| match e {})
+  ///   .catch_unwind(|payload| {
+  ///     payload.downcast_ref::<&str>().map_or("panic", |s| *s)
+  ///   })
+  ///   .map(|s| s.parse::<i32>().unwrap_or_else(|_| panic!("not a number")))
+  ///   .on_error(|e| error = Some(e))
   ///   .subscribe(|v| println!("{}", v));
   ///
-  /// // print logs:
-  /// // 5
-  /// // 6
-  /// // 7
-  /// // 8
-  /// // 9
+  /// assert_eq!(error, Some("not a number"));
   /// ```
   #[inline]
-  fn skip_while<F>(self, predicate: F) -> SkipWhileOp<Self, F>
+  fn catch_unwind<M>(self, mapper: M) -> CatchUnwindOp<Self, M>
   where
-    F: FnMut(&Item) -> bool,
+    M: FnOnce(Box<dyn Any + Send>) -> Err,
   {
-    SkipWhileOp { source: self, predicate }
+    CatchUnwindOp::new(self, mapper)
   }
 
-  /// Ignore the last `count` values emitted by the source Observable.
+  /// Maps the error of the source through `f`, changing the error type of
+  /// the observable, the items and the completion pass through untouched.
   ///
-  /// `skip_last` returns an Observable that ignore the last `count` values
-  /// emitted by the source Observable. If the source emits fewer than `count`
-  /// values then 0 of its values are emitted.
-  /// It will not emit values until source Observable complete.
+  /// Unifies the error types of observables to compose them with `merge`,
+  /// `on_error_resume_next` and so on.
   ///
   /// # Example
-  /// Skip the last 5 seconds of an infinite 1-second interval Observable
   ///
   /// ```
-  /// # use rxrust::prelude::*;
+  /// use rxrust::prelude::*;
   ///
-  /// observable::from_iter(0..10)
-  ///   .skip_last(5)
-  ///   .subscribe(|v| println!("{}", v));
+  /// #[derive(Debug, PartialEq)]
+  /// enum AppError {
+  ///   Parse(String),
+  ///   Io(u32),
+  /// }
   ///
-  /// // print logs:
-  /// // 0
-  /// // 1
-  /// // 2
-  /// // 3
-  /// // 4
+  /// let mut error = None;
+  /// observable::throw(String::from("bad number"))
+  ///   .map_err(AppError::Parse)
+  ///   .merge(observable::throw(404).map_err(AppError::Io))
+  ///   .on_error(|e| error = Some(e))
+  ///   .subscribe(|_: ()| {});
+  ///
+  /// assert_eq!(error, Some(AppError::Parse(String::from("bad number"))));
   /// ```
   #[inline]
-  fn skip_last(self, count: usize) -> SkipLastOp<Self> {
-    SkipLastOp { source: self, count }
-  }
-
+  fn map_err<B, F>(self, f: F) -> OnErrorMapOp<Self, F, Err>
+  where
+    F: FnOnce(Err) -> B,
+  {
+    OnErrorMapOp::new(self, f)
+  }
+
+  /// Maps emissions to a constant value.
+  ///
+  /// Every item of the source is replaced with a clone of `value`, the source
+  /// items themselves are not required to be `Clone`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// let mut clicks = 0;
+  /// observable::from_iter(["down", "up", "down"])
+  ///   .map_to(1)
+  ///   .subscribe(|v| clicks += v);
+  ///
+  /// assert_eq!(clicks, 3);
+  /// ```
+  #[inline]
+  fn map_to<B>(self, value: B) -> MapToOp<Self, B, Item> {
+    MapToOp::new(self, value)
+  }
+
+  /// combine two Observables into one by merging their emissions
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// let numbers = Subject::default();
+  /// // create a even stream by filter
+  /// let even = numbers.clone().filter(|v| *v % 2 == 0);
+  /// // create an odd stream by filter
+  /// let odd = numbers.clone().filter(|v| *v % 2 != 0);
+  ///
+  /// // merge odd and even stream again
+  /// let merged = even.merge(odd);
+  ///
+  /// // attach observers
+  /// merged.subscribe(|v: &i32| println!("{} ", v));
+  /// ```
+  #[inline]
+  fn merge<S>(self, other: S) -> MergeOp<Self, S>
+  where
+    S: ObservableExt<Item, Err>,
+  {
+    MergeOp::new(self, other)
+  }
+
+  /// A threads safe version of `merge`
+  #[inline]
+  fn merge_threads<S>(self, other: S) -> MergeOpThreads<Self, S>
+  where
+    S: ObservableExt<Item, Err>,
+  {
+    MergeOpThreads::new(self, other)
+  }
+
+  /// Converts a higher-order Observable into a first-order Observable which
+  /// concurrently delivers all values that are emitted on the inner
+  /// Observables.
+  ///
+  /// Every inner Observable is subscribed as soon as it's emitted, the result
+  /// completes only after the source and all the inner Observables complete.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// # use futures::executor::LocalPool;
+  /// # use std::time::Duration;
+  /// let mut local = LocalPool::new();
+  /// observable::from_iter(
+  ///   (0..3)
+  ///     .map(|_| interval(Duration::from_millis(1), local.spawner()).take(5)),
+  /// )
+  /// .merge_all()
+  /// .subscribe(move |i| println!("{}", i));
+  /// local.run();
+  /// ```
+  #[inline]
+  fn merge_all<'a, Item2>(self) -> MergeAllOp<'a, Self, Item>
+  where
+    Item: ObservableExt<Item2, Err>,
+  {
+    MergeAllOp::new(self, usize::MAX)
+  }
+
+  /// A threads safe version of `merge_all`
+  #[inline]
+  fn merge_all_threads<Item2>(self) -> MergeAllOpThreads<Self, Item>
+  where
+    Item: ObservableExt<Item2, Err>,
+  {
+    MergeAllOpThreads::new(self, usize::MAX)
+  }
+
+  /// Same as `merge_all`, but subscribes to at most `concurrent` inner
+  /// Observables at the same time. The other inner Observables are queued
+  /// and subscribed in order, each time a subscribed one completes.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `concurrent` is zero.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// # use futures::executor::LocalPool;
+  /// # use std::time::Duration;
+  /// let mut local = LocalPool::new();
+  /// observable::from_iter(
+  ///   (0..3)
+  ///     .map(|_| interval(Duration::from_millis(1), local.spawner()).take(5)),
+  /// )
+  /// .merge_all_concurrent(2)
+  /// .subscribe(move |i| println!("{}", i));
+  /// local.run();
+  /// ```
+  #[inline]
+  fn merge_all_concurrent<'a, Item2>(
+    self,
+    concurrent: usize,
+  ) -> MergeAllOp<'a, Self, Item>
+  where
+    Item: ObservableExt<Item2, Err>,
+  {
+    assert!(concurrent > 0, "concurrent must be greater than 0");
+    MergeAllOp::new(self, concurrent)
+  }
+
+  /// A threads safe version of `merge_all_concurrent`
+  #[inline]
+  fn merge_all_concurrent_threads<Item2>(
+    self,
+    concurrent: usize,
+  ) -> MergeAllOpThreads<Self, Item>
+  where
+    Item: ObservableExt<Item2, Err>,
+  {
+    assert!(concurrent > 0, "concurrent must be greater than 0");
+    MergeAllOpThreads::new(self, concurrent)
+  }
+
+  /// Converts a higher-order Observable into a first-order Observable by
+  /// subscribing to the inner Observables one at a time, in the order they
+  /// are emitted. The inner Observables emitted while another one is active
+  /// are queued.
+  ///
+  /// An error from the source or any inner Observable drops the queue and is
+  /// propagated. The result completes once the source completes and all the
+  /// queued inner Observables complete.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// let mut uploads = Subject::default();
+  /// uploads
+  ///   .clone()
+  ///   .concat_all()
+  ///   .subscribe(|v: &str| println!("{}", v));
+  ///
+  /// uploads.next(observable::from_iter(vec!["a1", "a2"]));
+  /// uploads.next(observable::from_iter(vec!["b1"]));
+  /// // print logs:
+  /// // a1
+  /// // a2
+  /// // b1
+  /// ```
+  #[inline]
+  fn concat_all<'a, Item2>(self) -> MergeAllOp<'a, Self, Item>
+  where
+    Item: ObservableExt<Item2, Err>,
+  {
+    self.merge_all_concurrent(1)
+  }
+
+  /// A threads safe version of `concat_all`
+  #[inline]
+  fn concat_all_threads<Item2>(self) -> MergeAllOpThreads<Self, Item>
+  where
+    Item: ObservableExt<Item2, Err>,
+  {
+    self.merge_all_concurrent_threads(1)
+  }
+
+  /// Emit only those items from an Observable that pass a predicate test
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust:: prelude::*;
+  ///
+  /// let mut coll = vec![];
+  /// let coll_clone = coll.clone();
+  ///
+  /// observable::from_iter(0..10)
+  ///   .filter(|v| *v % 2 == 0)
+  ///   .subscribe(|v| { coll.push(v); });
+  ///
+  /// // only even numbers received.
+  /// assert_eq!(coll, vec![0, 2, 4, 6, 8]);
+  /// ```
+  #[inline]
+  fn filter<F>(self, filter: F) -> FilterOp<Self, F>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    FilterOp { source: self, filter }
+  }
+
+  /// The closure must return an Option<T>. filter_map creates an iterator which
+  /// calls this closure on each element. If the closure returns Some(element),
+  /// then that element is returned. If the closure returns None, it will try
+  /// again, and call the closure on the next element, seeing if it will return
+  /// Some.
+  ///
+  /// Why filter_map and not just filter and map? The key is in this part:
+  ///
+  /// If the closure returns Some(element), then that element is returned.
+  ///
+  /// In other words, it removes the Option<T> layer automatically. If your
+  /// mapping is already returning an Option<T> and you want to skip over Nones,
+  /// then filter_map is much, much nicer to use.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  ///  # use rxrust::prelude::*;
+  ///  let mut res: Vec<i32> = vec![];
+  ///   observable::from_iter(["1", "lol", "3", "NaN", "5"].iter())
+  ///   .filter_map(|s: &&str| s.parse().ok())
+  ///   .subscribe(|v| res.push(v));
+  ///
+  /// assert_eq!(res, [1, 3, 5]);
+  /// ```
+  #[inline]
+  fn filter_map<F, OutputItem>(self, f: F) -> FilterMapOp<Self, F, Item>
+  where
+    F: FnMut(Item) -> Option<OutputItem>,
+  {
+    FilterMapOp::new(self, f)
+  }
+
+  /// Ignore the first `count` values emitted by the source Observable.
+  ///
+  /// `skip` returns an Observable that ignore the first `count` values
+  /// emitted by the source Observable. If the source emits fewer than `count`
+  /// values then 0 of its values are emitted. After that, it completes,
+  /// regardless if the source completes.
+  ///
+  /// # Example
+  /// Ignore the first 5 seconds of an infinite 1-second interval Observable
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10).skip(5).subscribe(|v| println!("{}", v));
+  ///
+  /// // print logs:
+  /// // 6
+  /// // 7
+  /// // 8
+  /// // 9
+  /// // 10
+  /// ```
+  #[inline]
+  fn skip(self, count: usize) -> SkipOp<Self> {
+    SkipOp::new(self, count)
+  }
+
+  /// Discard items emitted by an Observable until a second Observable emits an item
+  ///
+  /// After the notifier emits its first item, the source passes through
+  /// unchanged and the notifier is no longer observed. If the notifier completes
+  /// without emitting, the source items stay suppressed, but the source
+  /// completion is still forwarded.
+  ///
+  /// # Example
+  /// Ignore the numbers in the 0-10 range until the Observer emits 5 and trigger
+  ///  the notify observable.
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  ///
+  /// let mut items = vec![];
+  /// let notifier = Subject::<(), ()>::default();
+  /// let mut c_notifier = notifier.clone();
+  /// observable::from_iter(0..10)
+  ///   .tap(move |v| {
+  ///     if v == &5 {
+  ///       c_notifier.next(());
+  ///     }
+  ///   })
+  ///   .skip_until(notifier)
+  ///   .subscribe(|v| items.push(v));
+  ///
+  /// assert_eq!((5..10).collect::<Vec<i32>>(), items);
+  /// ```
+  #[inline]
+  fn skip_until<NotifyItem, NotifyErr, Other>(
+    self,
+    notifier: Other,
+  ) -> SkipUntilOp<Self, Other, NotifyItem, NotifyErr>
+  where
+    Other: ObservableExt<NotifyItem, NotifyErr>,
+  {
+    SkipUntilOp::new(self, notifier)
+  }
+
+  /// A threads safe version of `skip_until_threads`
+  #[inline]
+  fn skip_until_threads<NotifyItem, NotifyErr, Other>(
+    self,
+    notifier: Other,
+  ) -> SkipUntilOpThreads<Self, Other, NotifyItem, NotifyErr>
+  where
+    Other: ObservableExt<NotifyItem, NotifyErr>,
+  {
+    SkipUntilOpThreads::new(self, notifier)
+  }
+
+  /// Discard items emitted by an Observable until a specified condition becomes false
+  ///
+  /// Once the predicate returns false, that item and all subsequent items are
+  /// emitted and the predicate is never evaluated again.
+  ///
+  /// # Example
+  /// Suppress the items of an Observable while they are less than 5
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .skip_while(|v| v < &5)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print logs:
+  /// // 5
+  /// // 6
+  /// // 7
+  /// // 8
+  /// // 9
+  /// ```
+  #[inline]
+  fn skip_while<F>(self, predicate: F) -> SkipWhileOp<Self, F>
+  where
+    F: FnMut(&Item) -> bool,
+  {
+    SkipWhileOp { source: self, predicate }
+  }
+
+  /// Ignore the last `count` values emitted by the source Observable.
+  ///
+  /// `skip_last` returns an Observable that ignore the last `count` values
+  /// emitted by the source Observable. If the source emits fewer than `count`
+  /// values then 0 of its values are emitted.
+  /// Values are delayed by `count` items and emitted as soon as it is known
+  /// they are not among the last `count`, without waiting for the source
+  /// Observable to complete.
+  ///
+  /// # Example
+  /// Skip the last 5 values of the 0-10 range
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .skip_last(5)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print logs:
+  /// // 0
+  /// // 1
+  /// // 2
+  /// // 3
+  /// // 4
+  /// ```
+  #[inline]
+  fn skip_last(self, count: usize) -> SkipLastOp<Self> {
+    SkipLastOp { source: self, count }
+  }
+
   /// Emits only the first `count` values emitted by the source Observable.
   ///
   /// `take` returns an Observable that emits only the first `count` values
@@ -667,6 +1538,10 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// `notifier` emits a value, the output Observable stops mirroring the source
   /// Observable and completes. If the `notifier` doesn't emit any value and
   /// completes then `take_until` will pass all values.
+  ///
+  /// The source is unsubscribed as soon as the `notifier` emits, and if the
+  /// `notifier` emits right away on subscription the source is never
+  /// subscribed.
   #[inline]
   fn take_until<Notify, NotifyItem, NotifyErr>(
     self,
@@ -686,11 +1561,11 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// Emits values while result of an callback is true.
   ///
   /// `take_while` returns an Observable that emits values while result of an
-  /// callback is true emitted by the source Observable.
-  /// It will not emit values until source Observable complete.
+  /// callback is true emitted by the source Observable. It completes as soon
+  /// as the callback returns false, and the source stops producing.
   ///
   /// # Example
-  /// Take the first 5 seconds of an infinite 1-second interval Observable
+  /// Take the values less than 5
   ///
   /// ```
   /// # use rxrust::prelude::*;
@@ -698,7 +1573,7 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// observable::from_iter(0..10)
   ///   .take_while(|v| v < &5)
   /// .subscribe(|v| println!("{}", v));
-
+  ///
   /// // print logs:
   /// // 0
   /// // 1
@@ -718,8 +1593,11 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// Emits values while result of an callback is true and the last one that
   /// causes the callback to return false.
   ///
+  /// This is useful to read a stream until a sentinel value, including the
+  /// sentinel itself.
+  ///
   /// # Example
-  /// Take the first 5 seconds of an infinite 1-second interval Observable
+  /// Take the values until 4, including 4
   ///
   /// ```
   /// # use rxrust::prelude::*;
@@ -727,7 +1605,7 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// observable::from_iter(0..10)
   ///   .take_while_inclusive(|v| v < &4)
   /// .subscribe(|v| println!("{}", v));
-
+  ///
   /// // print logs:
   /// // 0
   /// // 1
@@ -749,10 +1627,12 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// `take_last` returns an Observable that emits only the last `count` values
   /// emitted by the source Observable. If the source emits fewer than `count`
   /// values then all of its values are emitted.
-  /// It will not emit values until source Observable complete.
+  /// It will not emit values until source Observable complete, and discards
+  /// them if the source emits an error. At most `count` values are kept in
+  /// memory.
   ///
   /// # Example
-  /// Take the last 5 seconds of an infinite 1-second interval Observable
+  /// Take the last 5 values of the 0-10 range
   ///
   /// ```
   /// # use rxrust::prelude::*;
@@ -760,7 +1640,7 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// observable::from_iter(0..10)
   ///   .take_last(5)
   /// .subscribe(|v| println!("{}", v));
-
+  ///
   /// // print logs:
   /// // 5
   /// // 6
@@ -814,14 +1694,55 @@ pub trait ObservableExt<Item, Err>: Sized {
 
   /// A threads safe version of `sample`
   #[inline]
-  fn sample_threads<Sample, SampleItem, SampleErr>(
+  fn sample_threads<Sample, SampleItem, SampleErr>(
+    self,
+    sampling: Sample,
+  ) -> SampleOpThreads<Self, Sample, SampleItem>
+  where
+    Sample: ObservableExt<SampleItem, SampleErr>,
+  {
+    SampleOpThreads::new(self, sampling)
+  }
+
+  /// Emits the most recently emitted value from the source Observable within
+  /// periodic time intervals.
+  ///
+  /// Realised as `sample` driven by an `interval`, so the sampling keeps
+  /// firing until the source completes or the subscription is unsubscribed.
+  /// Periods in which the source emitted nothing stay silent.
+  ///
+  /// #Example
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  /// use futures::executor::LocalPool;
+  ///
+  /// let mut local_scheduler = LocalPool::new();
+  /// let spawner = local_scheduler.spawner();
+  /// observable::interval(Duration::from_millis(2), spawner.clone())
+  ///   .sample_time(Duration::from_millis(5), spawner)
+  ///   .take(5)
+  ///   .subscribe(move |v| println!("{}", v));
+  ///
+  /// local_scheduler.run();
+  /// ```
+  #[inline]
+  fn sample_time<SD>(
+    self,
+    duration: Duration,
+    scheduler: SD,
+  ) -> SampleOp<Self, interval::IntervalObservable<SD>, usize> {
+    self.sample(observable::interval(duration, scheduler))
+  }
+
+  /// A threads safe version of `sample_time`
+  #[inline]
+  fn sample_time_threads<SD>(
     self,
-    sampling: Sample,
-  ) -> SampleOpThreads<Self, Sample, SampleItem>
-  where
-    Sample: ObservableExt<SampleItem, SampleErr>,
-  {
-    SampleOpThreads::new(self, sampling)
+    duration: Duration,
+    scheduler: SD,
+  ) -> SampleOpThreads<Self, interval::IntervalObservable<SD>, usize> {
+    self.sample_threads(observable::interval(duration, scheduler))
   }
 
   /// The Scan operator applies a function to the first item emitted by the
@@ -876,6 +1797,40 @@ pub trait ObservableExt<Item, Err>: Sized {
     ScanOp::new(self, binary_op, initial_value)
   }
 
+  /// Works like [`scan_initial`](Observable::scan_initial) but emits
+  /// `initial_value` on subscribe, before any item of the source, so the
+  /// subscribers always start with a state to render.
+  ///
+  /// The accumulated value is cloned once per emission.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut states = vec![];
+  /// observable::from_iter(["inc", "inc", "dec"])
+  ///   .scan_emit_initial(0, |state, action| match action {
+  ///     "inc" => state + 1,
+  ///     _ => state - 1,
+  ///   })
+  ///   .subscribe(|v| states.push(v));
+  ///
+  /// assert_eq!(states, [0, 1, 2, 1]);
+  /// ```
+  #[inline]
+  fn scan_emit_initial<OutputItem, BinaryOp>(
+    self,
+    initial_value: OutputItem,
+    binary_op: BinaryOp,
+  ) -> ScanOp<Self, BinaryOp, OutputItem, Item>
+  where
+    BinaryOp: FnMut(OutputItem, Item) -> OutputItem,
+    OutputItem: Clone,
+  {
+    ScanOp::new(self, binary_op, initial_value).emit_initial()
+  }
+
   /// Works like [`scan_initial`](Observable::scan_initial) but starts with a
   /// value defined by a [`Default`] trait for the first argument `binary_op`
   /// operator operates on.
@@ -898,7 +1853,9 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// Apply a function to each item emitted by an observable, sequentially,
   /// and emit the final value, after source observable completes.
   ///
-  /// Emits error when source observable emits it.
+  /// Emits `initial` if the source observable completes without any item.
+  /// Emits error when source observable emits it, the partial accumulated
+  /// value is discarded.
   ///
   /// # Arguments
   ///
@@ -954,6 +1911,7 @@ pub trait ObservableExt<Item, Err>: Sized {
 
   /// Emits the item from the source observable that had the maximum value.
   ///
+  /// Completes without emitting if the source observable is empty.
   /// Emits error when source observable emits it.
   ///
   /// # Examples
@@ -988,6 +1946,7 @@ pub trait ObservableExt<Item, Err>: Sized {
 
   /// Emits the item from the source observable that had the minimum value.
   ///
+  /// Completes without emitting if the source observable is empty.
   /// Emits error when source observable emits it.
   ///
   /// # Examples
@@ -1020,6 +1979,68 @@ pub trait ObservableExt<Item, Err>: Sized {
     MapOp::new(last, |v| v.unwrap())
   }
 
+  /// Emits the item from the source observable that had the maximum value of
+  /// the key returned by `key`, so the items don't need to be ordered.
+  ///
+  /// Completes without emitting if the source observable is empty.
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec!["a", "ccc", "bb"])
+  ///   .max_by_key(|s: &&str| s.len())
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // ccc
+  /// ```
+  #[inline]
+  fn max_by_key<K, F>(self, key: F) -> MinMaxByKeyOp<Self, F>
+  where
+    F: Fn(&Item) -> K,
+    K: PartialOrd,
+  {
+    MinMaxByKeyOp {
+      source: self,
+      key,
+      keep: std::cmp::Ordering::Greater,
+    }
+  }
+
+  /// Emits the item from the source observable that had the minimum value of
+  /// the key returned by `key`, so the items don't need to be ordered.
+  ///
+  /// Completes without emitting if the source observable is empty.
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec!["a", "ccc", "bb"])
+  ///   .min_by_key(|s: &&str| s.len())
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // a
+  /// ```
+  #[inline]
+  fn min_by_key<K, F>(self, key: F) -> MinMaxByKeyOp<Self, F>
+  where
+    F: Fn(&Item) -> K,
+    K: PartialOrd,
+  {
+    MinMaxByKeyOp {
+      source: self,
+      key,
+      keep: std::cmp::Ordering::Less,
+    }
+  }
+
   /// Calculates the sum of numbers emitted by an source observable and emits
   /// this sum when source completes.
   ///
@@ -1035,7 +2056,7 @@ pub trait ObservableExt<Item, Err>: Sized {
   ///   .sum()
   ///   .subscribe(|v| println!("{}", v));
   ///
-  /// // p rint log:
+  /// // print log:
   /// // 5
   /// ```
   #[inline]
@@ -1071,10 +2092,36 @@ pub trait ObservableExt<Item, Err>: Sized {
     self.reduce(|acc, _v| acc + 1)
   }
 
-  /// Calculates the sum of numbers emitted by an source observable and emits
-  /// this sum when source completes.
+  /// Emits the number of items emitted by a source observable that satisfy
+  /// the `predicate` when this source completes.
   ///
-  /// Emits zero when source completed as an and empty sequence.
+  /// Emits zero when no item satisfies the `predicate`.
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .count_by(|v| v % 2 == 0)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 5
+  /// ```
+  #[inline]
+  fn count_by<F>(self, predicate: F) -> CountByOp<Self, F, Item>
+  where
+    F: Fn(&Item) -> bool,
+  {
+    self.filter(predicate).count()
+  }
+
+  /// Calculates the average of numbers emitted by an source observable and
+  /// emits this average when source completes.
+  ///
+  /// Completes without emitting when source completed as an empty sequence.
   /// Emits error when source observable emits it.
   ///
   /// # Examples
@@ -1138,29 +2185,258 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// items when it is subscribed to, but only when the Connect operator is
   /// applied to it. In this way you can wait for all intended observers to
   /// subscribe to the Observable before the Observable begins emitting items.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let published = observable::from_iter(0..3).publish::<Subject<_, _>>();
+  /// published.clone().subscribe(|v| println!("first {}", v));
+  /// published.clone().subscribe(|v| println!("second {}", v));
+  /// // Nothing is emitted before the source is connected.
+  /// let connection = published.connect();
+  /// ```
   #[inline]
   fn publish<Subject: Default>(self) -> ConnectableObservable<Self, Subject> {
     ConnectableObservable::new(self)
   }
 
+  /// Like [`publish`](ObservableExt::publish), but multicasts through the
+  /// subject created by `subject_factory`.
+  #[inline]
+  fn multicast<Subject>(
+    self,
+    subject_factory: impl FnOnce() -> Subject,
+  ) -> ConnectableObservable<Self, Subject> {
+    ConnectableObservable { source: self, subject: subject_factory() }
+  }
+
   /// Returns a new Observable that multicast (shares) the original
   /// Observable. As long as there is at least one Subscriber this
   /// Observable will be subscribed and emitting data. When all subscribers
   /// have unsubscribed it will unsubscribe from the source Observable.
   /// Because the Observable is multicasting it makes the stream `hot`.
   /// This is an alias for `publish().ref_count()`
+  ///
+  /// Once the source terminates, or the last subscriber unsubscribes, the
+  /// next subscriber subscribes to a clone of the source again.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut subject = Subject::<i32, _>::default();
+  /// let shared = subject.clone().tap(|v| println!("run {}", v)).share();
+  /// shared.clone().subscribe(|v| println!("first {}", v));
+  /// shared.subscribe(|v| println!("second {}", v));
+  /// subject.next(1);
+  ///
+  /// // print logs:
+  /// // run 1
+  /// // first 1
+  /// // second 1
+  /// ```
   #[inline]
   fn share<'a>(self) -> ShareOp<'a, Item, Err, Self> {
-    ShareOp::new(self)
+    self.publish().ref_count()
   }
 
   #[inline]
   fn share_threads(self) -> ShareOpThreads<Item, Err, Self> {
-    ShareOpThreads::new(self)
+    self.publish().ref_count_threads()
+  }
+
+  /// Shares the source like [`share`](ObservableExt::share), and replays the
+  /// last `buffer_size` items, and the termination, to the late subscribers.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let shared = observable::from_iter(0..5).share_replay(2);
+  /// shared.clone().subscribe(|v| println!("first {}", v));
+  /// // The source has completed, only the last two items are replayed.
+  /// shared.subscribe(|v| println!("second {}", v));
+  ///
+  /// // print logs:
+  /// // first 0
+  /// // first 1
+  /// // first 2
+  /// // first 3
+  /// // first 4
+  /// // second 3
+  /// // second 4
+  /// ```
+  #[inline]
+  fn share_replay<'a>(
+    self,
+    buffer_size: usize,
+  ) -> ShareReplayOp<'a, Item, Err, Self> {
+    self
+      .multicast(|| ReplaySubject::new(buffer_size))
+      .ref_count()
+  }
+
+  #[inline]
+  fn share_replay_threads(
+    self,
+    buffer_size: usize,
+  ) -> ShareReplayOpThreads<Item, Err, Self> {
+    self
+      .multicast(|| ReplaySubjectThreads::new(buffer_size))
+      .ref_count_threads()
+  }
+
+  /// Like [`share_replay`](ObservableExt::share_replay), but only replays the
+  /// items emitted within `window` before the subscription, as measured by
+  /// `clock`.
+  ///
+  /// Pass `Instant::now` to measure the real time, or another clock to
+  /// control the time, as [`ReplaySubject::with_time`] does.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::{Duration, Instant};
+  ///
+  /// let mut source = Subject::default();
+  /// let shared = source.clone().share_replay_within(
+  ///   10,
+  ///   Duration::from_millis(20),
+  ///   Instant::now,
+  /// );
+  /// let _guard = shared.clone().subscribe(|_| {});
+  ///
+  /// source.next(1);
+  /// std::thread::sleep(Duration::from_millis(40));
+  /// source.next(2);
+  /// // `1` was emitted more than 20ms ago, only `2` is replayed.
+  /// shared.subscribe(|v| println!("late {}", v));
+  ///
+  /// // print logs:
+  /// // late 2
+  /// ```
+  #[inline]
+  fn share_replay_within<'a, C>(
+    self,
+    buffer_size: usize,
+    window: Duration,
+    clock: C,
+  ) -> ShareReplayOp<'a, Item, Err, Self, C>
+  where
+    C: Fn() -> Instant,
+  {
+    self
+      .multicast(|| ReplaySubject::with_time(buffer_size, window, clock))
+      .ref_count()
+  }
+
+  /// A threads safe version of
+  /// [`share_replay_within`](ObservableExt::share_replay_within).
+  #[inline]
+  fn share_replay_within_threads<C>(
+    self,
+    buffer_size: usize,
+    window: Duration,
+    clock: C,
+  ) -> ShareReplayOpThreads<Item, Err, Self, C>
+  where
+    C: Fn() -> Instant,
+  {
+    self
+      .multicast(|| ReplaySubjectThreads::with_time(buffer_size, window, clock))
+      .ref_count_threads()
+  }
+
+  /// Splits the source into two observables, the first one emits the items
+  /// satisfying `predicate` and the second one emits the rest.
+  ///
+  /// The two halves share the source with [`share`](ObservableExt::share):
+  /// while one half is subscribed the other one joins the same subscription,
+  /// and the source is unsubscribed when both halves are unsubscribed. A
+  /// source that has already completed is subscribed again, so each half of a
+  /// synchronous source gets all its items.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut source = Subject::default();
+  /// let (even, odd) = source.clone().partition(|v: &i32| v % 2 == 0);
+  /// even.subscribe(|v| println!("even {}", v));
+  /// odd.subscribe(|v| println!("odd {}", v));
+  ///
+  /// source.next(1);
+  /// source.next(2);
+  /// // print logs:
+  /// // odd 1
+  /// // even 2
+  /// ```
+  #[allow(clippy::type_complexity)]
+  fn partition<'a, F>(
+    self,
+    predicate: F,
+  ) -> (
+    PartitionOp<ShareOp<'a, Item, Err, Self>, F>,
+    PartitionOp<ShareOp<'a, Item, Err, Self>, F>,
+  )
+  where
+    F: Fn(&Item) -> bool + Clone,
+  {
+    let shared = self.share();
+    (
+      PartitionOp {
+        source: shared.clone(),
+        predicate: predicate.clone(),
+        expected: true,
+      },
+      PartitionOp {
+        source: shared,
+        predicate,
+        expected: false,
+      },
+    )
+  }
+
+  /// A threads safe version of `partition`
+  #[allow(clippy::type_complexity)]
+  fn partition_threads<F>(
+    self,
+    predicate: F,
+  ) -> (
+    PartitionOp<ShareOpThreads<Item, Err, Self>, F>,
+    PartitionOp<ShareOpThreads<Item, Err, Self>, F>,
+  )
+  where
+    F: Fn(&Item) -> bool + Clone,
+  {
+    let shared = self.share_threads();
+    (
+      PartitionOp {
+        source: shared.clone(),
+        predicate: predicate.clone(),
+        expected: true,
+      },
+      PartitionOp {
+        source: shared,
+        predicate,
+        expected: false,
+      },
+    )
   }
 
   /// Delays the emission of items from the source Observable by a given timeout
   /// or until a given `Instant`.
+  ///
+  /// The items keep their order, and the completion is delayed the same way
+  /// so it never overtakes a pending item. An error is emitted immediately
+  /// and drops the pending items. Unsubscribing cancels all the pending
+  /// emissions.
   #[inline]
   fn delay<SD>(self, dur: Duration, scheduler: SD) -> DelayOp<Self, SD> {
     DelayOp { source: self, delay: dur, scheduler }
@@ -1180,7 +2456,7 @@ pub trait ObservableExt<Item, Err>: Sized {
   fn delay_at<SD>(self, at: Instant, scheduler: SD) -> DelayOp<Self, SD> {
     DelayOp {
       source: self,
-      delay: at.elapsed(),
+      delay: at.saturating_duration_since(Instant::now()),
       scheduler,
     }
   }
@@ -1194,7 +2470,7 @@ pub trait ObservableExt<Item, Err>: Sized {
   ) -> DelayOpThreads<Self, SD> {
     DelayOpThreads {
       source: self,
-      delay: at.elapsed(),
+      delay: at.saturating_duration_since(Instant::now()),
       scheduler,
     }
   }
@@ -1202,6 +2478,28 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// It's similar to delay but rather than timeshifting the emissions from
   /// the source Observable, it timeshifts the moment of subscription to that
   /// Observable.
+  ///
+  /// Unsubscribing before the delay elapsed cancels the pending subscribe, so
+  /// the source is never subscribed, otherwise the source subscription is
+  /// unsubscribed.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let mut local_pool = FuturesLocalSchedulerPool::new();
+  /// observable::defer(|| {
+  ///   println!("connect");
+  ///   observable::of(1)
+  /// })
+  /// .delay_subscription(Duration::from_millis(10), local_pool.spawner())
+  /// .subscribe(|v| println!("{}", v))
+  /// // unsubscribe before the delay elapsed, "connect" is never printed.
+  /// .unsubscribe();
+  ///
+  /// local_pool.run();
+  /// ```
   #[inline]
   fn delay_subscription<SD>(
     self,
@@ -1219,7 +2517,7 @@ pub trait ObservableExt<Item, Err>: Sized {
   ) -> DelaySubscriptionOp<Self, SD> {
     DelaySubscriptionOp {
       source: self,
-      delay: at.elapsed(),
+      delay: at.saturating_duration_since(Instant::now()),
       scheduler,
     }
   }
@@ -1278,33 +2576,180 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// which will be used to reschedule notifications emitted by the source
   /// Observable.
   #[inline]
-  fn observe_on<SD>(self, scheduler: SD) -> ObserveOnOp<Self, SD> {
-    ObserveOnOp { source: self, scheduler }
+  fn observe_on<SD>(self, scheduler: SD) -> ObserveOnOp<Self, SD> {
+    ObserveOnOp { source: self, scheduler }
+  }
+
+  /// A thread safe version of `observe_on`
+  #[inline]
+  fn observe_on_threads<SD>(
+    self,
+    scheduler: SD,
+  ) -> ObserveOnOpThreads<Self, SD> {
+    ObserveOnOpThreads { source: self, scheduler }
+  }
+
+  /// Emits a value from the source Observable only after a particular time span
+  /// has passed without another source emission.
+  #[inline]
+  fn debounce<SD>(
+    self,
+    duration: Duration,
+    scheduler: SD,
+  ) -> DebounceOp<Self, SD> {
+    DebounceOp { source: self, duration, scheduler }
+  }
+
+  /// Emits a value from the source Observable only when the Observable
+  /// returned by `selector` for it emits before the next source value.
+  ///
+  /// Every source value replaces the pending one and unsubscribes its
+  /// notifier. A notifier completing without emitting leaves the value
+  /// pending, the pending value is flushed when the source completes.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut source = Subject::default();
+  /// let mut signal = Subject::default();
+  /// let c_signal = signal.clone();
+  ///
+  /// source
+  ///   .clone()
+  ///   .debounce_when(move |_| c_signal.clone())
+  ///   .subscribe(|v: i32| println!("{}", v));
+  ///
+  /// source.next(1);
+  /// source.next(2);
+  /// signal.next(());
+  /// source.next(3);
+  /// source.complete();
+  ///
+  /// // print logs:
+  /// // 2
+  /// // 3
+  /// ```
+  #[inline]
+  fn debounce_when<'a, F, N, NotifyItem>(
+    self,
+    selector: F,
+  ) -> DebounceWhenOp<'a, Self, F, NotifyItem>
+  where
+    F: FnMut(&Item) -> N,
+  {
+    DebounceWhenOp::new(self, selector)
+  }
+
+  /// A threads safe version of `debounce_when`
+  #[inline]
+  fn debounce_when_threads<F, N, NotifyItem>(
+    self,
+    selector: F,
+  ) -> DebounceWhenOpThreads<Self, F, NotifyItem>
+  where
+    F: FnMut(&Item) -> N,
+  {
+    DebounceWhenOpThreads::new(self, selector)
+  }
+
+  /// Ignores source values for `duration` after a value arrives, then emits
+  /// the most recent value seen during that window, and repeats this process.
+  ///
+  /// Unlike `debounce`, a busy source can not postpone the emission, and unlike
+  /// `throttle_time` with `ThrottleEdge::leading()`, the latest value of each
+  /// window is emitted instead of the first one. A pending value is flushed
+  /// when the source completes.
+  ///
+  /// #Example
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let mut local_pool = FuturesLocalSchedulerPool::new();
+  /// let scheduler = local_pool.spawner();
+  /// observable::interval(Duration::from_millis(1), scheduler.clone())
+  ///   .audit_time(Duration::from_millis(5), scheduler)
+  ///   .take(5)
+  ///   .subscribe(move |v| println!("{}", v));
+  ///
+  /// local_pool.run();
+  /// ```
+  #[inline]
+  fn audit_time<SD>(
+    self,
+    duration: Duration,
+    scheduler: SD,
+  ) -> AuditOp<Self, SD> {
+    AuditOp { source: self, duration, scheduler }
   }
 
-  /// A thread safe version of `observe_on`
+  /// Errors with a [`TimeoutError`](crate::ops::timeout::TimeoutError) if
+  /// the source does not emit an item within `duration` after being
+  /// subscribed, or after its previous item. The source is unsubscribed at
+  /// that moment.
+  ///
+  /// The error is sent through the error channel of the source, so `Err` must
+  /// be convertible from `TimeoutError`. Use `on_error_map` to give a source
+  /// such an error type first.
+  ///
+  /// #Example
+  /// ```
+  /// use rxrust::{ops::timeout::TimeoutError, prelude::*};
+  /// use std::time::Duration;
+  ///
+  /// let mut local_pool = FuturesLocalSchedulerPool::new();
+  /// let scheduler = local_pool.spawner();
+  /// observable::timer(1, Duration::from_millis(10), scheduler.clone())
+  ///   .on_error_map(|e| match e {})
+  ///   .timeout(Duration::from_millis(5), scheduler)
+  ///   .on_error(|e: TimeoutError| println!("{}", e))
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// local_pool.run();
+  /// ```
   #[inline]
-  fn observe_on_threads<SD>(
+  fn timeout<SD>(
     self,
+    duration: Duration,
     scheduler: SD,
-  ) -> ObserveOnOpThreads<Self, SD> {
-    ObserveOnOpThreads { source: self, scheduler }
+  ) -> TimeoutOp<Self, ThrowTimeout, SD> {
+    TimeoutOp {
+      source: self,
+      duration,
+      fallback: ThrowTimeout,
+      scheduler,
+    }
   }
 
-  /// Emits a value from the source Observable only after a particular time span
-  /// has passed without another source emission.
+  /// Same as `timeout`, but switches to `fallback` instead of erroring when
+  /// the source does not emit in time.
   #[inline]
-  fn debounce<SD>(
+  fn timeout_with<F, SD>(
     self,
     duration: Duration,
+    fallback: F,
     scheduler: SD,
-  ) -> DebounceOp<Self, SD> {
-    DebounceOp { source: self, duration, scheduler }
+  ) -> TimeoutOp<Self, F, SD>
+  where
+    F: ObservableExt<Item, Err>,
+  {
+    TimeoutOp {
+      source: self,
+      duration,
+      fallback,
+      scheduler,
+    }
   }
 
   /// Emits a value from the source Observable, then ignores subsequent source
   /// values for duration milliseconds, then repeats this process.
   ///
+  /// The `duration_selector` is called with the item starting a window to
+  /// derive its duration. An item given `Duration::ZERO` opens no window and
+  /// is emitted right away.
+  ///
   /// #Example
   /// ```
   /// use rxrust::{ prelude::*, ops::throttle::ThrottleEdge };
@@ -1380,6 +2825,10 @@ pub trait ObservableExt<Item, Err>: Sized {
 
   /// Returns an Observable that emits all items emitted by the source
   /// Observable that are distinct by comparison from previous items.
+  ///
+  /// Unlike `distinct_until_changed`, every emitted item is remembered until
+  /// the subscription ends, so an item is suppressed no matter how long ago
+  /// an equal one was emitted.
   #[inline]
   fn distinct(self) -> DistinctOp<Self> {
     DistinctOp { source: self }
@@ -1391,44 +2840,484 @@ pub trait ObservableExt<Item, Err>: Sized {
     DistinctKeyOp { source: self, key }
   }
 
+  /// Variant of distinct that only remembers the `capacity` most recently
+  /// seen items, so the memory stays bounded for a long-running source.
+  ///
+  /// A suppressed item counts as seen again. When a new item overflows the
+  /// capacity, the least recently seen item is forgotten, and an item equal
+  /// to a forgotten one is emitted again.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `capacity` is zero.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut values = vec![];
+  /// observable::from_iter([1, 2, 1, 3, 2, 1])
+  ///   .distinct_within(2)
+  ///   .subscribe(|v| values.push(v));
+  ///
+  /// // `2` is forgotten when `3` arrives, as `1` was seen after it, then `1`
+  /// // is forgotten when `2` arrives again.
+  /// assert_eq!(values, [1, 2, 3, 2, 1]);
+  /// ```
+  #[inline]
+  fn distinct_within(self, capacity: usize) -> DistinctWithinOp<Self> {
+    assert!(capacity > 0, "capacity must be greater than zero");
+    DistinctWithinOp { source: self, capacity }
+  }
+
+  /// Variant of distinct that forgets an emitted item once `duration` has
+  /// passed since its emission, an equal item arriving later is emitted
+  /// again.
+  ///
+  /// The forgotten items are also released periodically on `scheduler`, so
+  /// the memory stays bounded even when the source is quiet.
+  #[inline]
+  fn distinct_within_duration<SD>(
+    self,
+    duration: Duration,
+    scheduler: SD,
+  ) -> DistinctWithinDurationOp<Self, SD> {
+    DistinctWithinDurationOp { source: self, duration, scheduler }
+  }
+
   /// Only emit when the current value is different than the last
   #[inline]
-  fn distinct_until_changed(self) -> DistinctUntilChangedOp<Self> {
-    DistinctUntilChangedOp { source: self }
+  fn distinct_until_changed(self) -> DistinctUntilChangedOp<Self> {
+    DistinctUntilChangedOp { source: self }
+  }
+
+  /// Variant of distinct_until_changed that takes a key selector.
+  ///
+  /// Only the key of the last emitted item is retained and compared by
+  /// `PartialEq`, so the items themselves need neither `PartialEq` nor `Clone`.
+  #[inline]
+  fn distinct_until_key_changed<F>(
+    self,
+    key: F,
+  ) -> DistinctUntilKeyChangedOp<Self, F> {
+    DistinctUntilKeyChangedOp { source: self, key, compare: PartialEqKey }
+  }
+
+  /// Variant of distinct_until_key_changed that takes a comparator, which
+  /// returns `true` if the two keys are considered equal.
+  #[inline]
+  fn distinct_until_key_changed_by<F, C, K>(
+    self,
+    key: F,
+    compare: C,
+  ) -> DistinctUntilKeyChangedOp<Self, F, C>
+  where
+    F: Fn(&Item) -> K,
+    C: Fn(&K, &K) -> bool,
+  {
+    DistinctUntilKeyChangedOp { source: self, key, compare }
+  }
+
+  /// 'Zips up' two observable into a single observable of pairs.
+  ///
+  /// zip() returns a new observable that will emit over two other
+  /// observables,  returning a tuple where the first element comes from the
+  /// first observable, and  the second element comes from the second
+  /// observable.
+  ///
+  ///  In other words, it zips two observables together, into a single one.
+  ///
+  /// The observable running ahead has its items queued until the other one
+  /// catches up. Once one of them completes and its queue is exhausted, the
+  /// result completes and the other observable is unsubscribed.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .zip(observable::from_iter(['a', 'b', 'c', 'd']))
+  ///   .subscribe(|(i, c)| println!("{} {}", i, c));
+  ///
+  /// // print logs:
+  /// // 0 a
+  /// // 1 b
+  /// // 2 c
+  /// ```
+  #[inline]
+  fn zip<'a, Other, Item2>(
+    self,
+    other: Other,
+  ) -> ZipPairOp<'a, Self, Other, Item, Item2>
+  where
+    Other: ObservableExt<Item2, Err>,
+  {
+    ZipOp::new(self, other, |a, b| (a, b))
+  }
+
+  /// A threads safe version of `zip`
+  #[inline]
+  fn zip_threads<Other, Item2>(
+    self,
+    other: Other,
+  ) -> ZipPairOpThreads<Self, Other, Item, Item2>
+  where
+    Other: ObservableExt<Item2, Err>,
+  {
+    ZipOpThreads::new(self, other, |a, b| (a, b))
+  }
+
+  /// Same as `zip`, but applies `binary_op` to every pair of items and emits
+  /// its result instead of a tuple.
+  #[inline]
+  fn zip_with<'a, Other, Item2, BinaryOp, OutputItem>(
+    self,
+    other: Other,
+    binary_op: BinaryOp,
+  ) -> ZipOp<'a, Self, Other, BinaryOp, Item, Item2>
+  where
+    Other: ObservableExt<Item2, Err>,
+    BinaryOp: FnMut(Item, Item2) -> OutputItem,
+  {
+    ZipOp::new(self, other, binary_op)
+  }
+
+  /// A threads safe version of `zip_with`
+  #[inline]
+  fn zip_with_threads<Other, Item2, BinaryOp, OutputItem>(
+    self,
+    other: Other,
+    binary_op: BinaryOp,
+  ) -> ZipOpThreads<Self, Other, BinaryOp, Item, Item2>
+  where
+    Other: ObservableExt<Item2, Err>,
+    BinaryOp: FnMut(Item, Item2) -> OutputItem,
+  {
+    ZipOpThreads::new(self, other, binary_op)
+  }
+
+  /// Emits whether `other` emits the same items as this observable in the
+  /// same order, once both complete.
+  ///
+  /// Emits `false` as soon as a pair of items differs, or one observable has
+  /// more items than the other completed with, and unsubscribes both
+  /// observables at that moment.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .sequence_equal(observable::from_iter([0, 1, 2]))
+  ///   .subscribe(|b| println!("{}", b));
+  ///
+  /// // print logs:
+  /// // true
+  /// ```
+  #[inline]
+  fn sequence_equal<'a, Other>(
+    self,
+    other: Other,
+  ) -> SequenceEqualOp<'a, Self, Other, Item>
+  where
+    Other: ObservableExt<Item, Err>,
+    Item: PartialEq,
+  {
+    SequenceEqualOp::new(self, other)
+  }
+
+  /// A threads safe version of `sequence_equal`
+  #[inline]
+  fn sequence_equal_threads<Other>(
+    self,
+    other: Other,
+  ) -> SequenceEqualOpThreads<Self, Other, Item>
+  where
+    Other: ObservableExt<Item, Err>,
+    Item: PartialEq,
+  {
+    SequenceEqualOpThreads::new(self, other)
+  }
+
+  /// Resubscribes to the source when it errors, up to `count` times, and only
+  /// forwards the error once the attempts are exhausted. The items emitted
+  /// before a failure are forwarded as they are.
+  ///
+  /// Every attempt subscribes the source again, so it's meant for cold
+  /// observables, unsubscribing cancels the live attempt.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let attempts = std::cell::Cell::new(0);
+  /// observable::defer(|| {
+  ///   attempts.set(attempts.get() + 1);
+  ///   let attempt = attempts.get();
+  ///   observable::of_result(if attempt < 3 { Err(attempt) } else { Ok(0) })
+  /// })
+  /// .retry(2)
+  /// .on_error(|e| println!("failed {} times", e))
+  /// .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 0
+  /// ```
+  #[inline]
+  fn retry<'a>(self, count: usize) -> RetryOp<'a, Self>
+  where
+    Self: Clone,
+  {
+    RetryOp::new(self, count)
+  }
+
+  /// A threads safe version of `retry`
+  #[inline]
+  fn retry_threads(self, count: usize) -> RetryOpThreads<Self>
+  where
+    Self: Clone,
+  {
+    RetryOpThreads::new(self, count)
+  }
+
+  /// Same as `retry`, but resubscribes to the source every time it errors.
+  #[inline]
+  fn retry_forever<'a>(self) -> RetryOp<'a, Self>
+  where
+    Self: Clone,
+  {
+    RetryOp::new(self, usize::MAX)
+  }
+
+  /// A threads safe version of `retry_forever`
+  #[inline]
+  fn retry_forever_threads(self) -> RetryOpThreads<Self>
+  where
+    Self: Clone,
+  {
+    RetryOpThreads::new(self, usize::MAX)
+  }
+
+  /// Resubscribes to the source every time the notifier returned by `handler`
+  /// emits. The handler is called once per subscription with an observable of
+  /// the errors of the source, so the retries can be delayed or limited by
+  /// composing it. The completion or the error of the notifier terminates the
+  /// output.
+  ///
+  /// A source that fails synchronously is resubscribed in a loop instead of
+  /// recursively.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::{cell::Cell, rc::Rc, time::Duration};
+  ///
+  /// let mut local_pool = FuturesLocalSchedulerPool::new();
+  /// let scheduler = local_pool.spawner();
+  /// let attempts = Rc::new(Cell::new(0));
+  /// observable::defer(move || {
+  ///   attempts.set(attempts.get() + 1);
+  ///   let attempt = attempts.get();
+  ///   observable::of_result(if attempt < 3 { Err(()) } else { Ok(attempt) })
+  /// })
+  /// // retry three times at most, a little later every time.
+  /// .retry_when(move |errors| {
+  ///   errors
+  ///     .take(3)
+  ///     .scan_initial(0, |count, _| count + 1)
+  ///     .concat_map(move |count| {
+  ///       let delay = Duration::from_millis(count);
+  ///       observable::timer((), delay, scheduler.clone())
+  ///         .on_error_map(|e| match e {})
+  ///     })
+  /// })
+  /// .on_error(|_| println!("give up"))
+  /// .subscribe(|v| println!("{}", v));
+  ///
+  /// local_pool.run();
+  /// // print log:
+  /// // 3
+  /// ```
+  #[inline]
+  fn retry_when<'a, F, N, NotifyItem>(
+    self,
+    handler: F,
+  ) -> RetryWhenOp<'a, Self, F, NotifyItem>
+  where
+    Self: Clone,
+    F: FnOnce(Subject<'a, Err, Err>) -> N,
+    N: ObservableExt<NotifyItem, Err>,
+  {
+    RetryWhenOp::new(self, handler)
+  }
+
+  /// A threads safe version of `retry_when`
+  #[inline]
+  fn retry_when_threads<F, N, NotifyItem>(
+    self,
+    handler: F,
+  ) -> RetryWhenOpThreads<Self, F, NotifyItem>
+  where
+    Self: Clone,
+    F: FnOnce(SubjectThreads<Err, Err>) -> N,
+    N: ObservableExt<NotifyItem, Err>,
+  {
+    RetryWhenOpThreads::new(self, handler)
+  }
+
+  /// Resubscribes to the source every time it completes, until it has been
+  /// subscribed `count` times in total, then forwards the completion. An error
+  /// is forwarded right away and stops repeating.
+  ///
+  /// A source that completes synchronously is resubscribed in a loop instead
+  /// of recursively.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..2)
+  ///   .repeat(3)
+  ///   .subscribe(|v| print!("{} ", v));
+  ///
+  /// // print log:
+  /// // 0 1 0 1 0 1
+  /// ```
+  #[inline]
+  fn repeat<'a>(self, count: usize) -> RepeatOp<'a, Self>
+  where
+    Self: Clone,
+  {
+    RepeatOp::new(self, count)
+  }
+
+  /// A threads safe version of `repeat`
+  #[inline]
+  fn repeat_threads(self, count: usize) -> RepeatOpThreads<Self>
+  where
+    Self: Clone,
+  {
+    RepeatOpThreads::new(self, count)
+  }
+
+  /// Same as `repeat`, but resubscribes to the source every time it
+  /// completes.
+  #[inline]
+  fn repeat_forever<'a>(self) -> RepeatOp<'a, Self>
+  where
+    Self: Clone,
+  {
+    RepeatOp::new(self, usize::MAX)
+  }
+
+  /// A threads safe version of `repeat_forever`
+  #[inline]
+  fn repeat_forever_threads(self) -> RepeatOpThreads<Self>
+  where
+    Self: Clone,
+  {
+    RepeatOpThreads::new(self, usize::MAX)
+  }
+
+  /// Resubscribes to the source every time the notifier returned by `handler`
+  /// emits. The handler is called once per subscription with an observable
+  /// emitting `()` every time the source completes, so the repetitions can be
+  /// delayed or limited by composing it. The completion or the error of the
+  /// notifier terminates the output, an error of the source is forwarded
+  /// right away.
+  ///
+  /// A source that completes synchronously is resubscribed in a loop instead
+  /// of recursively.
+  ///
+  /// # Example
+  ///
+  /// Poll a value again a while after every successful poll.
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let mut local_pool = FuturesLocalSchedulerPool::new();
+  /// let scheduler = local_pool.spawner();
+  /// observable::of("polled")
+  ///   .repeat_when(move |completions| {
+  ///     completions.take(2).delay(Duration::from_millis(5), scheduler)
+  ///   })
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// local_pool.run();
+  /// // print log:
+  /// // polled
+  /// // polled
+  /// // polled
+  /// ```
+  #[inline]
+  fn repeat_when<'a, F, N, NotifyItem>(
+    self,
+    handler: F,
+  ) -> RepeatWhenOp<'a, Self, F, NotifyItem>
+  where
+    Self: Clone,
+    F: FnOnce(Subject<'a, (), Err>) -> N,
+    N: ObservableExt<NotifyItem, Err>,
+  {
+    RepeatWhenOp::new(self, handler)
   }
 
-  /// Variant of distinct_until_changed that takes a key selector.
+  /// A threads safe version of `repeat_when`
   #[inline]
-  fn distinct_until_key_changed<F>(
+  fn repeat_when_threads<F, N, NotifyItem>(
     self,
-    key: F,
-  ) -> DistinctUntilKeyChangedOp<Self, F> {
-    DistinctUntilKeyChangedOp { source: self, key }
+    handler: F,
+  ) -> RepeatWhenOpThreads<Self, F, NotifyItem>
+  where
+    Self: Clone,
+    F: FnOnce(SubjectThreads<(), Err>) -> N,
+    N: ObservableExt<NotifyItem, Err>,
+  {
+    RepeatWhenOpThreads::new(self, handler)
   }
 
-  /// 'Zips up' two observable into a single observable of pairs.
+  /// Mirrors whichever of `self` and `other` first emits an item, an error or
+  /// a completion, and unsubscribes the other one at that moment. See
+  /// [`observable::amb`](crate::observable::amb()) to race more than two
+  /// observables.
   ///
-  /// zip() returns a new observable that will emit over two other
-  /// observables,  returning a tuple where the first element comes from the
-  /// first observable, and  the second element comes from the second
-  /// observable.
+  /// `self` is subscribed first, if it emits synchronously `other` is never
+  /// subscribed.
   ///
-  ///  In other words, it zips two observables together, into a single one.
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut fast = Subject::default();
+  /// let mut slow = Subject::default();
+  /// slow
+  ///   .clone()
+  ///   .race(fast.clone())
+  ///   .subscribe(|v: &str| println!("{}", v));
+  ///
+  /// fast.next("fast");
+  /// slow.next("slow");
+  /// // print log:
+  /// // fast
+  /// ```
   #[inline]
-  fn zip<Other, Item2>(self, other: Other) -> ZipOp<Self, Other>
+  fn race<'a, Other>(self, other: Other) -> RaceOp<'a, Self, Other>
   where
-    Other: ObservableExt<Item2, Err>,
+    Other: ObservableExt<Item, Err>,
   {
-    ZipOp::new(self, other)
+    RaceOp::new(self, other)
   }
 
-  /// A threads safe version of `zip`
+  /// A threads safe version of `race`, the first signal is decided under a
+  /// lock, so only one side is ever mirrored.
   #[inline]
-  fn zip_threads<Other, Item2>(self, other: Other) -> ZipOpThreads<Self, Other>
+  fn race_threads<Other>(self, other: Other) -> RaceOpThreads<Self, Other>
   where
-    Other: ObservableExt<Item2, Err>,
+    Other: ObservableExt<Item, Err>,
   {
-    ZipOpThreads::new(self, other)
+    RaceOpThreads::new(self, other)
   }
 
   /// Combines the source Observable with other Observables to create an
@@ -1438,28 +3327,53 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// Whenever the source Observable emits a value, it computes a formula
   /// using that value plus the latest values from other input Observables,
   /// then emits the output of that formula.
+  ///
+  /// Values the source emits before `from` has emitted once are dropped, and
+  /// emissions of `from` alone produce nothing. Only the completion of the
+  /// source completes the result, an error from either side errors it.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut clicks = Subject::default();
+  /// let mut form = Subject::default();
+  /// clicks
+  ///   .clone()
+  ///   .with_latest_from(form.clone(), |_, state| state)
+  ///   .subscribe(|state: &str| println!("submit {}", state));
+  ///
+  /// form.next("draft");
+  /// clicks.next(());
+  /// // print log:
+  /// // submit draft
+  /// ```
   #[inline]
-  fn with_latest_from<From, OtherItem>(
+  fn with_latest_from<From, OtherItem, BinaryOp, OutputItem>(
     self,
     from: From,
-  ) -> WithLatestFromOp<Self, From>
+    binary_op: BinaryOp,
+  ) -> WithLatestFromOp<Self, From, BinaryOp, Item, OtherItem>
   where
     From: ObservableExt<OtherItem, Err>,
     OtherItem: Clone,
+    BinaryOp: FnMut(Item, OtherItem) -> OutputItem,
   {
-    WithLatestFromOp::new(self, from)
+    WithLatestFromOp::new(self, from, binary_op)
   }
 
   #[inline]
-  fn with_latest_from_threads<From, OtherItem>(
+  fn with_latest_from_threads<From, OtherItem, BinaryOp, OutputItem>(
     self,
     from: From,
-  ) -> WithLatestFromOpThreads<Self, From>
+    binary_op: BinaryOp,
+  ) -> WithLatestFromOpThreads<Self, From, BinaryOp, Item, OtherItem>
   where
     From: ObservableExt<OtherItem, Err>,
     OtherItem: Clone,
+    BinaryOp: FnMut(Item, OtherItem) -> OutputItem,
   {
-    WithLatestFromOpThreads::new(self, from)
+    WithLatestFromOpThreads::new(self, from, binary_op)
   }
 
   /// Emits default value if Observable completed with empty result
@@ -1483,6 +3397,128 @@ pub trait ObservableExt<Item, Err>: Sized {
     DefaultIfEmptyOp::new(self, default_value)
   }
 
+  /// Subscribes to a fallback Observable if Observable completed with empty
+  /// result.
+  ///
+  /// The fallback is not subscribed if the source emits any item or an error,
+  /// and unsubscribing cancels the fallback too.
+  ///
+  /// #Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::empty()
+  ///   .switch_if_empty(observable::from_iter(1..3))
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // Prints:
+  /// // 1
+  /// // 2
+  /// ```
+  #[inline]
+  fn switch_if_empty<F>(self, fallback: F) -> SwitchIfEmptyOp<Self, F>
+  where
+    F: ObservableExt<Item, Err>,
+  {
+    SwitchIfEmptyOp::new(self, fallback)
+  }
+
+  /// A threads safe version of `switch_if_empty`
+  #[inline]
+  fn switch_if_empty_threads<F>(
+    self,
+    fallback: F,
+  ) -> SwitchIfEmptyOpThreads<Self, F>
+  where
+    F: ObservableExt<Item, Err>,
+  {
+    SwitchIfEmptyOpThreads::new(self, fallback)
+  }
+
+  /// Replaces the source with the observable returned by `selector` when the
+  /// source errors, the error never reaches the downstream. The fallback is
+  /// subscribed with the same observer, so unsubscribing the returned
+  /// subscription unsubscribes it too, and its own error is forwarded.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::of_result(Err("timeout"))
+  ///   .on_error_resume_next(|e| observable::of(format!("cached: {}", e)))
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // Prints:
+  /// // cached: timeout
+  /// ```
+  #[inline]
+  fn on_error_resume_next<F, R>(
+    self,
+    selector: F,
+  ) -> OnErrorResumeNextOp<Self, F, Err>
+  where
+    F: FnOnce(Err) -> R,
+  {
+    OnErrorResumeNextOp::new(self, selector)
+  }
+
+  /// A threads safe version of `on_error_resume_next`
+  #[inline]
+  fn on_error_resume_next_threads<F, R>(
+    self,
+    selector: F,
+  ) -> OnErrorResumeNextOpThreads<Self, F, Err>
+  where
+    F: FnOnce(Err) -> R,
+  {
+    OnErrorResumeNextOpThreads::new(self, selector)
+  }
+
+  /// Emits `value` and completes instead of forwarding the error of the
+  /// source, a shortcut of `on_error_resume_next` with `observable::of`.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::of_result(Err("fail"))
+  ///   .catch_error_return(0)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // Prints:
+  /// // 0
+  /// ```
+  #[inline]
+  fn catch_error_return(
+    self,
+    value: Item,
+  ) -> OnErrorResumeNextOp<
+    Self,
+    impl FnOnce(Err) -> of::OfObservable<Item> + Clone,
+    Err,
+  >
+  where
+    Item: Clone,
+  {
+    OnErrorResumeNextOp::new(self, move |_| observable::of(value))
+  }
+
+  /// A threads safe version of `catch_error_return`
+  #[inline]
+  fn catch_error_return_threads(
+    self,
+    value: Item,
+  ) -> OnErrorResumeNextOpThreads<
+    Self,
+    impl FnOnce(Err) -> of::OfObservable<Item> + Clone,
+    Err,
+  >
+  where
+    Item: Clone,
+  {
+    OnErrorResumeNextOpThreads::new(self, move |_| observable::of(value))
+  }
+
   /// Buffers emitted values of type T in a Vec<T> and
   /// emits that Vec<T> as soon as the buffer's size equals
   /// the given count.
@@ -1492,6 +3528,10 @@ pub trait ObservableExt<Item, Err>: Sized {
   ///
   /// The operator never returns an empty buffer.
   ///
+  /// # Panics
+  ///
+  /// Panics if `count` is zero.
+  ///
   /// #Example
   /// ```
   /// use rxrust::prelude::*;
@@ -1506,86 +3546,282 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// ```
   #[inline]
   fn buffer_with_count(self, count: usize) -> BufferWithCountOp<Self> {
+    assert!(
+      count > 0,
+      "buffer_with_count: count must be greater than zero"
+    );
     BufferWithCountOp { source: self, count }
   }
 
-  /// Buffers emitted values of type T in a Vec<T> and
-  /// emits that Vec<T> periodically.
+  /// Buffers emitted values of type T in a Vec<T> and
+  /// emits that Vec<T> periodically.
+  ///
+  /// On complete, if the buffer is not empty,
+  /// it will be emitted.
+  /// On error, the buffer will be discarded.
+  ///
+  /// The operator never returns an empty buffer.
+  ///
+  /// #Example
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let pool = FuturesThreadPoolScheduler::new().unwrap();
+  ///
+  /// observable::create(|mut subscriber: SubscriberThreads<_>| {
+  ///   subscriber.next(0);
+  ///   subscriber.next(1);
+  ///   std::thread::sleep(Duration::from_millis(100));
+  ///   subscriber.next(2);
+  ///   subscriber.next(3);
+  ///   subscriber.complete();
+  /// })
+  ///   .buffer_with_time(Duration::from_millis(50), pool)
+  ///   .subscribe(|vec| println!("{:?}", vec));
+  ///
+  /// // Prints:
+  /// // [0, 1]
+  /// // [2, 3]
+  /// ```
+  #[inline]
+  fn buffer_with_time<S>(
+    self,
+    time: Duration,
+    scheduler: S,
+  ) -> BufferWithTimeOp<Self, S> {
+    BufferWithTimeOp {
+      source: self,
+      time,
+      scheduler,
+      emit_empty: false,
+    }
+  }
+
+  /// Same as `buffer_with_time`, but emits an empty Vec<T> for every period
+  /// in which the source emitted nothing.
+  ///
+  /// On complete, if the buffer is not empty,
+  /// it will be emitted.
+  /// On error, the buffer will be discarded.
+  ///
+  /// #Example
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let pool = FuturesThreadPoolScheduler::new().unwrap();
+  ///
+  /// observable::create(|mut subscriber: SubscriberThreads<_>| {
+  ///   subscriber.next(0);
+  ///   subscriber.next(1);
+  ///   std::thread::sleep(Duration::from_millis(120));
+  ///   subscriber.next(2);
+  ///   subscriber.complete();
+  /// })
+  ///   .buffer_with_time_emit_empty(Duration::from_millis(50), pool)
+  ///   .subscribe(|vec| println!("{:?}", vec));
+  ///
+  /// // Prints:
+  /// // [0, 1]
+  /// // []
+  /// // [2]
+  /// ```
+  #[inline]
+  fn buffer_with_time_emit_empty<S>(
+    self,
+    time: Duration,
+    scheduler: S,
+  ) -> BufferWithTimeOp<Self, S> {
+    BufferWithTimeOp {
+      source: self,
+      time,
+      scheduler,
+      emit_empty: true,
+    }
+  }
+
+  /// Buffers emitted values of type T in a Vec<T> and
+  /// emits that Vec<T> either if the buffer's size equals count, or
+  /// periodically. This operator combines the functionality of
+  /// buffer_with_count and buffer_with_time.
+  ///
+  /// Every time a buffer is emitted because it's full, the time window is
+  /// restarted.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `count` is zero.
+  ///
+  /// #Example
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let pool = FuturesThreadPoolScheduler::new().unwrap();
+  ///
+  /// observable::create(|mut subscriber: SubscriberThreads<_>| {
+  ///   subscriber.next(0);
+  ///   subscriber.next(1);
+  ///   subscriber.next(2);
+  ///   std::thread::sleep(Duration::from_millis(100));
+  ///   subscriber.next(3);
+  ///   subscriber.next(4);
+  ///   subscriber.complete();
+  /// })
+  ///   .buffer_with_count_and_time(2, Duration::from_millis(50), pool)
+  ///   .subscribe(|vec| println!("{:?}", vec));
+  ///
+  /// // Prints:
+  /// // [0, 1]
+  /// // [2]
+  /// // [3, 4]
+  /// ```
+  #[inline]
+  fn buffer_with_count_and_time<S>(
+    self,
+    count: usize,
+    time: Duration,
+    scheduler: S,
+  ) -> BufferWithCountOrTimerOp<Self, S> {
+    assert!(
+      count > 0,
+      "buffer_with_count_and_time: count must be greater than zero"
+    );
+    BufferWithCountOrTimerOp { source: self, count, time, scheduler }
+  }
+
+  /// Buffers emitted values of type T in a Vec<T>, the `closing_selector` is
+  /// called to get an observable when a buffer is opened, and the first
+  /// emission of that observable emits the buffer and opens a new one.
+  ///
+  /// On complete, if the buffer is not empty,
+  /// it will be emitted.
+  /// On error of the source or the closing observable, the buffer will be
+  /// discarded and the error is propagated.
+  ///
+  /// The operator never returns an empty buffer.
+  ///
+  /// #Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut source = Subject::default();
+  /// let mut flush = Subject::default();
+  /// let flush_c = flush.clone();
+  ///
+  /// source
+  ///   .clone()
+  ///   .buffer_when(move || flush_c.clone())
+  ///   .subscribe(|vec: Vec<i32>| println!("{:?}", vec));
+  ///
+  /// source.next(0);
+  /// source.next(1);
+  /// flush.next(());
+  /// source.next(2);
+  /// source.complete();
+  ///
+  /// // Prints:
+  /// // [0, 1]
+  /// // [2]
+  /// ```
+  #[inline]
+  fn buffer_when<'a, F, CloseItem>(
+    self,
+    closing_selector: F,
+  ) -> BufferWhenOp<'a, Self, F, CloseItem> {
+    BufferWhenOp::new(self, closing_selector)
+  }
+
+  /// A threads safe version of `buffer_when`
+  #[inline]
+  fn buffer_when_threads<F, CloseItem>(
+    self,
+    closing_selector: F,
+  ) -> BufferWhenOpThreads<Self, F, CloseItem> {
+    BufferWhenOpThreads::new(self, closing_selector)
+  }
+
+  /// Branch out source values into nested observables, each window emits
+  /// `count` values and then completes. A window is opened by the first
+  /// value after the previous window closed, so the emitted windows are never
+  /// empty.
   ///
-  /// On complete, if the buffer is not empty,
-  /// it will be emitted.
-  /// On error, the buffer will be discarded.
+  /// `Subject` is the type of the emitted windows, `Subject<_, _>` or
+  /// `SubjectThreads<_, _>`.
   ///
-  /// The operator never returns an empty buffer.
+  /// On complete or error of the source, the open window is completed or
+  /// errored first.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `count` is zero.
   ///
   /// #Example
   /// ```
   /// use rxrust::prelude::*;
-  /// use std::time::Duration;
-  ///
-  /// let pool = FuturesThreadPoolScheduler::new().unwrap();
   ///
-  /// observable::create(|mut subscriber: SubscriberThreads<_>| {
-  ///   subscriber.next(0);
-  ///   subscriber.next(1);
-  ///   std::thread::sleep(Duration::from_millis(100));
-  ///   subscriber.next(2);
-  ///   subscriber.next(3);
-  ///   subscriber.complete();
-  /// })
-  ///   .buffer_with_time(Duration::from_millis(50), pool)
-  ///   .subscribe(|vec| println!("{:?}", vec));
+  /// observable::from_iter(0..7)
+  ///   .window_with_count::<Subject<_, _>>(3)
+  ///   .flat_map(|window| window.sum())
+  ///   .subscribe(|sum| println!("{}", sum));
   ///
   /// // Prints:
-  /// // [0, 1]
-  /// // [2, 3]
+  /// // 3
+  /// // 12
+  /// // 6
   /// ```
   #[inline]
-  fn buffer_with_time<S>(
+  fn window_with_count<Subject>(
     self,
-    time: Duration,
-    scheduler: S,
-  ) -> BufferWithTimeOp<Self, S> {
-    BufferWithTimeOp { source: self, time, scheduler }
+    count: usize,
+  ) -> WindowWithCountOp<Self, Subject>
+  where
+    Subject: Clone + Default + Observer<Item, Err>,
+  {
+    assert!(
+      count > 0,
+      "window_with_count: count must be greater than zero"
+    );
+    WindowWithCountOp::new(self, count)
   }
 
-  /// Buffers emitted values of type T in a Vec<T> and
-  /// emits that Vec<T> either if the buffer's size equals count, or
-  /// periodically. This operator combines the functionality of
-  /// buffer_with_count and buffer_with_time.
+  /// Branch out source values into nested observables periodically, a new
+  /// window is opened every `time` and the previous one is completed. A
+  /// window is emitted even if no value arrives during its period.
+  ///
+  /// `Subject` is the type of the emitted windows, `Subject<_, _>` or
+  /// `SubjectThreads<_, _>`.
+  ///
+  /// On complete or error of the source, the open window is completed or
+  /// errored first.
   ///
   /// #Example
   /// ```
   /// use rxrust::prelude::*;
   /// use std::time::Duration;
+  /// use futures::executor::LocalPool;
   ///
-  /// let pool = FuturesThreadPoolScheduler::new().unwrap();
-  ///
-  /// observable::create(|mut subscriber: SubscriberThreads<_>| {
-  ///   subscriber.next(0);
-  ///   subscriber.next(1);
-  ///   subscriber.next(2);
-  ///   std::thread::sleep(Duration::from_millis(100));
-  ///   subscriber.next(3);
-  ///   subscriber.next(4);
-  ///   subscriber.complete();
-  /// })
-  ///   .buffer_with_count_and_time(2, Duration::from_millis(50), pool)
-  ///   .subscribe(|vec| println!("{:?}", vec));
+  /// let mut local_scheduler = LocalPool::new();
+  /// let spawner = local_scheduler.spawner();
+  /// observable::interval(Duration::from_millis(1), spawner.clone())
+  ///   .take(10)
+  ///   .window_with_time::<Subject<_, _>, _>(Duration::from_millis(3), spawner)
+  ///   .flat_map(|window| window.count())
+  ///   .subscribe(|count| println!("{}", count));
   ///
-  /// // Prints:
-  /// // [0, 1]
-  /// // [2]
-  /// // [3, 4]
+  /// local_scheduler.run();
   /// ```
   #[inline]
-  fn buffer_with_count_and_time<S>(
+  fn window_with_time<Subject, SD>(
     self,
-    count: usize,
     time: Duration,
-    scheduler: S,
-  ) -> BufferWithCountOrTimerOp<Self, S> {
-    BufferWithCountOrTimerOp { source: self, count, time, scheduler }
+    scheduler: SD,
+  ) -> WindowWithTimeOp<Self, SD, Subject>
+  where
+    Subject: Clone + Default + Observer<Item, Err>,
+  {
+    WindowWithTimeOp::new(self, time, scheduler)
   }
 
   /// Emits item which is combining latest items from two observables.
@@ -1594,7 +3830,12 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// by applying a binary operator on the latest item of two observable
   /// whenever each of observables produces an element.
   ///
-  /// #Example
+  /// Nothing is emitted until both observables have emitted at least once,
+  /// the latest items are cached and cloned for every combination. The result
+  /// completes once both observables complete, and errors as soon as either
+  /// of them errors.
+  ///
+  /// # Example
   /// ```
   /// use rxrust::prelude::*;
   /// use std::time::Duration;
@@ -1622,7 +3863,7 @@ pub trait ObservableExt<Item, Err>: Sized {
     self,
     other: Other,
     binary_op: BinaryOp,
-  ) -> CombineLatestOp<Self, Other, BinaryOp>
+  ) -> CombineLatestOp<Self, Other, BinaryOp, Item, OtherItem>
   where
     Other: ObservableExt<OtherItem, Err>,
     BinaryOp: FnMut(Item, OtherItem) -> OutputItem,
@@ -1634,7 +3875,7 @@ pub trait ObservableExt<Item, Err>: Sized {
     self,
     other: Other,
     binary_op: BinaryOp,
-  ) -> CombineLatestOpThread<Self, Other, BinaryOp>
+  ) -> CombineLatestOpThread<Self, Other, BinaryOp, Item, OtherItem>
   where
     Other: ObservableExt<OtherItem, Err>,
     BinaryOp: FnMut(Item, OtherItem) -> OutputItem,
@@ -1645,23 +3886,248 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// Returns an observable that, at the moment of subscription, will
   /// synchronously emit all values provided to this operator, then subscribe
   /// to the source and mirror all of its emissions to subscribers.
-  fn start_with<B>(self, values: Vec<B>) -> StartWithOp<Self, B> {
+  ///
+  /// The values are emitted again to every new subscriber, so `values` must be
+  /// `Clone` to subscribe more than once. Pass `[value]` to start with a
+  /// single value. If the subscriber is finished by the prepended values, the
+  /// source is never subscribed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(1..3)
+  ///   .start_with([0])
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print logs:
+  /// // 0
+  /// // 1
+  /// // 2
+  /// ```
+  fn start_with<I>(self, values: I) -> StartWithOp<Self, I>
+  where
+    I: IntoIterator<Item = Item>,
+  {
     StartWithOp { source: self, values }
   }
 
   /// Groups pairs of consecutive emissions together and emits them as an pair
   /// of two values.
+  ///
+  /// The pairs are `(previous, current)`, so the first one is emitted with the
+  /// second item of the source, a source with less than two items emits
+  /// nothing. Only the previous item is kept and cloned.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter([1, 4, 9])
+  ///   .pairwise()
+  ///   .subscribe(|(prev, cur)| println!("{}", cur - prev));
+  ///
+  /// // print logs:
+  /// // 3
+  /// // 5
+  /// ```
   fn pairwise(self) -> PairwiseOp<Self> {
     PairwiseOp { source: self }
   }
 
   /// Used to perform side-effects for notifications from the source observable
+  ///
+  /// The closure receives a reference to every item before it's forwarded, so
+  /// the items don't need to be `Clone`. Same as `tap_next`, see `tap_error`,
+  /// `tap_complete` and `tap_with` to observe the other signals.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .tap(|v| println!("before map {}", v))
+  ///   .map(|v| v * 2)
+  ///   .subscribe(|v| println!("after map {}", v));
+  /// ```
+  #[inline]
+  fn tap<F>(self, f: F) -> TapOp<Self, TapNext<F>>
+  where
+    F: FnMut(&Item),
+  {
+    TapOp { source: self, tap: TapNext(f) }
+  }
+
+  /// Calls `f` with a reference to every item before forwarding it.
   #[inline]
-  fn tap<F>(self, f: F) -> TapOp<Self, F>
+  fn tap_next<F>(self, f: F) -> TapOp<Self, TapNext<F>>
   where
     F: FnMut(&Item),
   {
-    TapOp { source: self, func: f }
+    TapOp { source: self, tap: TapNext(f) }
+  }
+
+  /// Calls `f` with a reference to the error before forwarding it.
+  #[inline]
+  fn tap_error<F>(self, f: F) -> TapOp<Self, TapError<F>>
+  where
+    F: FnOnce(&Err),
+  {
+    TapOp { source: self, tap: TapError(Some(f)) }
+  }
+
+  /// Calls `f` before forwarding the completion.
+  #[inline]
+  fn tap_complete<F>(self, f: F) -> TapOp<Self, TapComplete<F>>
+  where
+    F: FnOnce(),
+  {
+    TapOp { source: self, tap: TapComplete(Some(f)) }
+  }
+
+  /// Runs the side effects of a [`Tap`](crate::ops::tap::Tap) for all the
+  /// signals of the source, like an observer which only borrows the items and
+  /// the error.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::{ops::tap::Tap, prelude::*};
+  ///
+  /// struct Logger;
+  ///
+  /// impl<Item, Err> Tap<Item, Err> for Logger
+  /// where
+  ///   Item: std::fmt::Debug,
+  ///   Err: std::fmt::Debug,
+  /// {
+  ///   fn tap_next(&mut self, value: &Item) {
+  ///     println!("next {:?}", value);
+  ///   }
+  ///
+  ///   fn tap_error(&mut self, err: &Err) {
+  ///     println!("error {:?}", err);
+  ///   }
+  ///
+  ///   fn tap_complete(&mut self) {
+  ///     println!("complete");
+  ///   }
+  /// }
+  ///
+  /// observable::from_iter(0..2)
+  ///   .tap_with(Logger)
+  ///   .subscribe(|_| {});
+  ///
+  /// // print log:
+  /// // next 0
+  /// // next 1
+  /// // complete
+  /// ```
+  #[inline]
+  fn tap_with<T>(self, tap: T) -> TapOp<Self, T>
+  where
+    T: Tap<Item, Err>,
+  {
+    TapOp { source: self, tap }
+  }
+
+  /// Emits every signal of the source as a
+  /// [`Notification`](crate::ops::materialize::Notification) item, then
+  /// completes after the error or the completion of the source.
+  ///
+  /// The output never errors, it keeps the error type of the source so it can
+  /// be turned back with `dematerialize`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..2)
+  ///   .materialize()
+  ///   .subscribe(|n| println!("{:?}", n));
+  ///
+  /// // print log:
+  /// // Next(0)
+  /// // Next(1)
+  /// // Complete
+  /// ```
+  #[inline]
+  fn materialize(self) -> MaterializeOp<Self> {
+    MaterializeOp { source: self }
+  }
+
+  /// Replays the [`Notification`](crate::ops::materialize::Notification)
+  /// items of the source as signals, the reverse of `materialize`. Nothing is
+  /// forwarded after an `Error` or a `Complete` notification.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::{ops::materialize::Notification, prelude::*};
+  ///
+  /// observable::from_iter([
+  ///   Notification::Next(1),
+  ///   Notification::Complete,
+  ///   Notification::Next(2),
+  /// ])
+  /// .dematerialize()
+  /// .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 1
+  /// ```
+  #[inline]
+  fn dematerialize(self) -> DematerializeOp<Self> {
+    DematerializeOp { source: self }
+  }
+
+  /// Emits every item of the source as a
+  /// [`TimeInterval`](crate::ops::time_interval::TimeInterval) with the time
+  /// elapsed since the previous item, or since the subscription for the first
+  /// one.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let mut local_pool = FuturesLocalSchedulerPool::new();
+  /// observable::interval(Duration::from_millis(10), local_pool.spawner())
+  ///   .take(3)
+  ///   .time_interval()
+  ///   .subscribe(|t| println!("{} after {:?}", t.value, t.interval));
+  ///
+  /// local_pool.run();
+  /// ```
+  #[inline]
+  fn time_interval(self) -> TimeIntervalOp<Self> {
+    TimeIntervalOp { source: self }
+  }
+
+  /// Emits every item of the source as a
+  /// [`Timestamped`](crate::ops::timestamp::Timestamped) with the instant it
+  /// was received at.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::{ops::timestamp::Timestamped, prelude::*};
+  /// use std::time::Instant;
+  ///
+  /// let start = Instant::now();
+  /// observable::from_iter(0..3)
+  ///   .timestamp()
+  ///   .map(move |Timestamped { value, timestamp }| (value, timestamp - start))
+  ///   .subscribe(|(v, elapsed)| println!("{} at {:?}", v, elapsed));
+  /// ```
+  #[inline]
+  fn timestamp(self) -> TimestampOp<Self> {
+    TimestampOp { source: self }
   }
 
   /// Process the error of the observable and the return observable can't catch the error any more.
@@ -1712,13 +4178,29 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// // prints: [2,4,6]
   /// ```
   #[inline]
-  fn collect<C>(self) -> CollectOp<Self, C>
+  fn collect<C>(self) -> CollectOp<Self, C, Item>
   where
-    C: IntoIterator + Extend<C::Item> + Default,
+    C: Extend<Item> + Default,
   {
     self.collect_into(C::default())
   }
 
+  /// Emits all the items of the observable in a `Vec` when it completes, a
+  /// shortcut of `collect::<Vec<_>>()`. Nothing is emitted if it errors.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .to_vec()
+  ///   .subscribe(|values| assert_eq!(values, vec![0, 1, 2]));
+  /// ```
+  #[inline]
+  fn to_vec(self) -> CollectOp<Self, Vec<Item>, Item> {
+    self.collect_into(Vec::new())
+  }
+
   /// Collects all the items emitted by the observable into the given collection.
   ///
   /// # Example
@@ -1739,9 +4221,9 @@ pub trait ObservableExt<Item, Err>: Sized {
   /// }
   /// ```
   #[inline]
-  fn collect_into<C>(self, collection: C) -> CollectOp<Self, C>
+  fn collect_into<C>(self, collection: C) -> CollectOp<Self, C, Item>
   where
-    C: IntoIterator + Extend<C::Item>,
+    C: Extend<Item>,
   {
     CollectOp::new(self, collection)
   }
@@ -1813,6 +4295,31 @@ mod tests {
     s.element_at(21).subscribe(|_| panic!());
   }
 
+  #[test]
+  fn element_at_stop_source() {
+    let mut pulled = 0;
+    let mut completed = false;
+    let mut value = None;
+    observable::from_iter((0..).inspect(|_| pulled += 1))
+      .element_at(3)
+      .on_complete(|| completed = true)
+      .subscribe(|v| value = Some(v));
+
+    assert_eq!(value, Some(3));
+    assert_eq!(pulled, 4);
+    assert!(completed);
+  }
+
+  #[test]
+  fn element_at_or() {
+    let s = observable::from_iter(0..3);
+    let mut values = vec![];
+    s.clone().element_at_or(1, -1).subscribe(|v| values.push(v));
+    s.element_at_or(3, -1).subscribe(|v| values.push(v));
+
+    assert_eq!(values, [1, -1]);
+  }
+
   #[test]
   fn bench_element_at() {
     do_bench_element_at();
@@ -1884,6 +4391,49 @@ mod tests {
     b.iter(first_or);
   }
 
+  #[test]
+  fn first_with() {
+    let mut completed = false;
+    let mut values = vec![];
+
+    observable::from_iter(0..10)
+      .first_with(|v| *v > 2)
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![3]);
+    assert!(completed);
+
+    let mut completed = false;
+    observable::from_iter(0..10)
+      .first_with(|v| *v > 20)
+      .on_complete(|| completed = true)
+      .subscribe(|_| unreachable!());
+    assert!(completed);
+  }
+
+  #[test]
+  fn first_with_stop_infinite_source() {
+    use crate::rc::{MutRc, RcDeref, RcDerefMut};
+    use futures::executor::LocalPool;
+
+    let mut pool = LocalPool::new();
+    let produced = MutRc::own(0);
+    let c_produced = produced.clone();
+    let value = MutRc::own(None);
+    let c_value = value.clone();
+
+    observable::interval(Duration::from_millis(1), pool.spawner())
+      .tap(move |_| *c_produced.rc_deref_mut() += 1)
+      .first_with(|v| *v == 3)
+      .subscribe(move |v| *c_value.rc_deref_mut() = Some(v));
+    // Only returns once the interval stops.
+    pool.run();
+
+    assert_eq!(*value.rc_deref(), Some(3));
+    assert_eq!(*produced.rc_deref(), 4);
+  }
+
   #[test]
   fn first_support_fork() {
     let mut value = 0;
@@ -1924,6 +4474,29 @@ mod tests {
       .subscribe(move |_| panic!());
   }
 
+  #[test]
+  fn ignore_elements_mirror_termination() {
+    struct NotClone;
+
+    let mut completed = false;
+    observable::from_iter([NotClone, NotClone])
+      .ignore_elements()
+      .on_complete(|| completed = true)
+      .subscribe(|_| panic!());
+    assert!(completed);
+
+    let mut error = None;
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .ignore_elements()
+      .on_error(|e| error = Some(e))
+      .subscribe(|_: i32| panic!());
+    subject.next(1);
+    subject.error("err");
+    assert_eq!(error, Some("err"));
+  }
+
   #[test]
   fn bench_ignore() {
     do_bench_ignore();
@@ -1945,6 +4518,32 @@ mod tests {
       .subscribe(|b| assert!(!b));
   }
 
+  #[test]
+  fn all_empty() {
+    let mut values = vec![];
+    observable::empty()
+      .all(|v: i32| v > 0)
+      .subscribe(|b| values.push(b));
+    assert_eq!(values, vec![true]);
+  }
+
+  #[test]
+  fn all_short_circuit() {
+    let mut produced = 0;
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::from_iter(0..)
+      .tap(|_| produced += 1)
+      .all(|v| v < 2)
+      .on_complete(|| completed = true)
+      .subscribe(|b| values.push(b));
+
+    assert_eq!(produced, 3);
+    assert_eq!(values, vec![false]);
+    assert!(completed);
+  }
+
   #[test]
   fn bench_all() {
     do_bench_all();
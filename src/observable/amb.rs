@@ -0,0 +1,362 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+/// Creates an observable that mirrors the first of `sources` to emit an item,
+/// an error or a completion. All the other sources are unsubscribed as soon as
+/// the winner is known.
+///
+/// The sources are subscribed in order, if one of them emits synchronously
+/// when subscribed, the sources after it are never subscribed.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let mut primary = Subject::default();
+/// let mut fallback = Subject::default();
+/// observable::amb([primary.clone(), fallback.clone()])
+///   .subscribe(|v: &str| println!("{}", v));
+///
+/// fallback.next("fallback");
+/// primary.next("primary");
+/// // print log:
+/// // fallback
+/// ```
+pub fn amb<'a, S>(
+  sources: impl IntoIterator<Item = S>,
+) -> AmbObservable<'a, S> {
+  AmbObservable {
+    sources: sources.into_iter().collect(),
+    _hint: TypeHint::new(),
+  }
+}
+
+/// A threads safe version of [`amb`], the winner is decided under the lock of
+/// the shared state, so only one source can win.
+pub fn amb_threads<S>(
+  sources: impl IntoIterator<Item = S>,
+) -> AmbObservableThreads<S> {
+  AmbObservableThreads { sources: sources.into_iter().collect() }
+}
+
+#[derive(Clone)]
+pub struct AmbObservable<'a, S> {
+  sources: Vec<S>,
+  _hint: TypeHint<&'a ()>,
+}
+
+#[derive(Clone)]
+pub struct AmbObservableThreads<S> {
+  sources: Vec<S>,
+}
+
+macro_rules! impl_amb_observable {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S> Observable<Item, Err, O>
+      for $name<$($lf,)? S>
+    where
+      O: Observer<Item, Err>,
+      S: Observable<Item, Err, AmbObserver<$rc<AmbState<O, $box_unsub>>>>,
+      S::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = AmbSubscription<$rc<AmbState<O, $box_unsub>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(AmbState::new(observer));
+        for (index, source) in self.sources.into_iter().enumerate() {
+          if state.rc_deref().is_decided() {
+            break;
+          }
+          let observer = AmbObserver { state: state.clone(), index };
+          let unsub = source.actual_subscribe(observer);
+          add_subscription(&state, index, <$box_unsub>::new(unsub));
+        }
+        AmbSubscription(state)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S> ObservableExt<Item, Err> for $name<$($lf,)? S>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_amb_observable!(AmbObservable, MutRc, BoxSubscription<'a>, 'a);
+impl_amb_observable!(
+  AmbObservableThreads,
+  MutArc,
+  BoxSubscriptionThreads,
+  Send
+);
+
+/// The state shared by all the candidates of a race.
+pub struct AmbState<O, U> {
+  observer: Option<O>,
+  winner: Option<usize>,
+  subscriptions: Vec<(usize, U)>,
+}
+
+impl<O, U> AmbState<O, U> {
+  pub(crate) fn new(observer: O) -> Self {
+    AmbState {
+      observer: Some(observer),
+      winner: None,
+      subscriptions: vec![],
+    }
+  }
+
+  pub(crate) fn is_decided(&self) -> bool {
+    self.winner.is_some()
+  }
+
+  /// Makes the `index`th source the winner if there isn't one, and returns
+  /// the subscriptions of the losers. Returns `None` if another source won.
+  fn win(&mut self, index: usize) -> Option<Vec<U>> {
+    match self.winner {
+      Some(winner) => (winner == index).then(Vec::new),
+      None => {
+        self.winner = Some(index);
+        let (winner, losers) = std::mem::take(&mut self.subscriptions)
+          .into_iter()
+          .partition(|(i, _)| *i == index);
+        self.subscriptions = winner;
+        Some(losers.into_iter().map(|(_, u)| u).collect())
+      }
+    }
+  }
+}
+
+/// Keeps the subscription of the `index`th source, or unsubscribes it right
+/// away if another source already won.
+pub(crate) fn add_subscription<S, O, U>(state: &S, index: usize, unsub: U)
+where
+  S: RcDerefMut<Target = AmbState<O, U>>,
+  U: Subscription,
+{
+  let mut inner = state.rc_deref_mut();
+  if inner.winner.is_some_and(|winner| winner != index) {
+    drop(inner);
+    unsub.unsubscribe();
+  } else {
+    inner.subscriptions.push((index, unsub));
+  }
+}
+
+pub struct AmbObserver<S> {
+  pub(crate) state: S,
+  pub(crate) index: usize,
+}
+
+impl<Item, Err, O, U, S> Observer<Item, Err> for AmbObserver<S>
+where
+  S: RcDerefMut<Target = AmbState<O, U>> + RcDeref<Target = AmbState<O, U>>,
+  O: Observer<Item, Err>,
+  U: Subscription,
+{
+  fn next(&mut self, value: Item) {
+    let losers = {
+      let mut state = self.state.rc_deref_mut();
+      let Some(losers) = state.win(self.index) else {
+        return;
+      };
+      if let Some(observer) = state.observer.as_mut() {
+        observer.next(value);
+      }
+      losers
+    };
+    losers.into_iter().for_each(|u| u.unsubscribe());
+  }
+
+  fn error(self, err: Err) {
+    let mut state = self.state.rc_deref_mut();
+    if let Some(losers) = state.win(self.index) {
+      let observer = state.observer.take();
+      drop(state);
+      losers.into_iter().for_each(|u| u.unsubscribe());
+      if let Some(observer) = observer {
+        observer.error(err);
+      }
+    }
+  }
+
+  fn complete(self) {
+    let mut state = self.state.rc_deref_mut();
+    if let Some(losers) = state.win(self.index) {
+      let observer = state.observer.take();
+      drop(state);
+      losers.into_iter().for_each(|u| u.unsubscribe());
+      if let Some(observer) = observer {
+        observer.complete();
+      }
+    }
+  }
+
+  fn is_finished(&self) -> bool {
+    let state = self.state.rc_deref();
+    state.winner.is_some_and(|winner| winner != self.index)
+      || state.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+}
+
+pub struct AmbSubscription<S>(pub(crate) S);
+
+impl<S, O, U> Subscription for AmbSubscription<S>
+where
+  S: RcDerefMut<Target = AmbState<O, U>> + RcDeref<Target = AmbState<O, U>>,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let subscriptions =
+      std::mem::take(&mut self.0.rc_deref_mut().subscriptions);
+    subscriptions.into_iter().for_each(|(_, u)| u.unsubscribe());
+  }
+
+  fn is_closed(&self) -> bool {
+    self.0.rc_deref().subscriptions.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    observable::fake_timer::FakeClock,
+    ops::box_it::BoxOp,
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
+
+  #[test]
+  fn first_emission_wins() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let clock = FakeClock::default();
+
+    let sources: Vec<BoxOp<_, _>> = vec![
+      clock
+        .interval(Duration::from_millis(3))
+        .map(|v| (3, v))
+        .box_it(),
+      clock
+        .interval(Duration::from_millis(2))
+        .map(|v| (2, v))
+        .box_it(),
+      clock
+        .interval(Duration::from_millis(5))
+        .map(|v| (5, v))
+        .box_it(),
+    ];
+    observable::amb(sources)
+      .take(3)
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    clock.advance(Duration::from_millis(10));
+
+    assert_eq!(&*values.rc_deref(), &[(2, 0), (2, 1), (2, 2)]);
+  }
+
+  #[test]
+  fn unsubscribe_losers() {
+    let finalized = MutRc::own(vec![]);
+    let mut sources = vec![];
+    for i in 0..3 {
+      let c_finalized = finalized.clone();
+      let subject = Subject::<i32, _>::default();
+      sources.push((
+        subject.clone(),
+        subject.finalize(move || c_finalized.rc_deref_mut().push(i)),
+      ));
+    }
+    let mut second = sources[1].0.clone();
+
+    observable::amb(sources.iter().map(|(_, s)| s.clone())).subscribe(|_| {});
+    assert!(finalized.rc_deref().is_empty());
+
+    second.next(1);
+    assert_eq!(&*finalized.rc_deref(), &[0, 2]);
+  }
+
+  #[test]
+  fn termination_wins() {
+    let mut completed = false;
+    let mut values = vec![];
+    {
+      let first = Subject::default();
+      let mut second = Subject::default();
+      observable::amb([first.clone(), second.clone()])
+        .on_complete(|| completed = true)
+        .subscribe(|v: i32| values.push(v));
+
+      first.complete();
+      second.next(1);
+    }
+    assert!(completed);
+    assert!(values.is_empty());
+  }
+
+  #[test]
+  fn synchronous_winner_skip_rest() {
+    let subscribed = MutRc::own(false);
+    let c_subscribed = subscribed.clone();
+    let mut values = vec![];
+
+    let sources: Vec<BoxOp<_, _>> = vec![
+      observable::of(1).box_it(),
+      observable::defer(move || {
+        *c_subscribed.rc_deref_mut() = true;
+        observable::of(2)
+      })
+      .box_it(),
+    ];
+    observable::amb(sources).subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![1]);
+    assert!(!*subscribed.rc_deref());
+  }
+
+  #[test]
+  fn unsubscribe_all() {
+    let finalized = MutRc::own(0);
+    let (c1, c2) = (finalized.clone(), finalized.clone());
+
+    let sources: Vec<BoxOp<_, _>> = vec![
+      Subject::<i32, _>::default()
+        .finalize(move || *c1.rc_deref_mut() += 1)
+        .box_it(),
+      Subject::default()
+        .finalize(move || *c2.rc_deref_mut() += 1)
+        .box_it(),
+    ];
+    let subscription = observable::amb(sources).subscribe(|_| {});
+    subscription.unsubscribe();
+
+    assert_eq!(*finalized.rc_deref(), 2);
+  }
+
+  #[test]
+  fn amb_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut first = SubjectThreads::default();
+    let mut second = SubjectThreads::default();
+
+    observable::amb_threads([first.clone(), second.clone()])
+      .subscribe(move |v: i32| c_values.lock().unwrap().push(v));
+
+    second.next(2);
+    first.next(1);
+    second.next(3);
+
+    assert_eq!(&*values.lock().unwrap(), &[2, 3]);
+  }
+}
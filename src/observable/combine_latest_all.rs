@@ -0,0 +1,687 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+/// Creates an observable that combines a tuple of 2 to 8 observables, every
+/// time a source emits, it emits the tuple of the latest items of all the
+/// sources, once all of them have emitted.
+///
+/// It completes when all the sources have completed, or when a source
+/// completes without emitting any item. It emits the first error of the
+/// sources and unsubscribes the others.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let mut a = Subject::default();
+/// let mut b = Subject::default();
+/// let mut c = Subject::default();
+/// observable::combine_latest_tuple((a.clone(), b.clone(), c.clone()))
+///   .subscribe(|(a, b, c): (i32, char, &str)| println!("{a} {b} {c}"));
+///
+/// a.next(1);
+/// b.next('x');
+/// c.next("first");
+/// a.next(2);
+/// c.next("second");
+///
+/// // print logs:
+/// // 1 x first
+/// // 2 x first
+/// // 2 x second
+/// ```
+pub fn combine_latest_tuple<'a, Sources, Items>(
+  sources: Sources,
+) -> CombineLatestTupleOp<'a, Sources, Items> {
+  CombineLatestTupleOp { sources, _hint: TypeHint::new() }
+}
+
+/// A threads safe version of [`combine_latest_tuple`].
+pub fn combine_latest_tuple_threads<Sources, Items>(
+  sources: Sources,
+) -> CombineLatestTupleOpThreads<Sources, Items> {
+  CombineLatestTupleOpThreads { sources, _hint: TypeHint::new() }
+}
+
+/// Creates an observable that combines a runtime collection of observables
+/// of the same type, every time a source emits, it emits the snapshot of the
+/// latest items of all the sources, in the order of `sources`, once all of
+/// them have emitted.
+///
+/// It completes like [`combine_latest_tuple`], and right away if `sources` is
+/// empty.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let mut sources = vec![Subject::default(), Subject::default()];
+/// observable::combine_latest_iter(sources.clone())
+///   .subscribe(|row: Vec<i32>| println!("{:?}", row));
+///
+/// sources[0].next(1);
+/// sources[1].next(10);
+/// sources[0].next(2);
+///
+/// // print logs:
+/// // [1, 10]
+/// // [2, 10]
+/// ```
+pub fn combine_latest_iter<'a, S>(
+  sources: impl IntoIterator<Item = S>,
+) -> CombineLatestIterOp<'a, S> {
+  CombineLatestIterOp {
+    sources: sources.into_iter().collect(),
+    _hint: TypeHint::new(),
+  }
+}
+
+/// A threads safe version of [`combine_latest_iter`].
+pub fn combine_latest_iter_threads<S>(
+  sources: impl IntoIterator<Item = S>,
+) -> CombineLatestIterOpThreads<S> {
+  CombineLatestIterOpThreads { sources: sources.into_iter().collect() }
+}
+
+#[derive(Clone)]
+pub struct CombineLatestTupleOp<'a, Sources, Items> {
+  sources: Sources,
+  _hint: TypeHint<&'a Items>,
+}
+
+#[derive(Clone)]
+pub struct CombineLatestTupleOpThreads<Sources, Items> {
+  sources: Sources,
+  _hint: TypeHint<Items>,
+}
+
+#[derive(Clone)]
+pub struct CombineLatestIterOp<'a, S> {
+  sources: Vec<S>,
+  _hint: TypeHint<&'a ()>,
+}
+
+#[derive(Clone)]
+pub struct CombineLatestIterOpThreads<S> {
+  sources: Vec<S>,
+}
+
+/// The latest items of the sources of a combine_latest.
+pub trait LatestValues {
+  type Row;
+
+  /// Clones the latest items if every source has emitted one.
+  fn snapshot(&self) -> Option<Self::Row>;
+
+  fn has_value_at(&self, index: usize) -> bool;
+}
+
+impl<T: Clone> LatestValues for Vec<Option<T>> {
+  type Row = Vec<T>;
+
+  #[inline]
+  fn snapshot(&self) -> Option<Vec<T>> {
+    self.iter().cloned().collect()
+  }
+
+  #[inline]
+  fn has_value_at(&self, index: usize) -> bool {
+    self[index].is_some()
+  }
+}
+
+/// The state shared by all the sources of a combine_latest.
+pub struct CombineLatestAllState<O, V, U> {
+  observer: Option<O>,
+  values: V,
+  // The count of the sources not completed yet.
+  active: usize,
+  subscriptions: Vec<Option<U>>,
+}
+
+impl<O, V, U> CombineLatestAllState<O, V, U> {
+  fn new(observer: O, values: V, len: usize) -> Self {
+    Self {
+      observer: Some(observer),
+      values,
+      active: len,
+      subscriptions: (0..len).map(|_| None).collect(),
+    }
+  }
+
+  fn is_finished<Err>(&self) -> bool
+  where
+    V: LatestValues,
+    O: Observer<V::Row, Err>,
+  {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+
+  /// Takes the subscriptions of the sources other than the `index`th one,
+  /// the `index`th source is the one notifying and stops by itself.
+  fn take_others(&mut self, index: usize) -> Vec<U> {
+    std::mem::take(&mut self.subscriptions)
+      .into_iter()
+      .enumerate()
+      .filter_map(|(i, u)| u.filter(|_| i != index))
+      .collect()
+  }
+}
+
+/// Keeps the subscription of the `index`th source, or unsubscribes it right
+/// away if the combine_latest already finished.
+fn add_subscription<R, O, V, U, Err>(state: &R, index: usize, unsub: U)
+where
+  R: RcDeref<Target = CombineLatestAllState<O, V, U>>
+    + RcDerefMut<Target = CombineLatestAllState<O, V, U>>,
+  V: LatestValues,
+  O: Observer<V::Row, Err>,
+  U: Subscription,
+{
+  let mut inner = state.rc_deref_mut();
+  if inner.is_finished() {
+    drop(inner);
+    unsub.unsubscribe();
+  } else {
+    inner.subscriptions[index] = Some(unsub);
+  }
+}
+
+/// Observes the `index`th source of a combine_latest, `set` stores its
+/// latest item.
+pub struct CombineLatestSideObserver<R, V, Item> {
+  state: R,
+  index: usize,
+  set: fn(&mut V, usize, Item),
+}
+
+impl<Item, Err, O, V, U, R> Observer<Item, Err>
+  for CombineLatestSideObserver<R, V, Item>
+where
+  R: RcDeref<Target = CombineLatestAllState<O, V, U>>
+    + RcDerefMut<Target = CombineLatestAllState<O, V, U>>,
+  V: LatestValues,
+  O: Observer<V::Row, Err>,
+  U: Subscription,
+{
+  fn next(&mut self, value: Item) {
+    let mut state = self.state.rc_deref_mut();
+    let CombineLatestAllState { observer, values, .. } = &mut *state;
+    if let Some(observer) = observer.as_mut() {
+      (self.set)(values, self.index, value);
+      if let Some(row) = values.snapshot() {
+        observer.next(row);
+      }
+    }
+  }
+
+  fn error(self, err: Err) {
+    let mut state = self.state.rc_deref_mut();
+    if let Some(observer) = state.observer.take() {
+      let others = state.take_others(self.index);
+      drop(state);
+      others.into_iter().for_each(|u| u.unsubscribe());
+      observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    let mut state = self.state.rc_deref_mut();
+    state.active -= 1;
+    // A source completed without any item, no snapshot can be made.
+    if state.active > 0 && state.values.has_value_at(self.index) {
+      return;
+    }
+    if let Some(observer) = state.observer.take() {
+      let others = state.take_others(self.index);
+      drop(state);
+      others.into_iter().for_each(|u| u.unsubscribe());
+      observer.complete();
+    }
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.state.rc_deref().is_finished()
+  }
+}
+
+pub struct CombineLatestAllSubscription<R>(R);
+
+impl<R, O, V, U> Subscription for CombineLatestAllSubscription<R>
+where
+  R: RcDeref<Target = CombineLatestAllState<O, V, U>>
+    + RcDerefMut<Target = CombineLatestAllState<O, V, U>>,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let subscriptions =
+      std::mem::take(&mut self.0.rc_deref_mut().subscriptions);
+    subscriptions
+      .into_iter()
+      .flatten()
+      .for_each(|u| u.unsubscribe());
+  }
+
+  fn is_closed(&self) -> bool {
+    self.0.rc_deref().subscriptions.iter().all(Option::is_none)
+  }
+}
+
+macro_rules! impl_combine_latest_iter_op {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S> Observable<Vec<Item>, Err, O>
+      for $name<$($lf,)? S>
+    where
+      O: Observer<Vec<Item>, Err>,
+      Item: Clone,
+      S: Observable<
+        Item,
+        Err,
+        CombineLatestSideObserver<
+          $rc<CombineLatestAllState<O, Vec<Option<Item>>, $box_unsub>>,
+          Vec<Option<Item>>,
+          Item,
+        >,
+      >,
+      S::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = CombineLatestAllSubscription<
+        $rc<CombineLatestAllState<O, Vec<Option<Item>>, $box_unsub>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let len = self.sources.len();
+        let values = (0..len).map(|_| None).collect();
+        let state =
+          $rc::own(CombineLatestAllState::new(observer, values, len));
+        if len == 0 {
+          if let Some(observer) = state.rc_deref_mut().observer.take() {
+            observer.complete();
+          }
+        }
+        for (index, source) in self.sources.into_iter().enumerate() {
+          if state.rc_deref().is_finished() {
+            break;
+          }
+          let unsub = source.actual_subscribe(CombineLatestSideObserver {
+            state: state.clone(),
+            index,
+            set: |values: &mut Vec<Option<Item>>, i, v| values[i] = Some(v),
+          });
+          add_subscription(&state, index, <$box_unsub>::new(unsub));
+        }
+        CombineLatestAllSubscription(state)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S> ObservableExt<Vec<Item>, Err>
+      for $name<$($lf,)? S>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_combine_latest_iter_op!(
+  CombineLatestIterOp,
+  MutRc,
+  BoxSubscription<'a>,
+  'a
+);
+impl_combine_latest_iter_op!(
+  CombineLatestIterOpThreads,
+  MutArc,
+  BoxSubscriptionThreads,
+  Send
+);
+
+// Subscribes the sources of a tuple in order, stops once the combination
+// finished.
+macro_rules! combine_latest_tuple_subscribe {
+  (
+    $sources: expr, $observer: expr, $rc: ident, $box_unsub: ty,
+    $values: ty; $($S: ident $T: ident $idx: tt),+
+  ) => {{
+    let len = [$($idx),+].len();
+    let values = <$values>::default();
+    let state = $rc::own(CombineLatestAllState::new($observer, values, len));
+    let sources = $sources;
+    $(
+      if !state.rc_deref().is_finished() {
+        let set = |values: &mut $values, _, v| values.$idx = Some(v);
+        let unsub = sources.$idx.actual_subscribe(CombineLatestSideObserver {
+          state: state.clone(),
+          index: $idx,
+          set: set as fn(&mut $values, usize, $T),
+        });
+        add_subscription(&state, $idx, <$box_unsub>::new(unsub));
+      }
+    )+
+    CombineLatestAllSubscription(state)
+  }};
+}
+
+macro_rules! impl_combine_latest_tuple {
+  ($values: ty; $($S: ident $T: ident $idx: tt),+) => {
+    impl<$($T: Clone),+> LatestValues for $values {
+      type Row = ($($T,)+);
+
+      #[inline]
+      fn snapshot(&self) -> Option<Self::Row> {
+        Some(($(self.$idx.clone()?,)+))
+      }
+
+      fn has_value_at(&self, index: usize) -> bool {
+        match index {
+          $($idx => self.$idx.is_some(),)+
+          _ => false,
+        }
+      }
+    }
+
+    impl<'a, $($S, $T,)+ Err, O> Observable<($($T,)+), Err, O>
+      for CombineLatestTupleOp<'a, ($($S,)+), ($($T,)+)>
+    where
+      O: Observer<($($T,)+), Err>,
+      $(
+        $T: Clone,
+        $S: Observable<
+          $T,
+          Err,
+          CombineLatestSideObserver<
+            MutRc<CombineLatestAllState<O, $values, BoxSubscription<'a>>>,
+            $values,
+            $T,
+          >,
+        >,
+        $S::Unsub: 'a,
+      )+
+    {
+      type Unsub = CombineLatestAllSubscription<
+        MutRc<CombineLatestAllState<O, $values, BoxSubscription<'a>>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        combine_latest_tuple_subscribe!(
+          self.sources, observer, MutRc, BoxSubscription<'a>, $values;
+          $($S $T $idx),+
+        )
+      }
+    }
+
+    impl<'a, $($S, $T,)+ Err> ObservableExt<($($T,)+), Err>
+      for CombineLatestTupleOp<'a, ($($S,)+), ($($T,)+)>
+    where
+      $($S: ObservableExt<$T, Err>,)+
+    {
+    }
+
+    impl<$($S, $T,)+ Err, O> Observable<($($T,)+), Err, O>
+      for CombineLatestTupleOpThreads<($($S,)+), ($($T,)+)>
+    where
+      O: Observer<($($T,)+), Err>,
+      $(
+        $T: Clone,
+        $S: Observable<
+          $T,
+          Err,
+          CombineLatestSideObserver<
+            MutArc<CombineLatestAllState<O, $values, BoxSubscriptionThreads>>,
+            $values,
+            $T,
+          >,
+        >,
+        $S::Unsub: Send + 'static,
+      )+
+    {
+      type Unsub = CombineLatestAllSubscription<
+        MutArc<CombineLatestAllState<O, $values, BoxSubscriptionThreads>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        combine_latest_tuple_subscribe!(
+          self.sources, observer, MutArc, BoxSubscriptionThreads, $values;
+          $($S $T $idx),+
+        )
+      }
+    }
+
+    impl<$($S, $T,)+ Err> ObservableExt<($($T,)+), Err>
+      for CombineLatestTupleOpThreads<($($S,)+), ($($T,)+)>
+    where
+      $($S: ObservableExt<$T, Err>,)+
+    {
+    }
+  };
+}
+
+impl_combine_latest_tuple!(
+  (Option<T0>, Option<T1>);
+  S0 T0 0, S1 T1 1
+);
+impl_combine_latest_tuple!(
+  (Option<T0>, Option<T1>, Option<T2>);
+  S0 T0 0, S1 T1 1, S2 T2 2
+);
+impl_combine_latest_tuple!(
+  (Option<T0>, Option<T1>, Option<T2>, Option<T3>);
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3
+);
+impl_combine_latest_tuple!(
+  (Option<T0>, Option<T1>, Option<T2>, Option<T3>, Option<T4>);
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3, S4 T4 4
+);
+impl_combine_latest_tuple!(
+  (
+    Option<T0>,
+    Option<T1>,
+    Option<T2>,
+    Option<T3>,
+    Option<T4>,
+    Option<T5>,
+  );
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3, S4 T4 4, S5 T5 5
+);
+impl_combine_latest_tuple!(
+  (
+    Option<T0>,
+    Option<T1>,
+    Option<T2>,
+    Option<T3>,
+    Option<T4>,
+    Option<T5>,
+    Option<T6>,
+  );
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3, S4 T4 4, S5 T5 5, S6 T6 6
+);
+impl_combine_latest_tuple!(
+  (
+    Option<T0>,
+    Option<T1>,
+    Option<T2>,
+    Option<T3>,
+    Option<T4>,
+    Option<T5>,
+    Option<T6>,
+    Option<T7>,
+  );
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3, S4 T4 4, S5 T5 5, S6 T6 6, S7 T7 7
+);
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn combine_three() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut a = Subject::<i32, ()>::default();
+    let mut b = Subject::<char, ()>::default();
+    let mut c = Subject::<&str, ()>::default();
+
+    observable::combine_latest_tuple((a.clone(), b.clone(), c.clone()))
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+
+    a.next(1);
+    b.next('x');
+    a.next(2);
+    assert!(values.rc_deref().is_empty());
+    c.next("first");
+    b.next('y');
+
+    assert_eq!(&*values.rc_deref(), &[(2, 'x', "first"), (2, 'y', "first")]);
+  }
+
+  #[test]
+  fn combine_eight() {
+    let mut values = vec![];
+
+    observable::combine_latest_tuple((
+      observable::of(0),
+      observable::of(1u8),
+      observable::of(2u16),
+      observable::of(3u32),
+      observable::of(4u64),
+      observable::of('5'),
+      observable::of("6"),
+      observable::from_iter([7.0, 8.0]),
+    ))
+    .subscribe(|v| values.push(v));
+
+    assert_eq!(
+      values,
+      vec![
+        (0, 1, 2, 3, 4, '5', "6", 7.0),
+        (0, 1, 2, 3, 4, '5', "6", 8.0)
+      ]
+    );
+  }
+
+  #[test]
+  fn complete_after_all() {
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut a = Subject::<i32, ()>::default();
+    let mut b = Subject::<i32, ()>::default();
+    let c = Subject::<i32, ()>::default();
+
+    observable::combine_latest_tuple((a.clone(), b.clone(), c.clone()))
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .on_error(|_| {})
+      .subscribe(|_| {});
+
+    a.next(1);
+    b.next(1);
+    a.clone().complete();
+    b.clone().complete();
+    assert!(!*completed.rc_deref());
+    // Completing without any item, nothing can be combined anymore.
+    c.complete();
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn complete_without_item() {
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let a = Subject::<i32, ()>::default();
+    let b = Subject::<i32, ()>::default();
+
+    observable::combine_latest_iter([a.clone(), b.clone()])
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .on_error(|_| {})
+      .subscribe(|_| {});
+
+    b.complete();
+    assert!(*completed.rc_deref());
+    assert!(a.is_empty());
+  }
+
+  #[test]
+  fn error_unsubscribe_others() {
+    let mut error = None;
+    {
+      let a = Subject::<i32, &str>::default();
+      let b = Subject::<i32, &str>::default();
+      observable::combine_latest_iter([a.clone(), b.clone()])
+        .on_error(|e| error = Some(e))
+        .subscribe(|_| {});
+
+      b.error("fail");
+      assert!(a.is_empty());
+    }
+
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn combine_iter_snapshots() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut sources = (0..3)
+      .map(|_| Subject::<i32, ()>::default())
+      .collect::<Vec<_>>();
+
+    let subscription = observable::combine_latest_iter(sources.clone())
+      .on_error(|_| {})
+      .subscribe(move |row| c_values.rc_deref_mut().push(row));
+
+    sources[0].next(1);
+    sources[2].next(3);
+    sources[1].next(2);
+    sources[2].next(30);
+    subscription.unsubscribe();
+    sources[0].next(10);
+
+    assert_eq!(&*values.rc_deref(), &[vec![1, 2, 3], vec![1, 2, 30]]);
+    assert!(sources.iter().all(|s| s.is_empty()));
+  }
+
+  #[test]
+  fn combine_iter_empty() {
+    let mut completed = false;
+
+    observable::combine_latest_iter(Vec::<Subject<i32, ()>>::new())
+      .on_complete(|| completed = true)
+      .on_error(|_| {})
+      .subscribe(|_| unreachable!());
+
+    assert!(completed);
+  }
+
+  #[test]
+  fn combine_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let c_rows = values.clone();
+
+    observable::combine_latest_tuple_threads((
+      observable::of(0),
+      observable::of(1),
+      observable::from_iter(2..4),
+    ))
+    .subscribe(move |(a, b, c)| c_values.lock().unwrap().push(vec![a, b, c]));
+    observable::combine_latest_iter_threads((0..2).map(observable::of))
+      .subscribe(move |row| c_rows.lock().unwrap().push(row));
+
+    assert_eq!(
+      &*values.lock().unwrap(),
+      &[vec![0, 1, 2], vec![0, 1, 3], vec![0, 1]]
+    );
+  }
+}
@@ -0,0 +1,367 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+/// Creates an observable that emits the items of all the `sources` in order,
+/// it subscribes to a source only after the previous one completed.
+///
+/// The sources not subscribed yet are dropped when the returned subscription
+/// is unsubscribed or a source emits an error. The sources completing
+/// synchronously are subscribed in a loop, so any number of them can be
+/// concatenated.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let sources = (0..3).map(|i| observable::from_iter(i * 10..i * 10 + 2));
+/// observable::concat_iter(sources).subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // 0
+/// // 1
+/// // 10
+/// // 11
+/// // 20
+/// // 21
+/// ```
+pub fn concat_iter<'a, S>(
+  sources: impl IntoIterator<Item = S>,
+) -> ConcatIterObservable<'a, S> {
+  ConcatIterObservable {
+    sources: sources.into_iter().collect(),
+    _hint: TypeHint::new(),
+  }
+}
+
+/// A threads safe version of [`concat_iter`].
+pub fn concat_iter_threads<S>(
+  sources: impl IntoIterator<Item = S>,
+) -> ConcatIterObservableThreads<S> {
+  ConcatIterObservableThreads { sources: sources.into_iter().collect() }
+}
+
+#[derive(Clone)]
+pub struct ConcatIterObservable<'a, S> {
+  sources: Vec<S>,
+  _hint: TypeHint<&'a ()>,
+}
+
+#[derive(Clone)]
+pub struct ConcatIterObservableThreads<S> {
+  sources: Vec<S>,
+}
+
+pub struct ConcatIterState<O, S, U> {
+  observer: Option<O>,
+  sources: std::vec::IntoIter<S>,
+  current: Option<U>,
+  // Whether the current source has not completed yet.
+  active: bool,
+  // Whether the loop subscribing the sources is running, a source completing
+  // synchronously leaves the next one to it rather than recursing.
+  subscribing: bool,
+}
+
+impl<O, S, U> ConcatIterState<O, S, U> {
+  fn is_finished<Item, Err>(&self) -> bool
+  where
+    O: Observer<Item, Err>,
+  {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+}
+
+pub struct ConcatIterObserver<S> {
+  state: S,
+  // Subscribes the next sources, a function pointer avoids requiring them to
+  // accept this observer in the `Observer` implementation.
+  drain: fn(&S),
+}
+
+macro_rules! impl_concat_iter_observable {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S> Observable<Item, Err, O>
+      for $name<$($lf,)? S>
+    where
+      O: Observer<Item, Err>,
+      S: Observable<
+        Item,
+        Err,
+        ConcatIterObserver<$rc<ConcatIterState<O, S, $box_unsub>>>,
+      >,
+      S::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub =
+        ConcatIterSubscription<$rc<ConcatIterState<O, S, $box_unsub>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(ConcatIterState {
+          observer: Some(observer),
+          sources: self.sources.into_iter(),
+          current: None,
+          active: false,
+          subscribing: false,
+        });
+        ConcatIterObserver::<$rc<ConcatIterState<O, S, $box_unsub>>>::drain(
+          &state,
+        );
+        ConcatIterSubscription(state)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S> ObservableExt<Item, Err> for $name<$($lf,)? S>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+
+    impl<$($lf,)? O, S>
+      ConcatIterObserver<$rc<ConcatIterState<O, S, $box_unsub>>>
+    {
+      fn drain<Item, Err>(state: &$rc<ConcatIterState<O, S, $box_unsub>>)
+      where
+        O: Observer<Item, Err>,
+        S: Observable<Item, Err, Self>,
+        S::Unsub: $($lf)? $($send + 'static)?,
+      {
+        state.rc_deref_mut().subscribing = true;
+        loop {
+          let mut inner = state.rc_deref_mut();
+          if inner.active || inner.is_finished() {
+            inner.subscribing = false;
+            return;
+          }
+          let Some(source) = inner.sources.next() else {
+            inner.subscribing = false;
+            let observer = inner.observer.take();
+            drop(inner);
+            if let Some(observer) = observer {
+              observer.complete();
+            }
+            return;
+          };
+          inner.active = true;
+          drop(inner);
+
+          let unsub = source.actual_subscribe(ConcatIterObserver {
+            state: state.clone(),
+            drain: Self::drain,
+          });
+          let mut inner = state.rc_deref_mut();
+          if inner.active && inner.observer.is_some() {
+            inner.current = Some(<$box_unsub>::new(unsub));
+          } else if inner.active {
+            // Unsubscribed while the source was subscribing.
+            drop(inner);
+            unsub.unsubscribe();
+          }
+        }
+      }
+    }
+  };
+}
+
+impl_concat_iter_observable!(
+  ConcatIterObservable,
+  MutRc,
+  BoxSubscription<'a>,
+  'a
+);
+impl_concat_iter_observable!(
+  ConcatIterObservableThreads,
+  MutArc,
+  BoxSubscriptionThreads,
+  Send
+);
+
+impl<Item, Err, O, S, U, R> Observer<Item, Err> for ConcatIterObserver<R>
+where
+  R: RcDeref<Target = ConcatIterState<O, S, U>>
+    + RcDerefMut<Target = ConcatIterState<O, S, U>>,
+  O: Observer<Item, Err>,
+{
+  fn next(&mut self, value: Item) {
+    if let Some(observer) = self.state.rc_deref_mut().observer.as_mut() {
+      observer.next(value);
+    }
+  }
+
+  fn error(self, err: Err) {
+    let (observer, sources) = {
+      let mut state = self.state.rc_deref_mut();
+      state.current = None;
+      let sources = std::mem::take(&mut state.sources);
+      (state.observer.take(), sources)
+    };
+    drop(sources);
+    if let Some(observer) = observer {
+      observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    let subscribing = {
+      let mut state = self.state.rc_deref_mut();
+      state.active = false;
+      state.current = None;
+      state.subscribing
+    };
+    if !subscribing {
+      (self.drain)(&self.state);
+    }
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.state.rc_deref().is_finished()
+  }
+}
+
+pub struct ConcatIterSubscription<R>(R);
+
+impl<R, O, S, U> Subscription for ConcatIterSubscription<R>
+where
+  R: RcDeref<Target = ConcatIterState<O, S, U>>
+    + RcDerefMut<Target = ConcatIterState<O, S, U>>,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let (observer, sources, current) = {
+      let mut state = self.0.rc_deref_mut();
+      let sources = std::mem::take(&mut state.sources);
+      (state.observer.take(), sources, state.current.take())
+    };
+    drop((observer, sources));
+    if let Some(current) = current {
+      current.unsubscribe();
+    }
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.0.rc_deref().observer.is_none()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    ops::box_it::BoxOp,
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn subscribe_in_order() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut a = Subject::<i32, ()>::default();
+    let mut b = Subject::<i32, ()>::default();
+
+    observable::concat_iter([a.clone(), b.clone()])
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+
+    b.next(0);
+    a.next(1);
+    assert!(b.is_empty());
+    a.clone().complete();
+    b.next(2);
+    assert!(!*completed.rc_deref());
+    b.clone().complete();
+
+    assert_eq!(&*values.rc_deref(), &[1, 2]);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn synchronous_sources_not_recursive() {
+    let mut count = 0;
+    let mut completed = false;
+
+    observable::concat_iter((0..100_000).map(observable::of))
+      .on_complete(|| completed = true)
+      .subscribe(|_| count += 1);
+
+    assert_eq!(count, 100_000);
+    assert!(completed);
+  }
+
+  #[test]
+  fn queued_synchronous_sources_not_recursive() {
+    let mut count = 0;
+    {
+      let first = Subject::<i32, ()>::default();
+      let sources = std::iter::once(first.clone().box_it())
+        .chain(
+          (0..100_000)
+            .map(|v| observable::of(v).on_error_map(|e| match e {}).box_it()),
+        )
+        .collect::<Vec<BoxOp<_, _>>>();
+      observable::concat_iter(sources)
+        .on_error(|_| {})
+        .subscribe(|_| count += 1);
+      first.complete();
+    }
+
+    assert_eq!(count, 100_000);
+  }
+
+  #[test]
+  fn error_drop_rest() {
+    let subscribed = MutRc::own(false);
+    let c_subscribed = subscribed.clone();
+    let mut error = None;
+
+    let sources: Vec<BoxOp<_, _>> = vec![
+      observable::throw("fail").box_it(),
+      observable::defer(move || {
+        *c_subscribed.rc_deref_mut() = true;
+        observable::of(()).on_error_map(|e| match e {})
+      })
+      .box_it(),
+    ];
+    observable::concat_iter(sources)
+      .on_error(|e| error = Some(e))
+      .subscribe(|_| {});
+
+    assert_eq!(error, Some("fail"));
+    assert!(!*subscribed.rc_deref());
+  }
+
+  #[test]
+  fn unsubscribe_drop_rest() {
+    let a = Subject::<i32, ()>::default();
+    let b = Subject::<i32, ()>::default();
+    let subscription = observable::concat_iter([a.clone(), b.clone()])
+      .on_error(|_| {})
+      .subscribe(|_| {});
+    assert!(!a.is_empty());
+
+    subscription.unsubscribe();
+    assert!(a.is_empty());
+    a.complete();
+    assert!(b.is_empty());
+  }
+
+  #[test]
+  fn concat_iter_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    observable::concat_iter_threads((0..3).map(observable::of))
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[0, 1, 2]);
+  }
+}
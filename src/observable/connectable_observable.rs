@@ -1,8 +1,19 @@
-use crate::prelude::*;
+use crate::{
+  ops::ref_count::{RefCountOp, RefCountOpThreads},
+  prelude::*,
+};
 
+/// An observable that multicasts the source through a subject, the observers
+/// subscribe to the subject, and the source is only subscribed when
+/// [`connect`](ConnectableObservable::connect) is called.
+///
+/// This struct is created by the `publish` and `multicast` methods on
+/// [ObservableExt](ObservableExt::multicast). See their documentation for
+/// more.
+#[derive(Clone)]
 pub struct ConnectableObservable<S, Subject> {
-  source: S,
-  subject: Subject,
+  pub(crate) source: S,
+  pub(crate) subject: Subject,
 }
 
 impl<S, Subject, Item, Err, O> Observable<Item, Err, O>
@@ -19,6 +30,13 @@ where
   }
 }
 
+impl<S, Subject, Item, Err> ObservableExt<Item, Err>
+  for ConnectableObservable<S, Subject>
+where
+  Subject: ObservableExt<Item, Err>,
+{
+}
+
 impl<S, Subject> ConnectableObservable<S, Subject> {
   #[inline]
   pub fn new(source: S) -> Self
@@ -36,19 +54,59 @@ impl<S, Subject> ConnectableObservable<S, Subject> {
     self.subject.clone()
   }
 
+  /// Subscribes the subject to the source, the returned subscription
+  /// unsubscribes both the source and the observers of the subject.
   #[inline]
-  pub fn connect<Item, Err>(self) -> S::Unsub
+  pub fn connect<Item, Err>(self) -> ConnectionSubscription<Subject, S::Unsub>
   where
     S: Observable<Item, Err, Subject>,
-    Subject: Observer<Item, Err>,
+    Subject: Observer<Item, Err> + Clone,
   {
-    self.source.actual_subscribe(self.subject)
+    let source = self.source.actual_subscribe(self.subject.clone());
+    ConnectionSubscription { subject: self.subject, source }
+  }
+
+  /// Makes the connectable observable behave like an ordinary observable,
+  /// it connects when the first observer subscribes, and disconnects when
+  /// the last one unsubscribes.
+  #[inline]
+  pub fn ref_count<'a>(self) -> RefCountOp<'a, S, Subject> {
+    RefCountOp::new(self)
+  }
+
+  #[inline]
+  pub fn ref_count_threads(self) -> RefCountOpThreads<S, Subject> {
+    RefCountOpThreads::new(self)
+  }
+}
+
+/// The subscription returned by
+/// [`connect`](ConnectableObservable::connect).
+pub struct ConnectionSubscription<Subject, U> {
+  subject: Subject,
+  source: U,
+}
+
+impl<Subject, U> Subscription for ConnectionSubscription<Subject, U>
+where
+  Subject: Subscription,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    self.source.unsubscribe();
+    self.subject.unsubscribe();
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.source.is_closed()
   }
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
+  use crate::rc::{MutRc, RcDeref, RcDerefMut};
 
   #[test]
   fn smoke() {
@@ -77,6 +135,82 @@ mod test {
     assert_eq!(second, 100);
   }
 
+  #[test]
+  fn emit_after_connect() {
+    let values = MutRc::own(vec![]);
+    let (c_values1, c_values2) = (values.clone(), values.clone());
+    let published = observable::from_iter(0..2).publish::<Subject<_, _>>();
+    published
+      .clone()
+      .subscribe(move |v| c_values1.rc_deref_mut().push(v));
+    published
+      .clone()
+      .subscribe(move |v| c_values2.rc_deref_mut().push(v * 10));
+    assert!(values.rc_deref().is_empty());
+
+    published.connect();
+    assert_eq!(&*values.rc_deref(), &[0, 0, 1, 10]);
+  }
+
+  #[test]
+  fn disconnect_tear_down() {
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let mut source = Subject::<i32, ()>::default();
+    let published = source
+      .clone()
+      .finalize(move || *c_finalized.rc_deref_mut() = true)
+      .publish::<Subject<_, _>>();
+    let subject = published.fork();
+    published.clone().on_error(|_| {}).subscribe(|_| {});
+
+    let connection = published.connect();
+    source.next(1);
+    assert!(!subject.is_empty());
+    connection.unsubscribe();
+
+    assert!(*finalized.rc_deref());
+    assert!(source.is_empty());
+    assert!(subject.is_empty());
+  }
+
+  #[test]
+  fn multicast_factory() {
+    let mut values = vec![];
+    let connectable =
+      observable::from_iter(0..5).multicast(|| ReplaySubject::new(2));
+    connectable.clone().connect();
+    connectable
+      .on_error_map(|e| match e {})
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![3, 4]);
+  }
+
+  #[test]
+  fn connect_on_ref_count() {
+    let subscribed = MutRc::own(0);
+    let c_subscribed = subscribed.clone();
+    let mut subject = Subject::<i32, ()>::default();
+    let c_subject = subject.clone();
+    let shared = observable::defer(move || {
+      *c_subscribed.rc_deref_mut() += 1;
+      c_subject.clone()
+    })
+    .publish::<Subject<_, _>>()
+    .ref_count();
+    assert_eq!(*subscribed.rc_deref(), 0);
+
+    let s1 = shared.clone().on_error(|_| {}).subscribe(|_| {});
+    let s2 = shared.on_error(|_| {}).subscribe(|_| {});
+    subject.next(1);
+    assert_eq!(*subscribed.rc_deref(), 1);
+    s1.unsubscribe();
+    assert!(!subject.is_empty());
+    s2.unsubscribe();
+    assert!(subject.is_empty());
+  }
+
   #[test]
   fn bench() {
     do_bench();
@@ -4,6 +4,13 @@ use crate::prelude::*;
 /// that is supplied by a supplier-function which will be run once at each
 /// subscription
 ///
+/// The subscription of the supplied observable is the one returned to the
+/// subscriber. Subscribing consumes the observable, so subscribing again, or
+/// resubscribing with operators like `retry` and `repeat`, clones it and calls
+/// a clone of the supplier. The state captured by value is therefore fresh at
+/// every subscription, and the state to share between the subscriptions has to
+/// be captured by reference or with a shared pointer.
+///
 /// ```rust
 /// # use rxrust::prelude::*;
 ///
@@ -105,6 +112,51 @@ mod test {
     assert_eq!(*calls.lock().unwrap().deref(), 2);
   }
 
+  #[test]
+  fn supplied_at_subscription() {
+    let o = observable::defer(|| observable::of(std::time::Instant::now()));
+    let before = std::time::Instant::now();
+    let mut stamp = None;
+    o.subscribe(|v| stamp = Some(v));
+
+    assert!(stamp.unwrap() >= before);
+  }
+
+  #[test]
+  fn return_supplied_subscription() {
+    let subject = Subject::<i32, ()>::default();
+    let c_subject = subject.clone();
+    let subscription = observable::defer(move || c_subject.clone())
+      .on_error(|_| {})
+      .subscribe(|_| {});
+    assert!(!subject.is_empty());
+
+    subscription.unsubscribe();
+    assert!(subject.is_empty());
+  }
+
+  #[test]
+  fn resubscribe_with_retry() {
+    let attempts = Arc::new(Mutex::new(0));
+    let c_attempts = attempts.clone();
+    let mut values = vec![];
+    observable::defer(move || {
+      let mut attempts = c_attempts.lock().unwrap();
+      *attempts += 1;
+      observable::of_result(if *attempts < 3 {
+        Err(())
+      } else {
+        Ok(*attempts)
+      })
+    })
+    .retry(2)
+    .on_error(|_| {})
+    .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [3]);
+    assert_eq!(*attempts.lock().unwrap(), 3);
+  }
+
   #[test]
   fn bench() {
     do_bench();
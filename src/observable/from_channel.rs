@@ -0,0 +1,213 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, RcDeref, RcDerefMut},
+  scheduler::{NormalReturn, OnceTask, Scheduler, TaskHandle},
+};
+use std::{
+  convert::Infallible,
+  sync::mpsc::{Receiver, RecvTimeoutError},
+  time::Duration,
+};
+
+// How long the task blocks on the channel before checking if it's
+// unsubscribed.
+const RECV_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Creates an observable that emits the messages received from a
+/// `std::sync::mpsc::Receiver`, and completes when all the senders are
+/// dropped. Never emits an error.
+///
+/// The receiver is drained by a task spawned on the `scheduler`, which blocks
+/// while waiting for the messages, so use a thread pool, not the pool of the
+/// current thread. Unsubscribing stops the task even while it's blocked, with
+/// a delay of 50 milliseconds at most.
+///
+/// The receiver can only be subscribed once, a message is never emitted to more
+/// than one observer.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+/// use std::sync::mpsc::channel;
+/// use futures::executor::ThreadPool;
+///
+/// let (sender, receiver) = channel();
+/// std::thread::spawn(move || {
+///   for i in 0..3 {
+///     sender.send(i).unwrap();
+///   }
+/// });
+///
+/// let pool = ThreadPool::new().unwrap();
+/// let (o, status) = observable::from_channel(receiver, pool)
+///   .complete_status();
+/// o.subscribe(|v| println!("{}", v));
+/// rxrust::ops::complete_status::CompleteStatus::wait_for_end(status);
+///
+/// // print log:
+/// // 0
+/// // 1
+/// // 2
+/// ```
+pub fn from_channel<T, S>(
+  receiver: Receiver<T>,
+  scheduler: S,
+) -> ChannelObservable<T, S> {
+  ChannelObservable { receiver, scheduler }
+}
+
+pub struct ChannelObservable<T, S> {
+  receiver: Receiver<T>,
+  scheduler: S,
+}
+
+type ChannelTask<T, O> =
+  OnceTask<(Receiver<T>, MutArc<bool>, O), NormalReturn<()>>;
+
+fn channel_task<T, O>(
+  (receiver, closed, mut observer): (Receiver<T>, MutArc<bool>, O),
+) -> NormalReturn<()>
+where
+  O: Observer<T, Infallible>,
+{
+  loop {
+    match receiver.recv_timeout(RECV_TIMEOUT) {
+      Ok(_) | Err(RecvTimeoutError::Timeout) if *closed.rc_deref() => break,
+      Ok(v) => {
+        observer.next(v);
+        if observer.is_finished() {
+          break;
+        }
+      }
+      Err(RecvTimeoutError::Timeout) => {}
+      Err(RecvTimeoutError::Disconnected) => {
+        if !*closed.rc_deref() {
+          observer.complete();
+        }
+        break;
+      }
+    }
+  }
+  NormalReturn::new(())
+}
+
+impl<T, O, S> Observable<T, Infallible, O> for ChannelObservable<T, S>
+where
+  O: Observer<T, Infallible>,
+  S: Scheduler<ChannelTask<T, O>>,
+{
+  type Unsub = ChannelSubscription;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    let Self { receiver, scheduler } = self;
+    let closed = MutArc::own(false);
+    let task =
+      OnceTask::new(channel_task, (receiver, closed.clone(), observer));
+    let handle = scheduler.schedule(task, None);
+    ChannelSubscription { closed, handle }
+  }
+}
+
+impl<T, S> ObservableExt<T, Infallible> for ChannelObservable<T, S> {}
+
+pub struct ChannelSubscription {
+  closed: MutArc<bool>,
+  handle: TaskHandle<NormalReturn<()>>,
+}
+
+impl Subscription for ChannelSubscription {
+  fn unsubscribe(self) {
+    *self.closed.rc_deref_mut() = true;
+    self.handle.unsubscribe();
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    *self.closed.rc_deref() || self.handle.is_closed()
+  }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod test {
+  use crate::{ops::complete_status::CompleteStatus, prelude::*};
+  use futures::executor::ThreadPool;
+  use std::{
+    sync::{mpsc::channel, Arc, Mutex},
+    time::{Duration, Instant},
+  };
+
+  #[test]
+  fn complete_when_senders_dropped() {
+    let (sender, receiver) = channel();
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    let (o, status) =
+      observable::from_channel(receiver, ThreadPool::new().unwrap())
+        .complete_status();
+    o.subscribe(move |v| c_values.lock().unwrap().push(v));
+    let c_sender = sender.clone();
+    std::thread::spawn(move || {
+      c_sender.send(1).unwrap();
+      c_sender.send(2).unwrap();
+    });
+    sender.send(0).unwrap();
+    drop(sender);
+    CompleteStatus::wait_for_end(status);
+
+    let mut values = values.lock().unwrap().clone();
+    values.sort();
+    assert_eq!(values, [0, 1, 2]);
+  }
+
+  #[test]
+  fn unsubscribe_stop_blocked_task() {
+    let (sender, receiver) = channel();
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    let subscription =
+      observable::from_channel(receiver, ThreadPool::new().unwrap())
+        .subscribe(move |v| c_values.lock().unwrap().push(v));
+    sender.send(0).unwrap();
+    while values.lock().unwrap().is_empty() {
+      std::thread::yield_now();
+    }
+    subscription.unsubscribe();
+
+    // The receiver is dropped once the task stopped.
+    let stamp = Instant::now();
+    while sender.send(1).is_ok() {
+      assert!(stamp.elapsed() < Duration::from_secs(1));
+      std::thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(*values.lock().unwrap(), [0]);
+  }
+
+  #[test]
+  fn stop_when_finished() {
+    let (sender, receiver) = channel();
+    for i in 0..5 {
+      sender.send(i).unwrap();
+    }
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    let (o, status) =
+      observable::from_channel(receiver, ThreadPool::new().unwrap())
+        .take(2)
+        .complete_status();
+    o.subscribe(move |v| c_values.lock().unwrap().push(v));
+    CompleteStatus::wait_for_end(status);
+
+    assert_eq!(*values.lock().unwrap(), [0, 1]);
+    // The receiver is dropped without waiting for the senders.
+    let stamp = Instant::now();
+    while sender.send(5).is_ok() {
+      assert!(stamp.elapsed() < Duration::from_secs(1));
+      std::thread::sleep(Duration::from_millis(1));
+    }
+  }
+}
@@ -1,4 +1,8 @@
-use crate::{prelude::*, subscriber::Subscriber};
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDerefMut},
+  subscriber::Subscriber,
+};
 
 /// param `func`: the function that is called when the Observable is
 /// initially subscribed to. This function is given a `Observer`, to which
@@ -59,6 +63,190 @@ where
   }
 }
 
+/// Same as [`create`], but `func` returns a teardown function, which is called
+/// exactly once, when the subscription is unsubscribed or after the observable
+/// completes or emits an error.
+///
+/// If the observable terminated or was unsubscribed before `func` returned, the
+/// teardown is called right away. This is how to release the resources the
+/// producer registered, like event listeners or callbacks.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::create_with_teardown(|mut subscriber: Subscriber<_>| {
+///   println!("register listener");
+///   subscriber.next(1);
+///   move || println!("unregister listener")
+/// })
+/// .subscribe(|v: i32| println!("{}", v))
+/// .unsubscribe();
+///
+/// // print log:
+/// // register listener
+/// // 1
+/// // unregister listener
+/// ```
+pub fn create_with_teardown<F, T, Item, Err, P>(
+  func: F,
+) -> ObservableTeardownFn<F, P>
+where
+  F: FnOnce(P) -> T,
+  T: FnOnce(),
+  P: Observer<Item, Err> + Subscription,
+{
+  ObservableTeardownFn { func, _hint: TypeHint::default() }
+}
+
+pub struct ObservableTeardownFn<F, P> {
+  func: F,
+  _hint: TypeHint<P>,
+}
+
+pub enum TeardownSlot<T> {
+  /// The producer function hasn't returned the teardown yet.
+  Pending,
+  Ready(T),
+  /// Terminated or unsubscribed, a teardown returned from now on is called
+  /// right away.
+  Done,
+}
+
+impl<T: FnOnce()> TeardownSlot<T> {
+  fn call<R: RcDerefMut<Target = Self>>(slot: &R) {
+    let slot = std::mem::replace(&mut *slot.rc_deref_mut(), TeardownSlot::Done);
+    if let TeardownSlot::Ready(teardown) = slot {
+      teardown();
+    }
+  }
+}
+
+pub struct TeardownObserver<O, R> {
+  observer: O,
+  teardown: R,
+}
+
+impl<Item, Err, O, R, T> Observer<Item, Err> for TeardownObserver<O, R>
+where
+  O: Observer<Item, Err>,
+  R: RcDerefMut<Target = TeardownSlot<T>>,
+  T: FnOnce(),
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    self.observer.next(value)
+  }
+
+  fn error(self, err: Err) {
+    self.observer.error(err);
+    TeardownSlot::call(&self.teardown);
+  }
+
+  fn complete(self) {
+    self.observer.complete();
+    TeardownSlot::call(&self.teardown);
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+pub struct TeardownSubscription<S, R> {
+  subscriber: S,
+  teardown: R,
+}
+
+impl<S, R, T> Subscription for TeardownSubscription<S, R>
+where
+  S: Subscription,
+  R: RcDerefMut<Target = TeardownSlot<T>>,
+  T: FnOnce(),
+{
+  fn unsubscribe(self) {
+    self.subscriber.unsubscribe();
+    TeardownSlot::call(&self.teardown);
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.subscriber.is_closed()
+  }
+}
+
+macro_rules! impl_teardown_observable {
+  ($subscriber:ident, $rc: ident $($bounds: tt)*) => {
+    impl<F, T, Item, Err, O> Observable<Item, Err, O>
+      for ObservableTeardownFn<
+        F,
+        $subscriber<TeardownObserver<O, $rc<TeardownSlot<T>>>>,
+      >
+    where
+      F: FnOnce($subscriber<TeardownObserver<O, $rc<TeardownSlot<T>>>>) -> T,
+      T: FnOnce() $($bounds)*,
+      O: Observer<Item, Err> $($bounds)*
+    {
+      type Unsub = TeardownSubscription<
+        $subscriber<TeardownObserver<O, $rc<TeardownSlot<T>>>>,
+        $rc<TeardownSlot<T>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let teardown = $rc::own(TeardownSlot::Pending);
+        let subscriber = $subscriber::new(Some(TeardownObserver {
+          observer,
+          teardown: teardown.clone(),
+        }));
+        let func = (self.func)(subscriber.clone());
+        // Terminated or unsubscribed while the producer function was running.
+        let terminated = {
+          let mut slot = teardown.rc_deref_mut();
+          if let TeardownSlot::Done = *slot {
+            Some(func)
+          } else {
+            *slot = TeardownSlot::Ready(func);
+            None
+          }
+        };
+        if let Some(func) = terminated {
+          func();
+        }
+        TeardownSubscription { subscriber, teardown }
+      }
+    }
+
+    impl<F, T, Item, Err, O> ObservableExt<Item, Err>
+      for ObservableTeardownFn<
+        F,
+        $subscriber<TeardownObserver<O, $rc<TeardownSlot<T>>>>,
+      >
+    where
+      F: FnOnce($subscriber<TeardownObserver<O, $rc<TeardownSlot<T>>>>) -> T,
+      O: Observer<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_teardown_observable!(Subscriber, MutRc);
+impl_teardown_observable!(SubscriberThreads, MutArc + Send + 'static);
+
+impl<F, P> Clone for ObservableTeardownFn<F, P>
+where
+  F: Clone,
+{
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      func: self.func.clone(),
+      _hint: TypeHint::new(),
+    }
+  }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod test {
@@ -112,6 +300,85 @@ mod test {
     assert_eq!(*c_sum2.lock().unwrap(), 10);
   }
 
+  #[test]
+  fn teardown_on_unsubscribe() {
+    let teardowns = Arc::new(Mutex::new(0));
+    let c_teardowns = teardowns.clone();
+    let subscription =
+      observable::create_with_teardown(move |mut subscriber: Subscriber<_>| {
+        subscriber.next(1);
+        move || *c_teardowns.lock().unwrap() += 1
+      })
+      .subscribe(|_: i32| {});
+    assert_eq!(*teardowns.lock().unwrap(), 0);
+
+    subscription.unsubscribe();
+    assert_eq!(*teardowns.lock().unwrap(), 1);
+  }
+
+  #[test]
+  fn teardown_once_on_terminate() {
+    let teardowns = Arc::new(Mutex::new(0));
+    let c_teardowns = teardowns.clone();
+    let subject = Subject::<i32, &str>::default();
+    let c_subject = subject.clone();
+    let subscription =
+      observable::create_with_teardown(move |subscriber: Subscriber<_>| {
+        c_subject.clone().actual_subscribe(subscriber);
+        move || *c_teardowns.lock().unwrap() += 1
+      })
+      .on_error(|_| {})
+      .subscribe(|_| {});
+    assert!(!subject.is_empty());
+
+    subject.clone().error("fail");
+    assert_eq!(*teardowns.lock().unwrap(), 1);
+    subscription.unsubscribe();
+    assert_eq!(*teardowns.lock().unwrap(), 1);
+  }
+
+  #[test]
+  fn teardown_after_synchronous_complete() {
+    let teardowns = Arc::new(Mutex::new(0));
+    let c_teardowns = teardowns.clone();
+    let mut values = vec![];
+    let subscription =
+      observable::create_with_teardown(move |mut subscriber: Subscriber<_>| {
+        subscriber.next(1);
+        subscriber.complete();
+        move || *c_teardowns.lock().unwrap() += 1
+      })
+      .subscribe(|v: i32| values.push(v));
+    assert_eq!(*teardowns.lock().unwrap(), 1);
+
+    subscription.unsubscribe();
+    assert_eq!(*teardowns.lock().unwrap(), 1);
+    assert_eq!(values, [1]);
+  }
+
+  #[test]
+  fn teardown_threads() {
+    let teardowns = Arc::new(Mutex::new(0));
+    let c_teardowns = teardowns.clone();
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    let subscription = observable::create_with_teardown(
+      move |mut subscriber: SubscriberThreads<_>| {
+        std::thread::spawn(move || {
+          for v in receiver {
+            subscriber.next(v);
+          }
+        });
+        move || *c_teardowns.lock().unwrap() += 1
+      },
+    )
+    .subscribe(|_: i32| {});
+    sender.send(1).unwrap();
+
+    subscription.unsubscribe();
+    assert_eq!(*teardowns.lock().unwrap(), 1);
+  }
+
   #[test]
   fn bench() {
     do_bench();
@@ -22,6 +22,10 @@ use std::{convert::Infallible, future::Future};
 /// ```
 /// If your `Future` poll an `Result` type value, and you want dispatch the
 /// error by rxrust, you can use [`from_future_result`]
+///
+/// The future is spawned on the `scheduler` at each subscription, so it has to
+/// be `Send + 'static` with a thread pool and only `'static` with a local
+/// pool. Unsubscribing before it's ready aborts it, and nothing is emitted.
 pub fn from_future<F, Item, S>(f: F, scheduler: S) -> FutureObservable<F, S>
 where
   F: Future<Output = Item>,
@@ -50,7 +54,10 @@ where
   }
 }
 
-impl<F: Future, S> ObservableExt<F::Output, ()> for FutureObservable<F, S> {}
+impl<F: Future, S> ObservableExt<F::Output, Infallible>
+  for FutureObservable<F, S>
+{
+}
 
 fn item_task<Item, O>(item: Item, mut observer: O) -> NormalReturn<()>
 where
@@ -65,6 +72,32 @@ where
 /// [`from_future@from_future`]. But only work for which `Future::Output` is
 /// `Result` type, and `Result::Ok` emit to next handle, and `Result::Err` as an
 /// error to handle.
+///
+/// Unsubscribing before the future is ready aborts it like `from_future`. The
+/// future is consumed by the subscription, wrap the call creating it with
+/// [`defer`](crate::observable::defer) to run it again on every resubscription,
+/// for example with `retry`.
+///
+/// ```rust
+/// # use rxrust::prelude::*;
+/// use futures::executor::LocalPool;
+/// let mut local_scheduler = LocalPool::new();
+/// let spawner = local_scheduler.spawner();
+///
+/// observable::defer(move || {
+///   observable::from_future_result(
+///     async { Err::<i32, _>("connection refused") },
+///     spawner.clone(),
+///   )
+/// })
+/// .retry(2)
+/// .on_error(|e| println!("failed: {}", e))
+/// .subscribe(|v| println!("{}", v));
+///
+/// local_scheduler.run();
+/// // print log:
+/// // failed: connection refused
+/// ```
 pub fn from_future_result<F, S, Item, Err>(
   future: F,
   scheduler: S,
@@ -147,6 +180,127 @@ mod tests {
     assert_eq!(*value.borrow(), 2);
   }
 
+  #[test]
+  fn result_error() {
+    let mut local = LocalPool::new();
+    let error = Rc::new(RefCell::new(None));
+    let c_error = error.clone();
+    let emitted = Rc::new(RefCell::new(false));
+    let c_emitted = emitted.clone();
+    from_future_result(future::err::<i32, _>("fail"), local.spawner())
+      .on_error(move |e| *c_error.borrow_mut() = Some(e))
+      .subscribe(move |_| *c_emitted.borrow_mut() = true);
+    local.run();
+
+    assert_eq!(*error.borrow(), Some("fail"));
+    assert!(!*emitted.borrow());
+  }
+
+  #[test]
+  fn result_unsubscribe_abort_future() {
+    let mut local = LocalPool::new();
+    let polled = Rc::new(RefCell::new(false));
+    let c_polled = polled.clone();
+
+    let subscription = from_future_result(
+      async move {
+        *c_polled.borrow_mut() = true;
+        Err::<(), _>(())
+      },
+      local.spawner(),
+    )
+    .on_error(|_| {})
+    .subscribe(|_| {});
+    subscription.unsubscribe();
+    local.run();
+
+    assert!(!*polled.borrow());
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  #[test]
+  fn result_retry_spawn_again() {
+    use futures_time::{task::sleep, time::Duration};
+
+    let mut local = LocalPool::new();
+    let spawner = local.spawner();
+    let attempts = Rc::new(RefCell::new(0));
+    let c_attempts = attempts.clone();
+    let error = Rc::new(RefCell::new(None));
+    let c_error = error.clone();
+
+    observable::defer(move || {
+      let attempts = c_attempts.clone();
+      from_future_result(
+        async move {
+          sleep(Duration::from_millis(1)).await;
+          *attempts.borrow_mut() += 1;
+          Err::<i32, _>(*attempts.borrow())
+        },
+        spawner.clone(),
+      )
+    })
+    .retry(2)
+    .on_error(move |e| *c_error.borrow_mut() = Some(e))
+    .subscribe(|_| {});
+    local.run();
+
+    assert_eq!(*attempts.borrow(), 3);
+    assert_eq!(*error.borrow(), Some(3));
+  }
+
+  #[test]
+  fn unsubscribe_abort_future() {
+    let mut local = LocalPool::new();
+    let polled = Rc::new(RefCell::new(false));
+    let c_polled = polled.clone();
+    let emitted = Rc::new(RefCell::new(false));
+    let c_emitted = emitted.clone();
+
+    let subscription = from_future(
+      async move {
+        *c_polled.borrow_mut() = true;
+      },
+      local.spawner(),
+    )
+    .subscribe(move |_| *c_emitted.borrow_mut() = true);
+    subscription.unsubscribe();
+    local.run();
+
+    assert!(!*polled.borrow());
+    assert!(!*emitted.borrow());
+  }
+
+  #[test]
+  fn map_infallible_error() {
+    let mut local = LocalPool::new();
+    let value = Rc::new(RefCell::new(None));
+    let v_c = value.clone();
+    from_future(future::ready(1), local.spawner())
+      .map_err(|e| -> String { match e {} })
+      .on_error(|_| {})
+      .subscribe(move |v| *v_c.borrow_mut() = Some(v));
+    local.run();
+
+    assert_eq!(*value.borrow(), Some(1));
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  #[test]
+  fn shared() {
+    use futures::executor::ThreadPool;
+    use std::sync::{Arc, Mutex};
+
+    let pool = ThreadPool::new().unwrap();
+    let value = Arc::new(Mutex::new(None));
+    let c_value = value.clone();
+    let (o, status) = from_future(async { 3 }, pool).complete_status();
+    o.subscribe(move |v| *c_value.lock().unwrap() = Some(v));
+    crate::ops::complete_status::CompleteStatus::wait_for_end(status);
+
+    assert_eq!(*value.lock().unwrap(), Some(3));
+  }
+
   #[test]
   fn bench() {
     do_bench();
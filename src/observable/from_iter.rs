@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use std::{
   convert::Infallible,
-  iter::{Repeat, Take},
+  iter::{Repeat, RepeatN},
 };
 
 /// Creates an observable that produces values from an iterator.
@@ -49,7 +49,13 @@ where
   type Unsub = ();
 
   fn actual_subscribe(self, mut observer: O) -> Self::Unsub {
-    self.0.into_iter().for_each(|v| observer.next(v));
+    for v in self.0 {
+      observer.next(v);
+      // Stop pulling from the iterator once downstream no longer needs values.
+      if observer.is_finished() {
+        return;
+      }
+    }
     observer.complete();
   }
 }
@@ -81,11 +87,62 @@ impl<Iter> ObservableExt<Iter::Item, Infallible> for ObservableIter<Iter> where
 /// // 123
 /// // 123
 /// ```
-pub fn repeat<Item>(v: Item, n: usize) -> ObservableIter<Take<Repeat<Item>>>
+pub fn repeat<Item>(v: Item, n: usize) -> ObservableIter<RepeatN<Item>>
 where
   Item: Clone,
 {
-  from_iter(std::iter::repeat(v).take(n))
+  from_iter(std::iter::repeat_n(v, n))
+}
+
+/// Creates an observable emitting clones of `value` `count` times, then
+/// completes. The same as [`repeat`], named after its infinite variant
+/// [`repeat_value_forever`].
+///
+/// # Examples
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::repeat_value("tick", 2).subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // tick
+/// // tick
+/// ```
+#[inline]
+pub fn repeat_value<Item>(
+  value: Item,
+  count: usize,
+) -> ObservableIter<RepeatN<Item>>
+where
+  Item: Clone,
+{
+  repeat(value, count)
+}
+
+/// Creates an observable producing same value repeated forever.
+///
+/// Never completes nor emits an error, but stops as soon as the observer is
+/// finished, so operators like `take` can limit it.
+///
+/// # Examples
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::repeat_value_forever("tick")
+///   .take(2)
+///   .subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // tick
+/// // tick
+/// ```
+pub fn repeat_value_forever<Item>(value: Item) -> ObservableIter<Repeat<Item>>
+where
+  Item: Clone,
+{
+  from_iter(std::iter::repeat(value))
 }
 
 #[cfg(test)]
@@ -117,6 +174,18 @@ mod test {
     assert!(completed);
   }
 
+  #[test]
+  fn stop_when_observer_finished() {
+    let mut pulled = 0;
+    let mut hit_count = 0;
+    observable::from_iter((0..100).inspect(|_| pulled += 1))
+      .take(5)
+      .subscribe(|_| hit_count += 1);
+
+    assert_eq!(hit_count, 5);
+    assert_eq!(pulled, 5);
+  }
+
   #[test]
   fn repeat_three_times() {
     let mut hit_count = 0;
@@ -144,6 +213,49 @@ mod test {
     assert_eq!(0, hit_count);
     assert!(completed);
   }
+
+  #[test]
+  fn repeat_value_count_times() {
+    let mut values = vec![];
+    let mut completed = false;
+    repeat_value(String::from("v"), 2)
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, ["v", "v"]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn repeat_value_forever_stop_when_finished() {
+    let mut values = vec![];
+    let mut completed = false;
+    repeat_value_forever(String::from("v"))
+      .take(3)
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, ["v", "v", "v"]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn repeat_value_forever_threads() {
+    use std::sync::{Arc, Mutex};
+
+    let count = Arc::new(Mutex::new(0));
+    let c_count = count.clone();
+    std::thread::spawn(move || {
+      repeat_value_forever(Arc::new(1))
+        .take(4)
+        .subscribe(move |v| *c_count.lock().unwrap() += *v);
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*count.lock().unwrap(), 4);
+  }
+
   #[test]
   fn bench() {
     do_bench();
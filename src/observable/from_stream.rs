@@ -43,6 +43,9 @@ use super::{Observable, ObservableExt};
 ///
 /// # Remarks
 /// If you want convert a `Stream` that can fail use [`rxrust::from_stream_result`] instead.
+///
+/// The stream is driven by a task spawned on the `scheduler`, which stops
+/// polling it once the observer is finished, and unsubscribing aborts it.
 pub fn from_stream<S, SD>(stream: S, scheduler: SD) -> StreamObservable<S, SD>
 where
   S: Stream,
@@ -103,11 +106,13 @@ where
       match next {
         Some(value) => {
           // Send the item to the observer
-          this
-            .observer
-            .as_mut()
-            .expect("future polled before done")
-            .next(value);
+          let observer =
+            this.observer.as_mut().expect("future polled before done");
+          observer.next(value);
+          // Stop polling the stream once downstream no longer needs values.
+          if observer.is_finished() {
+            break Poll::Ready(NormalReturn::new(()));
+          }
         }
         None => {
           let observer =
@@ -172,6 +177,47 @@ mod tests {
     assert_eq!(cur, vec![1, 2, 3])
   }
 
+  #[test]
+  fn stop_infinite_stream() {
+    let mut scheduler = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let completed = MutRc::own(false);
+    {
+      let values = values.clone();
+      let completed = completed.clone();
+      from_stream(futures::stream::iter(0..), scheduler.spawner())
+        .take(3)
+        .on_complete(move || *completed.rc_deref_mut() = true)
+        .subscribe(move |x| values.rc_deref_mut().push(x));
+    }
+
+    scheduler.run();
+    assert_eq!(*values.rc_deref(), vec![0, 1, 2]);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn unsubscribe_abort_task() {
+    use futures::channel::mpsc::unbounded;
+
+    let mut scheduler = LocalPool::new();
+    let (sender, receiver) = unbounded();
+    let values = MutRc::own(vec![]);
+    let subscription = {
+      let values = values.clone();
+      from_stream(receiver, scheduler.spawner())
+        .subscribe(move |x| values.rc_deref_mut().push(x))
+    };
+
+    sender.unbounded_send(1).unwrap();
+    scheduler.run_until_stalled();
+    subscription.unsubscribe();
+    let _ = sender.unbounded_send(2);
+    scheduler.run();
+
+    assert_eq!(*values.rc_deref(), vec![1]);
+  }
+
   #[tokio::test]
   #[cfg(all(test, not(target_arch = "wasm32"), feature = "tokio-scheduler"))]
   async fn stream_channel_test() {
@@ -16,6 +16,8 @@ use super::{Observable, ObservableExt};
 /// until it completes or returns an error.
 ///
 /// This is similar to [`rxrust::from_stream`] but errors should be handled.
+/// The stream isn't polled anymore after the first error, and unsubscribing
+/// aborts the task driving it.
 pub fn from_stream_result<Item, Err, S, SD>(
   stream: S,
   scheduler: SD,
@@ -80,11 +82,13 @@ where
       match next {
         Some(Ok(value)) => {
           // Send the item to the observer
-          this
-            .observer
-            .as_mut()
-            .expect("future polled before done")
-            .next(value);
+          let observer =
+            this.observer.as_mut().expect("future polled before done");
+          observer.next(value);
+          // Stop polling the stream once downstream no longer needs values.
+          if observer.is_finished() {
+            break Poll::Ready(NormalReturn::new(()));
+          }
         }
         Some(Err(err)) => {
           let observer =
@@ -174,6 +178,42 @@ mod tests {
     assert_eq!(*error_count.rc_deref(), 1);
   }
 
+  #[test]
+  fn stop_at_first_error() {
+    let stream = futures::stream::iter([Ok(1), Err("first"), Ok(2)]);
+    let mut scheduler = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let errors = MutRc::own(vec![]);
+    {
+      let values = values.clone();
+      let errors = errors.clone();
+      from_stream_result(stream, scheduler.spawner())
+        .on_error(move |e| errors.rc_deref_mut().push(e))
+        .subscribe(move |x| values.rc_deref_mut().push(x));
+    }
+
+    scheduler.run();
+    assert_eq!(*values.rc_deref(), vec![1]);
+    assert_eq!(*errors.rc_deref(), vec!["first"]);
+  }
+
+  #[test]
+  fn unsubscribe_abort_task() {
+    let stream = futures::stream::iter([Ok::<_, ()>(1)]);
+    let mut scheduler = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let subscription = {
+      let values = values.clone();
+      from_stream_result(stream, scheduler.spawner())
+        .on_error(|_| {})
+        .subscribe(move |x| values.rc_deref_mut().push(x))
+    };
+    subscription.unsubscribe();
+
+    scheduler.run();
+    assert!(values.rc_deref().is_empty());
+  }
+
   #[tokio::test]
   #[cfg(all(test, not(target_arch = "wasm32"), feature = "tokio-scheduler"))]
   async fn stream_channel_test() {
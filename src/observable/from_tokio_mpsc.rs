@@ -0,0 +1,387 @@
+use crate::{observer::Observer, scheduler::TokioScheduler};
+use futures::Stream;
+use std::{
+  pin::Pin,
+  task::{Context, Poll},
+};
+use tokio::sync::mpsc::{
+  error::TrySendError, unbounded_channel, Receiver, Sender, UnboundedReceiver,
+  UnboundedSender,
+};
+
+use super::from_stream::{from_stream, StreamObservable};
+
+/// The receivers of the tokio `mpsc` channels, bounded or not.
+pub trait TokioMpscReceiver {
+  type Item;
+
+  fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+impl<T> TokioMpscReceiver for Receiver<T> {
+  type Item = T;
+
+  #[inline]
+  fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+    Receiver::poll_recv(self, cx)
+  }
+}
+
+impl<T> TokioMpscReceiver for UnboundedReceiver<T> {
+  type Item = T;
+
+  #[inline]
+  fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+    UnboundedReceiver::poll_recv(self, cx)
+  }
+}
+
+/// A `Stream` of the messages received by a tokio `mpsc` receiver.
+pub struct TokioMpscStream<R>(R);
+
+impl<R: TokioMpscReceiver + Unpin> Stream for TokioMpscStream<R> {
+  type Item = R::Item;
+
+  #[inline]
+  fn poll_next(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Self::Item>> {
+    self.0.poll_recv(cx)
+  }
+}
+
+/// Returns an `Observable` that emits the messages received by a tokio `mpsc`
+/// receiver, bounded or not, and completes when the channel is closed.
+///
+/// The messages are awaited in a task spawned on the tokio runtime of the
+/// caller, so it panics when called outside of a tokio runtime. Unsubscribing
+/// aborts the task and drops the receiver.
+///
+/// ```rust
+/// use rxrust::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+///   let (o, status) = observable::from_tokio_mpsc(receiver).complete_status();
+///   o.subscribe(|v| println!("{}", v));
+///
+///   for i in 0..3 {
+///     sender.send(i).unwrap();
+///   }
+///   drop(sender);
+///   while !status.is_closed() {
+///     tokio::task::yield_now().await;
+///   }
+///
+///   // prints:
+///   // 0
+///   // 1
+///   // 2
+/// }
+/// ```
+pub fn from_tokio_mpsc<R>(
+  receiver: R,
+) -> StreamObservable<TokioMpscStream<R>, TokioScheduler>
+where
+  R: TokioMpscReceiver + Unpin,
+{
+  from_stream(TokioMpscStream(receiver), TokioScheduler::current())
+}
+
+/// The senders of the tokio `mpsc` channels, bounded or not.
+pub trait TokioMpscSender<T> {
+  /// Sends `value` without waiting, it's given back if the channel is full or
+  /// closed.
+  fn send_now(&self, value: T) -> Result<(), TrySendError<T>>;
+
+  /// Spawns a task on the current tokio runtime sending the items of
+  /// `backlog` in order, each one waiting for the capacity of the channel.
+  fn spawn_backlog(&self, backlog: UnboundedReceiver<T>);
+
+  fn is_closed(&self) -> bool;
+}
+
+impl<T: Send + 'static> TokioMpscSender<T> for Sender<T> {
+  #[inline]
+  fn send_now(&self, value: T) -> Result<(), TrySendError<T>> {
+    self.try_send(value)
+  }
+
+  fn spawn_backlog(&self, mut backlog: UnboundedReceiver<T>) {
+    let sender = self.clone();
+    tokio::spawn(async move {
+      while let Some(value) = backlog.recv().await {
+        if sender.send(value).await.is_err() {
+          break;
+        }
+      }
+    });
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    Sender::is_closed(self)
+  }
+}
+
+impl<T: Send + 'static> TokioMpscSender<T> for UnboundedSender<T> {
+  #[inline]
+  fn send_now(&self, value: T) -> Result<(), TrySendError<T>> {
+    self.send(value).map_err(|e| TrySendError::Closed(e.0))
+  }
+
+  fn spawn_backlog(&self, mut backlog: UnboundedReceiver<T>) {
+    let sender = self.clone();
+    tokio::spawn(async move {
+      while let Some(value) = backlog.recv().await {
+        if sender.send(value).is_err() {
+          break;
+        }
+      }
+    });
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    UnboundedSender::is_closed(self)
+  }
+}
+
+/// An observer forwarding the notifications it receives into a tokio `mpsc`
+/// sender of `Result`s, so an observable can feed a consumer of the channel:
+/// the items are sent as `Ok` and the error as `Err`.
+///
+/// The sender is dropped when the observable completes or errors, which closes
+/// the channel once its other senders are dropped too. The observer finishes,
+/// so the source stops, when the receiver is dropped.
+///
+/// The observer can't wait for the capacity of a full bounded channel, so the
+/// one created by [`new`](TokioMpscObserver::new) finishes instead, dropping
+/// the item and stopping the source. The one created by
+/// [`with_backlog`](TokioMpscObserver::with_backlog) queues the items instead.
+///
+/// ```rust
+/// use rxrust::{observable::from_tokio_mpsc::TokioMpscObserver, prelude::*};
+///
+/// #[tokio::main]
+/// async fn main() {
+///   let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+///   observable::from_iter(0..3)
+///     .map_err(|_| "fail")
+///     .actual_subscribe(TokioMpscObserver::new(sender));
+///
+///   while let Some(v) = receiver.recv().await {
+///     println!("{:?}", v);
+///   }
+///
+///   // prints:
+///   // Ok(0)
+///   // Ok(1)
+///   // Ok(2)
+/// }
+/// ```
+pub struct TokioMpscObserver<S, T> {
+  sender: S,
+  // The messages waiting for the capacity of the channel once it was full,
+  // `None` until then or if the backlog is not enabled.
+  backlog: Option<UnboundedSender<T>>,
+  queue_when_full: bool,
+  failed: bool,
+}
+
+impl<S, T> TokioMpscObserver<S, T>
+where
+  S: TokioMpscSender<T>,
+{
+  /// Creates an observer finishing when the channel is full.
+  #[inline]
+  pub fn new(sender: S) -> Self {
+    Self {
+      sender,
+      backlog: None,
+      queue_when_full: false,
+      failed: false,
+    }
+  }
+
+  /// Creates an observer queuing the messages, without bound, once the channel
+  /// is full. They are sent in order by a task spawned on the current tokio
+  /// runtime, so it panics if the channel becomes full outside of a tokio
+  /// runtime.
+  ///
+  /// The queue defeats the backpressure of the bounded channel, use it only
+  /// when the source is known to be finite or slower than the consumer.
+  #[inline]
+  pub fn with_backlog(sender: S) -> Self {
+    Self {
+      sender,
+      backlog: None,
+      queue_when_full: true,
+      failed: false,
+    }
+  }
+
+  fn send(&mut self, value: T) {
+    if self.failed {
+      return;
+    }
+    // Once queued, the messages keep queued to be sent in order.
+    if let Some(backlog) = &self.backlog {
+      // The backlog task stops when the receiver is dropped.
+      self.failed = backlog.send(value).is_err();
+      return;
+    }
+    match self.sender.send_now(value) {
+      Ok(()) => {}
+      Err(TrySendError::Full(value)) if self.queue_when_full => {
+        let (backlog, receiver) = unbounded_channel();
+        let _ = backlog.send(value);
+        self.sender.spawn_backlog(receiver);
+        self.backlog = Some(backlog);
+      }
+      Err(_) => self.failed = true,
+    }
+  }
+}
+
+impl<Item, Err, S> Observer<Item, Err>
+  for TokioMpscObserver<S, Result<Item, Err>>
+where
+  S: TokioMpscSender<Result<Item, Err>>,
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    self.send(Ok(value))
+  }
+
+  #[inline]
+  fn error(mut self, err: Err) {
+    self.send(Err(err))
+  }
+
+  #[inline]
+  fn complete(self) {}
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.failed || self.sender.is_closed()
+  }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+  use super::*;
+  use crate::prelude::*;
+  use std::sync::{Arc, Mutex};
+
+  #[tokio::test]
+  async fn emit_bounded_messages() {
+    let (sender, receiver) = tokio::sync::mpsc::channel(2);
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    let (o, status) = from_tokio_mpsc(receiver).complete_status();
+    o.subscribe(move |v| c_values.lock().unwrap().push(v));
+    for i in 0..5 {
+      sender.send(i).await.unwrap();
+    }
+    drop(sender);
+    while !status.is_closed() {
+      tokio::task::yield_now().await;
+    }
+
+    assert_eq!(*values.lock().unwrap(), [0, 1, 2, 3, 4]);
+  }
+
+  #[tokio::test]
+  async fn unsubscribe_abort_task() {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    let subscription = from_tokio_mpsc(receiver)
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+    sender.send(0).unwrap();
+    while values.lock().unwrap().is_empty() {
+      tokio::task::yield_now().await;
+    }
+    subscription.unsubscribe();
+    let _ = sender.send(1);
+
+    // The receiver is dropped with the aborted task.
+    tokio::time::timeout(std::time::Duration::from_secs(1), sender.closed())
+      .await
+      .unwrap();
+    assert_eq!(*values.lock().unwrap(), [0]);
+  }
+
+  #[tokio::test]
+  async fn forward_into_sender() {
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    observable::from_iter(0..3)
+      .actual_subscribe(TokioMpscObserver::new(sender));
+
+    let mut values = vec![];
+    while let Some(v) = receiver.recv().await {
+      values.push(v.unwrap());
+    }
+    assert_eq!(values, [0, 1, 2]);
+  }
+
+  #[tokio::test]
+  async fn forward_error_into_sender() {
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(4);
+    observable::of(1)
+      .map_err(|_| "fail")
+      .merge(observable::throw("fail").with_item())
+      .actual_subscribe(TokioMpscObserver::new(sender));
+
+    assert_eq!(receiver.recv().await, Some(Ok(1)));
+    assert_eq!(receiver.recv().await, Some(Err("fail")));
+    assert_eq!(receiver.recv().await, None);
+  }
+
+  #[tokio::test]
+  async fn stop_source_when_channel_full() {
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(2);
+    let mut pulled = 0;
+    observable::from_iter((0..10).inspect(|_| pulled += 1))
+      .actual_subscribe(TokioMpscObserver::new(sender));
+    assert_eq!(pulled, 3);
+
+    let mut values = vec![];
+    while let Some(v) = receiver.recv().await {
+      values.push(v.unwrap());
+    }
+    assert_eq!(values, [0, 1]);
+  }
+
+  #[tokio::test]
+  async fn queue_items_of_full_channel() {
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(2);
+    observable::from_iter(0..10)
+      .map_err(|_| "fail")
+      .merge(observable::throw("fail").with_item())
+      .actual_subscribe(TokioMpscObserver::with_backlog(sender));
+
+    let mut values = vec![];
+    while let Some(v) = receiver.recv().await {
+      values.push(v);
+    }
+    let mut expected: Vec<_> = (0..10).map(Ok).collect();
+    expected.push(Err("fail"));
+    assert_eq!(values, expected);
+  }
+
+  #[tokio::test]
+  async fn stop_source_when_receiver_dropped() {
+    let (sender, receiver) = tokio::sync::mpsc::channel(2);
+    drop(receiver);
+    let mut pulled = 0;
+    observable::from_iter((0..10).inspect(|_| pulled += 1))
+      .actual_subscribe(TokioMpscObserver::with_backlog(sender));
+    assert_eq!(pulled, 1);
+  }
+}
@@ -0,0 +1,247 @@
+use crate::prelude::*;
+
+/// Creates an observable that decides at subscription which observable to
+/// subscribe to.
+///
+/// `condition` is called once at each subscription, and only the observable
+/// of the taken branch is built, by calling `then_factory` if it returned
+/// `true`, or `else_factory` otherwise.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let cached: Option<i32> = None;
+/// observable::iif(
+///   || cached.is_some(),
+///   || observable::of(cached.unwrap()),
+///   || observable::from_iter([1, 2]),
+/// )
+/// .subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // 1
+/// // 2
+/// ```
+pub fn iif<C, T, E, A, B>(
+  condition: C,
+  then_factory: T,
+  else_factory: E,
+) -> IifObservable<C, T, E>
+where
+  C: FnOnce() -> bool,
+  T: FnOnce() -> A,
+  E: FnOnce() -> B,
+{
+  IifObservable { condition, then_factory, else_factory }
+}
+
+/// Same as [`iif`], but completes right away without building any observable
+/// when `condition` returns `false`.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let verbose = false;
+/// observable::iif_option(|| verbose, || observable::of("details"))
+///   .on_complete(|| println!("completed"))
+///   .subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // completed
+/// ```
+pub fn iif_option<C, T, A>(
+  condition: C,
+  then_factory: T,
+) -> IifOptionObservable<C, T>
+where
+  C: FnOnce() -> bool,
+  T: FnOnce() -> A,
+{
+  IifOptionObservable { condition, then_factory }
+}
+
+#[derive(Clone)]
+pub struct IifObservable<C, T, E> {
+  condition: C,
+  then_factory: T,
+  else_factory: E,
+}
+
+#[derive(Clone)]
+pub struct IifOptionObservable<C, T> {
+  condition: C,
+  then_factory: T,
+}
+
+/// The subscription of the branch [`iif`] subscribed to.
+pub enum IifSubscription<T, E> {
+  Then(T),
+  Else(E),
+}
+
+impl<Item, Err, O, C, T, E, A, B> Observable<Item, Err, O>
+  for IifObservable<C, T, E>
+where
+  O: Observer<Item, Err>,
+  C: FnOnce() -> bool,
+  T: FnOnce() -> A,
+  E: FnOnce() -> B,
+  A: Observable<Item, Err, O>,
+  B: Observable<Item, Err, O>,
+{
+  type Unsub = IifSubscription<A::Unsub, B::Unsub>;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    if (self.condition)() {
+      IifSubscription::Then((self.then_factory)().actual_subscribe(observer))
+    } else {
+      IifSubscription::Else((self.else_factory)().actual_subscribe(observer))
+    }
+  }
+}
+
+impl<Item, Err, C, T, E, A, B> ObservableExt<Item, Err>
+  for IifObservable<C, T, E>
+where
+  T: FnOnce() -> A,
+  E: FnOnce() -> B,
+  A: ObservableExt<Item, Err>,
+  B: ObservableExt<Item, Err>,
+{
+}
+
+impl<Item, Err, O, C, T, A> Observable<Item, Err, O>
+  for IifOptionObservable<C, T>
+where
+  O: Observer<Item, Err>,
+  C: FnOnce() -> bool,
+  T: FnOnce() -> A,
+  A: Observable<Item, Err, O>,
+{
+  type Unsub = Option<A::Unsub>;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    if (self.condition)() {
+      Some((self.then_factory)().actual_subscribe(observer))
+    } else {
+      observer.complete();
+      None
+    }
+  }
+}
+
+impl<Item, Err, C, T, A> ObservableExt<Item, Err> for IifOptionObservable<C, T>
+where
+  T: FnOnce() -> A,
+  A: ObservableExt<Item, Err>,
+{
+}
+
+impl<T: Subscription, E: Subscription> Subscription for IifSubscription<T, E> {
+  #[inline]
+  fn unsubscribe(self) {
+    match self {
+      IifSubscription::Then(t) => t.unsubscribe(),
+      IifSubscription::Else(e) => e.unsubscribe(),
+    }
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    match self {
+      IifSubscription::Then(t) => t.is_closed(),
+      IifSubscription::Else(e) => e.is_closed(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+
+  #[test]
+  fn subscribe_one_branch() {
+    let built = MutRc::own(vec![]);
+    let flag = MutRc::own(true);
+    let (c_built, c_built2, c_flag) =
+      (built.clone(), built.clone(), flag.clone());
+    let o = observable::iif(
+      move || *c_flag.rc_deref(),
+      move || {
+        c_built.rc_deref_mut().push("then");
+        observable::of(1)
+      },
+      move || {
+        c_built2.rc_deref_mut().push("else");
+        observable::from_iter([2, 3])
+      },
+    );
+
+    let mut values = vec![];
+    o.clone().subscribe(|v| values.push(v));
+    assert_eq!(values, [1]);
+    assert_eq!(*built.rc_deref(), ["then"]);
+
+    *flag.rc_deref_mut() = false;
+    o.subscribe(|v| values.push(v));
+    assert_eq!(values, [1, 2, 3]);
+    assert_eq!(*built.rc_deref(), ["then", "else"]);
+  }
+
+  #[test]
+  fn unsubscribe_taken_branch() {
+    let then = Subject::<i32, ()>::default();
+    let other = Subject::<i32, ()>::default();
+    let (c_then, c_other) = (then.clone(), other.clone());
+    let subscription = observable::iif(
+      || false,
+      move || c_then.clone(),
+      move || c_other.clone(),
+    )
+    .on_error(|_| {})
+    .subscribe(|_| {});
+    assert!(then.is_empty());
+    assert!(!other.is_empty());
+
+    subscription.unsubscribe();
+    assert!(other.is_empty());
+  }
+
+  #[test]
+  fn option_complete_when_false() {
+    let built = MutRc::own(false);
+    let c_built = built.clone();
+    let mut completed = false;
+    let mut emitted = false;
+    observable::iif_option(
+      || false,
+      move || {
+        *c_built.rc_deref_mut() = true;
+        observable::throw("never")
+      },
+    )
+    .on_complete(|| completed = true)
+    .on_error(|_| {})
+    .subscribe(|_| emitted = true);
+
+    assert!(completed);
+    assert!(!emitted);
+    assert!(!*built.rc_deref());
+  }
+
+  #[test]
+  fn option_subscribe_when_true() {
+    let mut values = vec![];
+    observable::iif_option(|| true, || observable::from_iter([1, 2]))
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [1, 2]);
+  }
+}
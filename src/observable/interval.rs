@@ -9,6 +9,29 @@ use std::{
 
 /// Creates an observable which will fire at `dur` time into the future,
 /// and will repeat every `dur` interval after.
+///
+/// It emits the sequence numbers `0, 1, 2, ...` and never completes, the
+/// returned subscription cancels the scheduled task so no more ticks happen
+/// after it's unsubscribed.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+/// use std::time::Duration;
+///
+/// let mut pool = FuturesLocalSchedulerPool::new();
+/// observable::interval(Duration::from_millis(1), pool.spawner())
+///   .take(3)
+///   .subscribe(|seq| println!("tick {}", seq));
+///
+/// pool.run();
+///
+/// // print log:
+/// // tick 0
+/// // tick 1
+/// // tick 2
+/// ```
 pub fn interval<S>(dur: Duration, scheduler: S) -> IntervalObservable<S> {
   IntervalObservable { dur, delay: None, scheduler }
 }
@@ -106,4 +129,24 @@ mod tests {
     assert_eq!(*ticks.lock().unwrap(), 5);
     assert!(stamp.elapsed() > Duration::from_millis(5));
   }
+
+  #[test]
+  fn unsubscribe_stop_ticks() {
+    let mut local = LocalPool::new();
+    let ticks = Arc::new(Mutex::new(0));
+    let ticks_c = Arc::clone(&ticks);
+    let subscription = interval(Duration::from_millis(1), local.spawner())
+      .subscribe(move |_| *ticks_c.lock().unwrap() += 1);
+
+    while *ticks.lock().unwrap() == 0 {
+      std::thread::sleep(Duration::from_millis(1));
+      local.run_until_stalled();
+    }
+    subscription.unsubscribe();
+    let emitted = *ticks.lock().unwrap();
+
+    // The task is cancelled, so the pool finishes without any more tick.
+    local.run();
+    assert_eq!(*ticks.lock().unwrap(), emitted);
+  }
 }
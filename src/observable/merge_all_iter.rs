@@ -0,0 +1,152 @@
+use crate::{
+  observable::from_iter::ObservableIter,
+  ops::{
+    merge_all::{MergeAllOp, MergeAllOpThreads},
+    on_error_map::OnErrorMapOp,
+  },
+  prelude::*,
+};
+use std::convert::Infallible;
+
+type IterSources<S, Err> =
+  OnErrorMapOp<ObservableIter<Vec<S>>, fn(Infallible) -> Err, Infallible>;
+
+pub type MergeAllIterOp<'a, S, Err> = MergeAllOp<'a, IterSources<S, Err>, S>;
+
+pub type MergeAllIterOpThreads<S, Err> =
+  MergeAllOpThreads<IterSources<S, Err>, S>;
+
+/// Creates an observable that merges the items of all the `sources`, a
+/// runtime collection of observables of the same type.
+///
+/// It completes when all the sources have completed, and emits the first
+/// error of them. All the sources are unsubscribed by unsubscribing the
+/// returned subscription.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let sources = (0..3).map(|i| observable::from_iter(i * 10..i * 10 + 2));
+/// observable::merge_all_iter(sources).subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // 0
+/// // 1
+/// // 10
+/// // 11
+/// // 20
+/// // 21
+/// ```
+pub fn merge_all_iter<'a, S, Err>(
+  sources: impl IntoIterator<Item = S>,
+) -> MergeAllIterOp<'a, S, Err> {
+  MergeAllOp::new(iter_sources(sources), usize::MAX)
+}
+
+/// A threads safe version of [`merge_all_iter`].
+pub fn merge_all_iter_threads<S, Err>(
+  sources: impl IntoIterator<Item = S>,
+) -> MergeAllIterOpThreads<S, Err> {
+  MergeAllOpThreads::new(iter_sources(sources), usize::MAX)
+}
+
+fn iter_sources<S, Err>(
+  sources: impl IntoIterator<Item = S>,
+) -> IterSources<S, Err> {
+  let never = (|e| match e {}) as fn(Infallible) -> Err;
+  OnErrorMapOp::new(from_iter(sources.into_iter().collect()), never)
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn merge_runtime_sources() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let subjects = (0..3)
+      .map(|_| Subject::<i32, ()>::default())
+      .collect::<Vec<_>>();
+
+    observable::merge_all_iter(subjects.clone())
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+
+    for (i, s) in subjects.iter().enumerate().rev() {
+      s.clone().next(i as i32);
+    }
+    subjects[0].clone().complete();
+    subjects[2].clone().complete();
+    assert!(!*completed.rc_deref());
+    subjects[1].clone().complete();
+
+    assert_eq!(&*values.rc_deref(), &[2, 1, 0]);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn empty_sources_complete() {
+    let mut completed = false;
+    observable::merge_all_iter(Vec::<Subject<i32, ()>>::new())
+      .on_error(|_| {})
+      .on_complete(|| completed = true)
+      .subscribe(|_| {});
+
+    assert!(completed);
+  }
+
+  #[test]
+  fn error_propagate() {
+    let mut values = vec![];
+    let mut error = None;
+    {
+      let mut a = Subject::<i32, &str>::default();
+      let b = Subject::<i32, &str>::default();
+      observable::merge_all_iter([a.clone(), b.clone()])
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      a.next(1);
+      b.error("fail");
+      a.next(2);
+    }
+
+    assert_eq!(values, vec![1]);
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn unsubscribe_all() {
+    let subjects = (0..3)
+      .map(|_| Subject::<i32, ()>::default())
+      .collect::<Vec<_>>();
+    let subscription = observable::merge_all_iter(subjects.clone())
+      .on_error(|_| {})
+      .subscribe(|_| {});
+    assert!(subjects.iter().all(|s| !s.is_empty()));
+
+    subscription.unsubscribe();
+    assert!(subjects.iter().all(|s| s.is_empty()));
+  }
+
+  #[test]
+  fn merge_all_iter_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    observable::merge_all_iter_threads((0..3).map(observable::of))
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[0, 1, 2]);
+  }
+}
@@ -0,0 +1,185 @@
+use crate::prelude::*;
+use std::convert::Infallible;
+
+/// The integer types [`range`] and [`range_step`] can emit.
+pub trait RangeNum: Copy {
+  /// The step of [`range`].
+  const ONE: Self;
+
+  /// Returns `self + step`, or `None` if the result overflows.
+  fn checked_step(self, step: Self) -> Option<Self>;
+}
+
+macro_rules! impl_range_num {
+  ($($ty: ty),*) => {
+    $(
+      impl RangeNum for $ty {
+        const ONE: Self = 1;
+
+        #[inline]
+        fn checked_step(self, step: Self) -> Option<Self> {
+          self.checked_add(step)
+        }
+      }
+    )*
+  };
+}
+
+impl_range_num!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Creates an observable that emits `count` consecutive integers from `start`.
+///
+/// Completes after the last integer, or as soon as the next integer would
+/// overflow the type of `start`. Never emits an error.
+///
+/// # Examples
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::range(1u8, 3).subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // 1
+/// // 2
+/// // 3
+/// ```
+pub fn range<T: RangeNum>(start: T, count: usize) -> RangeObservable<T> {
+  range_step(start, T::ONE, count)
+}
+
+/// Creates an observable that emits `count` integers from `start`, each one
+/// being the previous one plus `step`, which may be negative.
+///
+/// Completes after the last integer, or as soon as the next integer would
+/// overflow the type of `start`. Never emits an error.
+///
+/// # Examples
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::range_step(10, -5, 4).subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // 10
+/// // 5
+/// // 0
+/// // -5
+/// ```
+pub fn range_step<T: RangeNum>(
+  start: T,
+  step: T,
+  count: usize,
+) -> RangeObservable<T> {
+  RangeObservable { start, step, count }
+}
+
+#[derive(Clone, Copy)]
+pub struct RangeObservable<T> {
+  start: T,
+  step: T,
+  count: usize,
+}
+
+impl<T, O> Observable<T, Infallible, O> for RangeObservable<T>
+where
+  T: RangeNum,
+  O: Observer<T, Infallible>,
+{
+  type Unsub = ();
+
+  fn actual_subscribe(self, mut observer: O) -> Self::Unsub {
+    let Self { start, step, count } = self;
+    let mut value = Some(start);
+    for i in 0..count {
+      let Some(v) = value else { break };
+      observer.next(v);
+      if observer.is_finished() {
+        return;
+      }
+      // Only step when another integer is needed, so the last one can be the
+      // maximum of its type.
+      if i + 1 < count {
+        value = v.checked_step(step);
+      }
+    }
+    observer.complete();
+  }
+}
+
+impl<T> ObservableExt<T, Infallible> for RangeObservable<T> {}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn consecutive() {
+    let mut values = vec![];
+    let mut completed = false;
+    observable::range(5, 4)
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [5, 6, 7, 8]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn zero_count() {
+    let mut emitted = false;
+    let mut completed = false;
+    observable::range(0u64, 0)
+      .on_complete(|| completed = true)
+      .subscribe(|_| emitted = true);
+
+    assert!(!emitted);
+    assert!(completed);
+  }
+
+  #[test]
+  fn negative_step() {
+    let mut values = vec![];
+    observable::range_step(3i8, -2, 4).subscribe(|v| values.push(v));
+
+    assert_eq!(values, [3, 1, -1, -3]);
+  }
+
+  #[test]
+  fn stop_on_overflow() {
+    let mut values = vec![];
+    let mut completed = false;
+    observable::range(u8::MAX - 2, 10)
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [253, 254, 255]);
+    assert!(completed);
+
+    let mut values = vec![];
+    observable::range_step(i32::MIN + 5, -3, 10).subscribe(|v| values.push(v));
+    assert_eq!(values, [i32::MIN + 5, i32::MIN + 2]);
+  }
+
+  #[test]
+  fn last_item_is_max() {
+    let mut values = vec![];
+    observable::range(u16::MAX - 1, 2).subscribe(|v| values.push(v));
+
+    assert_eq!(values, [u16::MAX - 1, u16::MAX]);
+  }
+
+  #[test]
+  fn stop_when_finished() {
+    let mut values = vec![];
+    let mut completed = false;
+    observable::range(0usize, usize::MAX)
+      .take(3)
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [0, 1, 2]);
+    assert!(completed);
+  }
+}
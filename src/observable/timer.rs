@@ -1,6 +1,8 @@
 use crate::{
   prelude::*,
-  scheduler::{NormalReturn, OnceTask, Scheduler, TaskHandle},
+  scheduler::{
+    NormalReturn, OnceTask, RepeatTask, Scheduler, SubscribeReturn, TaskHandle,
+  },
 };
 use std::{
   convert::Infallible,
@@ -76,6 +78,73 @@ where
 
 impl<Item, S> ObservableExt<Item, Infallible> for TimerObservable<Item, S> {}
 
+// Returns an observable which will emit `0` once after `initial_delay`, then
+// `1, 2, ...` every `period` after, using a given `scheduler`. Unsubscribing
+// before `initial_delay` elapsed cancels the first tick.
+pub fn timer_at_interval<S>(
+  initial_delay: Duration,
+  period: Duration,
+  scheduler: S,
+) -> TimerIntervalObservable<S> {
+  TimerIntervalObservable { initial_delay, period, scheduler }
+}
+
+// Emitter for `observable::timer_at_interval`.
+#[derive(Clone)]
+pub struct TimerIntervalObservable<S> {
+  initial_delay: Duration,
+  period: Duration,
+  scheduler: S,
+}
+
+type PeriodHandle = TaskHandle<NormalReturn<()>>;
+
+fn first_tick_task<O, S>(
+  (mut observer, scheduler, period): (O, S, Duration),
+) -> SubscribeReturn<PeriodHandle>
+where
+  O: Observer<usize, Infallible>,
+  S: Scheduler<RepeatTask<O>>,
+{
+  observer.next(0);
+  let handle = if observer.is_finished() {
+    TaskHandle::value_handle(NormalReturn::new(()))
+  } else {
+    scheduler.schedule(RepeatTask::new(period, period_task, observer), None)
+  };
+  SubscribeReturn::new(handle)
+}
+
+fn period_task<O>(observer: &mut O, seq: usize) -> bool
+where
+  O: Observer<usize, Infallible>,
+{
+  if !observer.is_finished() {
+    observer.next(seq + 1);
+    true
+  } else {
+    false
+  }
+}
+
+impl<O, S> Observable<usize, Infallible, O> for TimerIntervalObservable<S>
+where
+  O: Observer<usize, Infallible>,
+  S: Scheduler<OnceTask<(O, S, Duration), SubscribeReturn<PeriodHandle>>>
+    + Scheduler<RepeatTask<O>>,
+{
+  type Unsub = TaskHandle<SubscribeReturn<PeriodHandle>>;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    let Self { initial_delay, period, scheduler } = self;
+    let task =
+      OnceTask::new(first_tick_task, (observer, scheduler.clone(), period));
+    scheduler.schedule(task, Some(initial_delay))
+  }
+}
+
+impl<S> ObservableExt<usize, Infallible> for TimerIntervalObservable<S> {}
+
 #[cfg(test)]
 mod tests {
   use crate::prelude::*;
@@ -370,4 +439,98 @@ mod tests {
     assert!(now.elapsed() < duration);
     assert!(is_completed.load(Ordering::Relaxed));
   }
+
+  #[test]
+  fn timer_at_interval_shall_tick_after_initial_delay() {
+    let mut local = LocalPool::new();
+    let ticks = Arc::new(std::sync::Mutex::new(vec![]));
+    let c_ticks = ticks.clone();
+    let stamp = Instant::now();
+
+    observable::timer_at_interval(
+      Duration::from_millis(20),
+      Duration::from_millis(5),
+      local.spawner(),
+    )
+    .take(3)
+    .subscribe(move |seq| c_ticks.lock().unwrap().push((seq, stamp.elapsed())));
+
+    local.run();
+
+    let ticks = ticks.lock().unwrap();
+    assert_eq!(ticks.iter().map(|t| t.0).collect::<Vec<_>>(), [0, 1, 2]);
+    assert!(ticks[0].1 >= Duration::from_millis(20));
+    assert!(ticks[0].1 < Duration::from_millis(25));
+    assert!(ticks[2].1 >= Duration::from_millis(30));
+  }
+
+  #[test]
+  fn timer_at_interval_unsubscribe_before_initial_delay() {
+    let mut local = LocalPool::new();
+    let next_count = Arc::new(AtomicUsize::new(0));
+    let next_count_c = next_count.clone();
+
+    let subscription = observable::timer_at_interval(
+      Duration::from_millis(10),
+      Duration::from_millis(1),
+      local.spawner(),
+    )
+    .subscribe(move |_| {
+      next_count_c.fetch_add(1, Ordering::Relaxed);
+    });
+    subscription.unsubscribe();
+
+    local.run();
+    assert_eq!(next_count.load(Ordering::Relaxed), 0);
+  }
+
+  #[test]
+  fn timer_at_interval_unsubscribe_stop_ticks() {
+    let mut local = LocalPool::new();
+    let next_count = Arc::new(AtomicUsize::new(0));
+    let next_count_c = next_count.clone();
+
+    let subscription = observable::timer_at_interval(
+      Duration::from_millis(1),
+      Duration::from_millis(1),
+      local.spawner(),
+    )
+    .subscribe(move |_| {
+      next_count_c.fetch_add(1, Ordering::Relaxed);
+    });
+    while next_count.load(Ordering::Relaxed) < 2 {
+      std::thread::sleep(Duration::from_millis(1));
+      local.run_until_stalled();
+    }
+    subscription.unsubscribe();
+    let emitted = next_count.load(Ordering::Relaxed);
+
+    // The repeating task is cancelled, so the pool finishes.
+    local.run();
+    assert_eq!(next_count.load(Ordering::Relaxed), emitted);
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  #[test]
+  fn timer_at_interval_shared() {
+    use crate::ops::complete_status::CompleteStatus;
+
+    let pool = ThreadPool::new().unwrap();
+    let next_count = Arc::new(AtomicUsize::new(0));
+    let next_count_c = next_count.clone();
+
+    let (o, status) = observable::timer_at_interval(
+      Duration::from_millis(5),
+      Duration::from_millis(1),
+      pool,
+    )
+    .take(3)
+    .complete_status();
+    o.subscribe(move |_| {
+      next_count_c.fetch_add(1, Ordering::Relaxed);
+    });
+    CompleteStatus::wait_for_end(status);
+
+    assert_eq!(next_count.load(Ordering::Relaxed), 3);
+  }
 }
@@ -4,19 +4,30 @@ use crate::prelude::*;
 
 /// Creates an observable that emits no items, just terminates with an error.
 ///
+/// It emits `()` items, call [`with_item`](ThrowObservable::with_item) to
+/// give it the item type of the observables it's combined with.
+///
 /// # Arguments
 ///
 /// * `e` - An error to emit and terminate with
 pub fn throw<Err>(e: Err) -> ThrowObservable<Err> {
-  ThrowObservable(e)
+  ThrowObservable(e, TypeHint::new())
 }
 
 #[derive(Clone)]
-pub struct ThrowObservable<Err>(Err);
+pub struct ThrowObservable<Err, Item = ()>(Err, TypeHint<Item>);
 
-impl<Err, O> Observable<(), Err, O> for ThrowObservable<Err>
+impl<Err, Item> ThrowObservable<Err, Item> {
+  /// Changes the type of the items this observable never emits.
+  #[inline]
+  pub fn with_item<I>(self) -> ThrowObservable<Err, I> {
+    ThrowObservable(self.0, TypeHint::new())
+  }
+}
+
+impl<Item, Err, O> Observable<Item, Err, O> for ThrowObservable<Err, Item>
 where
-  O: Observer<(), Err>,
+  O: Observer<Item, Err>,
 {
   type Unsub = ();
 
@@ -25,11 +36,64 @@ where
   }
 }
 
-impl<Err> ObservableExt<(), Err> for ThrowObservable<Err> {}
+impl<Item, Err> ObservableExt<Item, Err> for ThrowObservable<Err, Item> {}
+
+/// Creates an observable that emits no items, just terminates with the error
+/// built by `factory`, which is called once at each subscription.
+///
+/// It emits `()` items, call [`with_item`](ThrowWithObservable::with_item) to
+/// give it the item type of the observables it's combined with.
+///
+/// # Examples
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let started = std::time::Instant::now();
+/// observable::throw_with(|| format!("failed after {:?}", started.elapsed()))
+///   .with_item::<i32>()
+///   .on_error(|e| println!("{}", e))
+///   .subscribe(|v| println!("{}", v));
+/// ```
+pub fn throw_with<F, Err>(factory: F) -> ThrowWithObservable<F>
+where
+  F: FnOnce() -> Err,
+{
+  ThrowWithObservable(factory, TypeHint::new())
+}
+
+#[derive(Clone)]
+pub struct ThrowWithObservable<F, Item = ()>(F, TypeHint<Item>);
+
+impl<F, Item> ThrowWithObservable<F, Item> {
+  /// Changes the type of the items this observable never emits.
+  #[inline]
+  pub fn with_item<I>(self) -> ThrowWithObservable<F, I> {
+    ThrowWithObservable(self.0, TypeHint::new())
+  }
+}
+
+impl<Item, Err, F, O> Observable<Item, Err, O> for ThrowWithObservable<F, Item>
+where
+  F: FnOnce() -> Err,
+  O: Observer<Item, Err>,
+{
+  type Unsub = ();
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    observer.error((self.0)());
+  }
+}
+
+impl<Item, Err, F> ObservableExt<Item, Err> for ThrowWithObservable<F, Item> where
+  F: FnOnce() -> Err
+{
+}
 
 /// Creates an observable that produces no values.
 ///
-/// Completes immediately. Never emits an error.
+/// Completes immediately. Never emits an error, call
+/// [`with_err`](EmptyObservable::with_err) to give it the error type of the
+/// observables it's combined with.
 ///
 /// # Examples
 /// ```
@@ -46,11 +110,25 @@ pub fn empty<Item>() -> EmptyObservable<Item> {
 }
 
 #[derive(Clone)]
-pub struct EmptyObservable<Item>(TypeHint<Item>);
+pub struct EmptyObservable<Item, Err = Infallible>(TypeHint<(Item, Err)>);
+
+impl<Item, Err> EmptyObservable<Item, Err> {
+  /// Changes the type of the items this observable never emits.
+  #[inline]
+  pub fn with_item<I>(self) -> EmptyObservable<I, Err> {
+    EmptyObservable(TypeHint::new())
+  }
 
-impl<Item, O> Observable<Item, Infallible, O> for EmptyObservable<Item>
+  /// Changes the type of the error this observable never emits.
+  #[inline]
+  pub fn with_err<E>(self) -> EmptyObservable<Item, E> {
+    EmptyObservable(TypeHint::new())
+  }
+}
+
+impl<Item, Err, O> Observable<Item, Err, O> for EmptyObservable<Item, Err>
 where
-  O: Observer<Item, Infallible>,
+  O: Observer<Item, Err>,
 {
   type Unsub = ();
 
@@ -59,30 +137,47 @@ where
   }
 }
 
-impl<Item> ObservableExt<Item, Infallible> for EmptyObservable<Item> {}
+impl<Item, Err> ObservableExt<Item, Err> for EmptyObservable<Item, Err> {}
+
 /// Creates an observable that never emits anything.
 ///
-/// Neither emits a value, nor completes, nor emits an error.
+/// Neither emits a value, nor completes, nor emits an error. Its item and
+/// error types are `()` and `Infallible`, call
+/// [`with_item`](NeverObservable::with_item) and
+/// [`with_err`](NeverObservable::with_err) to change them.
 #[inline]
 pub fn never() -> NeverObservable {
-  NeverObservable
+  NeverObservable(TypeHint::new())
 }
 
 #[derive(Clone)]
-pub struct NeverObservable;
+pub struct NeverObservable<Item = (), Err = Infallible>(TypeHint<(Item, Err)>);
+
+impl<Item, Err> NeverObservable<Item, Err> {
+  /// Changes the type of the items this observable never emits.
+  #[inline]
+  pub fn with_item<I>(self) -> NeverObservable<I, Err> {
+    NeverObservable(TypeHint::new())
+  }
 
-impl<O> Observable<(), Infallible, O> for NeverObservable
+  /// Changes the type of the error this observable never emits.
+  #[inline]
+  pub fn with_err<E>(self) -> NeverObservable<Item, E> {
+    NeverObservable(TypeHint::new())
+  }
+}
+
+impl<Item, Err, O> Observable<Item, Err, O> for NeverObservable<Item, Err>
 where
-  O: Observer<(), Infallible>,
+  O: Observer<Item, Err>,
 {
   type Unsub = ();
 
-  fn actual_subscribe(self, observer: O) -> Self::Unsub {
-    observer.complete();
-  }
+  fn actual_subscribe(self, _: O) -> Self::Unsub {}
 }
 
-impl ObservableExt<(), Infallible> for NeverObservable {}
+impl<Item, Err> ObservableExt<Item, Err> for NeverObservable<Item, Err> {}
+
 #[cfg(test)]
 mod test {
   use crate::prelude::*;
@@ -104,6 +199,69 @@ mod test {
     assert_eq!(error_emitted, "error");
   }
 
+  #[test]
+  fn throw_with_factory_per_subscription() {
+    let built = std::cell::Cell::new(0);
+    let mut errors = vec![];
+    observable::throw_with(|| {
+      built.set(built.get() + 1);
+      built.get()
+    })
+    .with_item::<i32>()
+    .retry(2)
+    .on_error(|e| errors.push(e))
+    .subscribe(|_| {});
+
+    assert_eq!(errors, [3]);
+  }
+
+  #[test]
+  fn throw_with_item() {
+    let mut values = vec![];
+    let mut error = None;
+    observable::of_result(Ok::<_, &str>(1))
+      .merge(observable::throw("fail").with_item())
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [1]);
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn empty_with_err() {
+    let mut values = vec![];
+    let mut completed = false;
+    observable::iif(
+      || false,
+      || observable::of_result(Err("fail")),
+      || observable::empty().with_err(),
+    )
+    .on_complete(|| completed = true)
+    .on_error(|_: &str| {})
+    .subscribe(|v: i32| values.push(v));
+
+    assert!(values.is_empty());
+    assert!(completed);
+  }
+
+  #[test]
+  fn never_emit_nothing() {
+    let mut emitted = false;
+    let mut completed = false;
+    let mut errored = false;
+    observable::never()
+      .with_item::<i32>()
+      .with_err::<&str>()
+      .on_complete(|| completed = true)
+      .on_error(|_| errored = true)
+      .subscribe(|_| emitted = true);
+
+    assert!(!emitted);
+    assert!(!completed);
+    assert!(!errored);
+  }
+
   #[test]
   fn empty() {
     let mut hits = 0;
@@ -0,0 +1,121 @@
+use crate::prelude::*;
+use std::convert::Infallible;
+
+/// Creates an observable that emits the items produced by a stateful
+/// generator.
+///
+/// At each subscription, `f` is called repeatedly with a fresh copy of `seed`
+/// and every `Some` it returns is emitted. The observable completes when `f`
+/// returns `None`, and stops calling `f` as soon as the downstream is
+/// finished, so an infinite generator can be limited by operators like `take`.
+/// Never emits an error.
+///
+/// # Examples
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::unfold((0, 1), |(a, b)| {
+///   let v = *a;
+///   (*a, *b) = (*b, *a + *b);
+///   Some(v)
+/// })
+/// .take(6)
+/// .subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // 0
+/// // 1
+/// // 1
+/// // 2
+/// // 3
+/// // 5
+/// ```
+pub fn unfold<S, F, Item>(seed: S, f: F) -> UnfoldObservable<S, F>
+where
+  F: FnMut(&mut S) -> Option<Item>,
+{
+  UnfoldObservable { seed, f }
+}
+
+#[derive(Clone)]
+pub struct UnfoldObservable<S, F> {
+  seed: S,
+  f: F,
+}
+
+impl<Item, S, F, O> Observable<Item, Infallible, O> for UnfoldObservable<S, F>
+where
+  F: FnMut(&mut S) -> Option<Item>,
+  O: Observer<Item, Infallible>,
+{
+  type Unsub = ();
+
+  fn actual_subscribe(self, mut observer: O) -> Self::Unsub {
+    let Self { mut seed, mut f } = self;
+    while let Some(v) = f(&mut seed) {
+      observer.next(v);
+      if observer.is_finished() {
+        return;
+      }
+    }
+    observer.complete();
+  }
+}
+
+impl<Item, S, F> ObservableExt<Item, Infallible> for UnfoldObservable<S, F> where
+  F: FnMut(&mut S) -> Option<Item>
+{
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn emit_until_none() {
+    let mut values = vec![];
+    let mut completed = false;
+    observable::unfold(1, |page| {
+      let v = (*page <= 3).then(|| format!("page {}", page));
+      *page += 1;
+      v
+    })
+    .on_complete(|| completed = true)
+    .subscribe(|v| values.push(v));
+
+    assert_eq!(values, ["page 1", "page 2", "page 3"]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn stop_infinite_generator() {
+    let mut calls = 0;
+    let mut values = vec![];
+    observable::unfold(0, |n| {
+      calls += 1;
+      *n += 2;
+      Some(*n)
+    })
+    .take(3)
+    .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [2, 4, 6]);
+    assert_eq!(calls, 3);
+  }
+
+  #[test]
+  fn fresh_seed_per_subscription() {
+    let o = observable::unfold(0, |n| {
+      *n += 1;
+      (*n <= 2).then_some(*n)
+    });
+    let mut first = vec![];
+    let mut second = vec![];
+    o.clone().subscribe(|v| first.push(v));
+    o.subscribe(|v| second.push(v));
+
+    assert_eq!(first, [1, 2]);
+    assert_eq!(second, [1, 2]);
+  }
+}
@@ -0,0 +1,305 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDerefMut},
+};
+
+/// Creates an observable whose subscription owns a resource, created by
+/// `resource_factory` at each subscription, and passed to `observable_factory`
+/// to build the observable to subscribe.
+///
+/// The resource is disposed once, when the observable completes or emits an
+/// error, or when the subscription is unsubscribed. It's dropped by default,
+/// call [`with_dispose`](UsingObservable::with_dispose) to give it to a
+/// function instead, like one returning a connection to its pool.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::using(
+///   || vec![1, 2, 3],
+///   |lines: &mut Vec<i32>| observable::from_iter(lines.clone()),
+/// )
+/// .with_dispose(|lines| println!("release {} lines", lines.len()))
+/// .subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // 1
+/// // 2
+/// // 3
+/// // release 3 lines
+/// ```
+pub fn using<RF, OF, R, S>(
+  resource_factory: RF,
+  observable_factory: OF,
+) -> UsingObservable<RF, OF, fn(R)>
+where
+  RF: FnOnce() -> R,
+  OF: FnOnce(&mut R) -> S,
+{
+  UsingObservable {
+    resource_factory,
+    observable_factory,
+    dispose: std::mem::drop,
+  }
+}
+
+/// A threads safe version of [`using`], the resource is disposed once even if
+/// the observable terminates on a thread while unsubscribing on another.
+pub fn using_threads<RF, OF, R, S>(
+  resource_factory: RF,
+  observable_factory: OF,
+) -> UsingObservableThreads<RF, OF, fn(R)>
+where
+  RF: FnOnce() -> R,
+  OF: FnOnce(&mut R) -> S,
+{
+  UsingObservableThreads {
+    resource_factory,
+    observable_factory,
+    dispose: std::mem::drop,
+  }
+}
+
+#[derive(Clone)]
+pub struct UsingObservable<RF, OF, D> {
+  resource_factory: RF,
+  observable_factory: OF,
+  dispose: D,
+}
+
+#[derive(Clone)]
+pub struct UsingObservableThreads<RF, OF, D> {
+  resource_factory: RF,
+  observable_factory: OF,
+  dispose: D,
+}
+
+macro_rules! impl_using_observable {
+  ($name: ident, $rc: ident) => {
+    impl<RF, OF, D> $name<RF, OF, D> {
+      /// Disposes the resource by calling `dispose` with it rather than
+      /// dropping it.
+      #[inline]
+      pub fn with_dispose<R, D2>(self, dispose: D2) -> $name<RF, OF, D2>
+      where
+        RF: FnOnce() -> R,
+        D2: FnOnce(R),
+      {
+        let Self { resource_factory, observable_factory, .. } = self;
+        $name {
+          resource_factory,
+          observable_factory,
+          dispose,
+        }
+      }
+    }
+
+    impl<Item, Err, O, RF, OF, D, R, S> Observable<Item, Err, O>
+      for $name<RF, OF, D>
+    where
+      O: Observer<Item, Err>,
+      RF: FnOnce() -> R,
+      OF: FnOnce(&mut R) -> S,
+      D: FnOnce(R),
+      S: Observable<Item, Err, UsingObserver<O, $rc<Option<(R, D)>>>>,
+    {
+      type Unsub = UsingSubscription<S::Unsub, $rc<Option<(R, D)>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let mut resource = (self.resource_factory)();
+        let source = (self.observable_factory)(&mut resource);
+        let resource = $rc::own(Some((resource, self.dispose)));
+        let subscription = source.actual_subscribe(UsingObserver {
+          observer,
+          resource: resource.clone(),
+        });
+        UsingSubscription { subscription, resource }
+      }
+    }
+
+    impl<Item, Err, RF, OF, D, R, S> ObservableExt<Item, Err>
+      for $name<RF, OF, D>
+    where
+      RF: FnOnce() -> R,
+      OF: FnOnce(&mut R) -> S,
+      S: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_using_observable!(UsingObservable, MutRc);
+impl_using_observable!(UsingObservableThreads, MutArc);
+
+pub struct UsingObserver<O, C> {
+  observer: O,
+  resource: C,
+}
+
+impl<Item, Err, O, C, R, D> Observer<Item, Err> for UsingObserver<O, C>
+where
+  O: Observer<Item, Err>,
+  C: RcDerefMut<Target = Option<(R, D)>>,
+  D: FnOnce(R),
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    self.observer.next(value);
+  }
+
+  fn error(self, err: Err) {
+    self.observer.error(err);
+    dispose_once(&self.resource);
+  }
+
+  fn complete(self) {
+    self.observer.complete();
+    dispose_once(&self.resource);
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+/// Disposes the resource if no one did yet, after releasing the lock.
+fn dispose_once<C, R, D>(resource: &C)
+where
+  C: RcDerefMut<Target = Option<(R, D)>>,
+  D: FnOnce(R),
+{
+  let resource = resource.rc_deref_mut().take();
+  if let Some((resource, dispose)) = resource {
+    dispose(resource)
+  }
+}
+
+pub struct UsingSubscription<U, C> {
+  subscription: U,
+  resource: C,
+}
+
+impl<U, C, R, D> Subscription for UsingSubscription<U, C>
+where
+  U: Subscription,
+  C: RcDerefMut<Target = Option<(R, D)>>,
+  D: FnOnce(R),
+{
+  fn unsubscribe(self) {
+    self.subscription.unsubscribe();
+    dispose_once(&self.resource);
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.subscription.is_closed()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  struct Resource(MutRc<Vec<&'static str>>);
+
+  impl Drop for Resource {
+    fn drop(&mut self) {
+      self.0.rc_deref_mut().push("dropped");
+    }
+  }
+
+  #[test]
+  fn drop_on_complete() {
+    let log = MutRc::own(vec![]);
+    let c_log = log.clone();
+    let c_log2 = log.clone();
+
+    observable::using(
+      move || {
+        c_log.rc_deref_mut().push("created");
+        Resource(c_log.clone())
+      },
+      |_: &mut Resource| observable::of(1),
+    )
+    .subscribe(move |_| c_log2.rc_deref_mut().push("next"));
+
+    assert_eq!(*log.rc_deref(), ["created", "next", "dropped"]);
+  }
+
+  #[test]
+  fn dispose_on_error() {
+    let disposed = MutRc::own(vec![]);
+    let c_disposed = disposed.clone();
+
+    observable::using(|| 7, |_: &mut i32| observable::throw("fail"))
+      .with_dispose(move |r| c_disposed.rc_deref_mut().push(r))
+      .on_error(|_| {})
+      .subscribe(|_| {});
+
+    assert_eq!(*disposed.rc_deref(), [7]);
+  }
+
+  #[test]
+  fn dispose_once_on_unsubscribe() {
+    let disposed = MutRc::own(0);
+    let c_disposed = disposed.clone();
+    let subject = Subject::<i32, ()>::default();
+    let c_subject = subject.clone();
+
+    let subscription =
+      observable::using(|| "connection", move |_: &mut &str| c_subject.clone())
+        .with_dispose(move |_| *c_disposed.rc_deref_mut() += 1)
+        .on_error(|_| {})
+        .subscribe(|_| {});
+    assert_eq!(*disposed.rc_deref(), 0);
+
+    subscription.unsubscribe();
+    assert_eq!(*disposed.rc_deref(), 1);
+    assert!(subject.is_empty());
+    subject.complete();
+    assert_eq!(*disposed.rc_deref(), 1);
+  }
+
+  #[test]
+  fn resource_per_subscription() {
+    let created = MutRc::own(0);
+    let c_created = created.clone();
+    let o = observable::using(
+      move || {
+        *c_created.rc_deref_mut() += 1;
+        *c_created.rc_deref()
+      },
+      |id: &mut i32| observable::of(*id),
+    );
+
+    let mut values = vec![];
+    o.clone().subscribe(|v| values.push(v));
+    o.subscribe(|v| values.push(v));
+    assert_eq!(values, [1, 2]);
+  }
+
+  #[test]
+  fn using_threads() {
+    let pool = Arc::new(Mutex::new(vec![]));
+    let c_pool = pool.clone();
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    observable::using_threads(
+      || String::from("conn"),
+      |conn: &mut String| observable::of(conn.len()),
+    )
+    .with_dispose(move |conn| c_pool.lock().unwrap().push(conn))
+    .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(*values.lock().unwrap(), [4]);
+    assert_eq!(*pool.lock().unwrap(), ["conn"]);
+  }
+}
@@ -0,0 +1,673 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+use std::collections::VecDeque;
+
+/// Creates an observable that zips a tuple of 2 to 8 observables, it emits
+/// the tuples of the items emitted at the same index by every source.
+///
+/// The sources running ahead have their items queued until the others catch
+/// up. Once a source completes and its queue is exhausted, the result
+/// completes and the other sources are unsubscribed.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::zip_tuple((
+///   observable::from_iter(0..3),
+///   observable::from_iter(['a', 'b', 'c', 'd']),
+///   observable::from_iter(["x", "y"]),
+/// ))
+/// .subscribe(|(i, c, s)| println!("{} {} {}", i, c, s));
+///
+/// // print logs:
+/// // 0 a x
+/// // 1 b y
+/// ```
+pub fn zip_tuple<'a, Sources, Items>(
+  sources: Sources,
+) -> ZipTupleOp<'a, Sources, Items> {
+  ZipTupleOp { sources, _hint: TypeHint::new() }
+}
+
+/// A threads safe version of [`zip_tuple`].
+pub fn zip_tuple_threads<Sources, Items>(
+  sources: Sources,
+) -> ZipTupleOpThreads<Sources, Items> {
+  ZipTupleOpThreads { sources, _hint: TypeHint::new() }
+}
+
+/// Creates an observable that zips a runtime collection of observables of
+/// the same type, it emits the rows of the items emitted at the same index by
+/// every source, in the order of `sources`.
+///
+/// It completes like [`zip_tuple`], and right away if `sources` is empty.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let sources = (0..3).map(|i| observable::from_iter(i * 10..i * 10 + 2));
+/// observable::zip_iter(sources).subscribe(|row| println!("{:?}", row));
+///
+/// // print logs:
+/// // [0, 10, 20]
+/// // [1, 11, 21]
+/// ```
+pub fn zip_iter<'a, S>(
+  sources: impl IntoIterator<Item = S>,
+) -> ZipIterOp<'a, S> {
+  ZipIterOp {
+    sources: sources.into_iter().collect(),
+    _hint: TypeHint::new(),
+  }
+}
+
+/// A threads safe version of [`zip_iter`].
+pub fn zip_iter_threads<S>(
+  sources: impl IntoIterator<Item = S>,
+) -> ZipIterOpThreads<S> {
+  ZipIterOpThreads { sources: sources.into_iter().collect() }
+}
+
+#[derive(Clone)]
+pub struct ZipTupleOp<'a, Sources, Items> {
+  sources: Sources,
+  _hint: TypeHint<&'a Items>,
+}
+
+#[derive(Clone)]
+pub struct ZipTupleOpThreads<Sources, Items> {
+  sources: Sources,
+  _hint: TypeHint<Items>,
+}
+
+#[derive(Clone)]
+pub struct ZipIterOp<'a, S> {
+  sources: Vec<S>,
+  _hint: TypeHint<&'a ()>,
+}
+
+#[derive(Clone)]
+pub struct ZipIterOpThreads<S> {
+  sources: Vec<S>,
+}
+
+/// The queues of the items waiting for the other sources of a zip.
+pub trait ZipQueues {
+  type Row;
+
+  /// Pops an item from every queue if none of them is empty.
+  fn pop_row(&mut self) -> Option<Self::Row>;
+
+  fn is_empty_at(&self, index: usize) -> bool;
+}
+
+impl<T> ZipQueues for Vec<VecDeque<T>> {
+  type Row = Vec<T>;
+
+  fn pop_row(&mut self) -> Option<Vec<T>> {
+    if self.iter().any(VecDeque::is_empty) {
+      return None;
+    }
+    Some(self.iter_mut().filter_map(VecDeque::pop_front).collect())
+  }
+
+  #[inline]
+  fn is_empty_at(&self, index: usize) -> bool {
+    self[index].is_empty()
+  }
+}
+
+/// The state shared by all the sources of a zip.
+pub struct ZipAllState<O, Q, U> {
+  observer: Option<O>,
+  queues: Q,
+  completed: Vec<bool>,
+  subscriptions: Vec<Option<U>>,
+}
+
+impl<O, Q, U> ZipAllState<O, Q, U> {
+  fn new(observer: O, queues: Q, len: usize) -> Self {
+    Self {
+      observer: Some(observer),
+      queues,
+      completed: vec![false; len],
+      subscriptions: (0..len).map(|_| None).collect(),
+    }
+  }
+
+  fn is_finished<Err>(&self) -> bool
+  where
+    Q: ZipQueues,
+    O: Observer<Q::Row, Err>,
+  {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+
+  /// Takes the subscriptions of the sources other than the `index`th one,
+  /// the `index`th source is the one notifying and stops by itself.
+  fn take_others(&mut self, index: usize) -> impl Iterator<Item = U> {
+    std::mem::take(&mut self.subscriptions)
+      .into_iter()
+      .enumerate()
+      .filter_map(move |(i, u)| u.filter(|_| i != index))
+  }
+}
+
+/// Keeps the subscription of the `index`th source, or unsubscribes it right
+/// away if the zip already finished.
+fn add_subscription<R, O, Q, U, Err>(state: &R, index: usize, unsub: U)
+where
+  R: RcDeref<Target = ZipAllState<O, Q, U>>
+    + RcDerefMut<Target = ZipAllState<O, Q, U>>,
+  Q: ZipQueues,
+  O: Observer<Q::Row, Err>,
+  U: Subscription,
+{
+  let mut inner = state.rc_deref_mut();
+  if inner.is_finished() {
+    drop(inner);
+    unsub.unsubscribe();
+  } else {
+    inner.subscriptions[index] = Some(unsub);
+  }
+}
+
+/// Observes the `index`th source of a zip, `push` queues its items.
+pub struct ZipSideObserver<R, Q, Item> {
+  state: R,
+  index: usize,
+  push: fn(&mut Q, usize, Item),
+}
+
+impl<Item, Err, O, Q, U, R> Observer<Item, Err> for ZipSideObserver<R, Q, Item>
+where
+  R: RcDeref<Target = ZipAllState<O, Q, U>>
+    + RcDerefMut<Target = ZipAllState<O, Q, U>>,
+  Q: ZipQueues,
+  O: Observer<Q::Row, Err>,
+  U: Subscription,
+{
+  fn next(&mut self, value: Item) {
+    let mut state = self.state.rc_deref_mut();
+    let ZipAllState { observer, queues, completed, .. } = &mut *state;
+    let Some(o) = observer.as_mut() else {
+      return;
+    };
+    (self.push)(queues, self.index, value);
+    let Some(row) = queues.pop_row() else {
+      return;
+    };
+    o.next(row);
+    // A completed source without queued items can't fill another row.
+    let exhausted = completed
+      .iter()
+      .enumerate()
+      .any(|(i, c)| *c && queues.is_empty_at(i));
+    if exhausted {
+      let observer = observer.take();
+      let others: Vec<_> = state.take_others(self.index).collect();
+      drop(state);
+      others.into_iter().for_each(|u| u.unsubscribe());
+      if let Some(observer) = observer {
+        observer.complete();
+      }
+    }
+  }
+
+  fn error(self, err: Err) {
+    let mut state = self.state.rc_deref_mut();
+    if let Some(observer) = state.observer.take() {
+      let others: Vec<_> = state.take_others(self.index).collect();
+      drop(state);
+      others.into_iter().for_each(|u| u.unsubscribe());
+      observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    let mut state = self.state.rc_deref_mut();
+    state.completed[self.index] = true;
+    if !state.queues.is_empty_at(self.index) {
+      return;
+    }
+    if let Some(observer) = state.observer.take() {
+      let others: Vec<_> = state.take_others(self.index).collect();
+      drop(state);
+      others.into_iter().for_each(|u| u.unsubscribe());
+      observer.complete();
+    }
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.state.rc_deref().is_finished()
+  }
+}
+
+pub struct ZipAllSubscription<R>(R);
+
+impl<R, O, Q, U> Subscription for ZipAllSubscription<R>
+where
+  R: RcDeref<Target = ZipAllState<O, Q, U>>
+    + RcDerefMut<Target = ZipAllState<O, Q, U>>,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let subscriptions =
+      std::mem::take(&mut self.0.rc_deref_mut().subscriptions);
+    subscriptions
+      .into_iter()
+      .flatten()
+      .for_each(|u| u.unsubscribe());
+  }
+
+  fn is_closed(&self) -> bool {
+    self.0.rc_deref().subscriptions.iter().all(Option::is_none)
+  }
+}
+
+macro_rules! impl_zip_iter_op {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S> Observable<Vec<Item>, Err, O>
+      for $name<$($lf,)? S>
+    where
+      O: Observer<Vec<Item>, Err>,
+      S: Observable<
+        Item,
+        Err,
+        ZipSideObserver<
+          $rc<ZipAllState<O, Vec<VecDeque<Item>>, $box_unsub>>,
+          Vec<VecDeque<Item>>,
+          Item,
+        >,
+      >,
+      S::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = ZipAllSubscription<
+        $rc<ZipAllState<O, Vec<VecDeque<Item>>, $box_unsub>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let len = self.sources.len();
+        let queues = (0..len).map(|_| VecDeque::new()).collect();
+        let state = $rc::own(ZipAllState::new(observer, queues, len));
+        if len == 0 {
+          if let Some(observer) = state.rc_deref_mut().observer.take() {
+            observer.complete();
+          }
+        }
+        for (index, source) in self.sources.into_iter().enumerate() {
+          if state.rc_deref().is_finished() {
+            break;
+          }
+          let unsub = source.actual_subscribe(ZipSideObserver {
+            state: state.clone(),
+            index,
+            push: |q: &mut Vec<VecDeque<Item>>, i, v| q[i].push_back(v),
+          });
+          add_subscription(&state, index, <$box_unsub>::new(unsub));
+        }
+        ZipAllSubscription(state)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S> ObservableExt<Vec<Item>, Err>
+      for $name<$($lf,)? S>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_zip_iter_op!(ZipIterOp, MutRc, BoxSubscription<'a>, 'a);
+impl_zip_iter_op!(ZipIterOpThreads, MutArc, BoxSubscriptionThreads, Send);
+
+// Subscribes the sources of a tuple in order, stops once the zip finished.
+macro_rules! zip_tuple_subscribe {
+  (
+    $sources: expr, $observer: expr, $rc: ident, $box_unsub: ty,
+    $queues: ty; $($S: ident $T: ident $idx: tt),+
+  ) => {{
+    let len = [$($idx),+].len();
+    let queues = <$queues>::default();
+    let state = $rc::own(ZipAllState::new($observer, queues, len));
+    let sources = $sources;
+    $(
+      if !state.rc_deref().is_finished() {
+        let push = |q: &mut $queues, _, v| q.$idx.push_back(v);
+        let unsub = sources.$idx.actual_subscribe(ZipSideObserver {
+          state: state.clone(),
+          index: $idx,
+          push: push as fn(&mut $queues, usize, $T),
+        });
+        add_subscription(&state, $idx, <$box_unsub>::new(unsub));
+      }
+    )+
+    ZipAllSubscription(state)
+  }};
+}
+
+macro_rules! impl_zip_tuple {
+  ($queues: ty; $($S: ident $T: ident $idx: tt),+) => {
+    impl<$($T),+> ZipQueues for $queues {
+      type Row = ($($T,)+);
+
+      fn pop_row(&mut self) -> Option<Self::Row> {
+        if $(self.$idx.is_empty())||+ {
+          return None;
+        }
+        Some(($(self.$idx.pop_front()?,)+))
+      }
+
+      fn is_empty_at(&self, index: usize) -> bool {
+        match index {
+          $($idx => self.$idx.is_empty(),)+
+          _ => true,
+        }
+      }
+    }
+
+    impl<'a, $($S, $T,)+ Err, O> Observable<($($T,)+), Err, O>
+      for ZipTupleOp<'a, ($($S,)+), ($($T,)+)>
+    where
+      O: Observer<($($T,)+), Err>,
+      $(
+        $S: Observable<
+          $T,
+          Err,
+          ZipSideObserver<
+            MutRc<ZipAllState<O, $queues, BoxSubscription<'a>>>,
+            $queues,
+            $T,
+          >,
+        >,
+        $S::Unsub: 'a,
+      )+
+    {
+      type Unsub = ZipAllSubscription<
+        MutRc<ZipAllState<O, $queues, BoxSubscription<'a>>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        zip_tuple_subscribe!(
+          self.sources, observer, MutRc, BoxSubscription<'a>, $queues;
+          $($S $T $idx),+
+        )
+      }
+    }
+
+    impl<'a, $($S, $T,)+ Err> ObservableExt<($($T,)+), Err>
+      for ZipTupleOp<'a, ($($S,)+), ($($T,)+)>
+    where
+      $($S: ObservableExt<$T, Err>,)+
+    {
+    }
+
+    impl<$($S, $T,)+ Err, O> Observable<($($T,)+), Err, O>
+      for ZipTupleOpThreads<($($S,)+), ($($T,)+)>
+    where
+      O: Observer<($($T,)+), Err>,
+      $(
+        $S: Observable<
+          $T,
+          Err,
+          ZipSideObserver<
+            MutArc<ZipAllState<O, $queues, BoxSubscriptionThreads>>,
+            $queues,
+            $T,
+          >,
+        >,
+        $S::Unsub: Send + 'static,
+      )+
+    {
+      type Unsub = ZipAllSubscription<
+        MutArc<ZipAllState<O, $queues, BoxSubscriptionThreads>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        zip_tuple_subscribe!(
+          self.sources, observer, MutArc, BoxSubscriptionThreads, $queues;
+          $($S $T $idx),+
+        )
+      }
+    }
+
+    impl<$($S, $T,)+ Err> ObservableExt<($($T,)+), Err>
+      for ZipTupleOpThreads<($($S,)+), ($($T,)+)>
+    where
+      $($S: ObservableExt<$T, Err>,)+
+    {
+    }
+  };
+}
+
+impl_zip_tuple!(
+  (VecDeque<T0>, VecDeque<T1>);
+  S0 T0 0, S1 T1 1
+);
+impl_zip_tuple!(
+  (VecDeque<T0>, VecDeque<T1>, VecDeque<T2>);
+  S0 T0 0, S1 T1 1, S2 T2 2
+);
+impl_zip_tuple!(
+  (VecDeque<T0>, VecDeque<T1>, VecDeque<T2>, VecDeque<T3>);
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3
+);
+impl_zip_tuple!(
+  (VecDeque<T0>, VecDeque<T1>, VecDeque<T2>, VecDeque<T3>, VecDeque<T4>);
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3, S4 T4 4
+);
+impl_zip_tuple!(
+  (
+    VecDeque<T0>,
+    VecDeque<T1>,
+    VecDeque<T2>,
+    VecDeque<T3>,
+    VecDeque<T4>,
+    VecDeque<T5>,
+  );
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3, S4 T4 4, S5 T5 5
+);
+impl_zip_tuple!(
+  (
+    VecDeque<T0>,
+    VecDeque<T1>,
+    VecDeque<T2>,
+    VecDeque<T3>,
+    VecDeque<T4>,
+    VecDeque<T5>,
+    VecDeque<T6>,
+  );
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3, S4 T4 4, S5 T5 5, S6 T6 6
+);
+impl_zip_tuple!(
+  (
+    VecDeque<T0>,
+    VecDeque<T1>,
+    VecDeque<T2>,
+    VecDeque<T3>,
+    VecDeque<T4>,
+    VecDeque<T5>,
+    VecDeque<T6>,
+    VecDeque<T7>,
+  );
+  S0 T0 0, S1 T1 1, S2 T2 2, S3 T3 3, S4 T4 4, S5 T5 5, S6 T6 6, S7 T7 7
+);
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn zip_three() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::zip_tuple((
+      observable::from_iter(0..3),
+      observable::from_iter(['a', 'b', 'c', 'd']),
+      observable::from_iter(["x", "y", "z"]).map(str::to_owned),
+    ))
+    .on_complete(|| completed = true)
+    .subscribe(|v| values.push(v));
+
+    assert_eq!(
+      values,
+      vec![
+        (0, 'a', "x".to_owned()),
+        (1, 'b', "y".to_owned()),
+        (2, 'c', "z".to_owned())
+      ]
+    );
+    assert!(completed);
+  }
+
+  #[test]
+  fn zip_eight() {
+    let mut values = vec![];
+
+    observable::zip_tuple((
+      observable::of(0),
+      observable::of(1u8),
+      observable::of(2u16),
+      observable::of(3u32),
+      observable::of(4u64),
+      observable::of('5'),
+      observable::of("6"),
+      observable::of(7.0),
+    ))
+    .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![(0, 1, 2, 3, 4, '5', "6", 7.0)]);
+  }
+
+  #[test]
+  fn complete_when_exhausted() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut a = Subject::<i32, ()>::default();
+    let mut b = Subject::<i32, ()>::default();
+    let mut c = Subject::<i32, ()>::default();
+
+    observable::zip_tuple((a.clone(), b.clone(), c.clone()))
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+
+    a.next(1);
+    a.next(2);
+    a.clone().complete();
+    b.next(1);
+    assert!(!*completed.rc_deref());
+    c.next(1);
+    assert!(!*completed.rc_deref());
+    b.next(2);
+    c.next(2);
+
+    assert_eq!(&*values.rc_deref(), &[(1, 1, 1), (2, 2, 2)]);
+    assert!(*completed.rc_deref());
+    assert!(b.is_empty());
+    assert!(c.is_empty());
+  }
+
+  #[test]
+  fn error_unsubscribe_others() {
+    let mut error = None;
+    {
+      let a = Subject::<i32, &str>::default();
+      let b = Subject::<i32, &str>::default();
+      let c = Subject::<i32, &str>::default();
+      observable::zip_tuple((a.clone(), b.clone(), c.clone()))
+        .on_error(|e| error = Some(e))
+        .subscribe(|_| {});
+
+      c.error("fail");
+      assert!(a.is_empty());
+      assert!(b.is_empty());
+    }
+
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn zip_iter_rows() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::zip_iter([
+      observable::from_iter(vec![1, 2, 3]),
+      observable::from_iter(vec![10, 20]),
+      observable::from_iter(vec![100, 200, 300]),
+    ])
+    .on_complete(|| completed = true)
+    .subscribe(|row| values.push(row));
+
+    assert_eq!(values, vec![vec![1, 10, 100], vec![2, 20, 200]]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn zip_iter_empty() {
+    let mut completed = false;
+
+    observable::zip_iter(Vec::<Subject<i32, ()>>::new())
+      .on_complete(|| completed = true)
+      .on_error(|_| {})
+      .subscribe(|_| unreachable!());
+
+    assert!(completed);
+  }
+
+  #[test]
+  fn zip_iter_unsubscribe() {
+    let subjects = (0..3)
+      .map(|_| Subject::<i32, ()>::default())
+      .collect::<Vec<_>>();
+    let subscription = observable::zip_iter(subjects.clone())
+      .on_error(|_| {})
+      .subscribe(|_| {});
+    assert!(subjects.iter().all(|s| !s.is_empty()));
+
+    subscription.unsubscribe();
+    assert!(subjects.iter().all(|s| s.is_empty()));
+  }
+
+  #[test]
+  fn zip_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let c_rows = values.clone();
+
+    observable::zip_tuple_threads((
+      observable::from_iter(0..2),
+      observable::from_iter(2..4),
+      observable::from_iter(4..6),
+    ))
+    .subscribe(move |(a, b, c)| c_values.lock().unwrap().push(vec![a, b, c]));
+    observable::zip_iter_threads(
+      (0..3).map(|i| observable::from_iter(i..i + 1)),
+    )
+    .subscribe(move |row| c_rows.lock().unwrap().push(row));
+
+    assert_eq!(
+      &*values.lock().unwrap(),
+      &[vec![0, 2, 4], vec![1, 3, 5], vec![0, 1, 2]]
+    );
+  }
+}
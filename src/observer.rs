@@ -130,7 +130,7 @@ macro_rules! impl_rc_observer {
       }
 
       fn is_finished(&self) -> bool {
-        self.rc_deref().as_ref().map_or(true, |o| o.is_finished())
+        self.rc_deref().as_ref().is_none_or(|o| o.is_finished())
       }
     }
   };
@@ -1,5 +1,7 @@
+pub mod audit;
 pub mod box_it;
 pub mod buffer;
+pub mod catch_unwind;
 pub mod collect;
 pub mod combine_latest;
 pub mod complete_status;
@@ -8,24 +10,39 @@ pub mod debounce;
 pub mod default_if_empty;
 pub mod delay;
 pub mod distinct;
+pub mod exhaust_map;
+pub mod expand;
 pub mod filter;
 pub mod filter_map;
 pub mod finalize;
+pub mod find;
+pub mod flat_map_iter;
 pub mod future;
 pub mod group_by;
 pub mod last;
 pub mod map;
 pub mod map_to;
+pub mod materialize;
 pub mod merge;
 pub mod merge_all;
+pub mod min_max_by_key;
 pub mod observe_on;
 pub mod on_complete;
 pub mod on_error;
 pub mod on_error_map;
+pub mod on_error_resume_next;
 pub mod pairwise;
+pub mod partition;
+pub mod race;
 pub mod ref_count;
+pub mod repeat;
+pub mod repeat_when;
+pub mod retry;
+pub mod retry_when;
 pub mod sample;
 pub mod scan;
+pub mod sequence_equal;
+pub mod single;
 pub mod skip;
 pub mod skip_last;
 pub mod skip_until;
@@ -33,24 +50,32 @@ pub mod skip_while;
 pub mod start_with;
 pub mod stream;
 pub mod subscribe_on;
+pub mod switch_on_next;
 pub mod take;
 pub mod take_last;
 pub mod take_until;
 pub mod take_while;
 pub mod tap;
 pub mod throttle;
+pub mod time_interval;
+pub mod timeout;
+pub mod timestamp;
+pub mod window;
 pub mod with_latest_from;
 pub mod zip;
 
 use default_if_empty::DefaultIfEmptyOp;
+use filter::FilterOp;
 use last::LastOp;
 use map::MapOp;
 use scan::ScanOp;
 
 use self::merge_all::{MergeAllOp, MergeAllOpThreads};
+use self::switch_on_next::{SwitchOnNextOp, SwitchOnNextOpThreads};
 
 pub type CountOp<Source, Item> =
   ReduceOp<Source, fn(usize, Item) -> usize, usize, Item>;
+pub type CountByOp<Source, F, Item> = CountOp<FilterOp<Source, F>, Item>;
 pub type SumOp<Source, Item> =
   ReduceOp<Source, fn(Item, Item) -> Item, Item, Item>;
 
@@ -92,6 +117,15 @@ pub type FlatMapOp<'a, Source, OutputItem, F, Input> =
 pub type FlatMapOpThreads<Source, OutputItem, F, Input> =
   MergeAllOpThreads<MapOp<Source, F, Input>, OutputItem>;
 
+/// Returns an Observable that emits items based on applying a function that you
+/// supply to each item emitted by the source Observable, where that function
+/// returns an Observable, and then emitting only the items of the most recent
+/// of those Observables.
+pub type SwitchMapOp<'a, Source, OutputItem, F, Input> =
+  SwitchOnNextOp<'a, MapOp<Source, F, Input>, OutputItem>;
+pub type SwitchMapOpThreads<Source, OutputItem, F, Input> =
+  SwitchOnNextOpThreads<MapOp<Source, F, Input>, OutputItem>;
+
 #[cfg(test)]
 mod test {
   use crate::prelude::*;
@@ -116,6 +150,43 @@ mod test {
     // expected to emit the initial value
     assert_eq!(100, emitted);
   }
+  #[test]
+  fn reduce_initial_emit_once_on_complete() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .reduce_initial(100, |acc, v| acc + v)
+      .on_complete(|| completed = true)
+      .subscribe(|v| emitted.push(v));
+
+    subject.next(1);
+    subject.next(2);
+    subject.complete();
+
+    assert_eq!(emitted, [103]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn reduce_initial_not_emit_on_error() {
+    let mut emitted = vec![];
+    let mut errors = 0;
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .reduce_initial(100, |acc, v| acc + v)
+      .on_error(|_| errors += 1)
+      .subscribe(|v| emitted.push(v));
+
+    subject.next(1);
+    subject.error("error");
+
+    assert!(emitted.is_empty());
+    assert_eq!(errors, 1);
+  }
+
   #[test]
   fn reduce() {
     let mut emitted = 0;
@@ -221,6 +292,43 @@ mod test {
     assert_eq!(None, emitted);
   }
 
+  #[test]
+  fn max_by_key() {
+    let mut emitted = vec![];
+    observable::from_iter(vec!["a", "ccc", "bb", "ddd"])
+      .max_by_key(|s: &&str| s.len())
+      .subscribe(|v| emitted.push(v));
+    assert_eq!(emitted, ["ddd"]);
+  }
+
+  #[test]
+  fn max_by_key_compute_key_once_per_item() {
+    // A local key closure, capturing an `Rc`, is accepted.
+    let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+    let c_calls = calls.clone();
+    let mut emitted = vec![];
+    observable::from_iter(vec![3, 1, 4, 1, 5])
+      .max_by_key(move |v: &i32| {
+        c_calls.set(c_calls.get() + 1);
+        *v
+      })
+      .subscribe(|v| emitted.push(v));
+    assert_eq!(emitted, [5]);
+    assert_eq!(calls.get(), 5);
+  }
+
+  #[test]
+  fn max_by_key_on_empty_observable() {
+    let mut completed = false;
+    let mut emitted: Option<&str> = None;
+    observable::empty()
+      .max_by_key(|s: &&str| s.len())
+      .on_complete(|| completed = true)
+      .subscribe(|v| emitted = Some(v));
+    assert_eq!(None, emitted);
+    assert!(completed);
+  }
+
   // -------------------------------------------------------------------
   // testing Min operator
   // -------------------------------------------------------------------
@@ -284,6 +392,15 @@ mod test {
     assert_eq!(None, emitted);
   }
 
+  #[test]
+  fn min_by_key() {
+    let mut emitted = vec![];
+    observable::from_iter(vec![(1, "a"), (-3, "b"), (2, "c"), (-3, "d")])
+      .min_by_key(|v: &(i32, &str)| v.0)
+      .subscribe(|v| emitted.push(v.1));
+    assert_eq!(emitted, ["d"]);
+  }
+
   #[test]
   fn sum() {
     let mut emitted = 0;
@@ -334,6 +451,33 @@ mod test {
     assert_eq!(0, emitted);
   }
 
+  #[test]
+  fn count_by() {
+    let mut emitted = 0;
+    observable::from_iter(0..10)
+      .count_by(|v| v % 3 == 0)
+      .subscribe(|v| emitted = v);
+    assert_eq!(4, emitted);
+  }
+
+  #[test]
+  fn count_not_emit_on_error() {
+    let mut emitted = vec![];
+    let mut errors = 0;
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .count()
+      .on_error(|_| errors += 1)
+      .subscribe(|v| emitted.push(v));
+
+    subject.next(1);
+    subject.error("error");
+
+    assert!(emitted.is_empty());
+    assert_eq!(errors, 1);
+  }
+
   #[test]
   fn average_of_floats() {
     let mut emitted = 0.0;
@@ -394,10 +538,13 @@ mod test {
   #[test]
   fn average_on_empty_observable() {
     let mut emitted: Option<f64> = None;
+    let mut completed = false;
     observable::empty()
       .average()
+      .on_complete(|| completed = true)
       .subscribe(|v| emitted = Some(v));
     assert_eq!(None, emitted);
+    assert!(completed);
   }
 
   // -------------------------------------------------------------------
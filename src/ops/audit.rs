@@ -0,0 +1,245 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, RcDeref, RcDerefMut},
+};
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct AuditOp<S, SD> {
+  pub(crate) source: S,
+  pub(crate) scheduler: SD,
+  pub(crate) duration: Duration,
+}
+
+type RcHandler = MutArc<Option<TaskHandle<NormalReturn<()>>>>;
+
+impl<Item, Err, O, S, SD> Observable<Item, Err, O> for AuditOp<S, SD>
+where
+  S: Observable<Item, Err, AuditObserver<O, SD, Item>>,
+  SD: Scheduler<
+    OnceTask<(MutArc<Option<O>>, MutArc<Option<Item>>), NormalReturn<()>>,
+  >,
+  O: Observer<Item, Err>,
+{
+  type Unsub = ZipSubscription<S::Unsub, RcHandler>;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    let Self { source, scheduler, duration } = self;
+    let task_handler = MutArc::own(None);
+    let observer = AuditObserver {
+      observer: MutArc::own(Some(observer)),
+      duration,
+      scheduler,
+      last_value: MutArc::own(None),
+      task_handler: task_handler.clone(),
+    };
+    let u = source.actual_subscribe(observer);
+    ZipSubscription::new(u, task_handler)
+  }
+}
+
+impl<Item, Err, S, SD> ObservableExt<Item, Err> for AuditOp<S, SD> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct AuditObserver<O, SD, Item> {
+  observer: MutArc<Option<O>>,
+  scheduler: SD,
+  duration: Duration,
+  last_value: MutArc<Option<Item>>,
+  task_handler: RcHandler,
+}
+
+impl<O, SD, Item> AuditObserver<O, SD, Item> {
+  fn timer_pending(&self) -> bool {
+    self
+      .task_handler
+      .rc_deref()
+      .as_ref()
+      .is_some_and(|h| !h.is_closed())
+  }
+
+  fn cancel_timer(&self) {
+    if let Some(handler) = self.task_handler.rc_deref_mut().take() {
+      handler.unsubscribe()
+    }
+  }
+}
+
+fn audit_task<O, Item, Err>(
+  (mut observer, value): (MutArc<Option<O>>, MutArc<Option<Item>>),
+) -> NormalReturn<()>
+where
+  O: Observer<Item, Err>,
+{
+  if let Some(value) = value.rc_deref_mut().take() {
+    observer.next(value);
+  }
+  NormalReturn::new(())
+}
+
+impl<Item, Err, O, SD> Observer<Item, Err> for AuditObserver<O, SD, Item>
+where
+  O: Observer<Item, Err>,
+  SD: Scheduler<
+    OnceTask<(MutArc<Option<O>>, MutArc<Option<Item>>), NormalReturn<()>>,
+  >,
+{
+  fn next(&mut self, value: Item) {
+    *self.last_value.rc_deref_mut() = Some(value);
+    if !self.timer_pending() {
+      let task = OnceTask::new(
+        audit_task,
+        (self.observer.clone(), self.last_value.clone()),
+      );
+      let handler = self.scheduler.schedule(task, Some(self.duration));
+      *self.task_handler.rc_deref_mut() = Some(handler);
+    }
+  }
+
+  fn error(self, err: Err) {
+    self.last_value.rc_deref_mut().take();
+    self.cancel_timer();
+    self.observer.error(err);
+  }
+
+  fn complete(mut self) {
+    self.cancel_timer();
+    if let Some(value) = self.last_value.rc_deref_mut().take() {
+      self.observer.next(value);
+    }
+    self.observer.complete();
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rc::MutRc;
+  use futures::executor::LocalPool;
+
+  #[test]
+  fn emit_last_value_of_window() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+
+    observable::interval(Duration::from_millis(2), spawner.clone())
+      .take(10)
+      .audit_time(Duration::from_millis(5), spawner)
+      .subscribe(move |v| x.rc_deref_mut().push(v));
+    pool.run();
+
+    let x = x_c.rc_deref();
+    assert!(!x.is_empty() && x.len() < 10);
+    assert!(x.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(x.last(), Some(&9));
+  }
+
+  #[test]
+  fn busy_source_not_postpone_emission() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+
+    // every value arrives before the previous debounce window ends, but audit
+    // still emits once per window.
+    observable::interval(Duration::from_millis(1), spawner.clone())
+      .take(30)
+      .audit_time(Duration::from_millis(5), spawner)
+      .subscribe(move |v| x.rc_deref_mut().push(v));
+    pool.run();
+
+    assert!(x_c.rc_deref().len() > 1);
+  }
+
+  #[test]
+  fn flush_pending_on_complete() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let completed = MutRc::own(false);
+    let c_c = completed.clone();
+    let mut pool = LocalPool::new();
+    let mut subject = Subject::default();
+
+    subject
+      .clone()
+      .audit_time(Duration::from_millis(100), pool.spawner())
+      .on_complete(move || *completed.rc_deref_mut() = true)
+      .subscribe(move |v| x.rc_deref_mut().push(v));
+    subject.next(1);
+    subject.next(2);
+    subject.complete();
+    pool.run();
+
+    assert_eq!(&*x_c.rc_deref(), &[2]);
+    assert!(*c_c.rc_deref());
+  }
+
+  #[test]
+  fn drop_pending_on_error() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = LocalPool::new();
+    let mut subject = Subject::default();
+
+    subject
+      .clone()
+      .audit_time(Duration::from_millis(5), pool.spawner())
+      .on_error(|_: &str| {})
+      .subscribe(move |v| x.rc_deref_mut().push(v));
+    subject.next(1);
+    subject.error("error");
+    pool.run();
+
+    assert!(x_c.rc_deref().is_empty());
+  }
+
+  #[test]
+  fn unsubscribe_cancel_timer() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = LocalPool::new();
+    let mut subject = Subject::default();
+
+    let sub = subject
+      .clone()
+      .audit_time(Duration::from_millis(5), pool.spawner())
+      .subscribe(move |v: i32| x.rc_deref_mut().push(v));
+    subject.next(1);
+    sub.unsubscribe();
+    pool.run();
+
+    assert!(x_c.rc_deref().is_empty());
+  }
+
+  #[test]
+  fn shared_audit_time() {
+    use crate::ops::complete_status::CompleteStatus;
+    use futures::executor::ThreadPool;
+
+    let x = MutArc::own(vec![]);
+    let x_c = x.clone();
+    let pool = ThreadPool::new().unwrap();
+
+    let (o, status) =
+      observable::interval(Duration::from_millis(1), pool.clone())
+        .take(10)
+        .audit_time(Duration::from_millis(3), pool)
+        .complete_status();
+    o.subscribe(move |v| x.rc_deref_mut().push(v));
+    CompleteStatus::wait_for_end(status);
+
+    let x = x_c.rc_deref();
+    assert!(x.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(x.last(), Some(&9));
+  }
+}
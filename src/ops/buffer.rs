@@ -1,6 +1,7 @@
 use crate::{
+  observer::{BoxObserver, BoxObserverThreads},
   prelude::*,
-  rc::{MutArc, RcDerefMut},
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
 };
 use std::{time::Duration, vec};
 
@@ -67,10 +68,17 @@ impl<O, Item> BufferObserver<O, Item> {
     O: Observer<Vec<Item>, Err>,
   {
     if !self.data.is_empty() {
-      let buffer = std::mem::take(&mut self.data);
-      self.observer.next(buffer);
+      self.flush();
     }
   }
+
+  fn flush<Err>(&mut self)
+  where
+    O: Observer<Vec<Item>, Err>,
+  {
+    let buffer = std::mem::take(&mut self.data);
+    self.observer.next(buffer);
+  }
 }
 
 #[derive(Clone)]
@@ -112,6 +120,7 @@ pub struct BufferWithTimeOp<Source, Scheduler> {
   pub(crate) source: Source,
   pub(crate) time: Duration,
   pub(crate) scheduler: Scheduler,
+  pub(crate) emit_empty: bool,
 }
 
 type RcBufferObserver<O, Item> = MutArc<Option<BufferObserver<O, Item>>>;
@@ -133,6 +142,23 @@ where
   }
 }
 
+fn emit_buffer_with_empty<O, Item, Err>(
+  observer: &mut RcBufferObserver<O, Item>,
+  _seq: usize,
+) -> bool
+where
+  O: Observer<Vec<Item>, Err>,
+{
+  if !observer.is_finished() {
+    if let Some(v) = observer.rc_deref_mut().as_mut() {
+      v.flush()
+    }
+    true
+  } else {
+    false
+  }
+}
+
 impl<S, Item, Err, O, SD> Observable<Vec<Item>, Err, O>
   for BufferWithTimeOp<S, SD>
 where
@@ -143,12 +169,17 @@ where
   type Unsub = ZipSubscription<TaskHandle<NormalReturn<()>>, S::Unsub>;
 
   fn actual_subscribe(self, observer: O) -> Self::Unsub {
-    let Self { source, time, scheduler } = self;
+    let Self { source, time, scheduler, emit_empty } = self;
     let observer = BufferObserver { observer, data: vec![] };
     let observer = MutArc::own(Some(observer));
 
-    let handler = scheduler
-      .schedule(RepeatTask::new(time, emit_buffer, observer.clone()), None);
+    let task = if emit_empty {
+      emit_buffer_with_empty
+    } else {
+      emit_buffer
+    };
+    let handler =
+      scheduler.schedule(RepeatTask::new(time, task, observer.clone()), None);
 
     let subscription = source.actual_subscribe(observer);
     ZipSubscription::new(handler, subscription)
@@ -168,54 +199,103 @@ pub struct BufferWithCountOrTimerOp<Source, Scheduler> {
   pub(crate) scheduler: Scheduler,
 }
 
-type RcBufferWitchCountObserver<O, Item> =
-  MutArc<Option<BufferWithCountObserver<O, Item>>>;
-
-fn emit_count_buffer<O, Item, Err>(
-  observer: &mut RcBufferWitchCountObserver<O, Item>,
-  _seq: usize,
-) -> bool
-where
-  O: Observer<Vec<Item>, Err>,
-{
-  if !observer.is_finished() {
-    if let Some(v) = observer.rc_deref_mut().as_mut() {
-      v.buffer.emit()
-    }
-    true
-  } else {
-    false
-  }
-}
+type RcHandler = MutArc<Option<TaskHandle<NormalReturn<()>>>>;
 
 impl<S, SD, Item, Err, O> Observable<Vec<Item>, Err, O>
   for BufferWithCountOrTimerOp<S, SD>
 where
   O: Observer<Vec<Item>, Err>,
-  S: Observable<Item, Err, RcBufferWitchCountObserver<O, Item>>,
-  SD: Scheduler<RepeatTask<RcBufferWitchCountObserver<O, Item>>>,
+  S: Observable<Item, Err, BufferWithCountOrTimerObserver<O, Item, SD>>,
+  SD: Scheduler<RepeatTask<RcBufferObserver<O, Item>>>,
 {
-  type Unsub = ZipSubscription<TaskHandle<NormalReturn<()>>, S::Unsub>;
+  type Unsub = ZipSubscription<RcHandler, S::Unsub>;
 
   fn actual_subscribe(self, observer: O) -> Self::Unsub {
     let Self { source, time, scheduler, count } = self;
 
-    let observer = BufferWithCountObserver {
-      buffer: BufferObserver { observer, data: vec![] },
+    let buffer = MutArc::own(Some(BufferObserver { observer, data: vec![] }));
+    let handler = MutArc::own(None);
+    let observer = BufferWithCountOrTimerObserver {
+      buffer,
       count,
+      time,
+      scheduler,
+      handler: handler.clone(),
     };
-    let observer = MutArc::own(Some(observer));
-
-    let handler = scheduler.schedule(
-      RepeatTask::new(time, emit_count_buffer, observer.clone()),
-      None,
-    );
+    observer.restart_timer();
 
     let subscription = source.actual_subscribe(observer);
     ZipSubscription::new(handler, subscription)
   }
 }
 
+pub struct BufferWithCountOrTimerObserver<O, Item, SD> {
+  buffer: RcBufferObserver<O, Item>,
+  count: usize,
+  time: Duration,
+  scheduler: SD,
+  handler: RcHandler,
+}
+
+impl<O, Item, SD> BufferWithCountOrTimerObserver<O, Item, SD> {
+  fn cancel_timer(&self) {
+    if let Some(handler) = self.handler.rc_deref_mut().take() {
+      handler.unsubscribe()
+    }
+  }
+
+  /// Start a new time window, the window of the previous timer is dropped.
+  fn restart_timer<Err>(&self)
+  where
+    O: Observer<Vec<Item>, Err>,
+    SD: Scheduler<RepeatTask<RcBufferObserver<O, Item>>>,
+  {
+    let task = RepeatTask::new(self.time, emit_buffer, self.buffer.clone());
+    let new_handler = self.scheduler.schedule(task, None);
+    let old = self.handler.rc_deref_mut().replace(new_handler);
+    if let Some(old) = old {
+      old.unsubscribe()
+    }
+  }
+}
+
+impl<O, Item, Err, SD> Observer<Item, Err>
+  for BufferWithCountOrTimerObserver<O, Item, SD>
+where
+  O: Observer<Vec<Item>, Err>,
+  SD: Scheduler<RepeatTask<RcBufferObserver<O, Item>>>,
+{
+  fn next(&mut self, value: Item) {
+    let full = self.buffer.rc_deref_mut().as_mut().is_some_and(|b| {
+      b.data.push(value);
+      if b.data.len() >= self.count {
+        b.emit();
+        true
+      } else {
+        false
+      }
+    });
+    if full {
+      self.restart_timer();
+    }
+  }
+
+  fn complete(self) {
+    self.cancel_timer();
+    self.buffer.complete()
+  }
+
+  fn error(self, err: Err) {
+    self.cancel_timer();
+    self.buffer.error(err)
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.buffer.is_finished()
+  }
+}
+
 impl<Item, Err, S, SD> ObservableExt<Item, Err>
   for BufferWithCountOrTimerOp<S, SD>
 where
@@ -223,6 +303,337 @@ where
 {
 }
 
+#[derive(Clone)]
+pub struct BufferWhenOp<'a, S, F, CloseItem> {
+  source: S,
+  closing_selector: F,
+  _hint: TypeHint<&'a CloseItem>,
+}
+
+#[derive(Clone)]
+pub struct BufferWhenOpThreads<S, F, CloseItem> {
+  source: S,
+  closing_selector: F,
+  _hint: TypeHint<CloseItem>,
+}
+
+pub struct BufferWhenState<Item, F> {
+  data: Vec<Item>,
+  // Taken out while it's called, so the state isn't locked meanwhile.
+  closing_selector: Option<F>,
+  // Increased every time a buffer closed, so a notifier of a closed buffer
+  // will be ignored.
+  generation: usize,
+}
+
+/// Holds the subscriptions of the source and of the notifiers. A notifier
+/// can't be unsubscribed during its own emission, so the one that closed a
+/// buffer is kept in `fired` and unsubscribed by the first call to the
+/// operator after its emission returned.
+pub struct ClosingSubscription<U> {
+  source: Option<U>,
+  current: Option<U>,
+  fired: Vec<U>,
+  emitting: bool,
+}
+
+impl<U> ClosingSubscription<U> {
+  fn take_fired(&mut self) -> Vec<U> {
+    if self.emitting {
+      vec![]
+    } else {
+      std::mem::take(&mut self.fired)
+    }
+  }
+
+  /// Unsubscribes the notifiers when the source terminated, the source
+  /// itself is in its emission.
+  fn unsubscribe_notifiers(mut self)
+  where
+    U: Subscription,
+  {
+    let fired = self.take_fired();
+    fired
+      .into_iter()
+      .chain(self.current)
+      .for_each(|u| u.unsubscribe());
+  }
+}
+
+impl<U: Subscription> Subscription for ClosingSubscription<U> {
+  fn unsubscribe(self) {
+    let Self { source, current, fired, .. } = self;
+    source.unsubscribe();
+    current.unsubscribe();
+    fired.into_iter().for_each(|u| u.unsubscribe());
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.source.as_ref().is_some_and(|u| u.is_closed())
+  }
+}
+
+fn unsubscribe_fired<U, C>(closing: &C)
+where
+  U: Subscription,
+  C: RcDerefMut<Target = Option<ClosingSubscription<U>>>,
+{
+  let fired = closing.rc_deref_mut().as_mut().map(|c| c.take_fired());
+  fired.into_iter().flatten().for_each(|u| u.unsubscribe());
+}
+
+fn unsubscribe_notifiers<U, C>(closing: &C)
+where
+  U: Subscription,
+  C: RcDerefMut<Target = Option<ClosingSubscription<U>>>,
+{
+  let closing = closing.rc_deref_mut().take();
+  if let Some(closing) = closing {
+    closing.unsubscribe_notifiers();
+  }
+}
+
+type LocalClosing<'a> = MutRc<Option<ClosingSubscription<BoxSubscription<'a>>>>;
+type ThreadsClosing =
+  MutArc<Option<ClosingSubscription<BoxSubscriptionThreads>>>;
+
+pub struct BufferWhenObserver<O, State, Closing> {
+  observer: O,
+  state: State,
+  closing: Closing,
+}
+
+pub struct ClosingObserver<O, State, Closing> {
+  observer: O,
+  state: State,
+  closing: Closing,
+  generation: usize,
+}
+
+macro_rules! impl_buffer_when {
+  (
+    $name: ident $(<$lf: lifetime>)?, $closing: ty,
+    $rc: ident, $box_observer: ident, $box_unsub: ident, $($bounds: tt)+
+  ) => {
+    impl<$($lf,)? S, F, CloseItem> $name<$($lf,)? S, F, CloseItem> {
+      #[inline]
+      pub(crate) fn new(source: S, closing_selector: F) -> Self {
+        Self {
+          source,
+          closing_selector,
+          _hint: TypeHint::default(),
+        }
+      }
+    }
+
+    impl<$($lf,)? Item, Err, O, S, F, N, CloseItem> Observable<Vec<Item>, Err, O>
+      for $name<$($lf,)? S, F, CloseItem>
+    where
+      O: Observer<Vec<Item>, Err> + $($bounds)+,
+      S: Observable<
+        Item,
+        Err,
+        BufferWhenObserver<
+          $rc<Option<O>>,
+          $rc<BufferWhenState<Item, F>>,
+          $closing,
+        >,
+      >,
+      S::Unsub: $($bounds)+,
+      Item: $($bounds)+,
+      F: FnMut() -> N + $($bounds)+,
+      N: Observable<CloseItem, Err, $box_observer<$($lf,)? CloseItem, Err>>,
+      N::Unsub: $($bounds)+,
+    {
+      type Unsub = $closing;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let Self { source, mut closing_selector, .. } = self;
+        let notifier = closing_selector();
+        let observer = $rc::own(Some(observer));
+        let state = $rc::own(BufferWhenState {
+          data: vec![],
+          closing_selector: Some(closing_selector),
+          generation: 0,
+        });
+        let closing = $rc::own(Some(ClosingSubscription {
+          source: None,
+          current: None,
+          fired: vec![],
+          emitting: false,
+        }));
+
+        ClosingObserver {
+          observer: observer.clone(),
+          state: state.clone(),
+          closing: closing.clone(),
+          generation: 0,
+        }
+        .subscribe_to(notifier);
+        let unsub = source.actual_subscribe(BufferWhenObserver {
+          observer,
+          state,
+          closing: closing.clone(),
+        });
+        if let Some(c) = closing.rc_deref_mut().as_mut() {
+          c.source = Some($box_unsub::new(unsub));
+          return closing.clone();
+        }
+        // terminated during the subscription.
+        unsub.unsubscribe();
+        closing
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S, F, CloseItem> ObservableExt<Vec<Item>, Err>
+      for $name<$($lf,)? S, F, CloseItem>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+
+    impl<$($lf,)? O, Item, F>
+      ClosingObserver<$rc<Option<O>>, $rc<BufferWhenState<Item, F>>, $closing>
+    {
+      fn subscribe_to<N, CloseItem, Err>(self, notifier: N)
+      where
+        Self: Observer<CloseItem, Err> + $($bounds)+,
+        N: Observable<CloseItem, Err, $box_observer<$($lf,)? CloseItem, Err>>,
+        N::Unsub: $($bounds)+,
+      {
+        let closing = self.closing.clone();
+        let unsub = notifier.actual_subscribe($box_observer::new(self));
+        if let Some(closing) = closing.rc_deref_mut().as_mut() {
+          closing.current = Some($box_unsub::new(unsub));
+          return;
+        }
+        // the whole subscription is already unsubscribed.
+        unsub.unsubscribe();
+      }
+    }
+
+    impl<$($lf,)? O, Item, Err, F, N, CloseItem> Observer<CloseItem, Err>
+      for ClosingObserver<
+        $rc<Option<O>>,
+        $rc<BufferWhenState<Item, F>>,
+        $closing,
+      >
+    where
+      O: Observer<Vec<Item>, Err> + $($bounds)+,
+      Item: $($bounds)+,
+      F: FnMut() -> N + $($bounds)+,
+      N: Observable<CloseItem, Err, $box_observer<$($lf,)? CloseItem, Err>>,
+      N::Unsub: $($bounds)+,
+    {
+      fn next(&mut self, _: CloseItem) {
+        let (buffer, mut closing_selector) = {
+          let mut state = self.state.rc_deref_mut();
+          if state.generation != self.generation {
+            return;
+          }
+          let Some(closing_selector) = state.closing_selector.take() else {
+            return;
+          };
+          state.generation += 1;
+          (std::mem::take(&mut state.data), closing_selector)
+        };
+        let (fired, emitting) = {
+          let mut closing = self.closing.rc_deref_mut();
+          let Some(closing) = closing.as_mut() else { return };
+          let fired = closing.take_fired();
+          closing.fired.extend(closing.current.take());
+          (fired, std::mem::replace(&mut closing.emitting, true))
+        };
+        fired.into_iter().for_each(|u| u.unsubscribe());
+
+        if !buffer.is_empty() {
+          self.observer.next(buffer);
+        }
+        let notifier = closing_selector();
+        self.state.rc_deref_mut().closing_selector = Some(closing_selector);
+        ClosingObserver {
+          observer: self.observer.clone(),
+          state: self.state.clone(),
+          closing: self.closing.clone(),
+          generation: self.generation + 1,
+        }
+        .subscribe_to(notifier);
+
+        if let Some(closing) = self.closing.rc_deref_mut().as_mut() {
+          closing.emitting = emitting;
+        }
+      }
+
+      fn error(self, err: Err) {
+        if self.state.rc_deref().generation != self.generation {
+          return;
+        }
+        let closing = self.closing.rc_deref_mut().take();
+        self.observer.error(err);
+        // the notifier in its error emission is skipped.
+        if let Some(mut closing) = closing {
+          let fired = closing.take_fired();
+          fired.into_iter().chain(closing.source).for_each(|u| u.unsubscribe());
+        }
+      }
+
+      #[inline]
+      fn complete(self) {}
+
+      fn is_finished(&self) -> bool {
+        self.state.rc_deref().generation != self.generation
+          || self.observer.is_finished()
+      }
+    }
+  };
+}
+
+impl_buffer_when!(
+  BufferWhenOp<'a>, LocalClosing<'a>, MutRc, BoxObserver, BoxSubscription, 'a
+);
+impl_buffer_when!(
+  BufferWhenOpThreads,
+  ThreadsClosing,
+  MutArc,
+  BoxObserverThreads,
+  BoxSubscriptionThreads,
+  Send + 'static
+);
+
+impl<Item, Err, O, F, State, Closing, U> Observer<Item, Err>
+  for BufferWhenObserver<O, State, Closing>
+where
+  O: Observer<Vec<Item>, Err>,
+  State: RcDerefMut<Target = BufferWhenState<Item, F>>,
+  Closing: RcDerefMut<Target = Option<ClosingSubscription<U>>>,
+  U: Subscription,
+{
+  fn next(&mut self, value: Item) {
+    self.state.rc_deref_mut().data.push(value);
+    unsubscribe_fired(&self.closing);
+  }
+
+  fn error(self, err: Err) {
+    self.observer.error(err);
+    unsubscribe_notifiers(&self.closing);
+  }
+
+  fn complete(mut self) {
+    let buffer = std::mem::take(&mut self.state.rc_deref_mut().data);
+    if !buffer.is_empty() {
+      self.observer.next(buffer);
+    }
+    self.observer.complete();
+    unsubscribe_notifiers(&self.closing);
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use crate::prelude::*;
@@ -230,7 +641,7 @@ mod tests {
   use std::cell::RefCell;
   use std::rc::Rc;
   use std::sync::atomic::{AtomicBool, Ordering};
-  use std::time::Duration;
+  use std::time::{Duration, Instant};
 
   #[test]
   fn it_shall_buffer_with_count() {
@@ -244,6 +655,12 @@ mod tests {
     assert_eq!(expected, actual);
   }
 
+  #[test]
+  #[should_panic]
+  fn it_shall_reject_zero_count() {
+    let _ = observable::from_iter(0..10).buffer_with_count(0);
+  }
+
   #[test]
   fn it_shall_emit_buffer_on_completed() {
     let expected = vec![vec![0, 1], vec![2, 3], vec![4]];
@@ -327,6 +744,42 @@ mod tests {
     local.run();
   }
 
+  #[test]
+  fn it_shall_emit_empty_buffer_with_time() {
+    let mut local = LocalPool::new();
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+
+    Subject::<i32, _>::default()
+      .buffer_with_time_emit_empty(Duration::from_millis(1), local.spawner())
+      .take(3)
+      .subscribe(move |vec| actual_c.borrow_mut().push(vec));
+
+    local.run();
+
+    assert_eq!(vec![Vec::<i32>::new(); 3], *actual.borrow());
+  }
+
+  #[test]
+  fn it_shall_cancel_buffer_with_time_on_unsubscribe() {
+    let mut local = LocalPool::new();
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let mut subject = Subject::<i32, _>::default();
+
+    let subscription = subject
+      .clone()
+      .buffer_with_time(Duration::from_millis(1), local.spawner())
+      .subscribe(move |vec| actual_c.borrow_mut().push(vec));
+    subject.next(1);
+    subscription.unsubscribe();
+
+    // blocks forever if the repeat task is still alive.
+    local.run();
+
+    assert!(actual.borrow().is_empty());
+  }
+
   #[test]
   fn it_shall_buffer_with_count_and_time() {
     let mut local = LocalPool::new();
@@ -350,6 +803,95 @@ mod tests {
     assert_eq!(expected, *actual.borrow());
   }
 
+  #[test]
+  fn it_shall_buffer_with_count_and_time_by_timer() {
+    let mut local = LocalPool::new();
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let mut subject = Subject::<i32, _>::default();
+
+    subject
+      .clone()
+      .buffer_with_count_and_time(10, Duration::from_millis(1), local.spawner())
+      .take(1)
+      .subscribe(move |vec| actual_c.borrow_mut().push(vec));
+    subject.next(0);
+    subject.next(1);
+
+    local.run();
+
+    assert_eq!(vec![vec![0, 1]], *actual.borrow());
+  }
+
+  #[test]
+  fn it_shall_restart_time_window_after_count_flush() {
+    let mut local = LocalPool::new();
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let subject = Subject::<i32, _>::default();
+    let start = Instant::now();
+
+    subject
+      .clone()
+      .buffer_with_count_and_time(
+        2,
+        Duration::from_millis(200),
+        local.spawner(),
+      )
+      .take(2)
+      .subscribe(move |vec| actual_c.borrow_mut().push((vec, start.elapsed())));
+    // the count flush at 100ms restarts the window, so the next time flush
+    // happens at 300ms instead of 400ms.
+    [(1, 100), (2, 100), (3, 220)]
+      .into_iter()
+      .for_each(|(v, ms)| {
+        let mut subject = subject.clone();
+        observable::timer(v, Duration::from_millis(ms), local.spawner())
+          .subscribe(move |v| subject.next(v));
+      });
+
+    local.run();
+
+    let actual = actual.borrow();
+    assert_eq!(actual.len(), 2);
+    assert_eq!(actual[0].0, vec![1, 2]);
+    assert_eq!(actual[1].0, vec![3]);
+    assert!(actual[1].1 >= Duration::from_millis(300));
+    assert!(actual[1].1 < Duration::from_millis(400));
+  }
+
+  #[test]
+  fn it_shall_cancel_count_and_time_timer_on_unsubscribe() {
+    let mut local = LocalPool::new();
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let mut subject = Subject::<i32, _>::default();
+
+    let subscription = subject
+      .clone()
+      .buffer_with_count_and_time(2, Duration::from_millis(1), local.spawner())
+      .subscribe(move |vec| actual_c.borrow_mut().push(vec));
+    // every full buffer restarts the timer, the replaced ones must not leak.
+    (0..4).for_each(|v| subject.next(v));
+    subscription.unsubscribe();
+
+    // blocks forever if any timer is still alive.
+    local.run();
+
+    assert_eq!(vec![vec![0, 1], vec![2, 3]], *actual.borrow());
+  }
+
+  #[test]
+  #[should_panic]
+  fn it_shall_reject_zero_count_with_time() {
+    let local = LocalPool::new();
+    let _ = observable::from_iter(0..10).buffer_with_count_and_time(
+      0,
+      Duration::from_millis(1),
+      local.spawner(),
+    );
+  }
+
   #[test]
   fn it_shall_buffer_with_count_and_time_on_error() {
     let mut local = LocalPool::new();
@@ -376,4 +918,239 @@ mod tests {
     assert_eq!(expected, *actual.borrow());
     assert!(error_called.load(Ordering::Relaxed));
   }
+
+  #[test]
+  fn it_shall_buffer_when_closing_emits() {
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let is_completed = Rc::new(AtomicBool::new(false));
+    let is_completed_c = is_completed.clone();
+    let mut source = Subject::default();
+    let mut flush = Subject::<(), _>::default();
+    let flush_c = flush.clone();
+
+    source
+      .clone()
+      .buffer_when(move || flush_c.clone())
+      .on_complete(move || is_completed_c.store(true, Ordering::Relaxed))
+      .subscribe(move |vec| actual_c.borrow_mut().push(vec));
+
+    source.next(0);
+    source.next(1);
+    flush.next(());
+    flush.next(());
+    source.next(2);
+    source.complete();
+
+    assert_eq!(vec![vec![0, 1], vec![2]], *actual.borrow());
+    assert!(is_completed.load(Ordering::Relaxed));
+  }
+
+  #[test]
+  fn it_shall_renew_closing_observable_every_buffer() {
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let notifiers = Rc::new(RefCell::new(vec![]));
+    let notifiers_c = notifiers.clone();
+    let mut source = Subject::default();
+
+    source
+      .clone()
+      .buffer_when(move || {
+        let notifier = Subject::<(), _>::default();
+        notifiers_c.borrow_mut().push(notifier.clone());
+        notifier
+      })
+      .subscribe(move |vec| actual_c.borrow_mut().push(vec));
+
+    source.next(0);
+    let mut first = notifiers.borrow()[0].clone();
+    first.next(());
+    assert_eq!(notifiers.borrow().len(), 2);
+
+    // the notifier of a closed buffer has no effect anymore.
+    source.next(1);
+    first.next(());
+    assert_eq!(vec![vec![0]], *actual.borrow());
+
+    let mut second = notifiers.borrow()[1].clone();
+    second.next(());
+    assert_eq!(vec![vec![0], vec![1]], *actual.borrow());
+    assert_eq!(notifiers.borrow().len(), 3);
+  }
+
+  #[test]
+  fn it_shall_buffer_when_propagate_closing_error() {
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let err_called = Rc::new(AtomicBool::new(false));
+    let err_called_c = err_called.clone();
+    let mut source = Subject::default();
+    let flush = Subject::<(), _>::default();
+    let flush_c = flush.clone();
+
+    source
+      .clone()
+      .buffer_when(move || flush_c.clone())
+      .on_error(move |_| err_called_c.store(true, Ordering::Relaxed))
+      .subscribe(move |vec| actual_c.borrow_mut().push(vec));
+
+    source.next(0);
+    flush.error("error");
+    source.next(1);
+
+    assert!(actual.borrow().is_empty());
+    assert!(err_called.load(Ordering::Relaxed));
+  }
+
+  #[test]
+  fn it_shall_buffer_when_propagate_source_error() {
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let err_called = Rc::new(AtomicBool::new(false));
+    let err_called_c = err_called.clone();
+    let source = Subject::default();
+    let mut flush = Subject::<(), _>::default();
+    let flush_c = flush.clone();
+
+    source
+      .clone()
+      .buffer_when(move || flush_c.clone())
+      .on_error(move |_| err_called_c.store(true, Ordering::Relaxed))
+      .subscribe(move |vec: Vec<i32>| actual_c.borrow_mut().push(vec));
+
+    source.error("error");
+    flush.next(());
+
+    assert!(actual.borrow().is_empty());
+    assert!(err_called.load(Ordering::Relaxed));
+  }
+
+  #[test]
+  fn it_shall_buffer_when_unsubscribe_fired_notifier() {
+    let unsubscribed = Rc::new(RefCell::new(0));
+    let unsubscribed_c = unsubscribed.clone();
+    let notifiers = Rc::new(RefCell::new(vec![]));
+    let notifiers_c = notifiers.clone();
+    let mut source = Subject::<i32, _>::default();
+
+    source
+      .clone()
+      .buffer_when(move || {
+        let notifier = Subject::<(), _>::default();
+        notifiers_c.borrow_mut().push(notifier.clone());
+        let unsubscribed = unsubscribed_c.clone();
+        notifier.finalize(move || *unsubscribed.borrow_mut() += 1)
+      })
+      .subscribe(|_| {});
+
+    source.next(0);
+    let mut first = notifiers.borrow()[0].clone();
+    first.next(());
+    assert_eq!(*unsubscribed.borrow(), 0);
+
+    // the first call after the emission unsubscribes the fired notifier.
+    source.next(1);
+    assert_eq!(*unsubscribed.borrow(), 1);
+  }
+
+  #[test]
+  fn it_shall_buffer_when_unsubscribe_source_on_closing_error() {
+    let unsubscribed = Rc::new(AtomicBool::new(false));
+    let unsubscribed_c = unsubscribed.clone();
+    let mut source = Subject::default();
+    let flush = Subject::<(), _>::default();
+    let flush_c = flush.clone();
+
+    source
+      .clone()
+      .finalize(move || unsubscribed_c.store(true, Ordering::Relaxed))
+      .buffer_when(move || flush_c.clone())
+      .on_error(|_| {})
+      .subscribe(|_: Vec<i32>| {});
+
+    source.next(0);
+    flush.error("error");
+
+    assert!(unsubscribed.load(Ordering::Relaxed));
+  }
+
+  #[test]
+  fn it_shall_buffer_when_reentrant_emission() {
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let mut source = Subject::default();
+    let mut source_c = source.clone();
+    let mut flush = Subject::<(), _>::default();
+    let flush_c = flush.clone();
+
+    source
+      .clone()
+      .buffer_when(move || flush_c.clone())
+      .subscribe(move |vec: Vec<i32>| {
+        // the state isn't locked while emitting, so a value can be pushed
+        // back to the source.
+        if vec == [0] {
+          source_c.next(1);
+        }
+        actual_c.borrow_mut().push(vec);
+      });
+
+    source.next(0);
+    flush.next(());
+    flush.next(());
+
+    assert_eq!(vec![vec![0], vec![1]], *actual.borrow());
+  }
+
+  #[test]
+  fn it_shall_buffer_when_unsubscribe_closing() {
+    let mut local = LocalPool::new();
+    let spawner = local.spawner();
+    let actual = Rc::new(RefCell::new(vec![]));
+    let actual_c = actual.clone();
+    let mut source = Subject::<i32, _>::default();
+
+    let subscription = source
+      .clone()
+      .buffer_when(move || {
+        observable::interval(Duration::from_millis(1), spawner.clone())
+      })
+      .subscribe(move |vec| actual_c.borrow_mut().push(vec));
+    source.next(0);
+    subscription.unsubscribe();
+
+    // blocks forever if the closing interval is still alive.
+    local.run();
+
+    assert!(actual.borrow().is_empty());
+  }
+
+  #[test]
+  fn it_shall_buffer_when_threads() {
+    use crate::ops::complete_status::CompleteStatus;
+    use crate::rc::{MutArc, RcDeref, RcDerefMut};
+    use futures::executor::ThreadPool;
+
+    let pool = ThreadPool::new().unwrap();
+    let actual = MutArc::own(vec![]);
+    let actual_c = actual.clone();
+
+    let (o, status) =
+      observable::interval(Duration::from_millis(1), pool.clone())
+        .take(10)
+        .buffer_when_threads(move || {
+          observable::timer((), Duration::from_millis(3), pool.clone())
+        })
+        .complete_status();
+    o.subscribe(move |vec| actual_c.rc_deref_mut().push(vec));
+    CompleteStatus::wait_for_end(status);
+
+    let actual = actual.rc_deref();
+    assert!(actual.len() > 1);
+    assert_eq!(
+      actual.iter().flatten().copied().collect::<Vec<_>>(),
+      (0..10).collect::<Vec<_>>()
+    );
+  }
 }
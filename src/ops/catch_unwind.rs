@@ -0,0 +1,168 @@
+use crate::prelude::*;
+use std::{
+  any::Any,
+  panic::{self, AssertUnwindSafe},
+};
+
+#[derive(Clone)]
+pub struct CatchUnwindOp<S, M> {
+  source: S,
+  mapper: M,
+}
+
+impl<S, M> CatchUnwindOp<S, M> {
+  #[inline]
+  pub fn new(source: S, mapper: M) -> Self {
+    Self { source, mapper }
+  }
+}
+
+impl<Item, Err, O, S, M> Observable<Item, Err, O> for CatchUnwindOp<S, M>
+where
+  O: Observer<Item, Err>,
+  S: Observable<Item, Err, CatchUnwindObserver<O, M>>,
+  M: FnOnce(Box<dyn Any + Send>) -> Err,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self.source.actual_subscribe(CatchUnwindObserver {
+      observer: Some(observer),
+      mapper: Some(self.mapper),
+    })
+  }
+}
+
+impl<Item, Err, S, M> ObservableExt<Item, Err> for CatchUnwindOp<S, M> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct CatchUnwindObserver<O, M> {
+  observer: Option<O>,
+  mapper: Option<M>,
+}
+
+impl<Item, Err, O, M> Observer<Item, Err> for CatchUnwindObserver<O, M>
+where
+  O: Observer<Item, Err>,
+  M: FnOnce(Box<dyn Any + Send>) -> Err,
+{
+  fn next(&mut self, value: Item) {
+    let Some(observer) = self.observer.as_mut() else {
+      return;
+    };
+    // The observer is never used again after a panic but to emit the error,
+    // so a broken invariant of it can't be observed.
+    let res = panic::catch_unwind(AssertUnwindSafe(|| observer.next(value)));
+    if let Err(payload) = res {
+      let observer = self.observer.take();
+      if let (Some(observer), Some(mapper)) = (observer, self.mapper.take()) {
+        observer.error(mapper(payload));
+      }
+    }
+  }
+
+  #[inline]
+  fn error(mut self, err: Err) {
+    if let Some(observer) = self.observer.take() {
+      observer.error(err)
+    }
+  }
+
+  #[inline]
+  fn complete(mut self) {
+    if let Some(observer) = self.observer.take() {
+      observer.complete()
+    }
+  }
+
+  fn is_finished(&self) -> bool {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+      .downcast_ref::<&str>()
+      .map(|s| s.to_string())
+      .unwrap_or_default()
+  }
+
+  #[test]
+  fn panic_to_error() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut error = None;
+
+    observable::from_iter(0..10)
+      .map_err(|e| match e {})
+      .catch_unwind(panic_message)
+      .on_error(|e| error = Some(e))
+      .subscribe(move |v| {
+        if v == 2 {
+          panic!("bad element");
+        }
+        c_values.rc_deref_mut().push(v);
+      });
+
+    assert_eq!(&*values.rc_deref(), &[0, 1]);
+    assert_eq!(error.as_deref(), Some("bad element"));
+  }
+
+  #[test]
+  fn unsubscribe_upstream_after_panic() {
+    let errors = MutRc::own(0);
+    let c_errors = errors.clone();
+    let mut subject = Subject::<i32, String>::default();
+    subject
+      .clone()
+      .catch_unwind(panic_message)
+      .map(|v| if v > 0 { panic!("positive") } else { v })
+      .on_error(move |_| *c_errors.rc_deref_mut() += 1)
+      .subscribe(|_| {});
+
+    subject.next(0);
+    assert_eq!(subject.len(), 1);
+    subject.next(1);
+    subject.next(2);
+    subject.retain();
+
+    assert!(subject.is_empty());
+    assert_eq!(*errors.rc_deref(), 1);
+  }
+
+  #[test]
+  fn pass_through_source_error() {
+    let mut error = None;
+    observable::throw("fail")
+      .catch_unwind(|_| "panic")
+      .on_error(|e| error = Some(e))
+      .subscribe(|_| {});
+
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn catch_unwind_threads() {
+    let error = Arc::new(Mutex::new(None));
+    let c_error = error.clone();
+    let mut subject = SubjectThreads::<i32, String>::default();
+    subject
+      .clone()
+      .catch_unwind(panic_message)
+      .on_error(move |e| *c_error.lock().unwrap() = Some(e))
+      .subscribe(|_| panic!("shared"));
+
+    std::thread::spawn(move || subject.next(1)).join().unwrap();
+    assert_eq!(error.lock().unwrap().as_deref(), Some("shared"));
+  }
+}
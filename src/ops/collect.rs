@@ -1,25 +1,31 @@
 use crate::{
   observable::{Observable, ObservableExt},
   observer::Observer,
+  type_hint::TypeHint,
 };
 
 #[derive(Clone)]
-pub struct CollectOp<S, C> {
+pub struct CollectOp<S, C, Item> {
   source: S,
   collection: C,
+  _hint: TypeHint<Item>,
 }
 
-impl<S, C> CollectOp<S, C> {
+impl<S, C, Item> CollectOp<S, C, Item> {
   pub fn new(source: S, collection: C) -> Self {
-    CollectOp { source, collection }
+    CollectOp {
+      source,
+      collection,
+      _hint: TypeHint::new(),
+    }
   }
 }
 
-impl<Err, O, S, C> Observable<C, Err, O> for CollectOp<S, C>
+impl<Item, Err, O, S, C> Observable<C, Err, O> for CollectOp<S, C, Item>
 where
-  C: IntoIterator + Extend<C::Item>,
+  C: Extend<Item>,
   O: Observer<C, Err>,
-  S: Observable<C::Item, Err, CollectObserver<O, C>>,
+  S: Observable<Item, Err, CollectObserver<O, C>>,
 {
   type Unsub = S::Unsub;
 
@@ -31,10 +37,10 @@ where
   }
 }
 
-impl<Err, S, C> ObservableExt<C, Err> for CollectOp<S, C>
+impl<Item, Err, S, C> ObservableExt<C, Err> for CollectOp<S, C, Item>
 where
-  C: IntoIterator + Extend<C::Item>,
-  S: ObservableExt<C::Item, Err>,
+  C: Extend<Item>,
+  S: ObservableExt<Item, Err>,
 {
 }
 
@@ -74,6 +80,7 @@ mod tests {
     prelude::*,
     rc::{MutRc, RcDeref, RcDerefMut},
   };
+  use std::collections::{BTreeMap, HashSet};
 
   #[test]
   fn collect_test() {
@@ -130,6 +137,42 @@ mod tests {
     assert!(values.rc_deref().is_empty());
   }
 
+  #[test]
+  fn to_vec_test() {
+    let mut data = vec![];
+
+    observable::from_iter(0..3)
+      .map(|v| v * 2)
+      .to_vec()
+      .subscribe(|values| data = values);
+
+    assert_eq!(data, vec![0, 2, 4]);
+  }
+
+  #[test]
+  fn collect_other_containers_test() {
+    let mut text = String::new();
+    let mut set = HashSet::new();
+    let mut map = BTreeMap::new();
+
+    observable::from_iter(['r', 'x'])
+      .collect::<String>()
+      .subscribe(|v| text = v);
+    observable::from_iter([1, 2, 1])
+      .collect::<HashSet<_>>()
+      .subscribe(|v| set = v);
+    observable::from_iter([("b", 2), ("a", 1)])
+      .collect::<BTreeMap<_, _>>()
+      .subscribe(|v| map = v);
+
+    assert_eq!(text, "rx");
+    assert_eq!(set, HashSet::from([1, 2]));
+    assert_eq!(
+      map.into_iter().collect::<Vec<_>>(),
+      vec![("a", 1), ("b", 2)]
+    );
+  }
+
   #[test]
   fn collect_empty_test() {
     let mut data = vec![];
@@ -4,36 +4,35 @@ use crate::{
 };
 
 #[derive(Clone)]
-pub struct CombineLatestOp<A, B, BinaryOp> {
+pub struct CombineLatestOp<A, B, BinaryOp, ItemA, ItemB> {
   a: A,
   b: B,
   binary_op: BinaryOp,
+  _hint: TypeHint<(ItemA, ItemB)>,
 }
 
 #[derive(Clone)]
-pub struct CombineLatestOpThread<A, B, BinaryOp> {
+pub struct CombineLatestOpThread<A, B, BinaryOp, ItemA, ItemB> {
   a: A,
   b: B,
   binary_op: BinaryOp,
+  _hint: TypeHint<(ItemA, ItemB)>,
 }
 
 macro_rules! impl_combine_latest_op {
   ($name: ident, $rc: ident) => {
-    impl<A, B, BinaryOp> $name<A, B, BinaryOp> {
+    impl<A, B, BinaryOp, ItemA, ItemB> $name<A, B, BinaryOp, ItemA, ItemB> {
       #[inline]
-      pub(crate) fn new(
-        a: A,
-        b: B,
-        binary_op: BinaryOp,
-      ) -> $name<A, B, BinaryOp> {
-        $name { a, b, binary_op }
+      pub(crate) fn new(a: A, b: B, binary_op: BinaryOp) -> Self {
+        $name { a, b, binary_op, _hint: TypeHint::new() }
       }
     }
 
-    impl<A, B, ItemA, ItemB, Err, O, BinaryOp>
-      Observable<(ItemA, ItemB), Err, O> for $name<A, B, BinaryOp>
+    impl<A, B, ItemA, ItemB, OutputItem, Err, O, BinaryOp>
+      Observable<OutputItem, Err, O> for $name<A, B, BinaryOp, ItemA, ItemB>
     where
-      O: Observer<(ItemA, ItemB), Err>,
+      O: Observer<OutputItem, Err>,
+      BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
       A: Observable<
         ItemA,
         Err,
@@ -63,11 +62,12 @@ macro_rules! impl_combine_latest_op {
       }
     }
 
-    impl<A, B, ItemA, ItemB, Err, BinaryOp> ObservableExt<(ItemA, ItemB), Err>
-      for $name<A, B, BinaryOp>
+    impl<A, B, ItemA, ItemB, OutputItem, Err, BinaryOp>
+      ObservableExt<OutputItem, Err> for $name<A, B, BinaryOp, ItemA, ItemB>
     where
       A: ObservableExt<ItemA, Err>,
       B: ObservableExt<ItemB, Err>,
+      BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
     {
     }
   };
@@ -221,6 +221,7 @@ mod tests {
   use std::time::Duration;
 
   use crate::observable::fake_timer::FakeClock;
+  use crate::rc::MutRc;
 
   use super::*;
 
@@ -258,7 +259,7 @@ mod tests {
       let s1 = Subject::default();
       let s2 = Subject::default();
       s1.clone()
-        .zip(s2.clone())
+        .combine_latest(s2.clone(), |a: (), b: ()| (a, b))
         .on_complete(|| complete = true)
         .subscribe(|((), ())| {});
 
@@ -270,7 +271,7 @@ mod tests {
       let s1 = Subject::default();
       let s2 = Subject::default();
       s1.clone()
-        .zip(s2.clone())
+        .combine_latest(s2.clone(), |a: (), b: ()| (a, b))
         .on_complete(|| complete = true)
         .subscribe(|((), ())| {});
 
@@ -279,4 +280,67 @@ mod tests {
     }
     assert!(complete);
   }
+
+  #[test]
+  fn wait_both_emitted() {
+    let mut values = vec![];
+    {
+      let mut a = Subject::default();
+      let mut b = Subject::default();
+      a.clone()
+        .combine_latest(b.clone(), |x: i32, y: i32| x * 10 + y)
+        .subscribe(|v| values.push(v));
+
+      a.next(1);
+      a.next(2);
+      b.next(3);
+      b.next(4);
+      a.next(5);
+    }
+    assert_eq!(values, vec![23, 24, 54]);
+  }
+
+  #[test]
+  fn error_from_either_side() {
+    let mut error = None;
+    let mut values = vec![];
+    {
+      let mut a = Subject::default();
+      let mut b = Subject::default();
+      a.clone()
+        .combine_latest(b.clone(), |x: i32, y: i32| x + y)
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      a.next(1);
+      b.next(2);
+      b.clone().error("b error");
+      a.next(3);
+    }
+    assert_eq!(values, vec![3]);
+    assert_eq!(error, Some("b error"));
+  }
+
+  #[test]
+  fn unsubscribe_cancel_both() {
+    let finalized = MutRc::own(0);
+    let c_finalized1 = finalized.clone();
+    let c_finalized2 = finalized.clone();
+    let a = Subject::<i32, _>::default();
+    let b = Subject::<i32, _>::default();
+
+    let subscription = a
+      .clone()
+      .finalize(move || *c_finalized1.rc_deref_mut() += 1)
+      .combine_latest(
+        b.clone()
+          .finalize(move || *c_finalized2.rc_deref_mut() += 1),
+        |x, y| x + y,
+      )
+      .subscribe(|_| {});
+    assert_eq!(*finalized.rc_deref(), 0);
+
+    subscription.unsubscribe();
+    assert_eq!(*finalized.rc_deref(), 2);
+  }
 }
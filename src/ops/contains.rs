@@ -1,46 +1,123 @@
 use crate::prelude::*;
 
+/// Decides which items stop a [`ContainsOp`].
+pub trait ItemMatcher<Item> {
+  fn matches(&self, value: Item) -> bool;
+}
+
+/// Matches the items equal to a value, used by
+/// [`contains`](ObservableExt::contains).
+#[derive(Clone)]
+pub struct Equals<Item>(pub(crate) Item);
+
+/// Matches the items satisfying a predicate, used by
+/// [`contains_by`](ObservableExt::contains_by).
+#[derive(Clone)]
+pub struct MatchBy<F>(pub(crate) F);
+
+/// Matches the items failing a predicate, used by
+/// [`all`](ObservableExt::all).
 #[derive(Clone)]
-pub struct ContainsOp<S, Item> {
-  pub(crate) source: S,
-  pub(crate) target: Item,
+pub struct Fails<F>(pub(crate) F);
+
+/// Matches any item, used by
+/// [`emits_nothing`](ObservableExt::emits_nothing).
+#[derive(Clone)]
+pub struct AnyItem;
+
+impl<Item: PartialEq> ItemMatcher<Item> for Equals<Item> {
+  #[inline]
+  fn matches(&self, value: Item) -> bool {
+    self.0 == value
+  }
+}
+
+impl<Item, F> ItemMatcher<Item> for MatchBy<F>
+where
+  F: Fn(&Item) -> bool,
+{
+  #[inline]
+  fn matches(&self, value: Item) -> bool {
+    (self.0)(&value)
+  }
+}
+
+impl<Item> ItemMatcher<Item> for AnyItem {
+  #[inline]
+  fn matches(&self, _: Item) -> bool {
+    true
+  }
 }
 
-impl<Item, Err, O, S> Observable<bool, Err, O> for ContainsOp<S, Item>
+impl<Item, F> ItemMatcher<Item> for Fails<F>
 where
-  S: Observable<Item, Err, ContainsObserver<O, Item>>,
+  F: Fn(Item) -> bool,
+{
+  #[inline]
+  fn matches(&self, value: Item) -> bool {
+    !(self.0)(value)
+  }
+}
+
+/// Emits `found` and completes at the first matching item, stopping the
+/// source, or emits `!found` when the source completes without one.
+#[derive(Clone)]
+pub struct ContainsOp<S, M, Item> {
+  source: S,
+  matcher: M,
+  found: bool,
+  _hint: TypeHint<Item>,
+}
+
+impl<S, M, Item> ContainsOp<S, M, Item> {
+  #[inline]
+  pub(crate) fn new(source: S, matcher: M, found: bool) -> Self {
+    Self {
+      source,
+      matcher,
+      found,
+      _hint: TypeHint::new(),
+    }
+  }
+}
+
+impl<Item, Err, O, S, M> Observable<bool, Err, O> for ContainsOp<S, M, Item>
+where
+  S: Observable<Item, Err, ContainsObserver<O, M>>,
   O: Observer<bool, Err>,
-  Item: PartialEq,
+  M: ItemMatcher<Item>,
 {
   type Unsub = S::Unsub;
 
   fn actual_subscribe(self, observer: O) -> Self::Unsub {
     self.source.actual_subscribe(ContainsObserver {
       observer: Some(observer),
-      target: self.target,
+      matcher: self.matcher,
+      found: self.found,
     })
   }
 }
 
-impl<Item, Err, S> ObservableExt<bool, Err> for ContainsOp<S, Item> where
+impl<Item, Err, S, M> ObservableExt<bool, Err> for ContainsOp<S, M, Item> where
   S: ObservableExt<Item, Err>
 {
 }
 
-pub struct ContainsObserver<S, T> {
-  observer: Option<S>,
-  target: T,
+pub struct ContainsObserver<O, M> {
+  observer: Option<O>,
+  matcher: M,
+  found: bool,
 }
 
-impl<O, Item, Err> Observer<Item, Err> for ContainsObserver<O, Item>
+impl<O, Item, Err, M> Observer<Item, Err> for ContainsObserver<O, M>
 where
   O: Observer<bool, Err>,
-  Item: PartialEq,
+  M: ItemMatcher<Item>,
 {
   fn next(&mut self, value: Item) {
-    if self.target == value {
+    if self.observer.is_some() && self.matcher.matches(value) {
       if let Some(mut observer) = self.observer.take() {
-        observer.next(true);
+        observer.next(self.found);
         observer.complete();
       }
     }
@@ -54,13 +131,13 @@ where
 
   fn complete(mut self) {
     if let Some(mut observer) = self.observer.take() {
-      observer.next(false);
+      observer.next(!self.found);
       observer.complete();
     }
   }
 
   fn is_finished(&self) -> bool {
-    self.observer.as_ref().map_or(true, |o| o.is_finished())
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
   }
 }
 
@@ -78,6 +155,59 @@ mod test {
     observable::empty().contains(1).subscribe(|b| assert!(!b));
   }
 
+  #[test]
+  fn contains_short_circuit() {
+    let mut produced = 0;
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::from_iter(0..)
+      .tap(|_| produced += 1)
+      .contains(2)
+      .on_complete(|| completed = true)
+      .subscribe(|b| values.push(b));
+
+    assert_eq!(produced, 3);
+    assert_eq!(values, vec![true]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn contains_by() {
+    struct NotEq(i32);
+
+    let mut values = vec![];
+    observable::from_iter(0..10)
+      .map(NotEq)
+      .contains_by(|v| v.0 == 4)
+      .subscribe(|b| values.push(b));
+    observable::from_iter(0..10)
+      .map(NotEq)
+      .contains_by(|v| v.0 > 10)
+      .subscribe(|b| values.push(b));
+
+    assert_eq!(values, vec![true, false]);
+  }
+
+  #[test]
+  fn emits_nothing() {
+    let mut values = vec![];
+    observable::empty::<i32>()
+      .emits_nothing()
+      .subscribe(|b| values.push(b));
+    observable::of(1)
+      .emits_nothing()
+      .subscribe(|b| values.push(b));
+    assert_eq!(values, vec![true, false]);
+
+    let mut produced = 0;
+    observable::from_iter(0..)
+      .tap(|_| produced += 1)
+      .emits_nothing()
+      .subscribe(|b| assert!(!b));
+    assert_eq!(produced, 1);
+  }
+
   #[test]
   fn bench() {
     do_bench();
@@ -1,6 +1,6 @@
 use crate::{
   prelude::*,
-  rc::{MutArc, RcDerefMut},
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
 };
 use std::time::Duration;
 #[derive(Clone)]
@@ -49,6 +49,14 @@ pub struct DebounceObserver<O, SD, Item> {
   task_handler: RcHandler,
 }
 
+impl<O, SD, Item> DebounceObserver<O, SD, Item> {
+  fn cancel_pending(&self) {
+    if let Some(handler) = self.task_handler.rc_deref_mut().take() {
+      handler.unsubscribe()
+    }
+  }
+}
+
 fn debounce_task<O, Item, Err>(
   (mut observer, value): (MutArc<Option<O>>, MutArc<Option<Item>>),
 ) -> NormalReturn<()>
@@ -73,19 +81,19 @@ where
     let observer = self.observer.clone();
     let tail_value = self.trailing_value.clone();
     let task = OnceTask::new(debounce_task, (observer, tail_value));
-    if let Some(handler) = self.task_handler.rc_deref_mut().take() {
-      handler.unsubscribe()
-    }
+    self.cancel_pending();
     let handler = self.scheduler.schedule(task, Some(self.delay));
     *self.task_handler.rc_deref_mut() = Some(handler);
   }
 
-  #[inline]
   fn error(self, err: Err) {
+    self.trailing_value.rc_deref_mut().take();
+    self.cancel_pending();
     self.observer.error(err);
   }
 
   fn complete(mut self) {
+    self.cancel_pending();
     if let Some(value) = self.trailing_value.rc_deref_mut().take() {
       self.observer.next(value);
     }
@@ -98,11 +106,273 @@ where
   }
 }
 
+#[derive(Clone)]
+pub struct DebounceWhenOp<'a, S, F, NotifyItem> {
+  source: S,
+  selector: F,
+  _hint: TypeHint<&'a NotifyItem>,
+}
+
+#[derive(Clone)]
+pub struct DebounceWhenOpThreads<S, F, NotifyItem> {
+  source: S,
+  selector: F,
+  _hint: TypeHint<NotifyItem>,
+}
+
+pub struct DebounceWhenState<O, Item, U> {
+  observer: Option<O>,
+  pending: Option<Item>,
+  // Increased by every source item, so a notifier of a replaced item will be
+  // ignored.
+  generation: usize,
+  notifier: Option<U>,
+}
+
+pub struct DebounceWhenObserver<R, F, NotifyItem> {
+  state: R,
+  selector: F,
+  _hint: TypeHint<NotifyItem>,
+}
+
+pub struct DebounceWhenNotifier<R> {
+  state: R,
+  generation: usize,
+}
+
+pub struct DebounceWhenSubscription<R>(R);
+
+macro_rules! impl_debounce_when {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? S, F, NotifyItem> $name<$($lf,)? S, F, NotifyItem> {
+      #[inline]
+      pub(crate) fn new(source: S, selector: F) -> Self {
+        Self { source, selector, _hint: TypeHint::default() }
+      }
+    }
+
+    impl<$($lf,)? Item, Err, O, S, F, N, NotifyItem> Observable<Item, Err, O>
+      for $name<$($lf,)? S, F, NotifyItem>
+    where
+      O: Observer<Item, Err>,
+      S: Observable<
+        Item,
+        Err,
+        DebounceWhenObserver<
+          $rc<DebounceWhenState<O, Item, $box_unsub>>,
+          F,
+          NotifyItem,
+        >,
+      >,
+      F: FnMut(&Item) -> N,
+      N: Observable<
+        NotifyItem,
+        Err,
+        DebounceWhenNotifier<$rc<DebounceWhenState<O, Item, $box_unsub>>>,
+      >,
+      N::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = ZipSubscription<
+        S::Unsub,
+        DebounceWhenSubscription<$rc<DebounceWhenState<O, Item, $box_unsub>>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(DebounceWhenState {
+          observer: Some(observer),
+          pending: None,
+          generation: 0,
+          notifier: None,
+        });
+        let unsub = self.source.actual_subscribe(DebounceWhenObserver {
+          state: state.clone(),
+          selector: self.selector,
+          _hint: TypeHint::default(),
+        });
+        ZipSubscription::new(unsub, DebounceWhenSubscription(state))
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S, F, NotifyItem> ObservableExt<Item, Err>
+      for $name<$($lf,)? S, F, NotifyItem>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+
+    impl<$($lf,)? Item, Err, O, F, N, NotifyItem> Observer<Item, Err>
+      for DebounceWhenObserver<
+        $rc<DebounceWhenState<O, Item, $box_unsub>>,
+        F,
+        NotifyItem,
+      >
+    where
+      O: Observer<Item, Err>,
+      F: FnMut(&Item) -> N,
+      N: Observable<
+        NotifyItem,
+        Err,
+        DebounceWhenNotifier<$rc<DebounceWhenState<O, Item, $box_unsub>>>,
+      >,
+      N::Unsub: $($lf)? $($send + 'static)?,
+    {
+      fn next(&mut self, value: Item) {
+        let notifier = (self.selector)(&value);
+        let (generation, replaced) = {
+          let mut state = self.state.rc_deref_mut();
+          if state.observer.is_none() {
+            return;
+          }
+          state.pending = Some(value);
+          state.generation += 1;
+          (state.generation, state.notifier.take())
+        };
+        if let Some(replaced) = replaced {
+          replaced.unsubscribe();
+        }
+
+        let unsub = notifier.actual_subscribe(DebounceWhenNotifier {
+          state: self.state.clone(),
+          generation,
+        });
+        let mut state = self.state.rc_deref_mut();
+        if state.generation == generation && state.observer.is_some() {
+          state.notifier = Some(<$box_unsub>::new(unsub));
+        } else {
+          drop(state);
+          unsub.unsubscribe();
+        }
+      }
+
+      fn error(self, err: Err) {
+        let (observer, notifier) = {
+          let mut state = self.state.rc_deref_mut();
+          state.pending = None;
+          (state.observer.take(), state.notifier.take())
+        };
+        if let Some(notifier) = notifier {
+          notifier.unsubscribe();
+        }
+        if let Some(observer) = observer {
+          observer.error(err);
+        }
+      }
+
+      fn complete(self) {
+        let (observer, pending, notifier) = {
+          let mut state = self.state.rc_deref_mut();
+          (
+            state.observer.take(),
+            state.pending.take(),
+            state.notifier.take(),
+          )
+        };
+        if let Some(notifier) = notifier {
+          notifier.unsubscribe();
+        }
+        if let Some(mut observer) = observer {
+          if let Some(value) = pending {
+            observer.next(value);
+          }
+          observer.complete();
+        }
+      }
+
+      #[inline]
+      fn is_finished(&self) -> bool {
+        let state = self.state.rc_deref();
+        state.observer.as_ref().is_none_or(|o| o.is_finished())
+      }
+    }
+  };
+}
+
+impl_debounce_when!(DebounceWhenOp, MutRc, BoxSubscription<'a>, 'a);
+impl_debounce_when!(
+  DebounceWhenOpThreads,
+  MutArc,
+  BoxSubscriptionThreads,
+  Send
+);
+
+impl<NotifyItem, Item, Err, O, U, R> Observer<NotifyItem, Err>
+  for DebounceWhenNotifier<R>
+where
+  R: RcDeref<Target = DebounceWhenState<O, Item, U>>
+    + RcDerefMut<Target = DebounceWhenState<O, Item, U>>,
+  O: Observer<Item, Err>,
+{
+  fn next(&mut self, _: NotifyItem) {
+    let mut state = self.state.rc_deref_mut();
+    if state.generation != self.generation {
+      return;
+    }
+    let DebounceWhenState { observer, pending, .. } = &mut *state;
+    if let (Some(observer), Some(value)) = (observer.as_mut(), pending.take()) {
+      observer.next(value);
+    }
+  }
+
+  fn error(self, err: Err) {
+    let observer = {
+      let mut state = self.state.rc_deref_mut();
+      if state.generation != self.generation {
+        return;
+      }
+      state.pending = None;
+      state.observer.take()
+    };
+    if let Some(observer) = observer {
+      observer.error(err);
+    }
+  }
+
+  // A notifier completing without emitting leaves the item pending until the
+  // next source item or the source completion.
+  #[inline]
+  fn complete(self) {}
+
+  fn is_finished(&self) -> bool {
+    let state = self.state.rc_deref();
+    state.generation != self.generation
+      || state.pending.is_none()
+      || state.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+}
+
+impl<R, O, Item, U> Subscription for DebounceWhenSubscription<R>
+where
+  R: RcDeref<Target = DebounceWhenState<O, Item, U>>
+    + RcDerefMut<Target = DebounceWhenState<O, Item, U>>,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let (observer, notifier) = {
+      let mut state = self.0.rc_deref_mut();
+      state.pending = None;
+      (state.observer.take(), state.notifier.take())
+    };
+    drop(observer);
+    if let Some(notifier) = notifier {
+      notifier.unsubscribe();
+    }
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.0.rc_deref().observer.is_none()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
   use crate::rc::{MutRc, RcDeref};
   use futures::executor::LocalPool;
+  use std::convert::Infallible;
   #[test]
   fn smoke_last() {
     let x = MutRc::own(vec![]);
@@ -146,4 +416,169 @@ mod tests {
     sub.unsubscribe();
     assert_eq!(&*x_c.rc_deref(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
   }
+
+  #[test]
+  fn burst_collapse_to_last() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = LocalPool::new();
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .debounce(Duration::from_millis(5), pool.spawner())
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    subject.next(1);
+    subject.next(2);
+    subject.next(3);
+    pool.run();
+    assert_eq!(&*x.rc_deref(), &[3]);
+  }
+
+  #[test]
+  fn flush_pending_on_complete() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let pool = LocalPool::new();
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .debounce(Duration::from_millis(5), pool.spawner())
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    subject.next(1);
+    subject.next(2);
+    subject.complete();
+    assert_eq!(&*x.rc_deref(), &[2]);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn drop_pending_on_error() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = LocalPool::new();
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .debounce(Duration::from_millis(5), pool.spawner())
+      .on_error(|_| {})
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    subject.next(1);
+    subject.error(());
+    pool.run();
+    assert!(x.rc_deref().is_empty());
+  }
+
+  #[test]
+  fn debounce_when_notifier() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut source = Subject::<i32, ()>::default();
+    let signal = Subject::<(), ()>::default();
+    let mut c_signal = signal.clone();
+
+    source
+      .clone()
+      .debounce_when(move |_| signal.clone())
+      .on_error(|_| {})
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    source.next(1);
+    source.next(2);
+    c_signal.next(());
+    // nothing pending, the signal is ignored.
+    c_signal.next(());
+    source.next(3);
+    c_signal.next(());
+
+    assert_eq!(&*x.rc_deref(), &[2, 3]);
+  }
+
+  #[test]
+  fn debounce_when_unsubscribe_replaced_notifier() {
+    let notifiers = MutRc::own(vec![]);
+    let c_notifiers = notifiers.clone();
+    let mut source = Subject::<i32, ()>::default();
+
+    let subscription = source
+      .clone()
+      .debounce_when(move |_| {
+        let notifier = Subject::<(), ()>::default();
+        c_notifiers.rc_deref_mut().push(notifier.clone());
+        notifier
+      })
+      .on_error(|_| {})
+      .subscribe(|_| {});
+
+    source.next(1);
+    source.next(2);
+    {
+      let notifiers = notifiers.rc_deref();
+      assert!(notifiers[0].is_empty());
+      assert!(!notifiers[1].is_empty());
+    }
+    subscription.unsubscribe();
+    assert!(notifiers.rc_deref()[1].is_empty());
+  }
+
+  #[test]
+  fn debounce_when_per_item_duration() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let mut source = Subject::<u64, Infallible>::default();
+
+    source
+      .clone()
+      .debounce_when(move |v| {
+        observable::timer((), Duration::from_millis(*v), spawner.clone())
+      })
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    source.next(1);
+    pool.run();
+    source.next(50);
+    source.next(2);
+    pool.run();
+
+    assert_eq!(&*x.rc_deref(), &[1, 2]);
+  }
+
+  #[test]
+  fn debounce_when_flush_on_complete() {
+    let mut values = vec![];
+    let mut completed = false;
+    {
+      let mut source = Subject::<i32, Infallible>::default();
+      source
+        .clone()
+        .debounce_when(|_| observable::empty::<()>())
+        .on_complete(|| completed = true)
+        .subscribe(|v| values.push(v));
+
+      source.next(1);
+      source.next(2);
+      source.complete();
+    }
+
+    assert_eq!(values, [2]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn debounce_when_threads() {
+    let x = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let x_c = x.clone();
+    observable::from_iter(0..3)
+      .debounce_when_threads(|_| observable::of(()))
+      .subscribe(move |v| x_c.lock().unwrap().push(v));
+
+    assert_eq!(&*x.lock().unwrap(), &[0, 1, 2]);
+  }
 }
@@ -1,4 +1,7 @@
-use crate::prelude::*;
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDerefMut},
+};
 
 #[derive(Clone)]
 pub struct DefaultIfEmptyOp<S, Item> {
@@ -17,7 +20,6 @@ impl<Item, Err, O, S> Observable<Item, Err, O> for DefaultIfEmptyOp<S, Item>
 where
   S: Observable<Item, Err, DefaultIfEmptyObserver<O, Item>>,
   O: Observer<Item, Err>,
-  Item: Clone,
 {
   type Unsub = S::Unsub;
 
@@ -43,7 +45,6 @@ pub struct DefaultIfEmptyObserver<O, Item> {
 impl<Item, Err, O> Observer<Item, Err> for DefaultIfEmptyObserver<O, Item>
 where
   O: Observer<Item, Err>,
-  Item: Clone,
 {
   fn next(&mut self, value: Item) {
     self.observer.next(value);
@@ -59,7 +60,7 @@ where
 
   fn complete(mut self) {
     if self.is_empty {
-      self.observer.next(self.default_value.clone());
+      self.observer.next(self.default_value);
     }
     self.observer.complete()
   }
@@ -70,10 +71,109 @@ where
   }
 }
 
+#[derive(Clone)]
+pub struct SwitchIfEmptyOp<S, F> {
+  source: S,
+  fallback: F,
+}
+
+#[derive(Clone)]
+pub struct SwitchIfEmptyOpThreads<S, F> {
+  source: S,
+  fallback: F,
+}
+
+macro_rules! impl_switch_if_empty_op {
+  ($name: ident, $rc: ident) => {
+    impl<S, F> $name<S, F> {
+      #[inline]
+      pub(crate) fn new(source: S, fallback: F) -> Self {
+        Self { source, fallback }
+      }
+    }
+
+    impl<Item, Err, O, S, F> Observable<Item, Err, O> for $name<S, F>
+    where
+      O: Observer<Item, Err>,
+      F: Observable<Item, Err, O>,
+      S: Observable<
+        Item,
+        Err,
+        SwitchIfEmptyObserver<O, F, $rc<Option<F::Unsub>>>,
+      >,
+    {
+      type Unsub = ZipSubscription<S::Unsub, $rc<Option<F::Unsub>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        // The fallback is subscribed only when the source completes empty, so
+        // its subscription is shared with the downstream subscription.
+        let fallback_unsub = $rc::own(None);
+        let source_unsub =
+          self.source.actual_subscribe(SwitchIfEmptyObserver {
+            observer,
+            fallback: self.fallback,
+            is_empty: true,
+            fallback_unsub: fallback_unsub.clone(),
+          });
+        ZipSubscription::new(source_unsub, fallback_unsub)
+      }
+    }
+
+    impl<Item, Err, S, F> ObservableExt<Item, Err> for $name<S, F>
+    where
+      S: ObservableExt<Item, Err>,
+      F: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_switch_if_empty_op!(SwitchIfEmptyOp, MutRc);
+impl_switch_if_empty_op!(SwitchIfEmptyOpThreads, MutArc);
+
+pub struct SwitchIfEmptyObserver<O, F, U> {
+  observer: O,
+  fallback: F,
+  is_empty: bool,
+  fallback_unsub: U,
+}
+
+impl<Item, Err, O, F, U> Observer<Item, Err> for SwitchIfEmptyObserver<O, F, U>
+where
+  O: Observer<Item, Err>,
+  F: Observable<Item, Err, O>,
+  U: RcDerefMut<Target = Option<F::Unsub>>,
+{
+  fn next(&mut self, value: Item) {
+    self.is_empty = false;
+    self.observer.next(value);
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    self.observer.error(err)
+  }
+
+  fn complete(self) {
+    if self.is_empty {
+      let unsub = self.fallback.actual_subscribe(self.observer);
+      *self.fallback_unsub.rc_deref_mut() = Some(unsub);
+    } else {
+      self.observer.complete()
+    }
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
 #[cfg(test)]
 mod test {
   use crate::prelude::*;
   use bencher::Bencher;
+  use std::convert::Infallible;
 
   #[test]
   fn base_function() {
@@ -103,6 +203,82 @@ mod test {
     assert!(completed);
   }
 
+  #[test]
+  fn default_not_emit_on_error() {
+    let mut value = None;
+    let mut error = None;
+    let subject = Subject::<i32, _>::default();
+
+    subject
+      .clone()
+      .default_if_empty(5)
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| value = Some(v));
+    subject.error("err");
+
+    assert_eq!(value, None);
+    assert_eq!(error, Some("err"));
+  }
+
+  #[test]
+  fn switch_if_empty() {
+    let mut values = vec![];
+    let mut completed = 0;
+
+    observable::empty()
+      .switch_if_empty(observable::from_iter(1..3))
+      .on_complete(|| completed += 1)
+      .subscribe(|v| values.push(v));
+    observable::of(0)
+      .switch_if_empty(observable::from_iter(1..3))
+      .on_complete(|| completed += 1)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [1, 2, 0]);
+    assert_eq!(completed, 2);
+  }
+
+  #[test]
+  fn switch_if_empty_not_switch_on_error() {
+    let mut value = None;
+    let mut error = None;
+    {
+      let subject = Subject::<i32, _>::default();
+      let fallback = Subject::default();
+
+      subject
+        .clone()
+        .switch_if_empty(fallback.clone())
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| value = Some(v));
+      subject.error("err");
+
+      assert!(fallback.is_empty());
+    }
+    assert_eq!(value, None);
+    assert_eq!(error, Some("err"));
+  }
+
+  #[test]
+  fn switch_if_empty_unsubscribe_fallback() {
+    let mut values = vec![];
+    {
+      let source = Subject::<i32, Infallible>::default();
+      let mut fallback = Subject::default();
+
+      let subscription = source
+        .clone()
+        .switch_if_empty(fallback.clone())
+        .subscribe(|v| values.push(v));
+      source.complete();
+      fallback.next(1);
+      subscription.unsubscribe();
+      fallback.next(2);
+    }
+
+    assert_eq!(values, [1]);
+  }
+
   #[test]
   fn bench_base() {
     bench_b();
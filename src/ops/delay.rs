@@ -1,8 +1,8 @@
 use crate::{
   prelude::*,
-  rc::{MutArc, MutRc},
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
 };
-use std::time::Duration;
+use std::{collections::VecDeque, time::Duration};
 
 #[derive(Debug, Clone)]
 pub struct DelayOp<S, SD> {
@@ -25,20 +25,74 @@ pub struct DelaySubscriptionOp<S, SD> {
   pub(crate) scheduler: SD,
 }
 
-pub struct DelayObserver<O, SD> {
+pub struct DelayObserver<O, SD, Item> {
   delay: Duration,
   scheduler: SD,
   observer: MutRc<Option<O>>,
+  queue: MutRc<DelayQueue<Item>>,
   subscription: MultiSubscription<'static>,
 }
 
-pub struct DelayObserverThreads<O, SD> {
+pub struct DelayObserverThreads<O, SD, Item> {
   delay: Duration,
   scheduler: SD,
   observer: MutArc<Option<O>>,
+  queue: MutArc<DelayQueue<Item>>,
   subscription: MultiSubscriptionThreads,
 }
 
+/// The values waiting for their delay, every scheduled task emits the oldest
+/// one, so the order is kept even if tasks wake up out of order.
+pub struct DelayQueue<Item> {
+  items: VecDeque<Item>,
+  completed: bool,
+}
+
+impl<Item> Default for DelayQueue<Item> {
+  fn default() -> Self {
+    DelayQueue { items: VecDeque::new(), completed: false }
+  }
+}
+
+fn delay_emit_value<Item, Err, O, Q>(
+  (mut observer, queue): (O, Q),
+) -> NormalReturn<()>
+where
+  O: Observer<Item, Err>,
+  Q: RcDerefMut<Target = DelayQueue<Item>>,
+{
+  let (value, last) = {
+    let mut queue = queue.rc_deref_mut();
+    let value = queue.items.pop_front();
+    (value, queue.completed && queue.items.is_empty())
+  };
+  if let Some(value) = value {
+    observer.next(value);
+  }
+  if last {
+    observer.complete();
+  }
+  NormalReturn::new(())
+}
+
+fn delay_complete<Item, Err, O, Q>(
+  (observer, queue): (O, Q),
+) -> NormalReturn<()>
+where
+  O: Observer<Item, Err>,
+  Q: RcDerefMut<Target = DelayQueue<Item>>,
+{
+  let empty = {
+    let mut queue = queue.rc_deref_mut();
+    queue.completed = true;
+    queue.items.is_empty()
+  };
+  if empty {
+    observer.complete();
+  }
+  NormalReturn::new(())
+}
+
 impl<Item, Err, O, S, SD> Observable<Item, Err, O>
   for DelaySubscriptionOp<S, SD>
 where
@@ -56,24 +110,28 @@ where
 }
 
 macro_rules! impl_delay_op {
-  ($op: ty, $rc: ident, $observer: ident, $multi_unsub: ty, $box_unsub: ident) => {
+  (
+    $op: ty, $rc: ident, $observer: ident,
+    $multi_unsub: ty, $box_unsub: ident
+  ) => {
     impl<Item, Err, O, S, SD> Observable<Item, Err, O> for $op
     where
       O: Observer<Item, Err>,
-      S: Observable<Item, Err, $observer<O, SD>>,
-      SD: Scheduler<OnceTask<($rc<Option<O>>, Item), NormalReturn<()>>>,
-      SD: Scheduler<OnceTask<$rc<Option<O>>, NormalReturn<()>>>,
+      S: Observable<Item, Err, $observer<O, SD, Item>>,
+      SD: Scheduler<
+        OnceTask<($rc<Option<O>>, $rc<DelayQueue<Item>>), NormalReturn<()>>,
+      >,
     {
       type Unsub = ZipSubscription<S::Unsub, $multi_unsub>;
 
       fn actual_subscribe(self, observer: O) -> Self::Unsub {
         let Self { source, delay, scheduler } = self;
         let subscription: $multi_unsub = <_>::default();
-        let observer = $rc::own(Some(observer));
         let observer = $observer {
           delay,
           scheduler,
-          observer,
+          observer: $rc::own(Some(observer)),
+          queue: $rc::own(DelayQueue::default()),
           subscription: subscription.clone(),
         };
         let unsub = source.actual_subscribe(observer);
@@ -81,52 +139,45 @@ macro_rules! impl_delay_op {
       }
     }
 
-    impl<Item, Err, O, SD> Observer<Item, Err> for $observer<O, SD>
+    impl<Item, Err, O, SD> Observer<Item, Err> for $observer<O, SD, Item>
     where
       O: Observer<Item, Err>,
-      SD: Scheduler<OnceTask<($rc<Option<O>>, Item), NormalReturn<()>>>,
-      SD: Scheduler<OnceTask<$rc<Option<O>>, NormalReturn<()>>>,
+      SD: Scheduler<
+        OnceTask<($rc<Option<O>>, $rc<DelayQueue<Item>>), NormalReturn<()>>,
+      >,
     {
       fn next(&mut self, value: Item) {
-        fn delay_emit_value<Item, Err>(
-          (mut observer, value): (impl Observer<Item, Err>, Item),
-        ) -> NormalReturn<()> {
-          observer.next(value);
-          NormalReturn::new(())
+        let idle = {
+          let mut queue = self.queue.rc_deref_mut();
+          queue.items.push_back(value);
+          queue.items.len() == 1
+        };
+        // Every task pops one item, so all the previous tasks are done.
+        if idle {
+          self.subscription.clear();
         }
-
-        let observer = self.observer.clone();
-        let task = OnceTask::new(delay_emit_value, (observer, value));
-        self.subscription.retain();
+        let args = (self.observer.clone(), self.queue.clone());
+        let task = OnceTask::new(delay_emit_value, args);
         let handler = self.scheduler.schedule(task, Some(self.delay));
         self.subscription.append($box_unsub::new(handler));
       }
 
-      #[inline]
       fn error(self, err: Err) {
-        self.observer.error(err)
+        self.queue.rc_deref_mut().items.clear();
+        self.subscription.unsubscribe();
+        self.observer.error(err);
       }
 
-      #[inline]
       fn complete(mut self) {
-        fn delay_complete<Item, Err>(
-          observer: impl Observer<Item, Err>,
-        ) -> NormalReturn<()> {
-          observer.complete();
-          NormalReturn::new(())
-        }
-
-        let observer = self.observer.clone();
-        let task = OnceTask::new(delay_complete, observer);
+        let args = (self.observer.clone(), self.queue.clone());
+        let task = OnceTask::new(delay_complete, args);
         self.subscription.retain();
-
         let handler = self.scheduler.schedule(task, Some(self.delay));
         self.subscription.append($box_unsub::new(handler));
       }
 
-      #[inline]
       fn is_finished(&self) -> bool {
-        self.observer.is_finished()
+        self.queue.rc_deref().completed || self.observer.is_finished()
       }
     }
 
@@ -137,8 +188,20 @@ macro_rules! impl_delay_op {
   };
 }
 
-impl_delay_op!(DelayOp<S,SD>, MutRc, DelayObserver, MultiSubscription<'static>, BoxSubscription);
-impl_delay_op!(DelayOpThreads<S,SD>, MutArc, DelayObserverThreads, MultiSubscriptionThreads, BoxSubscriptionThreads);
+impl_delay_op!(
+  DelayOp<S, SD>,
+  MutRc,
+  DelayObserver,
+  MultiSubscription<'static>,
+  BoxSubscription
+);
+impl_delay_op!(
+  DelayOpThreads<S, SD>,
+  MutArc,
+  DelayObserverThreads,
+  MultiSubscriptionThreads,
+  BoxSubscriptionThreads
+);
 
 impl<Item, Err, S, SD> ObservableExt<Item, Err> for DelaySubscriptionOp<S, SD> where
   S: ObservableExt<Item, Err>
@@ -253,4 +316,156 @@ mod tests {
     );
     assert!(accept_stamp.rc_deref().elapsed() < Duration::from_millis(1));
   }
+
+  #[test]
+  fn keep_order_and_complete_last() {
+    let values = MutRc::own(vec![]);
+    let (c_values, c_values2) = (values.clone(), values.clone());
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut pool = LocalPool::new();
+
+    observable::from_iter(0..100)
+      .delay(Duration::from_millis(1), pool.spawner())
+      .on_complete(move || {
+        assert_eq!(c_values2.rc_deref().len(), 100);
+        *c_completed.rc_deref_mut() = true;
+      })
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    pool.run();
+
+    assert!(*completed.rc_deref());
+    assert_eq!(*values.rc_deref(), (0..100).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn unsubscribe_cancel_pending_values() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut pool = LocalPool::new();
+    let mut subject = Subject::default();
+
+    let subscription = subject
+      .clone()
+      .delay(Duration::from_millis(1), pool.spawner())
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    subject.next(1);
+    subject.next(2);
+    subscription.unsubscribe();
+    pool.run();
+
+    assert!(values.rc_deref().is_empty());
+  }
+
+  #[test]
+  fn error_drop_pending_values() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let error = MutRc::own(false);
+    let c_error = error.clone();
+    let mut pool = LocalPool::new();
+    let mut subject = Subject::default();
+
+    subject
+      .clone()
+      .delay(Duration::from_millis(1), pool.spawner())
+      .on_error(move |_| *c_error.rc_deref_mut() = true)
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    subject.next(1);
+    subject.error(());
+    assert!(*error.rc_deref());
+    pool.run();
+
+    assert!(values.rc_deref().is_empty());
+  }
+
+  #[test]
+  fn reentrant_emission() {
+    let mut pool = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let subject = Subject::<i32, std::convert::Infallible>::default();
+    let mut c_subject = subject.clone();
+    let c_values = values.clone();
+    subject
+      .clone()
+      .delay(Duration::from_millis(1), pool.spawner())
+      .take(3)
+      .subscribe(move |v| {
+        c_values.rc_deref_mut().push(v);
+        c_subject.next(v + 1);
+      });
+    subject.clone().next(0);
+
+    pool.run();
+    assert_eq!(*values.rc_deref(), vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn not_require_clone() {
+    struct NoClone(i32);
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut pool = LocalPool::new();
+
+    observable::of(NoClone(1))
+      .delay(Duration::from_millis(1), pool.spawner())
+      .subscribe(move |v: NoClone| c_values.rc_deref_mut().push(v.0));
+    pool.run();
+
+    assert_eq!(&*values.rc_deref(), &[1]);
+  }
+
+  #[test]
+  fn unsubscribe_before_delay_never_subscribe() {
+    let subscribed = MutRc::own(false);
+    let c_subscribed = subscribed.clone();
+    let mut pool = LocalPool::new();
+
+    observable::defer(move || {
+      *c_subscribed.rc_deref_mut() = true;
+      observable::of(1)
+    })
+    .delay_subscription(Duration::from_millis(1), pool.spawner())
+    .subscribe(|_| {})
+    .unsubscribe();
+    pool.run();
+
+    assert!(!*subscribed.rc_deref());
+  }
+
+  #[test]
+  fn unsubscribe_after_delay_unsubscribe_source() {
+    let mut pool = LocalPool::new();
+    let subject = Subject::<i32, _>::default();
+
+    let subscription = subject
+      .clone()
+      .delay_subscription(Duration::from_millis(1), pool.spawner())
+      .subscribe(|_| {});
+    assert!(subject.is_empty());
+    pool.run();
+    assert!(!subject.is_empty());
+
+    subscription.unsubscribe();
+    assert!(subject.is_empty());
+  }
+
+  #[test]
+  fn delay_subscription_at() {
+    let mut pool = LocalPool::new();
+    let subject = Subject::<i32, _>::default();
+    let stamp = Instant::now();
+
+    subject
+      .clone()
+      .delay_subscription_at(
+        Instant::now() + Duration::from_millis(5),
+        pool.spawner(),
+      )
+      .subscribe(|_| {});
+    pool.run();
+
+    assert!(!subject.is_empty());
+    assert!(stamp.elapsed() >= Duration::from_millis(5));
+  }
 }
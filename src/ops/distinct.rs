@@ -1,5 +1,13 @@
-use crate::prelude::*;
-use std::{cmp::Eq, collections::HashSet, hash::Hash};
+use crate::{
+  prelude::*,
+  rc::{MutArc, RcDerefMut},
+};
+use std::{
+  cmp::Eq,
+  collections::{HashMap, HashSet, VecDeque},
+  hash::Hash,
+  time::{Duration, Instant},
+};
 
 #[derive(Clone)]
 pub struct DistinctOp<S> {
@@ -69,7 +77,7 @@ where
   S: Observable<Item, Err, DistinctKeyObserver<O, F, K>>,
   O: Observer<Item, Err>,
   F: Fn(&Item) -> K,
-  K: Eq + Hash + Clone,
+  K: Eq + Hash,
 {
   type Unsub = S::Unsub;
 
@@ -95,13 +103,11 @@ pub struct DistinctKeyObserver<O, F, K> {
 impl<O, F, K, Item, Err> Observer<Item, Err> for DistinctKeyObserver<O, F, K>
 where
   O: Observer<Item, Err>,
-  K: Hash + Eq + Clone,
+  K: Hash + Eq,
   F: Fn(&Item) -> K,
 {
   fn next(&mut self, value: Item) {
-    let key = (self.key)(&value);
-    if !self.seen.contains(&key) {
-      self.seen.insert(key);
+    if self.seen.insert((self.key)(&value)) {
       self.observer.next(value);
     }
   }
@@ -180,20 +186,45 @@ where
   }
 }
 
+/// Tells if two keys of `distinct_until_key_changed_by` are considered equal,
+/// it's implemented for the closures `Fn(&K, &K) -> bool`.
+pub trait KeyCompare<K> {
+  fn is_equal(&self, a: &K, b: &K) -> bool;
+}
+
+impl<K, C: Fn(&K, &K) -> bool> KeyCompare<K> for C {
+  #[inline]
+  fn is_equal(&self, a: &K, b: &K) -> bool {
+    self(a, b)
+  }
+}
+
+/// The comparator of `distinct_until_key_changed`, compares the keys by
+/// `PartialEq`.
+#[derive(Clone, Copy, Default)]
+pub struct PartialEqKey;
+
+impl<K: PartialEq> KeyCompare<K> for PartialEqKey {
+  #[inline]
+  fn is_equal(&self, a: &K, b: &K) -> bool {
+    a == b
+  }
+}
+
 #[derive(Clone)]
-pub struct DistinctUntilKeyChangedOp<S, F> {
+pub struct DistinctUntilKeyChangedOp<S, F, C = PartialEqKey> {
   pub(crate) source: S,
   pub(crate) key: F,
+  pub(crate) compare: C,
 }
 
-impl<Item, Err, O, S, F, K> Observable<Item, Err, O>
-  for DistinctUntilKeyChangedOp<S, F>
+impl<Item, Err, O, S, F, C, K> Observable<Item, Err, O>
+  for DistinctUntilKeyChangedOp<S, F, C>
 where
-  S: Observable<Item, Err, DistinctUntilKeyChangedObserver<O, F, Item>>,
+  S: Observable<Item, Err, DistinctUntilKeyChangedObserver<O, F, C, K>>,
   O: Observer<Item, Err>,
-  K: Eq,
-  Item: Clone,
   F: Fn(&Item) -> K,
+  C: KeyCompare<K>,
 {
   type Unsub = S::Unsub;
 
@@ -203,36 +234,40 @@ where
       .actual_subscribe(DistinctUntilKeyChangedObserver {
         observer,
         key: self.key,
+        compare: self.compare,
         last: None,
       })
   }
 }
 
-impl<Item, Err, S, F> ObservableExt<Item, Err>
-  for DistinctUntilKeyChangedOp<S, F>
+impl<Item, Err, S, F, C> ObservableExt<Item, Err>
+  for DistinctUntilKeyChangedOp<S, F, C>
 where
   S: ObservableExt<Item, Err>,
 {
 }
-pub struct DistinctUntilKeyChangedObserver<O, F, Item> {
+pub struct DistinctUntilKeyChangedObserver<O, F, C, K> {
   observer: O,
   key: F,
-  last: Option<Item>,
+  compare: C,
+  last: Option<K>,
 }
 
-impl<O, F, K, Item, Err> Observer<Item, Err>
-  for DistinctUntilKeyChangedObserver<O, F, Item>
+impl<O, F, C, K, Item, Err> Observer<Item, Err>
+  for DistinctUntilKeyChangedObserver<O, F, C, K>
 where
   O: Observer<Item, Err>,
-  Item: Clone,
-  K: Eq,
   F: Fn(&Item) -> K,
+  C: KeyCompare<K>,
 {
   fn next(&mut self, value: Item) {
-    if self.last.is_none()
-      || (self.key)(self.last.as_ref().unwrap()) != (self.key)(&value)
-    {
-      self.last = Some(value.clone());
+    let key = (self.key)(&value);
+    let changed = self
+      .last
+      .as_ref()
+      .is_none_or(|last| !self.compare.is_equal(last, &key));
+    if changed {
+      self.last = Some(key);
       self.observer.next(value);
     }
   }
@@ -253,9 +288,235 @@ where
   }
 }
 
+#[derive(Clone)]
+pub struct DistinctWithinOp<S> {
+  pub(crate) source: S,
+  pub(crate) capacity: usize,
+}
+
+impl<Item, Err, O, S> Observable<Item, Err, O> for DistinctWithinOp<S>
+where
+  S: Observable<Item, Err, DistinctWithinObserver<O, Item>>,
+  O: Observer<Item, Err>,
+  Item: Eq + Hash + Clone,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self.source.actual_subscribe(DistinctWithinObserver {
+      observer,
+      capacity: self.capacity,
+      seen: HashMap::new(),
+      order: VecDeque::new(),
+      stamp: 0,
+    })
+  }
+}
+
+impl<Item, Err, S> ObservableExt<Item, Err> for DistinctWithinOp<S> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct DistinctWithinObserver<O, Item> {
+  observer: O,
+  capacity: usize,
+  // The stamp of the last time every remembered item was seen.
+  seen: HashMap<Item, usize>,
+  // The items in the order they were seen, an entry whose stamp is older than
+  // the one in `seen` is stale and skipped.
+  order: VecDeque<(Item, usize)>,
+  stamp: usize,
+}
+
+impl<O, Item> DistinctWithinObserver<O, Item>
+where
+  Item: Hash + Eq,
+{
+  fn evict_least_recent(&mut self) {
+    while let Some((item, stamp)) = self.order.pop_front() {
+      if self.seen.get(&item) == Some(&stamp) {
+        self.seen.remove(&item);
+        return;
+      }
+    }
+  }
+
+  fn drop_stale(&mut self) {
+    let seen = &self.seen;
+    self
+      .order
+      .retain(|(item, stamp)| seen.get(item) == Some(stamp));
+  }
+}
+
+impl<O, Item, Err> Observer<Item, Err> for DistinctWithinObserver<O, Item>
+where
+  O: Observer<Item, Err>,
+  Item: Hash + Eq + Clone,
+{
+  fn next(&mut self, value: Item) {
+    self.stamp += 1;
+    if let Some(stamp) = self.seen.get_mut(&value) {
+      *stamp = self.stamp;
+      self.order.push_back((value, self.stamp));
+      if self.order.len() > self.capacity * 2 {
+        self.drop_stale();
+      }
+      return;
+    }
+
+    self.seen.insert(value.clone(), self.stamp);
+    self.order.push_back((value.clone(), self.stamp));
+    if self.seen.len() > self.capacity {
+      self.evict_least_recent();
+    }
+    self.observer.next(value);
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    self.observer.error(err)
+  }
+
+  #[inline]
+  fn complete(self) {
+    self.observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+#[derive(Clone)]
+pub struct DistinctWithinDurationOp<S, SD> {
+  pub(crate) source: S,
+  pub(crate) duration: Duration,
+  pub(crate) scheduler: SD,
+}
+
+/// The items emitted in the last window, in the order they were emitted.
+pub struct DistinctWindow<Item> {
+  seen: HashSet<Item>,
+  order: VecDeque<(Instant, Item)>,
+  duration: Duration,
+}
+
+impl<Item: Hash + Eq> DistinctWindow<Item> {
+  fn purge(&mut self, now: Instant) {
+    while let Some((at, _)) = self.order.front() {
+      if *at + self.duration > now {
+        break;
+      }
+      if let Some((_, item)) = self.order.pop_front() {
+        self.seen.remove(&item);
+      }
+    }
+  }
+}
+
+type RcWindow<Item> = MutArc<DistinctWindow<Item>>;
+type RcHandler = MutArc<Option<TaskHandle<NormalReturn<()>>>>;
+
+fn purge_task<Item: Hash + Eq>(window: &mut RcWindow<Item>, _: usize) -> bool {
+  window.rc_deref_mut().purge(Instant::now());
+  true
+}
+
+impl<Item, Err, O, S, SD> Observable<Item, Err, O>
+  for DistinctWithinDurationOp<S, SD>
+where
+  S: Observable<Item, Err, DistinctWithinDurationObserver<O, Item>>,
+  O: Observer<Item, Err>,
+  SD: Scheduler<RepeatTask<RcWindow<Item>>>,
+  Item: Eq + Hash + Clone,
+{
+  type Unsub = ZipSubscription<S::Unsub, RcHandler>;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    let Self { source, duration, scheduler } = self;
+    let window = MutArc::own(DistinctWindow {
+      seen: HashSet::new(),
+      order: VecDeque::new(),
+      duration,
+    });
+    // Releases the items out of the window even if the source is quiet.
+    let task = RepeatTask::new(duration, purge_task, window.clone());
+    let handler = MutArc::own(Some(scheduler.schedule(task, None)));
+    let unsub = source.actual_subscribe(DistinctWithinDurationObserver {
+      observer,
+      window,
+      handler: handler.clone(),
+    });
+    ZipSubscription::new(unsub, handler)
+  }
+}
+
+impl<Item, Err, S, SD> ObservableExt<Item, Err>
+  for DistinctWithinDurationOp<S, SD>
+where
+  S: ObservableExt<Item, Err>,
+{
+}
+
+pub struct DistinctWithinDurationObserver<O, Item> {
+  observer: O,
+  window: RcWindow<Item>,
+  handler: RcHandler,
+}
+
+impl<O, Item> DistinctWithinDurationObserver<O, Item> {
+  fn cancel_purge(&self) {
+    if let Some(handler) = self.handler.rc_deref_mut().take() {
+      handler.unsubscribe()
+    }
+  }
+}
+
+impl<O, Item, Err> Observer<Item, Err>
+  for DistinctWithinDurationObserver<O, Item>
+where
+  O: Observer<Item, Err>,
+  Item: Hash + Eq + Clone,
+{
+  fn next(&mut self, value: Item) {
+    let now = Instant::now();
+    let fresh = {
+      let mut window = self.window.rc_deref_mut();
+      window.purge(now);
+      let fresh = window.seen.insert(value.clone());
+      if fresh {
+        window.order.push_back((now, value.clone()));
+      }
+      fresh
+    };
+    if fresh {
+      self.observer.next(value);
+    }
+  }
+
+  fn error(self, err: Err) {
+    self.cancel_purge();
+    self.observer.error(err)
+  }
+
+  fn complete(self) {
+    self.cancel_purge();
+    self.observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::rc::RcDeref;
   use std::{cell::RefCell, rc::Rc};
 
   #[test]
@@ -269,6 +530,116 @@ mod tests {
     assert_eq!(&*x_c.borrow(), &[0, 1, 2, 3, 4]);
   }
 
+  #[test]
+  fn out_of_order_from_merged_stream() {
+    let x = Rc::new(RefCell::new(vec![]));
+    let x_c = x.clone();
+    let mut a = Subject::default();
+    let mut b = Subject::default();
+
+    a.clone()
+      .merge(b.clone())
+      .distinct()
+      .subscribe(move |v| x.borrow_mut().push(v));
+    a.next(1);
+    b.next(3);
+    b.next(1);
+    a.next(2);
+    a.next(3);
+    b.next(2);
+
+    assert_eq!(&*x_c.borrow(), &[1, 3, 2]);
+  }
+
+  #[test]
+  fn drop_seen_on_unsubscribe() {
+    let value = Rc::new(1);
+    let mut subject = Subject::default();
+
+    let subscription = subject.clone().distinct().subscribe(|_| {});
+    subject.next(value.clone());
+    assert_eq!(Rc::strong_count(&value), 2);
+
+    subscription.unsubscribe();
+    assert_eq!(Rc::strong_count(&value), 1);
+  }
+
+  #[test]
+  fn distinct_within_evict_least_recent() {
+    let mut values = vec![];
+    observable::from_iter([1, 2, 3, 1, 3, 4, 2, 3])
+      .distinct_within(2)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [1, 2, 3, 1, 4, 2, 3]);
+  }
+
+  #[test]
+  fn distinct_within_bounded_bookkeeping() {
+    let mut count = 0;
+    observable::from_iter((0..1000).map(|i| i % 3))
+      .distinct_within(4)
+      .subscribe(|_| count += 1);
+
+    assert_eq!(count, 3);
+  }
+
+  #[test]
+  #[should_panic]
+  fn distinct_within_reject_zero_capacity() {
+    let _ = observable::of(1).distinct_within(0);
+  }
+
+  #[test]
+  fn distinct_within_duration_forget() {
+    let values = MutArc::own(vec![]);
+    let c_values = values.clone();
+    let mut pool = FuturesLocalSchedulerPool::new();
+    let mut subject = Subject::<i32, ()>::default();
+    subject
+      .clone()
+      .distinct_within_duration(Duration::from_millis(20), pool.spawner())
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+
+    subject.next(1);
+    subject.next(1);
+    subject.next(2);
+    std::thread::sleep(Duration::from_millis(25));
+    subject.next(1);
+    subject.next(1);
+    // the purge task is cancelled, so the pool can finish.
+    subject.complete();
+    pool.run();
+
+    assert_eq!(&*values.rc_deref(), &[1, 2, 1]);
+  }
+
+  #[test]
+  fn distinct_within_duration_purge_quiet_source() {
+    let mut pool = FuturesLocalSchedulerPool::new();
+    let window = MutArc::own(DistinctWindow {
+      seen: HashSet::new(),
+      order: VecDeque::new(),
+      duration: Duration::from_millis(5),
+    });
+    let now = Instant::now();
+    {
+      let mut window = window.rc_deref_mut();
+      window.seen.insert(1);
+      window.order.push_back((now, 1));
+    }
+    let task =
+      RepeatTask::new(Duration::from_millis(10), purge_task, window.clone());
+    let handler = pool.spawner().schedule(task, None);
+    pool.run_until_stalled();
+    std::thread::sleep(Duration::from_millis(15));
+    pool.run_until_stalled();
+    handler.unsubscribe();
+
+    assert!(window.rc_deref().seen.is_empty());
+  }
+
   #[test]
   fn bench() {
     do_bench();
@@ -308,24 +679,65 @@ mod tests {
   fn distinct_until_key_changed() {
     let x = Rc::new(RefCell::new(vec![]));
     let x_c = x.clone();
-    observable::from_iter(
-      vec![(1, 2), (2, 2), (2, 1), (1, 1), (2, 2), (3, 2)].into_iter(),
-    )
-    .map(|v| v)
-    .distinct_until_key_changed(|tup: &(i32, i32)| tup.0)
-    .subscribe(move |v| x.borrow_mut().push(v));
+    observable::from_iter(vec![(1, 2), (2, 2), (2, 1), (1, 1), (2, 2), (3, 2)])
+      .map(|v| v)
+      .distinct_until_key_changed(|tup: &(i32, i32)| tup.0)
+      .subscribe(move |v| x.borrow_mut().push(v));
     assert_eq!(&*x_c.borrow(), &[(1, 2), (2, 2), (1, 1), (2, 2), (3, 2)]);
   }
 
+  #[test]
+  fn distinct_until_key_changed_turbofish() {
+    let x = Rc::new(RefCell::new(vec![]));
+    let x_c = x.clone();
+    observable::from_iter([1, 1, 2, 2, 1])
+      .distinct_until_key_changed::<fn(&i32) -> i32>(|v| *v)
+      .subscribe(move |v| x.borrow_mut().push(v));
+    assert_eq!(&*x_c.borrow(), &[1, 2, 1]);
+  }
+
+  #[test]
+  fn distinct_until_key_changed_without_partial_eq_item() {
+    struct User {
+      id: u32,
+      name: &'static str,
+    }
+
+    let x = Rc::new(RefCell::new(vec![]));
+    let x_c = x.clone();
+    observable::from_iter([
+      User { id: 1, name: "a" },
+      User { id: 1, name: "b" },
+      User { id: 2, name: "c" },
+      User { id: 1, name: "d" },
+    ])
+    .distinct_until_key_changed(|u: &User| u.id)
+    .subscribe(move |u| x.borrow_mut().push(u.name));
+
+    assert_eq!(&*x_c.borrow(), &["a", "c", "d"]);
+  }
+
+  #[test]
+  fn distinct_until_key_changed_by() {
+    let x = Rc::new(RefCell::new(vec![]));
+    let x_c = x.clone();
+    observable::from_iter([1.0, 1.05, 1.5, 1.55, 1.0])
+      .distinct_until_key_changed_by(
+        |v: &f64| *v,
+        |a: &f64, b: &f64| (a - b).abs() < 0.1,
+      )
+      .subscribe(move |v| x.borrow_mut().push(v));
+
+    assert_eq!(&*x_c.borrow(), &[1.0, 1.5, 1.0]);
+  }
+
   #[test]
   fn distinct_key() {
     let x = Rc::new(RefCell::new(vec![]));
     let x_c = x.clone();
-    observable::from_iter(
-      vec![(1, 2), (2, 2), (2, 1), (1, 1), (2, 2), (3, 2)].into_iter(),
-    )
-    .distinct_key(|tup: &(i32, i32)| tup.0)
-    .subscribe(move |v| x.borrow_mut().push(v));
+    observable::from_iter(vec![(1, 2), (2, 2), (2, 1), (1, 1), (2, 2), (3, 2)])
+      .distinct_key(|tup: &(i32, i32)| tup.0)
+      .subscribe(move |v| x.borrow_mut().push(v));
 
     assert_eq!(&*x_c.borrow(), &[(1, 2), (2, 2), (3, 2)]);
   }
@@ -0,0 +1,309 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+#[derive(Clone)]
+pub struct ExhaustMapOp<'a, S, F, Item> {
+  source: S,
+  project: F,
+  _marker: TypeHint<&'a Item>,
+}
+
+#[derive(Clone)]
+pub struct ExhaustMapOpThreads<S, F, Item> {
+  source: S,
+  project: F,
+  _marker: TypeHint<Item>,
+}
+
+macro_rules! impl_new_method {
+  ($name: ident $(,$lf:lifetime)?) => {
+    impl<$($lf,)? S, F, Item> $name<$($lf,)? S, F, Item> {
+      #[inline]
+      pub(crate) fn new(source: S, project: F) -> Self {
+        Self { source, project, _marker: TypeHint::default() }
+      }
+    }
+  };
+}
+
+impl_new_method!(ExhaustMapOp, 'a);
+impl_new_method!(ExhaustMapOpThreads);
+
+macro_rules! impl_observable_method {
+  ($box_unsub: ty, $outside_observer: ident, $rc: ident) => {
+    type Unsub = ZipSubscription<S::Unsub, $rc<Option<$box_unsub>>>;
+
+    fn actual_subscribe(self, observer: O) -> Self::Unsub {
+      let inner_unsub = $rc::own(None);
+      let outside_observer = $outside_observer {
+        data: $rc::own(Some(ExhaustData {
+          observer,
+          inner_active: false,
+          outside_completed: false,
+        })),
+        project: self.project,
+        inner_unsub: inner_unsub.clone(),
+        _hint: TypeHint::new(),
+      };
+      let unsub = self.source.actual_subscribe(outside_observer);
+      ZipSubscription::new(unsub, inner_unsub)
+    }
+  };
+}
+
+impl<'a, Item, Item2, Err, O, S, F, V> Observable<Item2, Err, O>
+  for ExhaustMapOp<'a, S, F, Item>
+where
+  O: Observer<Item2, Err> + 'a,
+  S: Observable<Item, Err, OutsideObserver<'a, O, F, Item2>>,
+  F: FnMut(Item) -> V,
+  V: Observable<Item2, Err, InnerObserver<O>>,
+  V::Unsub: 'a,
+{
+  impl_observable_method!(BoxSubscription<'a>, OutsideObserver, MutRc);
+}
+
+impl<'a, Item, Item2, Err, S, F, V> ObservableExt<Item2, Err>
+  for ExhaustMapOp<'a, S, F, Item>
+where
+  S: ObservableExt<Item, Err>,
+  F: FnMut(Item) -> V,
+  V: ObservableExt<Item2, Err>,
+{
+}
+
+impl<Item, Item2, Err, O, S, F, V> Observable<Item2, Err, O>
+  for ExhaustMapOpThreads<S, F, Item>
+where
+  O: Observer<Item2, Err> + Send + 'static,
+  S: Observable<Item, Err, OutsideObserverThreads<O, F, Item2>>,
+  F: FnMut(Item) -> V,
+  V: Observable<Item2, Err, InnerObserverThreads<O>>,
+  V::Unsub: Send + 'static,
+{
+  impl_observable_method!(
+    BoxSubscriptionThreads,
+    OutsideObserverThreads,
+    MutArc
+  );
+}
+
+impl<Item, Item2, Err, S, F, V> ObservableExt<Item2, Err>
+  for ExhaustMapOpThreads<S, F, Item>
+where
+  S: ObservableExt<Item, Err>,
+  F: FnMut(Item) -> V,
+  V: ObservableExt<Item2, Err>,
+{
+}
+
+struct ExhaustData<O> {
+  observer: O,
+  inner_active: bool,
+  outside_completed: bool,
+}
+
+pub struct OutsideObserver<'a, O, F, Item> {
+  data: MutRc<Option<ExhaustData<O>>>,
+  project: F,
+  inner_unsub: MutRc<Option<BoxSubscription<'a>>>,
+  _hint: TypeHint<Item>,
+}
+
+pub struct OutsideObserverThreads<O, F, Item> {
+  data: MutArc<Option<ExhaustData<O>>>,
+  project: F,
+  inner_unsub: MutArc<Option<BoxSubscriptionThreads>>,
+  _hint: TypeHint<Item>,
+}
+
+pub struct InnerObserver<O>(MutRc<Option<ExhaustData<O>>>);
+
+pub struct InnerObserverThreads<O>(MutArc<Option<ExhaustData<O>>>);
+
+macro_rules! impl_inner_observer {
+  ($ty: ty) => {
+    impl<Item, Err, O> Observer<Item, Err> for $ty
+    where
+      O: Observer<Item, Err>,
+    {
+      fn next(&mut self, value: Item) {
+        if let Some(data) = self.0.rc_deref_mut().as_mut() {
+          data.observer.next(value)
+        }
+      }
+
+      fn error(self, err: Err) {
+        if let Some(data) = self.0.rc_deref_mut().take() {
+          data.observer.error(err)
+        }
+      }
+
+      fn complete(self) {
+        let mut data = self.0.rc_deref_mut();
+        if let Some(inner) = data.as_mut() {
+          inner.inner_active = false;
+          if inner.outside_completed {
+            data.take().unwrap().observer.complete();
+          }
+        }
+      }
+
+      fn is_finished(&self) -> bool {
+        self
+          .0
+          .rc_deref()
+          .as_ref()
+          .is_none_or(|data| data.observer.is_finished())
+      }
+    }
+  };
+}
+
+impl_inner_observer!(InnerObserver<O>);
+impl_inner_observer!(InnerObserverThreads<O>);
+
+macro_rules! impl_outside_observer {
+  ($outside_ty: ty, $inner: ident, $box_unsub: ty, $($lf:lifetime)? $($send:ident)?) => {
+    impl<$($lf,)? Item, Item2, Err, O, F, V> Observer<Item, Err>
+      for $outside_ty
+    where
+      O: Observer<Item2, Err>,
+      F: FnMut(Item) -> V,
+      V: Observable<Item2, Err, $inner<O>>,
+      V::Unsub: $($lf)? $($send + 'static)?,
+    {
+      fn next(&mut self, value: Item) {
+        match self.data.rc_deref_mut().as_mut() {
+          Some(data) if !data.inner_active => data.inner_active = true,
+          // Ignore the value while the inner observable is still active.
+          _ => return,
+        }
+        let inner = (self.project)(value);
+        let unsub = inner.actual_subscribe($inner(self.data.clone()));
+        *self.inner_unsub.rc_deref_mut() = Some(<$box_unsub>::new(unsub));
+      }
+
+      fn error(self, err: Err) {
+        let data = self.data.rc_deref_mut().take();
+        if let Some(data) = data {
+          self.inner_unsub.unsubscribe();
+          data.observer.error(err);
+        }
+      }
+
+      fn complete(self) {
+        let mut data = self.data.rc_deref_mut();
+        if let Some(inner) = data.as_mut() {
+          inner.outside_completed = true;
+          if !inner.inner_active {
+            data.take().unwrap().observer.complete();
+          }
+        }
+      }
+
+      fn is_finished(&self) -> bool {
+        self
+          .data
+          .rc_deref()
+          .as_ref()
+          .is_none_or(|data| data.observer.is_finished())
+      }
+    }
+  };
+}
+
+impl_outside_observer!(OutsideObserver<'a, O, F, Item2>, InnerObserver, BoxSubscription<'a>, 'a);
+impl_outside_observer!(OutsideObserverThreads<O, F, Item2>, InnerObserverThreads, BoxSubscriptionThreads, Send);
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    observable::fake_timer::FakeClock,
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
+
+  #[test]
+  fn ignore_outer_while_inner_active() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let projected = Rc::new(RefCell::new(0));
+    let c_projected = projected.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let clock = FakeClock::default();
+    let c_clock = clock.clone();
+
+    clock
+      .interval(Duration::from_millis(2))
+      .take(5)
+      .exhaust_map(move |i| {
+        *c_projected.borrow_mut() += 1;
+        c_clock.delay(Duration::from_millis(3)).map(move |_| i)
+      })
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(move |v| values.borrow_mut().push(v));
+
+    clock.advance(Duration::from_millis(11));
+    assert_eq!(&*c_values.borrow(), &[0, 2]);
+    assert!(!*completed.rc_deref());
+
+    clock.advance(Duration::from_millis(3));
+    assert_eq!(&*c_values.borrow(), &[0, 2, 4]);
+    assert_eq!(*projected.borrow(), 3);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn unsubscribe_cancel_inner() {
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let mut outer = Subject::default();
+
+    let subscription = outer
+      .clone()
+      .exhaust_map(move |_| {
+        let c_finalized = c_finalized.clone();
+        Subject::<i32, _>::default()
+          .finalize(move || *c_finalized.rc_deref_mut() = true)
+      })
+      .subscribe(|_| {});
+    outer.next(());
+    assert!(!*finalized.rc_deref());
+
+    subscription.unsubscribe();
+    assert!(*finalized.rc_deref());
+  }
+
+  #[test]
+  fn exhaust_map_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut outer = SubjectThreads::default();
+    let inner = SubjectThreads::default();
+    let c_inner = inner.clone();
+
+    outer
+      .clone()
+      .exhaust_map_threads(move |i| c_inner.clone().map(move |v: i32| v + i))
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    outer.next(10);
+    outer.next(20);
+    let mut c_inner = inner.clone();
+    c_inner.next(1);
+    inner.complete();
+    outer.next(30);
+
+    assert_eq!(&*values.lock().unwrap(), &[11]);
+  }
+}
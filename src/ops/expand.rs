@@ -0,0 +1,370 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+use std::collections::VecDeque;
+
+/// An observable that emits the items of the source, and recursively the
+/// items of the observables projected from every emitted item.
+///
+/// This struct is created by the expand method on
+/// [ObservableExt](ObservableExt::expand). See its documentation for more.
+#[derive(Clone)]
+pub struct ExpandOp<'a, S, F> {
+  source: S,
+  project: F,
+  concurrent: usize,
+  _hint: TypeHint<&'a ()>,
+}
+
+#[derive(Clone)]
+pub struct ExpandOpThreads<S, F> {
+  source: S,
+  project: F,
+  concurrent: usize,
+}
+
+impl<'a, S, F> ExpandOp<'a, S, F> {
+  #[inline]
+  pub(crate) fn new(source: S, project: F, concurrent: usize) -> Self {
+    Self {
+      source,
+      project,
+      concurrent,
+      _hint: TypeHint::new(),
+    }
+  }
+}
+
+impl<S, F> ExpandOpThreads<S, F> {
+  #[inline]
+  pub(crate) fn new(source: S, project: F, concurrent: usize) -> Self {
+    Self { source, project, concurrent }
+  }
+}
+
+pub struct ExpandState<O, F, R> {
+  observer: Option<O>,
+  project: F,
+  // The projected observables waiting for a free slot, or for the running
+  // subscribe to return, so a synchronous expansion doesn't recurse.
+  queue: VecDeque<R>,
+  active: usize,
+  concurrent: usize,
+  subscribing: bool,
+  source_completed: bool,
+}
+
+impl<O, F, R> ExpandState<O, F, R> {
+  fn is_finished<Item, Err>(&self) -> bool
+  where
+    O: Observer<Item, Err>,
+  {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+}
+
+pub struct ExpandObserver<S, U> {
+  state: S,
+  subscription: U,
+  // Whether it observes a projected observable rather than the source.
+  inner: bool,
+  // Subscribes the queued observables, a function pointer avoids requiring
+  // them to accept this observer in the `Observer` implementation.
+  drain: fn(&Self),
+}
+
+macro_rules! impl_expand_op {
+  (
+    $name: ident, $rc: ident, $multi: ty, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S, F, R> Observable<Item, Err, O>
+      for $name<$($lf,)? S, F>
+    where
+      O: Observer<Item, Err>,
+      F: FnMut(&Item) -> R,
+      S: Observable<
+        Item,
+        Err,
+        ExpandObserver<$rc<ExpandState<O, F, R>>, $multi>,
+      >,
+      R: Observable<
+        Item,
+        Err,
+        ExpandObserver<$rc<ExpandState<O, F, R>>, $multi>,
+      >,
+      S::Unsub: $($lf)? $($send + 'static)?,
+      R::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = $multi;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let mut subscription = <$multi>::default();
+        let state = $rc::own(ExpandState {
+          observer: Some(observer),
+          project: self.project,
+          queue: VecDeque::new(),
+          active: 0,
+          concurrent: self.concurrent,
+          subscribing: false,
+          source_completed: false,
+        });
+        let unsub = self.source.actual_subscribe(ExpandObserver {
+          state,
+          subscription: subscription.clone(),
+          inner: false,
+          drain: ExpandObserver::<$rc<ExpandState<O, F, R>>, $multi>::drain,
+        });
+        subscription.append(<$box_unsub>::new(unsub));
+        subscription
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S, F, R> ObservableExt<Item, Err>
+      for $name<$($lf,)? S, F>
+    where
+      S: ObservableExt<Item, Err>,
+      F: FnMut(&Item) -> R,
+      R: ObservableExt<Item, Err>,
+    {
+    }
+
+    impl<$($lf,)? O, F, R> ExpandObserver<$rc<ExpandState<O, F, R>>, $multi> {
+      fn drain<Item, Err>(&self)
+      where
+        O: Observer<Item, Err>,
+        F: FnMut(&Item) -> R,
+        R: Observable<Item, Err, Self>,
+        R::Unsub: $($lf)? $($send + 'static)?,
+      {
+        loop {
+          let mut state = self.state.rc_deref_mut();
+          if state.subscribing || state.is_finished() {
+            return;
+          }
+          if state.active < state.concurrent {
+            if let Some(next) = state.queue.pop_front() {
+              state.active += 1;
+              state.subscribing = true;
+              drop(state);
+              let unsub = next.actual_subscribe(ExpandObserver {
+                state: self.state.clone(),
+                subscription: self.subscription.clone(),
+                inner: true,
+                drain: self.drain,
+              });
+              self.subscription.clone().append(<$box_unsub>::new(unsub));
+              self.state.rc_deref_mut().subscribing = false;
+              continue;
+            }
+          }
+          if state.source_completed && state.active == 0 {
+            let observer = state.observer.take();
+            drop(state);
+            if let Some(observer) = observer {
+              observer.complete();
+            }
+          }
+          return;
+        }
+      }
+    }
+  };
+}
+
+impl_expand_op!(
+  ExpandOp,
+  MutRc,
+  MultiSubscription<'a>,
+  BoxSubscription<'a>,
+  'a
+);
+impl_expand_op!(
+  ExpandOpThreads,
+  MutArc,
+  MultiSubscriptionThreads,
+  BoxSubscriptionThreads,
+  Send
+);
+
+impl<Item, Err, S, U, O, F, R> Observer<Item, Err> for ExpandObserver<S, U>
+where
+  S: RcDeref<Target = ExpandState<O, F, R>>
+    + RcDerefMut<Target = ExpandState<O, F, R>>,
+  O: Observer<Item, Err>,
+  F: FnMut(&Item) -> R,
+{
+  fn next(&mut self, value: Item) {
+    {
+      let mut state = self.state.rc_deref_mut();
+      let ExpandState { observer, project, queue, .. } = &mut *state;
+      let Some(observer) = observer.as_mut().filter(|o| !o.is_finished())
+      else {
+        return;
+      };
+      let next = project(&value);
+      observer.next(value);
+      queue.push_back(next);
+    }
+    (self.drain)(self);
+  }
+
+  fn error(self, err: Err) {
+    let observer = {
+      let mut state = self.state.rc_deref_mut();
+      state.queue.clear();
+      state.observer.take()
+    };
+    if let Some(observer) = observer {
+      observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    {
+      let mut state = self.state.rc_deref_mut();
+      if self.inner {
+        state.active -= 1;
+      } else {
+        state.source_completed = true;
+      }
+    }
+    (self.drain)(&self);
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.state.rc_deref().is_finished()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+  };
+
+  #[test]
+  fn expand_tree() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::of(1)
+      .expand(|v| {
+        observable::from_iter(if *v < 8 {
+          vec![v * 2, v * 2 + 1]
+        } else {
+          vec![]
+        })
+      })
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    values.sort();
+    assert_eq!(values, (1..16).collect::<Vec<_>>());
+    assert!(completed);
+  }
+
+  #[test]
+  fn expand_pages() {
+    let pages = [vec![1, 2], vec![3], vec![4, 5]];
+    let mut items = vec![];
+
+    observable::of(0)
+      .expand(|page: &usize| observable::from_iter(Some(page + 1)))
+      .take_while(|page| *page < pages.len())
+      .flat_map(|page| observable::from_iter(pages[page].clone()))
+      .subscribe(|v| items.push(v));
+
+    assert_eq!(items, vec![1, 2, 3, 4, 5]);
+  }
+
+  #[test]
+  fn synchronous_expansion_not_recursive() {
+    let mut count = 0;
+    let mut completed = false;
+
+    observable::of(0)
+      .expand(|v| observable::from_iter((*v < 100_000).then_some(v + 1)))
+      .on_complete(|| completed = true)
+      .subscribe(|_| count += 1);
+
+    assert_eq!(count, 100_001);
+    assert!(completed);
+  }
+
+  #[test]
+  fn expand_concurrent_limit() {
+    let subjects = MutRc::own(vec![]);
+    let c_subjects = subjects.clone();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    {
+      observable::from_iter([1, 2])
+        .expand_concurrent(
+          move |_| {
+            let subject = Subject::<i32, Infallible>::default();
+            c_subjects.rc_deref_mut().push(subject.clone());
+            subject
+          },
+          1,
+        )
+        .on_complete(move || *c_completed.rc_deref_mut() = true)
+        .subscribe(move |v| c_values.rc_deref_mut().push(v));
+
+      // Both items are projected, but only one projection is subscribed.
+      let first = subjects.rc_deref()[0].clone();
+      let second = subjects.rc_deref()[1].clone();
+      assert!(!first.is_empty());
+      assert!(second.is_empty());
+
+      first.clone().next(0);
+      first.complete();
+      assert!(!second.is_empty());
+      second.complete();
+
+      let third = subjects.rc_deref()[2].clone();
+      assert!(!third.is_empty());
+      third.complete();
+    }
+
+    assert_eq!(&*values.rc_deref(), &[1, 2, 0]);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn projected_error_terminate() {
+    let mut values = vec![];
+    let mut error = None;
+
+    observable::of_result(Ok(1))
+      .expand(|v| {
+        observable::of_result(if *v < 3 { Ok(v + 1) } else { Err("stop") })
+      })
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![1, 2, 3]);
+    assert_eq!(error, Some("stop"));
+  }
+
+  #[test]
+  fn expand_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    observable::of(1)
+      .expand_threads(|v| observable::from_iter((*v < 4).then_some(v + 1)))
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[1, 2, 3, 4]);
+  }
+}
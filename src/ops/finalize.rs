@@ -68,16 +68,12 @@ where
 
   fn error(self, err: Err) {
     self.observer.error(err);
-    if let Some(func) = self.func.rc_deref_mut().take() {
-      func()
-    }
+    call_once(&self.func);
   }
 
   fn complete(self) {
     self.observer.complete();
-    if let Some(func) = self.func.rc_deref_mut().take() {
-      func()
-    }
+    call_once(&self.func);
   }
 
   #[inline]
@@ -86,6 +82,20 @@ where
   }
 }
 
+/// Calls the finalizer if no one did yet. The finalizer is taken under the
+/// lock, so the termination and the unsubscribe racing from different threads
+/// can't both call it, and it runs after the lock is released.
+fn call_once<C, F>(func: &C)
+where
+  C: RcDerefMut<Target = Option<F>>,
+  F: FnOnce(),
+{
+  let func = func.rc_deref_mut().take();
+  if let Some(func) = func {
+    func()
+  }
+}
+
 pub struct FinalizerSubscription<U, F> {
   subscription: U,
   func: F,
@@ -99,9 +109,7 @@ where
 {
   fn unsubscribe(self) {
     self.subscription.unsubscribe();
-    if let Some(func) = self.func.rc_deref_mut().take() {
-      func()
-    }
+    call_once(&self.func);
   }
 
   #[inline]
@@ -116,7 +124,7 @@ mod test {
   use std::cell::Cell;
   use std::rc::Rc;
   use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
     Arc,
   };
 
@@ -239,6 +247,27 @@ mod test {
     assert!(finalized.load(Ordering::Relaxed));
   }
 
+  #[test]
+  fn finalize_once_when_complete_race_unsubscribe() {
+    for _ in 0..100 {
+      let count = Arc::new(AtomicUsize::new(0));
+      let c_count = count.clone();
+      let s = SubjectThreads::<i32, _>::default();
+      let subscription = s
+        .clone()
+        .finalize_threads(move || {
+          c_count.fetch_add(1, Ordering::SeqCst);
+        })
+        .subscribe(|_| ());
+
+      let handle = std::thread::spawn(move || s.complete());
+      subscription.unsubscribe();
+      handle.join().unwrap();
+
+      assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+  }
+
   #[test]
   fn bench() {
     do_bench();
@@ -0,0 +1,170 @@
+use crate::{
+  ops::{
+    default_if_empty::DefaultIfEmptyOp, filter::FilterOp, map::MapOp,
+    take::TakeOp,
+  },
+  prelude::*,
+};
+
+/// The first matching item as an `Option`, `None` if the source completes
+/// without one.
+pub type FindOptOp<S, F, Item> = DefaultIfEmptyOp<
+  MapOp<TakeOp<FilterOp<S, F>>, fn(Item) -> Option<Item>, Item>,
+  Option<Item>,
+>;
+
+#[derive(Clone)]
+pub struct FindIndexOp<S, F, Item> {
+  source: S,
+  predicate: F,
+  _hint: TypeHint<Item>,
+}
+
+impl<S, F, Item> FindIndexOp<S, F, Item> {
+  #[inline]
+  pub(crate) fn new(source: S, predicate: F) -> Self {
+    Self {
+      source,
+      predicate,
+      _hint: TypeHint::new(),
+    }
+  }
+}
+
+impl<Item, Err, O, S, F> Observable<usize, Err, O> for FindIndexOp<S, F, Item>
+where
+  O: Observer<usize, Err>,
+  S: Observable<Item, Err, FindIndexObserver<O, F>>,
+  F: Fn(&Item) -> bool,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self.source.actual_subscribe(FindIndexObserver {
+      observer: Some(observer),
+      predicate: self.predicate,
+      index: 0,
+    })
+  }
+}
+
+impl<Item, Err, S, F> ObservableExt<usize, Err> for FindIndexOp<S, F, Item> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct FindIndexObserver<O, F> {
+  observer: Option<O>,
+  predicate: F,
+  index: usize,
+}
+
+impl<Item, Err, O, F> Observer<Item, Err> for FindIndexObserver<O, F>
+where
+  O: Observer<usize, Err>,
+  F: Fn(&Item) -> bool,
+{
+  fn next(&mut self, value: Item) {
+    if self.observer.is_some() && (self.predicate)(&value) {
+      if let Some(mut observer) = self.observer.take() {
+        observer.next(self.index);
+        observer.complete();
+      }
+    }
+    self.index += 1;
+  }
+
+  fn error(self, err: Err) {
+    if let Some(observer) = self.observer {
+      observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    if let Some(observer) = self.observer {
+      observer.complete();
+    }
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn find() {
+    let mut values = vec![];
+    let mut completed = 0;
+
+    observable::from_iter(0..10)
+      .find(|v| v % 4 == 3)
+      .on_complete(|| completed += 1)
+      .subscribe(|v| values.push(v));
+    observable::from_iter(0..10)
+      .find(|v| *v > 10)
+      .on_complete(|| completed += 1)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![3]);
+    assert_eq!(completed, 2);
+  }
+
+  #[test]
+  fn find_index() {
+    let mut values = vec![];
+    let mut completed = 0;
+
+    observable::from_iter(["a", "b", "c"])
+      .find_index(|v| *v == "c")
+      .on_complete(|| completed += 1)
+      .subscribe(|i| values.push(i));
+    observable::from_iter(["a", "b", "c"])
+      .find_index(|v| *v == "d")
+      .on_complete(|| completed += 1)
+      .subscribe(|i| values.push(i));
+
+    assert_eq!(values, vec![2]);
+    assert_eq!(completed, 2);
+  }
+
+  #[test]
+  fn find_opt() {
+    let mut values = vec![];
+
+    observable::from_iter(0..10)
+      .find_opt(|v| *v == 5)
+      .subscribe(|v| values.push(v));
+    observable::from_iter(0..10)
+      .find_opt(|v| *v > 10)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![Some(5), None]);
+  }
+
+  #[test]
+  fn stop_infinite_source() {
+    let mut produced = 0;
+    let mut found = None;
+    let mut index = None;
+
+    observable::from_iter(0..)
+      .tap(|_| produced += 1)
+      .find(|v| *v == 3)
+      .subscribe(|v| found = Some(v));
+    assert_eq!(produced, 4);
+
+    observable::from_iter(0..)
+      .tap(|_| produced += 1)
+      .find_index(|v| *v == 3)
+      .subscribe(|i| index = Some(i));
+    assert_eq!(produced, 8);
+
+    assert_eq!(found, Some(3));
+    assert_eq!(index, Some(3));
+  }
+}
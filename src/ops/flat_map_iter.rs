@@ -0,0 +1,130 @@
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct FlatMapIterOp<S, F, Item> {
+  source: S,
+  f: F,
+  _m: TypeHint<Item>,
+}
+
+impl<S, F, Item> FlatMapIterOp<S, F, Item> {
+  #[inline]
+  pub fn new(source: S, f: F) -> Self {
+    Self { source, f, _m: TypeHint::default() }
+  }
+}
+
+impl<OutputItem, Item, Err, O, S, F, I> Observable<OutputItem, Err, O>
+  for FlatMapIterOp<S, F, Item>
+where
+  S: Observable<Item, Err, FlatMapIterObserver<O, F>>,
+  O: Observer<OutputItem, Err>,
+  F: FnMut(Item) -> I,
+  I: IntoIterator<Item = OutputItem>,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self.source.actual_subscribe(FlatMapIterObserver {
+      down_observer: observer,
+      f: self.f,
+    })
+  }
+}
+
+impl<OutputItem, Item, Err, S, F, I> ObservableExt<OutputItem, Err>
+  for FlatMapIterOp<S, F, Item>
+where
+  S: ObservableExt<Item, Err>,
+  F: FnMut(Item) -> I,
+  I: IntoIterator<Item = OutputItem>,
+{
+}
+
+pub struct FlatMapIterObserver<O, F> {
+  down_observer: O,
+  f: F,
+}
+
+impl<O, F, Item, Err, OutputItem, I> Observer<Item, Err>
+  for FlatMapIterObserver<O, F>
+where
+  O: Observer<OutputItem, Err>,
+  F: FnMut(Item) -> I,
+  I: IntoIterator<Item = OutputItem>,
+{
+  fn next(&mut self, value: Item) {
+    let mut iter = (self.f)(value).into_iter();
+    // Stop draining as soon as the downstream unsubscribed.
+    while !self.down_observer.is_finished() {
+      let Some(v) = iter.next() else { break };
+      self.down_observer.next(v);
+    }
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    self.down_observer.error(err)
+  }
+
+  #[inline]
+  fn complete(self) {
+    self.down_observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.down_observer.is_finished()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn explode_items() {
+    let mut words = vec![];
+    let mut completed = false;
+    observable::from_iter(["a b", "", "c"])
+      .flat_map_iter(|line| line.split_whitespace())
+      .on_complete(|| completed = true)
+      .subscribe(|w| words.push(w));
+
+    assert_eq!(words, ["a", "b", "c"]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn stop_draining_on_unsubscribe() {
+    let pulled = std::cell::Cell::new(0);
+    let mut values = vec![];
+    observable::of(())
+      .flat_map_iter(|_| (0..).inspect(|_| pulled.set(pulled.get() + 1)))
+      .take(3)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [0, 1, 2]);
+    assert_eq!(pulled.get(), 3);
+  }
+
+  #[test]
+  fn error_pass_through() {
+    let mut values = vec![];
+    let mut error = None;
+    {
+      let mut subject = Subject::<i32, &str>::default();
+      subject
+        .clone()
+        .flat_map_iter(|v| vec![v; 2])
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      subject.next(1);
+      subject.error("fail");
+    }
+
+    assert_eq!(values, [1, 1]);
+    assert_eq!(error, Some("fail"));
+  }
+}
@@ -1,4 +1,7 @@
-use crate::prelude::*;
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
 use std::{clone::Clone, collections::HashMap, hash::Hash};
 
 /// Observable used to keep track of the key of the items emitted by the contained subject.
@@ -86,7 +89,8 @@ macro_rules! impl_observable_for_group_by {
       ObservableExt<KeyObservable<Key, $ty>, Err>
       for GroupByOp<Source, Discr, $ty>
     where
-      Source: ObservableExt<Item, Err>
+      Source: ObservableExt<Item, Err>,
+      Discr: FnMut(&Item) -> Key,
     {
     }
   };
@@ -137,6 +141,335 @@ where
   }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+
+/// Observable returned by the group_by_until method, every group is closed
+/// when the observable returned by `duration_selector` for its key emits or
+/// completes.
+#[derive(Clone)]
+pub struct GroupByUntilOp<Source, Discr, DurationSelector, Subject, NotifyItem>
+{
+  source: Source,
+  discr: Discr,
+  duration_selector: DurationSelector,
+  _hint: TypeHint<(Subject, NotifyItem)>,
+}
+
+impl<Source, Discr, DurationSelector, Subject, NotifyItem>
+  GroupByUntilOp<Source, Discr, DurationSelector, Subject, NotifyItem>
+{
+  #[inline]
+  pub fn new(
+    source: Source,
+    discr: Discr,
+    duration_selector: DurationSelector,
+  ) -> Self {
+    Self {
+      source,
+      discr,
+      duration_selector,
+      _hint: TypeHint::default(),
+    }
+  }
+}
+
+struct UntilGroup<Subject, U> {
+  // Tells apart the groups opened again with the same key.
+  id: usize,
+  subject: Subject,
+  duration: Option<U>,
+}
+
+pub struct GroupByUntilState<Key, Subject, U> {
+  groups: HashMap<Key, UntilGroup<Subject, U>>,
+  next_id: usize,
+  // The durations that closed their group, they can't be unsubscribed during
+  // their own emission, so they're unsubscribed by the next source item.
+  retired: Vec<U>,
+  closed: bool,
+}
+
+impl<Key: Hash + Eq, Subject, U: Subscription>
+  GroupByUntilState<Key, Subject, U>
+{
+  fn close_group(&mut self, key: &Key, id: usize) -> Option<Subject> {
+    if self.groups.get(key).is_none_or(|g| g.id != id) {
+      return None;
+    }
+    let group = self.groups.remove(key)?;
+    self.retired.extend(group.duration);
+    Some(group.subject)
+  }
+
+  fn take_all(&mut self) -> (Vec<Subject>, Vec<U>) {
+    self.closed = true;
+    let mut subscriptions = std::mem::take(&mut self.retired);
+    let subjects = self
+      .groups
+      .drain()
+      .map(|(_, g)| {
+        subscriptions.extend(g.duration);
+        g.subject
+      })
+      .collect();
+    (subjects, subscriptions)
+  }
+}
+
+pub struct GroupByUntilObserver<O, Discr, DurationSelector, R, NotifyItem> {
+  observer: O,
+  discr: Discr,
+  duration_selector: DurationSelector,
+  state: R,
+  _hint: TypeHint<NotifyItem>,
+}
+
+pub struct GroupDurationObserver<R, Key> {
+  state: R,
+  key: Key,
+  id: usize,
+}
+
+pub struct GroupByUntilSubscription<R>(R);
+
+macro_rules! impl_group_by_until {
+  (
+    $ty: ty, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<
+      $($lf,)? Source, Discr, DurationSelector, N, NotifyItem, Key, Item, Err,
+      O,
+    >
+      Observable<KeyObservable<Key, $ty>, Err, O>
+      for GroupByUntilOp<Source, Discr, DurationSelector, $ty, NotifyItem>
+    where
+      O: Observer<KeyObservable<Key, $ty>, Err>,
+      Source: Observable<
+        Item,
+        Err,
+        GroupByUntilObserver<
+          O,
+          Discr,
+          DurationSelector,
+          $rc<GroupByUntilState<Key, $ty, $box_unsub>>,
+          NotifyItem,
+        >,
+      >,
+      Discr: FnMut(&Item) -> Key,
+      DurationSelector: FnMut(&Key) -> N,
+      N: Observable<
+        NotifyItem,
+        Err,
+        GroupDurationObserver<
+          $rc<GroupByUntilState<Key, $ty, $box_unsub>>,
+          Key,
+        >,
+      >,
+      N::Unsub: $($lf)? $($send + 'static)?,
+      Key: Hash + Eq + Clone,
+      Item: Clone,
+      Err: Clone,
+    {
+      type Unsub = ZipSubscription<
+        Source::Unsub,
+        GroupByUntilSubscription<$rc<GroupByUntilState<Key, $ty, $box_unsub>>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(GroupByUntilState {
+          groups: HashMap::new(),
+          next_id: 0,
+          retired: vec![],
+          closed: false,
+        });
+        let unsub = self.source.actual_subscribe(GroupByUntilObserver {
+          observer,
+          discr: self.discr,
+          duration_selector: self.duration_selector,
+          state: state.clone(),
+          _hint: TypeHint::default(),
+        });
+        ZipSubscription::new(unsub, GroupByUntilSubscription(state))
+      }
+    }
+
+    impl<$($lf,)? Source, Discr, DurationSelector, NotifyItem, Key, Item, Err>
+      ObservableExt<KeyObservable<Key, $ty>, Err>
+      for GroupByUntilOp<Source, Discr, DurationSelector, $ty, NotifyItem>
+    where
+      Source: ObservableExt<Item, Err>,
+      Discr: FnMut(&Item) -> Key,
+    {
+    }
+
+    impl<$($lf,)? Discr, DurationSelector, N, NotifyItem, Key, Item, Err, O>
+      Observer<Item, Err>
+      for GroupByUntilObserver<
+        O,
+        Discr,
+        DurationSelector,
+        $rc<GroupByUntilState<Key, $ty, $box_unsub>>,
+        NotifyItem,
+      >
+    where
+      O: Observer<KeyObservable<Key, $ty>, Err>,
+      Discr: FnMut(&Item) -> Key,
+      DurationSelector: FnMut(&Key) -> N,
+      N: Observable<
+        NotifyItem,
+        Err,
+        GroupDurationObserver<
+          $rc<GroupByUntilState<Key, $ty, $box_unsub>>,
+          Key,
+        >,
+      >,
+      N::Unsub: $($lf)? $($send + 'static)?,
+      Key: Hash + Eq + Clone,
+      Item: Clone,
+      Err: Clone,
+    {
+      fn next(&mut self, value: Item) {
+        let key = (self.discr)(&value);
+        let retired = std::mem::take(&mut self.state.rc_deref_mut().retired);
+        retired.into_iter().for_each(|u| u.unsubscribe());
+
+        let opened =
+          self.state.rc_deref().groups.get(&key).map(|g| g.subject.clone());
+        if let Some(mut subject) = opened {
+          subject.next(value);
+          return;
+        }
+
+        let mut subject = <$ty>::default();
+        let id = {
+          let mut state = self.state.rc_deref_mut();
+          let id = state.next_id;
+          state.next_id += 1;
+          let group =
+            UntilGroup { id, subject: subject.clone(), duration: None };
+          state.groups.insert(key.clone(), group);
+          id
+        };
+        let wrapper =
+          KeyObservable { key: key.clone(), subject: subject.clone() };
+        self.observer.next(wrapper);
+        subject.next(value);
+
+        let duration = (self.duration_selector)(&key);
+        let unsub = duration.actual_subscribe(GroupDurationObserver {
+          state: self.state.clone(),
+          key: key.clone(),
+          id,
+        });
+        let mut state = self.state.rc_deref_mut();
+        match state.groups.get_mut(&key).filter(|g| g.id == id) {
+          Some(group) => group.duration = Some(<$box_unsub>::new(unsub)),
+          None => {
+            // The duration closed the group synchronously.
+            drop(state);
+            unsub.unsubscribe();
+          }
+        }
+      }
+
+      fn error(self, err: Err) {
+        let (subjects, subscriptions) = self.state.rc_deref_mut().take_all();
+        subscriptions.into_iter().for_each(|u| u.unsubscribe());
+        for subject in subjects {
+          subject.error(err.clone());
+        }
+        self.observer.error(err)
+      }
+
+      fn complete(self) {
+        let (subjects, subscriptions) = self.state.rc_deref_mut().take_all();
+        subscriptions.into_iter().for_each(|u| u.unsubscribe());
+        for subject in subjects {
+          subject.complete();
+        }
+        self.observer.complete()
+      }
+
+      #[inline]
+      fn is_finished(&self) -> bool {
+        self.observer.is_finished()
+      }
+    }
+
+    impl<$($lf,)? NotifyItem, Key, Item, Err> Observer<NotifyItem, Err>
+      for GroupDurationObserver<
+        $rc<GroupByUntilState<Key, $ty, $box_unsub>>,
+        Key,
+      >
+    where
+      Key: Hash + Eq,
+      Item: Clone,
+      Err: Clone,
+    {
+      fn next(&mut self, _: NotifyItem) {
+        let subject =
+          self.state.rc_deref_mut().close_group(&self.key, self.id);
+        if let Some(subject) = subject {
+          subject.complete();
+        }
+      }
+
+      fn error(self, err: Err) {
+        let subject =
+          self.state.rc_deref_mut().close_group(&self.key, self.id);
+        if let Some(subject) = subject {
+          subject.error(err);
+        }
+      }
+
+      fn complete(self) {
+        let subject =
+          self.state.rc_deref_mut().close_group(&self.key, self.id);
+        if let Some(subject) = subject {
+          subject.complete();
+        }
+      }
+
+      fn is_finished(&self) -> bool {
+        self
+          .state
+          .rc_deref()
+          .groups
+          .get(&self.key)
+          .is_none_or(|g| g.id != self.id)
+      }
+    }
+  };
+}
+
+impl_group_by_until!(Subject<'a, Item, Err>, MutRc, BoxSubscription<'a>, 'a);
+impl_group_by_until!(
+  SubjectThreads<Item, Err>,
+  MutArc,
+  BoxSubscriptionThreads,
+  Send
+);
+
+impl<R, Key, Subject, U> Subscription for GroupByUntilSubscription<R>
+where
+  R: RcDeref<Target = GroupByUntilState<Key, Subject, U>>
+    + RcDerefMut<Target = GroupByUntilState<Key, Subject, U>>,
+  Key: Hash + Eq,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let (subjects, subscriptions) = self.0.rc_deref_mut().take_all();
+    drop(subjects);
+    subscriptions.into_iter().for_each(|u| u.unsubscribe());
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.0.rc_deref().closed
+  }
+}
+
 #[cfg(test)]
 mod test {
   use crate::prelude::*;
@@ -188,6 +521,161 @@ mod test {
     assert_eq!(sum, 6);
   }
 
+  #[test]
+  fn sum_by_parity() {
+    let mut sums = vec![];
+    observable::from_iter(0..10)
+      .group_by::<_, _, Subject<_, _>>(|v| v % 2 == 0)
+      .flat_map(|group| {
+        let key = group.key;
+        group.reduce(|acc, v| acc + v).map(move |sum| (key, sum))
+      })
+      .subscribe(|v| sums.push(v));
+
+    // groups complete in the iteration order of a `HashMap`.
+    sums.sort();
+    assert_eq!(sums, [(false, 25), (true, 20)]);
+  }
+
+  #[test]
+  fn propagates_error() {
+    let errors = MutRc::own(0);
+    let c_errors = errors.clone();
+    let c_errors2 = errors.clone();
+    let mut source = Subject::default();
+
+    source
+      .clone()
+      .group_by::<_, _, Subject<_, _>>(|v: &i32| *v)
+      .on_error(move |_| *c_errors.rc_deref_mut() += 1)
+      .subscribe(move |group| {
+        let c_errors = c_errors2.clone();
+        group
+          .on_error(move |_: &str| *c_errors.rc_deref_mut() += 1)
+          .subscribe(|_| {});
+      });
+
+    source.next(1);
+    source.next(2);
+    source.error("error");
+
+    // both groups and the outer observable receive the error.
+    assert_eq!(*errors.rc_deref(), 3);
+  }
+
+  #[test]
+  fn group_by_until_reopen_group() {
+    let groups = MutRc::own(vec![]);
+    let c_groups = groups.clone();
+    let mut source = Subject::<i32, ()>::default();
+    let flush = Subject::<(), ()>::default();
+    let mut c_flush = flush.clone();
+
+    source
+      .clone()
+      .group_by_until::<_, _, Subject<_, _>, _, _, _>(
+        |v: &i32| v % 2,
+        move |_| flush.clone(),
+      )
+      .flat_map(|group| {
+        let key = group.key;
+        group.to_vec().map(move |items| (key, items))
+      })
+      .on_error(|_| {})
+      .subscribe(move |v| c_groups.rc_deref_mut().push(v));
+
+    source.next(1);
+    source.next(2);
+    source.next(3);
+    c_flush.next(());
+    assert_eq!(&*groups.rc_deref(), &[(1, vec![1, 3]), (0, vec![2])]);
+    // the closed durations are unsubscribed by the next item.
+    source.next(5);
+    assert_eq!(c_flush.len(), 1);
+    source.clone().complete();
+
+    assert_eq!(
+      &*groups.rc_deref(),
+      &[(1, vec![1, 3]), (0, vec![2]), (1, vec![5])]
+    );
+    assert!(c_flush.is_empty());
+  }
+
+  #[test]
+  fn group_by_until_per_key_duration() {
+    let completed = MutRc::own(vec![]);
+    let c_completed = completed.clone();
+    let durations = MutRc::own(vec![]);
+    let c_durations = durations.clone();
+    let mut source = Subject::<&str, ()>::default();
+
+    source
+      .clone()
+      .group_by_until::<_, _, Subject<_, _>, _, _, _>(
+        |v: &&str| *v,
+        move |_| {
+          let duration = Subject::<(), ()>::default();
+          c_durations.rc_deref_mut().push(duration.clone());
+          duration
+        },
+      )
+      .on_error(|_| {})
+      .subscribe(move |group| {
+        let completed = c_completed.clone();
+        let key = group.key;
+        group
+          .on_complete(move || completed.rc_deref_mut().push(key))
+          .on_error(|_| {})
+          .subscribe(|_| {});
+      });
+
+    source.next("a");
+    source.next("b");
+    // a duration completing closes its group too.
+    durations.rc_deref()[1].clone().complete();
+    assert_eq!(&*completed.rc_deref(), &["b"]);
+    source.next("b");
+    durations.rc_deref()[0].clone().next(());
+    assert_eq!(&*completed.rc_deref(), &["b", "a"]);
+    assert_eq!(durations.rc_deref().len(), 3);
+  }
+
+  #[test]
+  fn group_by_until_unsubscribe_durations() {
+    let flush = Subject::<(), ()>::default();
+    let c_flush = flush.clone();
+    let mut source = Subject::<i32, ()>::default();
+    let subscription = source
+      .clone()
+      .group_by_until::<_, _, Subject<_, _>, _, _, _>(
+        |v: &i32| *v,
+        move |_| c_flush.clone(),
+      )
+      .on_error(|_| {})
+      .subscribe(|_| {});
+
+    source.next(1);
+    source.next(2);
+    assert_eq!(flush.len(), 2);
+    subscription.unsubscribe();
+    assert!(flush.is_empty());
+  }
+
+  #[test]
+  fn group_by_until_threads() {
+    let count = std::sync::Arc::new(std::sync::Mutex::new(0));
+    let c_count = count.clone();
+    observable::from_iter([1, 1, 2])
+      .group_by_until::<_, _, SubjectThreads<_, _>, _, _, _>(
+        |v| *v,
+        |_| observable::of(()),
+      )
+      .subscribe(move |_| *c_count.lock().unwrap() += 1);
+
+    // every group is closed right after its first item.
+    assert_eq!(*count.lock().unwrap(), 3);
+  }
+
   #[test]
   fn bench() {
     do_bench();
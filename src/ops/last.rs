@@ -168,6 +168,53 @@ mod test {
     assert_eq!(default, 100);
   }
 
+  #[test]
+  fn last_with() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::from_iter(0..10)
+      .last_with(|v| v % 4 == 0)
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![8]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn error_drop_last() {
+    let mut values = vec![];
+    let mut error = None;
+    {
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .last()
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      subject.next(1);
+      subject.error("fail");
+    }
+
+    assert!(values.is_empty());
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn last_not_clone_item() {
+    struct NotClone(i32);
+    let mut value = None;
+
+    observable::from_iter(0..3)
+      .map(NotClone)
+      .last()
+      .subscribe(|v| value = Some(v.0));
+
+    assert_eq!(value, Some(2));
+  }
+
   #[test]
   fn bench() {
     do_bench();
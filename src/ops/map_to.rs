@@ -95,6 +95,32 @@ mod test {
     assert_eq!(i, 3);
   }
 
+  #[test]
+  fn source_item_not_clone() {
+    struct NotClone;
+    let mut values = vec![];
+    observable::from_iter([NotClone, NotClone])
+      .map_to("tick".to_string())
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, ["tick", "tick"]);
+  }
+
+  #[test]
+  fn map_to_threads() {
+    let values = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut subject = SubjectThreads::<i32, ()>::default();
+    subject
+      .clone()
+      .map_to('x')
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    std::thread::spawn(move || subject.next(1)).join().unwrap();
+    assert_eq!(&*values.lock().unwrap(), &['x']);
+  }
+
   #[test]
   fn bench() {
     do_bench();
@@ -0,0 +1,238 @@
+use crate::prelude::*;
+
+/// A signal of an observable as a value, emitted by
+/// [`materialize`](ObservableExt::materialize) and replayed by
+/// [`dematerialize`](ObservableExt::dematerialize).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notification<Item, Err> {
+  Next(Item),
+  Error(Err),
+  Complete,
+}
+
+impl<Item, Err> Notification<Item, Err> {
+  /// Sends this notification to `observer`, returns the observer back if it
+  /// can still be notified.
+  pub fn accept<O>(self, mut observer: O) -> Option<O>
+  where
+    O: Observer<Item, Err>,
+  {
+    match self {
+      Notification::Next(value) => {
+        observer.next(value);
+        Some(observer)
+      }
+      Notification::Error(err) => {
+        observer.error(err);
+        None
+      }
+      Notification::Complete => {
+        observer.complete();
+        None
+      }
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct MaterializeOp<S> {
+  pub(crate) source: S,
+}
+
+impl<Item, Err, O, S> Observable<Notification<Item, Err>, Err, O>
+  for MaterializeOp<S>
+where
+  O: Observer<Notification<Item, Err>, Err>,
+  S: Observable<Item, Err, MaterializeObserver<O>>,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self
+      .source
+      .actual_subscribe(MaterializeObserver { observer })
+  }
+}
+
+impl<Item, Err, S> ObservableExt<Notification<Item, Err>, Err>
+  for MaterializeOp<S>
+where
+  S: ObservableExt<Item, Err>,
+{
+}
+
+pub struct MaterializeObserver<O> {
+  observer: O,
+}
+
+impl<Item, Err, O> Observer<Item, Err> for MaterializeObserver<O>
+where
+  O: Observer<Notification<Item, Err>, Err>,
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    self.observer.next(Notification::Next(value));
+  }
+
+  fn error(mut self, err: Err) {
+    self.observer.next(Notification::Error(err));
+    self.observer.complete();
+  }
+
+  fn complete(mut self) {
+    self.observer.next(Notification::Complete);
+    self.observer.complete();
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+#[derive(Clone)]
+pub struct DematerializeOp<S> {
+  pub(crate) source: S,
+}
+
+impl<Item, Err, O, S> Observable<Item, Err, O> for DematerializeOp<S>
+where
+  O: Observer<Item, Err>,
+  S: Observable<Notification<Item, Err>, Err, DematerializeObserver<O>>,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self
+      .source
+      .actual_subscribe(DematerializeObserver { observer: Some(observer) })
+  }
+}
+
+impl<Item, Err, S> ObservableExt<Item, Err> for DematerializeOp<S> where
+  S: ObservableExt<Notification<Item, Err>, Err>
+{
+}
+
+pub struct DematerializeObserver<O> {
+  // Taken by a replayed termination, the notifications after it are ignored.
+  observer: Option<O>,
+}
+
+impl<Item, Err, O> Observer<Notification<Item, Err>, Err>
+  for DematerializeObserver<O>
+where
+  O: Observer<Item, Err>,
+{
+  fn next(&mut self, value: Notification<Item, Err>) {
+    if let Some(observer) = self.observer.take() {
+      self.observer = value.accept(observer);
+    }
+  }
+
+  fn error(self, err: Err) {
+    if let Some(observer) = self.observer {
+      observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    if let Some(observer) = self.observer {
+      observer.complete();
+    }
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::Notification;
+  use crate::prelude::*;
+
+  #[test]
+  fn materialize_signals() {
+    let mut notifications = vec![];
+    {
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .materialize()
+        .on_error(|_| unreachable!())
+        .subscribe(|n| notifications.push(n));
+
+      subject.next(1);
+      subject.error("fail");
+    }
+
+    assert_eq!(
+      notifications,
+      vec![Notification::Next(1), Notification::Error("fail")]
+    );
+
+    let mut notifications = vec![];
+    observable::of(1)
+      .materialize()
+      .subscribe(|n| notifications.push(n));
+    assert_eq!(
+      notifications,
+      vec![Notification::Next(1), Notification::Complete]
+    );
+  }
+
+  #[test]
+  fn dematerialize_stop_after_termination() {
+    let mut values = vec![];
+    let mut completed = 0;
+
+    observable::from_iter([
+      Notification::Next(1),
+      Notification::Complete,
+      Notification::Next(2),
+      Notification::Complete,
+    ])
+    .dematerialize()
+    .on_complete(|| completed += 1)
+    .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![1]);
+    assert_eq!(completed, 1);
+  }
+
+  #[test]
+  fn round_trip_is_identity() {
+    let mut values = vec![];
+    let mut error = None;
+    let mut completed = false;
+    {
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .materialize()
+        .dematerialize()
+        .on_complete(|| completed = true)
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      subject.next(1);
+      subject.next(2);
+      subject.error("fail");
+    }
+    assert_eq!(values, vec![1, 2]);
+    assert_eq!(error, Some("fail"));
+    assert!(!completed);
+
+    let mut values = vec![];
+    let mut completed = false;
+    observable::from_iter(0..3)
+      .materialize()
+      .dematerialize()
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+    assert_eq!(values, vec![0, 1, 2]);
+    assert!(completed);
+  }
+}
@@ -160,6 +160,9 @@ macro_rules! impl_inner_observer {
         let mut inner = self.0.rc_deref_mut();
         if let Some(data) = inner.as_mut() {
           if let Some(task) = data.subscribe_tasks.pop_front() {
+            // Release the borrow first, the next inner observable may emit
+            // right away when subscribed.
+            drop(inner);
             task();
           } else {
             data.subscribed -= 1;
@@ -257,7 +260,13 @@ mod test {
   use crate::observable::fake_timer::FakeClock;
 
   use super::*;
-  use std::{cell::RefCell, rc::Rc, time::Duration};
+  use crate::ops::box_it::{BoxOp, CloneableBoxOp};
+  use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
 
   #[test]
   fn smoke() {
@@ -268,7 +277,7 @@ mod test {
     observable::from_iter(
       (0..3).map(|_| clock.interval(Duration::from_millis(1)).take(5)),
     )
-    .merge_all(2)
+    .merge_all_concurrent(2)
     .subscribe(move |i| values.borrow_mut().push(i));
     clock.advance(Duration::from_millis(11));
 
@@ -285,7 +294,7 @@ mod test {
     let mut subject = Subject::default();
 
     let subscription = observable::of(subject.clone())
-      .merge_all(1)
+      .merge_all()
       .subscribe(move |v| values.push(v));
     subscription.unsubscribe();
 
@@ -293,4 +302,329 @@ mod test {
 
     assert_eq!(&c_values, &[]);
   }
+
+  #[test]
+  fn interleave_inner_emissions() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let mut outer = Subject::default();
+    let mut a = Subject::default();
+    let mut b = Subject::default();
+
+    outer
+      .clone()
+      .merge_all()
+      .subscribe(move |v: i32| values.borrow_mut().push(v));
+
+    outer.next(a.clone());
+    a.next(1);
+    outer.next(b.clone());
+    b.next(2);
+    a.next(3);
+
+    assert_eq!(&*c_values.borrow(), &[1, 2, 3]);
+  }
+
+  #[test]
+  fn complete_after_outer_and_all_inners() {
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut outer = Subject::default();
+    let a = Subject::<i32, _>::default();
+    let b = Subject::default();
+
+    outer
+      .clone()
+      .merge_all()
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(|_| {});
+
+    outer.next(a.clone());
+    outer.next(b.clone());
+    outer.complete();
+    assert!(!*completed.rc_deref());
+
+    a.complete();
+    assert!(!*completed.rc_deref());
+
+    b.complete();
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn complete_with_outer_after_inners() {
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut outer = Subject::default();
+
+    outer
+      .clone()
+      .merge_all()
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(|_: i32| {});
+
+    outer.next(observable::of(1));
+    outer.next(observable::of(2));
+    assert!(!*completed.rc_deref());
+
+    outer.complete();
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn complete_after_queued_inners() {
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut outer = Subject::default();
+    let a = Subject::<i32, _>::default();
+    let b = Subject::default();
+
+    outer
+      .clone()
+      .merge_all_concurrent(1)
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(|_| {});
+
+    outer.next(a.clone());
+    outer.next(b.clone());
+    outer.complete();
+    a.complete();
+    assert!(!*completed.rc_deref());
+
+    b.complete();
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  #[should_panic]
+  fn merge_all_concurrent_reject_zero() {
+    observable::of(observable::of(1)).merge_all_concurrent(0);
+  }
+
+  #[test]
+  fn concat_all_in_arrival_order() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut outer = Subject::<CloneableBoxOp<_, _>, _>::default();
+    let mut a = Subject::default();
+    let mut b = Subject::default();
+
+    outer
+      .clone()
+      .concat_all()
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(move |v: i32| values.borrow_mut().push(v));
+
+    outer.next(a.clone().box_it());
+    outer.next(b.clone().box_it());
+    outer.next(observable::from_iter([5, 6]).box_it());
+    outer.clone().complete();
+    b.next(1);
+    a.next(2);
+    a.clone().complete();
+    b.next(3);
+    assert!(!*completed.rc_deref());
+
+    b.clone().complete();
+    assert_eq!(&*c_values.borrow(), &[2, 3, 5, 6]);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn concat_all_inner_error_abort() {
+    let subscribed = MutRc::own(false);
+    let c_subscribed = subscribed.clone();
+    let mut error = None;
+    {
+      let mut outer = Subject::<CloneableBoxOp<_, _>, _>::default();
+      let a = Subject::<i32, _>::default();
+
+      outer
+        .clone()
+        .concat_all()
+        .on_error(|e| error = Some(e))
+        .subscribe(|_| {});
+
+      outer.next(a.clone().box_it());
+      outer.next(
+        observable::defer(move || {
+          *c_subscribed.rc_deref_mut() = true;
+          Subject::default()
+        })
+        .box_it(),
+      );
+      a.error("inner");
+    }
+    assert_eq!(error, Some("inner"));
+    assert!(!*subscribed.rc_deref());
+  }
+
+  #[test]
+  fn flat_map_concurrent_bounds() {
+    fn run(
+      subscribe: impl FnOnce(FakeClock, MutRc<Vec<(usize, usize)>>),
+    ) -> Vec<(usize, usize)> {
+      let values = MutRc::own(vec![]);
+      let clock = FakeClock::default();
+      subscribe(clock.clone(), values.clone());
+      clock.advance(Duration::from_millis(100));
+      let values = values.rc_deref().clone();
+      values
+    }
+    let project = |clock: FakeClock| {
+      move |i: usize| {
+        clock
+          .interval(Duration::from_millis(i as u64 + 1))
+          .take(3)
+          .map(move |j| (i, j))
+      }
+    };
+
+    let concat = run(|clock, values| {
+      observable::from_iter(0..4)
+        .concat_map(project(clock))
+        .subscribe(move |v| values.rc_deref_mut().push(v));
+    });
+    let one = run(|clock, values| {
+      observable::from_iter(0..4)
+        .flat_map_concurrent(project(clock), 1)
+        .subscribe(move |v| values.rc_deref_mut().push(v));
+    });
+    assert_eq!(concat, one);
+
+    let merged = run(|clock, values| {
+      observable::from_iter(0..4)
+        .flat_map(project(clock))
+        .subscribe(move |v| values.rc_deref_mut().push(v));
+    });
+    let unbounded = run(|clock, values| {
+      observable::from_iter(0..4)
+        .flat_map_concurrent(project(clock), usize::MAX)
+        .subscribe(move |v| values.rc_deref_mut().push(v));
+    });
+    assert_eq!(merged, unbounded);
+    assert_ne!(concat, merged);
+  }
+
+  #[test]
+  fn flat_map_concurrent_limit() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let clock = FakeClock::default();
+    let c_clock = clock.clone();
+
+    observable::from_iter(0..3)
+      .flat_map_concurrent(
+        move |i| {
+          c_clock
+            .interval(Duration::from_millis(1))
+            .take(2)
+            .map(move |j| (i, j))
+        },
+        2,
+      )
+      .subscribe(move |v| values.borrow_mut().push(v));
+    clock.advance(Duration::from_millis(10));
+
+    // the third inner observable is subscribed only after one of the first
+    // two completes.
+    let mut values = c_values.borrow().clone();
+    assert_eq!(values[4..], [(2, 0), (2, 1)]);
+    values[..4].sort();
+    assert_eq!(values[..4], [(0, 0), (0, 1), (1, 0), (1, 1)]);
+  }
+
+  #[test]
+  #[should_panic]
+  fn flat_map_concurrent_reject_zero() {
+    observable::from_iter(0..3).flat_map_concurrent(observable::of, 0);
+  }
+
+  #[test]
+  fn flat_map_concurrent_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut source = SubjectThreads::default();
+    let first = SubjectThreads::default();
+    let c_first = first.clone();
+
+    source
+      .clone()
+      .flat_map_concurrent_threads(
+        move |i: i32| c_first.clone().map(move |v: i32| v + i),
+        1,
+      )
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    source.next(10);
+    source.next(20);
+    let mut c_first = first.clone();
+    c_first.next(1);
+    c_first.clone().complete();
+    assert_eq!(&*values.lock().unwrap(), &[11]);
+  }
+
+  #[test]
+  fn concat_map_keep_order() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let mut source = Subject::default();
+    let mut first = Subject::default();
+    let c_first = first.clone();
+
+    source
+      .clone()
+      .concat_map(move |i| {
+        let inner: BoxOp<_, _> = if i == 0 {
+          c_first.clone().box_it()
+        } else {
+          observable::from_iter([i, i * 10]).box_it()
+        };
+        inner
+      })
+      .subscribe(move |v| values.borrow_mut().push(v));
+
+    source.next(0);
+    source.next(1);
+    source.next(2);
+    first.next(-1);
+    assert_eq!(&*c_values.borrow(), &[-1]);
+
+    first.complete();
+    assert_eq!(&*c_values.borrow(), &[-1, 1, 10, 2, 20]);
+  }
+
+  #[test]
+  fn concat_map_inner_error_drop_queue() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let error = Rc::new(RefCell::new(None));
+    let c_error = error.clone();
+    let mut source = Subject::default();
+    let first = Subject::<i32, _>::default();
+    let second = Subject::default();
+    let (c_first, c_second) = (first.clone(), second.clone());
+
+    source
+      .clone()
+      .concat_map(move |i| {
+        if i == 0 {
+          c_first.clone()
+        } else {
+          c_second.clone()
+        }
+      })
+      .on_error(move |e| *c_error.borrow_mut() = Some(e))
+      .subscribe(move |v| values.borrow_mut().push(v));
+
+    source.next(0);
+    source.next(1);
+    first.error("err");
+
+    assert!(second.is_empty());
+    assert!(c_values.borrow().is_empty());
+    assert_eq!(*error.borrow(), Some("err"));
+  }
 }
@@ -0,0 +1,81 @@
+use crate::prelude::*;
+use std::cmp::Ordering;
+
+/// Emits the item with the maximum or the minimum key once the source
+/// completes. Created by [`max_by_key`](ObservableExt::max_by_key) and
+/// [`min_by_key`](ObservableExt::min_by_key).
+#[derive(Clone)]
+pub struct MinMaxByKeyOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) key: F,
+  // The ordering of the kept key against a new one that keeps it.
+  pub(crate) keep: Ordering,
+}
+
+impl<Item, Err, O, S, F, K> Observable<Item, Err, O> for MinMaxByKeyOp<S, F>
+where
+  S: Observable<Item, Err, MinMaxByKeyObserver<O, F, Item, K>>,
+  O: Observer<Item, Err>,
+  F: Fn(&Item) -> K,
+  K: PartialOrd,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self.source.actual_subscribe(MinMaxByKeyObserver {
+      observer,
+      key: self.key,
+      keep: self.keep,
+      acc: None,
+    })
+  }
+}
+
+impl<Item, Err, S, F> ObservableExt<Item, Err> for MinMaxByKeyOp<S, F> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct MinMaxByKeyObserver<O, F, Item, K> {
+  observer: O,
+  key: F,
+  keep: Ordering,
+  // The key is stored with its item, so it's computed once per item.
+  acc: Option<(Item, K)>,
+}
+
+impl<Item, Err, O, F, K> Observer<Item, Err>
+  for MinMaxByKeyObserver<O, F, Item, K>
+where
+  O: Observer<Item, Err>,
+  F: Fn(&Item) -> K,
+  K: PartialOrd,
+{
+  fn next(&mut self, value: Item) {
+    let key = (self.key)(&value);
+    let keep = self
+      .acc
+      .as_ref()
+      .is_some_and(|(_, k)| k.partial_cmp(&key) == Some(self.keep));
+    if !keep {
+      self.acc = Some((value, key));
+    }
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    self.observer.error(err)
+  }
+
+  fn complete(mut self) {
+    if let Some((item, _)) = self.acc.take() {
+      self.observer.next(item);
+    }
+    self.observer.complete();
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
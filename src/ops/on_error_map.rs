@@ -19,7 +19,7 @@ impl<Item, Err, OutputErr, O, S, M> Observable<Item, OutputErr, O>
 where
   S: Observable<Item, Err, OnErrorMapObserver<O, M>>,
   O: Observer<Item, OutputErr>,
-  M: FnMut(Err) -> OutputErr,
+  M: FnOnce(Err) -> OutputErr,
 {
   type Unsub = S::Unsub;
 
@@ -34,7 +34,7 @@ impl<Item, Err, OutputErr, S, M> ObservableExt<Item, OutputErr>
   for OnErrorMapOp<S, M, Err>
 where
   S: ObservableExt<Item, Err>,
-  M: FnMut(Err) -> OutputErr,
+  M: FnOnce(Err) -> OutputErr,
 {
 }
 
@@ -46,7 +46,7 @@ pub struct OnErrorMapObserver<O, M> {
 
 impl<Item, Err, O, M, B> Observer<Item, Err> for OnErrorMapObserver<O, M>
 where
-  M: FnMut(Err) -> B,
+  M: FnOnce(Err) -> B,
   O: Observer<Item, B>,
 {
   #[inline]
@@ -55,7 +55,7 @@ where
   }
 
   #[inline]
-  fn error(mut self, err: Err) {
+  fn error(self, err: Err) {
     self.observer.error((self.map)(err))
   }
 
@@ -133,6 +133,90 @@ mod test {
     .subscribe(|_| {});
   }
 
+  #[derive(Debug, PartialEq, Clone)]
+  enum ParseError {
+    Empty,
+  }
+
+  #[derive(Debug, PartialEq, Clone)]
+  enum NetError {
+    Timeout,
+  }
+
+  #[derive(Debug, PartialEq, Clone)]
+  enum AppError {
+    Parse(ParseError),
+    Net(NetError),
+  }
+
+  #[test]
+  fn map_err_unify_merged_errors() {
+    let mut values = vec![];
+    let mut error = None;
+    {
+      let mut parsed = Subject::<i32, ParseError>::default();
+      let fetched = Subject::<i32, NetError>::default();
+      parsed
+        .clone()
+        .map_err(AppError::Parse)
+        .merge(fetched.clone().map_err(AppError::Net))
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      parsed.next(1);
+      fetched.error(NetError::Timeout);
+      parsed.next(2);
+    }
+
+    assert_eq!(values, [1]);
+    assert_eq!(error, Some(AppError::Net(NetError::Timeout)));
+  }
+
+  #[test]
+  fn map_err_resume_next() {
+    let mut resumed = false;
+    observable::throw(ParseError::Empty)
+      .map_err(AppError::Parse)
+      .on_error_resume_next(|e| {
+        assert_eq!(e, AppError::Parse(ParseError::Empty));
+        observable::of(()).map_err(|e| match e {})
+      })
+      .on_error(|_: AppError| {})
+      .subscribe(|_| resumed = true);
+
+    assert!(resumed);
+  }
+
+  #[test]
+  fn map_err_once_closure() {
+    let context = String::from("while loading");
+    let mut error = None;
+    observable::throw(NetError::Timeout)
+      .map_err(move |e| format!("{e:?} {context}"))
+      .on_error(|e| error = Some(e))
+      .subscribe(|_: ()| {});
+
+    assert_eq!(error.as_deref(), Some("Timeout while loading"));
+  }
+
+  #[test]
+  fn map_err_threads() {
+    let error = std::sync::Arc::new(std::sync::Mutex::new(None));
+    let c_error = error.clone();
+    observable::throw(ParseError::Empty)
+      .map_err(AppError::Parse)
+      .merge_threads(
+        observable::throw(NetError::Timeout).map_err(AppError::Net),
+      )
+      .on_error(move |e| *c_error.lock().unwrap() = Some(e))
+      .subscribe(|_: ()| {});
+
+    assert_eq!(
+      *error.lock().unwrap(),
+      Some(AppError::Parse(ParseError::Empty))
+    );
+  }
+
   #[test]
   fn benchmark() {
     do_bench();
@@ -0,0 +1,196 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDerefMut},
+};
+
+#[derive(Clone)]
+pub struct OnErrorResumeNextOp<S, F, Err> {
+  source: S,
+  selector: F,
+  _hint: TypeHint<Err>,
+}
+
+#[derive(Clone)]
+pub struct OnErrorResumeNextOpThreads<S, F, Err> {
+  source: S,
+  selector: F,
+  _hint: TypeHint<Err>,
+}
+
+macro_rules! impl_on_error_resume_next_op {
+  ($name: ident, $rc: ident) => {
+    impl<S, F, Err> $name<S, F, Err> {
+      #[inline]
+      pub(crate) fn new(source: S, selector: F) -> Self {
+        Self { source, selector, _hint: TypeHint::new() }
+      }
+    }
+
+    impl<Item, Err, OutputErr, O, S, F, R> Observable<Item, OutputErr, O>
+      for $name<S, F, Err>
+    where
+      O: Observer<Item, OutputErr>,
+      F: FnOnce(Err) -> R,
+      R: Observable<Item, OutputErr, O>,
+      S: Observable<
+        Item,
+        Err,
+        OnErrorResumeNextObserver<O, F, $rc<Option<R::Unsub>>, OutputErr>,
+      >,
+    {
+      type Unsub = ZipSubscription<S::Unsub, $rc<Option<R::Unsub>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        // The fallback is subscribed only when the source errors, so its
+        // subscription is shared with the downstream subscription.
+        let fallback_unsub = $rc::own(None);
+        let source_unsub =
+          self.source.actual_subscribe(OnErrorResumeNextObserver {
+            observer,
+            selector: self.selector,
+            fallback_unsub: fallback_unsub.clone(),
+            _hint: TypeHint::new(),
+          });
+        ZipSubscription::new(source_unsub, fallback_unsub)
+      }
+    }
+
+    impl<Item, Err, OutputErr, S, F, R> ObservableExt<Item, OutputErr>
+      for $name<S, F, Err>
+    where
+      S: ObservableExt<Item, Err>,
+      F: FnOnce(Err) -> R,
+      R: ObservableExt<Item, OutputErr>,
+    {
+    }
+  };
+}
+
+impl_on_error_resume_next_op!(OnErrorResumeNextOp, MutRc);
+impl_on_error_resume_next_op!(OnErrorResumeNextOpThreads, MutArc);
+
+pub struct OnErrorResumeNextObserver<O, F, U, OutputErr> {
+  observer: O,
+  selector: F,
+  fallback_unsub: U,
+  _hint: TypeHint<OutputErr>,
+}
+
+impl<Item, Err, OutputErr, O, F, R, U> Observer<Item, Err>
+  for OnErrorResumeNextObserver<O, F, U, OutputErr>
+where
+  O: Observer<Item, OutputErr>,
+  F: FnOnce(Err) -> R,
+  R: Observable<Item, OutputErr, O>,
+  U: RcDerefMut<Target = Option<R::Unsub>>,
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    self.observer.next(value);
+  }
+
+  fn error(self, err: Err) {
+    let unsub = (self.selector)(err).actual_subscribe(self.observer);
+    *self.fallback_unsub.rc_deref_mut() = Some(unsub);
+  }
+
+  #[inline]
+  fn complete(self) {
+    self.observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn resume_with_fallback() {
+    let mut values = vec![];
+    let mut completed = false;
+    {
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .on_error_resume_next(|e: &str| observable::of(e.len()))
+        .on_complete(|| completed = true)
+        .subscribe(|v| values.push(v));
+
+      subject.next(1);
+      subject.error("fail");
+    }
+
+    assert_eq!(values, vec![1, 4]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn fallback_error_propagate() {
+    let mut values = vec![];
+    let mut error = None;
+
+    observable::of_result(Err("source"))
+      .on_error_resume_next(|e| observable::of_result(Err::<i32, _>(e.len())))
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| values.push(v));
+
+    assert!(values.is_empty());
+    assert_eq!(error, Some(6));
+  }
+
+  #[test]
+  fn unsubscribe_fallback() {
+    let fallback = Subject::<i32, ()>::default();
+    let c_fallback = fallback.clone();
+    let source = Subject::<i32, &str>::default();
+
+    let subscription = source
+      .clone()
+      .on_error_resume_next(move |_| c_fallback)
+      .on_error(|_| {})
+      .subscribe(|_| {});
+    source.error("fail");
+    assert!(!fallback.is_empty());
+
+    subscription.unsubscribe();
+    assert!(fallback.is_empty());
+  }
+
+  #[test]
+  fn catch_error_return() {
+    let mut values = vec![];
+    let mut completed = false;
+    {
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .catch_error_return(0)
+        .on_complete(|| completed = true)
+        .subscribe(|v| values.push(v));
+
+      subject.next(1);
+      subject.error("fail");
+    }
+
+    assert_eq!(values, vec![1, 0]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn on_error_resume_next_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    observable::of_result(Err(3))
+      .on_error_resume_next_threads(|e| observable::from_iter(0..e))
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[0, 1, 2]);
+  }
+}
@@ -16,7 +16,7 @@ where
   fn actual_subscribe(self, observer: O) -> Self::Unsub {
     self
       .source
-      .actual_subscribe(PairwiseObserver { observer, pair: (None, None) })
+      .actual_subscribe(PairwiseObserver { observer, prev: None })
   }
 }
 
@@ -28,7 +28,7 @@ impl<Item, Err, S> ObservableExt<(Item, Item), Err> for PairwiseOp<S> where
 #[derive(Clone)]
 pub struct PairwiseObserver<O, Item> {
   observer: O,
-  pair: (Option<Item>, Option<Item>),
+  prev: Option<Item>,
 }
 
 impl<O, Item, Err> Observer<Item, Err> for PairwiseObserver<O, Item>
@@ -37,11 +37,8 @@ where
   Item: Clone,
 {
   fn next(&mut self, value: Item) {
-    let (_x, y) = std::mem::take(&mut self.pair);
-    self.pair = (y, Some(value));
-
-    if let (Some(a), Some(b)) = &self.pair {
-      self.observer.next((a.clone(), b.clone()));
+    if let Some(prev) = self.prev.replace(value.clone()) {
+      self.observer.next((prev, value));
     }
   }
 
@@ -85,4 +82,38 @@ mod tests {
 
     assert_eq!(expected, actual);
   }
+
+  #[test]
+  fn less_than_two_items() {
+    let mut actual = vec![];
+    let mut completed = false;
+    observable::of(1)
+      .pairwise()
+      .on_complete(|| completed = true)
+      .subscribe(|pair| actual.push(pair));
+
+    assert!(actual.is_empty());
+    assert!(completed);
+  }
+
+  #[test]
+  fn error_pass_through() {
+    let mut actual = vec![];
+    let mut error = None;
+    {
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .pairwise()
+        .on_error(|e| error = Some(e))
+        .subscribe(|pair: (i32, i32)| actual.push(pair));
+
+      subject.next(1);
+      subject.next(2);
+      subject.error("err");
+    }
+
+    assert_eq!(actual, vec![(1, 2)]);
+    assert_eq!(error, Some("err"));
+  }
 }
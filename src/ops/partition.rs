@@ -0,0 +1,159 @@
+use crate::prelude::*;
+
+/// One half of a [`partition`](ObservableExt::partition), it emits the source
+/// items for which the predicate returns `expected`.
+#[derive(Clone)]
+pub struct PartitionOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) predicate: F,
+  pub(crate) expected: bool,
+}
+
+impl<Item, Err, O, S, F> Observable<Item, Err, O> for PartitionOp<S, F>
+where
+  S: Observable<Item, Err, PartitionObserver<O, F>>,
+  O: Observer<Item, Err>,
+  F: Fn(&Item) -> bool,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self.source.actual_subscribe(PartitionObserver {
+      observer,
+      predicate: self.predicate,
+      expected: self.expected,
+    })
+  }
+}
+
+impl<Item, Err, S, F> ObservableExt<Item, Err> for PartitionOp<S, F> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct PartitionObserver<O, F> {
+  observer: O,
+  predicate: F,
+  expected: bool,
+}
+
+impl<Item, Err, O, F> Observer<Item, Err> for PartitionObserver<O, F>
+where
+  O: Observer<Item, Err>,
+  F: Fn(&Item) -> bool,
+{
+  fn next(&mut self, value: Item) {
+    if (self.predicate)(&value) == self.expected {
+      self.observer.next(value)
+    }
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    self.observer.error(err)
+  }
+
+  #[inline]
+  fn complete(self) {
+    self.observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn split_by_predicate() {
+    let evens = MutRc::own(vec![]);
+    let odds = MutRc::own(vec![]);
+    let completed = MutRc::own(0);
+    let mut source = Subject::default();
+
+    let (even, odd) = source.clone().partition(|v: &i32| v % 2 == 0);
+    let (c_evens, c_completed) = (evens.clone(), completed.clone());
+    even
+      .on_complete(move || *c_completed.rc_deref_mut() += 1)
+      .subscribe(move |v| c_evens.rc_deref_mut().push(v));
+    let (c_odds, c_completed) = (odds.clone(), completed.clone());
+    odd
+      .on_complete(move || *c_completed.rc_deref_mut() += 1)
+      .subscribe(move |v| c_odds.rc_deref_mut().push(v));
+
+    (0..6).for_each(|v| source.next(v));
+    source.complete();
+
+    assert_eq!(&*evens.rc_deref(), &[0, 2, 4]);
+    assert_eq!(&*odds.rc_deref(), &[1, 3, 5]);
+    assert_eq!(*completed.rc_deref(), 2);
+  }
+
+  #[test]
+  fn subscribe_source_once() {
+    let subscribed = MutRc::own(0);
+    let finalized = MutRc::own(false);
+    let (c_subscribed, c_finalized) = (subscribed.clone(), finalized.clone());
+    let source = Subject::<i32, _>::default();
+    let c_source = source.clone();
+
+    let (even, odd) = observable::defer(move || {
+      *c_subscribed.rc_deref_mut() += 1;
+      c_source.clone()
+    })
+    .finalize(move || *c_finalized.rc_deref_mut() = true)
+    .partition(|v| v % 2 == 0);
+    let even = even.subscribe(|_| {});
+    let odd = odd.subscribe(|_| {});
+    assert_eq!(*subscribed.rc_deref(), 1);
+
+    even.unsubscribe();
+    assert!(!*finalized.rc_deref());
+    odd.unsubscribe();
+    assert!(*finalized.rc_deref());
+  }
+
+  #[test]
+  fn resubscribe_completed_source() {
+    let subscribed = MutRc::own(0);
+    let evens = MutRc::own(vec![]);
+    let odds = MutRc::own(vec![]);
+    let c_subscribed = subscribed.clone();
+
+    let (even, odd) = observable::defer(move || {
+      *c_subscribed.rc_deref_mut() += 1;
+      observable::from_iter(0..6)
+    })
+    .partition(|v| v % 2 == 0);
+    let c_evens = evens.clone();
+    even.subscribe(move |v| c_evens.rc_deref_mut().push(v));
+    let c_odds = odds.clone();
+    odd.subscribe(move |v| c_odds.rc_deref_mut().push(v));
+
+    assert_eq!(*subscribed.rc_deref(), 2);
+    assert_eq!(&*evens.rc_deref(), &[0, 2, 4]);
+    assert_eq!(&*odds.rc_deref(), &[1, 3, 5]);
+  }
+
+  #[test]
+  fn partition_threads() {
+    let evens = Arc::new(Mutex::new(vec![]));
+    let c_evens = evens.clone();
+    let mut source = SubjectThreads::default();
+
+    let (even, odd) = source.clone().partition_threads(|v: &i32| v % 2 == 0);
+    even.subscribe(move |v| c_evens.lock().unwrap().push(v));
+    odd.subscribe(|_| {});
+
+    (0..4).for_each(|v| source.next(v));
+    assert_eq!(&*evens.lock().unwrap(), &[0, 2]);
+  }
+}
@@ -0,0 +1,184 @@
+use crate::{
+  observable::amb::{add_subscription, AmbObserver, AmbState, AmbSubscription},
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref},
+};
+
+/// An observable that mirrors whichever of two observables signals first.
+///
+/// This struct is created by the race method on
+/// [ObservableExt](ObservableExt::race). See its documentation for more.
+#[derive(Clone)]
+pub struct RaceOp<'a, A, B> {
+  a: A,
+  b: B,
+  _hint: TypeHint<&'a ()>,
+}
+
+#[derive(Clone)]
+pub struct RaceOpThreads<A, B> {
+  a: A,
+  b: B,
+}
+
+impl<'a, A, B> RaceOp<'a, A, B> {
+  #[inline]
+  pub fn new(a: A, b: B) -> Self {
+    RaceOp { a, b, _hint: TypeHint::new() }
+  }
+}
+
+impl<A, B> RaceOpThreads<A, B> {
+  #[inline]
+  pub fn new(a: A, b: B) -> Self {
+    RaceOpThreads { a, b }
+  }
+}
+
+macro_rules! impl_race_op {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, A, B> Observable<Item, Err, O>
+      for $name<$($lf,)? A, B>
+    where
+      O: Observer<Item, Err>,
+      A: Observable<Item, Err, AmbObserver<$rc<AmbState<O, $box_unsub>>>>,
+      B: Observable<Item, Err, AmbObserver<$rc<AmbState<O, $box_unsub>>>>,
+      A::Unsub: $($lf)? $($send + 'static)?,
+      B::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = AmbSubscription<$rc<AmbState<O, $box_unsub>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(AmbState::new(observer));
+        let unsub = self
+          .a
+          .actual_subscribe(AmbObserver { state: state.clone(), index: 0 });
+        add_subscription(&state, 0, <$box_unsub>::new(unsub));
+        if !state.rc_deref().is_decided() {
+          let unsub = self
+            .b
+            .actual_subscribe(AmbObserver { state: state.clone(), index: 1 });
+          add_subscription(&state, 1, <$box_unsub>::new(unsub));
+        }
+        AmbSubscription(state)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, A, B> ObservableExt<Item, Err>
+      for $name<$($lf,)? A, B>
+    where
+      A: ObservableExt<Item, Err>,
+      B: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_race_op!(RaceOp, MutRc, BoxSubscription<'a>, 'a);
+impl_race_op!(RaceOpThreads, MutArc, BoxSubscriptionThreads, Send);
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn mirror_the_first_emitter() {
+    let mut values = vec![];
+    {
+      let mut a = Subject::default();
+      let mut b = Subject::default();
+      a.clone().race(b.clone()).subscribe(|v: i32| values.push(v));
+
+      b.next(1);
+      a.next(2);
+      b.next(3);
+    }
+    assert_eq!(values, vec![1, 3]);
+  }
+
+  #[test]
+  fn unsubscribe_loser() {
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let mut a = Subject::<i32, _>::default();
+    let b = Subject::default();
+
+    a.clone()
+      .race(
+        b.clone()
+          .finalize(move || *c_finalized.rc_deref_mut() = true),
+      )
+      .subscribe(|_| {});
+    assert!(!*finalized.rc_deref());
+
+    a.next(1);
+    assert!(*finalized.rc_deref());
+  }
+
+  #[test]
+  fn error_wins() {
+    let mut error = None;
+    let mut values = vec![];
+    {
+      let mut a = Subject::default();
+      let b = Subject::default();
+      a.clone()
+        .race(b.clone())
+        .on_error(|e| error = Some(e))
+        .subscribe(|v: i32| values.push(v));
+
+      b.error("b");
+      a.next(1);
+    }
+    assert_eq!(error, Some("b"));
+    assert!(values.is_empty());
+  }
+
+  #[test]
+  fn synchronous_source_never_subscribe_other() {
+    let mut values = vec![];
+    {
+      let b = Subject::<i32, _>::default();
+      observable::of(1)
+        .race(b.clone())
+        .subscribe(|v| values.push(v));
+      assert!(b.is_empty());
+    }
+    assert_eq!(values, vec![1]);
+  }
+
+  #[test]
+  fn race_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let a = SubjectThreads::default();
+    let b = SubjectThreads::default();
+
+    a.clone()
+      .race_threads(b.clone())
+      .subscribe(move |v: i32| c_values.lock().unwrap().push(v));
+
+    let handles: Vec<_> = [a, b]
+      .into_iter()
+      .enumerate()
+      .map(|(i, mut s)| {
+        std::thread::spawn(move || {
+          (0..100).for_each(|v| s.next(i as i32 + v * 2))
+        })
+      })
+      .collect();
+    handles.into_iter().for_each(|h| h.join().unwrap());
+
+    let values = values.lock().unwrap();
+    assert_eq!(values.len(), 100);
+    let winner = values[0] % 2;
+    assert!(values.iter().all(|v| v % 2 == winner));
+  }
+}
@@ -15,117 +15,184 @@ use crate::{
   prelude::*,
   rc::{MutArc, MutRc, RcDerefMut},
 };
+use std::time::Instant;
 
-pub struct ShareOp<'a, Item, Err, Source>(
-  MutRc<InnerShareOp<Source, Subject<'a, Item, Err>>>,
+/// The observable created by `share`, an alias of `publish().ref_count()`.
+pub type ShareOp<'a, Item, Err, Source> =
+  RefCountOp<'a, Source, Subject<'a, Item, Err>>;
+
+pub type ShareOpThreads<Item, Err, Source> =
+  RefCountOpThreads<Source, SubjectThreads<Item, Err>>;
+
+/// The observable created by `share_replay`, it replays the last items and
+/// the termination of the source to the late subscribers.
+pub type ShareReplayOp<'a, Item, Err, Source, C = fn() -> Instant> =
+  RefCountOp<'a, Source, ReplaySubject<'a, Item, Err, C>>;
+
+pub type ShareReplayOpThreads<Item, Err, Source, C = fn() -> Instant> =
+  RefCountOpThreads<Source, ReplaySubjectThreads<Item, Err, C>>;
+
+pub struct RefCountOp<'a, Source, Subject>(
+  MutRc<InnerRefCount<Source, Subject, LocalConnection<'a>>>,
 );
 
-pub struct ShareOpThreads<Item, Err, Source>(
-  MutArc<InnerShareOp<Source, SubjectThreads<Item, Err>>>,
+pub struct RefCountOpThreads<Source, Subject>(
+  MutArc<InnerRefCount<Source, Subject, ThreadsConnection>>,
 );
 
-enum InnerShareOp<Source, Subject> {
-  Connectable(ConnectableObservable<Source, Subject>),
-  Connected(Subject),
+type LocalConnection<'a> = MutRc<Option<BoxSubscription<'a>>>;
+type ThreadsConnection = MutArc<Option<BoxSubscriptionThreads>>;
+
+struct InnerRefCount<Source, Subject, Connection> {
+  // Its subject is replaced by a renewed one once it's closed by the source
+  // terminating or by the last subscriber unsubscribing.
+  connectable: ConnectableObservable<Source, Subject>,
+  connection: Option<Connection>,
+}
+
+/// Creates an empty subject configured like this one, `ref_count` subscribes
+/// a renewed subject to the source when it connects again.
+pub trait Renew {
+  fn renew(&self) -> Self;
+}
+
+impl<'a, Item, Err> Renew for Subject<'a, Item, Err> {
+  #[inline]
+  fn renew(&self) -> Self {
+    Self::default()
+  }
+}
+
+impl<Item, Err> Renew for SubjectThreads<Item, Err> {
+  #[inline]
+  fn renew(&self) -> Self {
+    Self::default()
+  }
+}
+
+impl<'a, Item, Err, C> Renew for ReplaySubject<'a, Item, Err, C>
+where
+  C: Fn() -> Instant + Clone,
+{
+  #[inline]
+  fn renew(&self) -> Self {
+    self.empty_copy()
+  }
+}
+
+impl<Item, Err, C> Renew for ReplaySubjectThreads<Item, Err, C>
+where
+  C: Fn() -> Instant + Clone,
+{
+  #[inline]
+  fn renew(&self) -> Self {
+    self.empty_copy()
+  }
 }
 
-macro_rules! impl_trivial {
-  ($name: ident, $rc: ident $(,$lf: lifetime)?) => {
-    impl<$($lf,)? Item, Err, S> Clone for $name<$($lf,)? Item, Err, S> {
+macro_rules! impl_ref_count_op {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? S, Subject> Clone for $name<$($lf,)? S, Subject> {
       fn clone(&self) -> Self {
         Self(self.0.clone())
       }
     }
 
-    impl<$($lf,)? Item, Err, S> $name<$($lf,)? Item, Err, S> {
+    impl<$($lf,)? S, Subject> $name<$($lf,)? S, Subject> {
       #[inline]
-      pub fn new(source: S) -> Self {
-        let inner = InnerShareOp::Connectable(ConnectableObservable::new(source));
-        $name($rc::own(inner))
+      pub fn new(connectable: ConnectableObservable<S, Subject>) -> Self {
+        $name($rc::own(InnerRefCount { connectable, connection: None }))
       }
     }
-  };
-}
-
-impl_trivial!(ShareOp, MutRc, 'a);
-impl_trivial!(ShareOpThreads, MutArc);
 
-macro_rules! impl_observable_methods {
-  ($subject: ty) => {
-    type Unsub = RefCountSubscription<
-      $subject,
-      <$subject as Observable<Item, Err, O>>::Unsub,
-    >;
-
-    fn actual_subscribe(self, observer: O) -> Self::Unsub {
-      let mut inner = self.0.rc_deref_mut();
-      match &mut *inner {
-        InnerShareOp::Connectable(c) => {
-          let subject = c.fork();
-
-          let subscription = subject.clone().actual_subscribe(observer);
-          let connected = InnerShareOp::Connected(subject.clone());
-          let connectable = std::mem::replace(&mut *inner, connected);
+    impl<$($lf,)? Item, Err, O, S, Subject> Observable<Item, Err, O>
+      for $name<$($lf,)? S, Subject>
+    where
+      O: Observer<Item, Err>,
+      S: Observable<Item, Err, Subject> + Clone,
+      S::Unsub: $($lf)? $($send + 'static)?,
+      Subject: Observable<Item, Err, O>
+        + Observer<Item, Err>
+        + Subscription
+        + SubjectSize
+        + Renew
+        + Clone
+        $(+ $lf)? $(+ $send + 'static)?,
+    {
+      type Unsub =
+        RefCountSubscription<Subject, Subject::Unsub, $rc<Option<$box_unsub>>>;
 
-          match connectable {
-            InnerShareOp::Connectable(connectable) => connectable.connect(),
-            InnerShareOp::Connected { .. } => unreachable!(),
-          };
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let (connectable, connection, connect) = {
+          let mut inner = self.0.rc_deref_mut();
+          let inner = &mut *inner;
+          match &inner.connection {
+            Some(connection) if !inner.connectable.subject.is_closed() => {
+              (inner.connectable.clone(), connection.clone(), false)
+            }
+            _ => {
+              if inner.connection.is_some() {
+                let subject = &mut inner.connectable.subject;
+                *subject = subject.renew();
+              }
+              let connection = $rc::own(None);
+              inner.connection = Some(connection.clone());
+              (inner.connectable.clone(), connection, true)
+            }
+          }
+        };
 
-          RefCountSubscription { subject, subscription }
-        }
-        InnerShareOp::Connected(subject) => {
-          let subscription = subject.clone().actual_subscribe(observer);
-          RefCountSubscription { subject: subject.clone(), subscription }
+        let subject = connectable.fork();
+        let subscription = subject.clone().actual_subscribe(observer);
+        // Connect after the borrow is released, the source may emit right away
+        // and its subscribers may subscribe again.
+        if connect {
+          let unsub = connectable.connect();
+          if subject.is_closed() {
+            unsub.unsubscribe();
+          } else {
+            *connection.rc_deref_mut() = Some(<$box_unsub>::new(unsub));
+          }
         }
+
+        RefCountSubscription { subject, subscription, connection }
       }
     }
-  };
-}
 
-impl<'a, S, Item, Err, O> Observable<Item, Err, O> for ShareOp<'a, Item, Err, S>
-where
-  Item: Clone,
-  Err: Clone,
-  O: Observer<Item, Err> + 'a,
-  S: Observable<Item, Err, Subject<'a, Item, Err>>,
-{
-  impl_observable_methods!(Subject<'a, Item, Err>);
+    impl<$($lf,)? Item, Err, S, Subject> ObservableExt<Item, Err>
+      for $name<$($lf,)? S, Subject>
+    where
+      Subject: ObservableExt<Item, Err>,
+    {
+    }
+  };
 }
 
-impl<'a, S, Item, Err> ObservableExt<Item, Err> for ShareOp<'a, Item, Err, S> where
-  S: ObservableExt<Item, Err>
-{
-}
+impl_ref_count_op!(RefCountOp, MutRc, BoxSubscription<'a>, 'a);
+impl_ref_count_op!(RefCountOpThreads, MutArc, BoxSubscriptionThreads, Send);
 
-impl<S, Item, Err, O> Observable<Item, Err, O> for ShareOpThreads<Item, Err, S>
-where
-  Item: Clone,
-  Err: Clone,
-  O: Observer<Item, Err> + Send + 'static,
-  S: Observable<Item, Err, SubjectThreads<Item, Err>>,
-{
-  impl_observable_methods!(SubjectThreads< Item, Err>);
-}
-
-impl<S, Item, Err> ObservableExt<Item, Err> for ShareOpThreads<Item, Err, S> where
-  S: ObservableExt<Item, Err>
-{
-}
-pub struct RefCountSubscription<Subject, U> {
+/// The subscription of a shared observable, the source is unsubscribed once
+/// the last subscriber unsubscribes.
+pub struct RefCountSubscription<Subject, U, C> {
   subject: Subject,
   subscription: U,
+  connection: C,
 }
 
-impl<U, Subject> Subscription for RefCountSubscription<Subject, U>
+impl<U, Subject, C> Subscription for RefCountSubscription<Subject, U, C>
 where
   Subject: Subscription + SubjectSize,
   U: Subscription,
+  C: Subscription,
 {
   fn unsubscribe(self) {
     self.subscription.unsubscribe();
     if self.subject.is_empty() {
-      self.subject.unsubscribe()
+      self.subject.unsubscribe();
+      self.connection.unsubscribe();
     }
   }
 
@@ -137,7 +204,14 @@ where
 
 #[cfg(test)]
 mod test {
-  use crate::prelude::*;
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+  };
 
   #[test]
   fn smoke() {
@@ -150,7 +224,8 @@ mod test {
     }
 
     assert_eq!(accept1, 1);
-    assert_eq!(accept2, 0);
+    // The source completed before, the second subscriber reconnects it.
+    assert_eq!(accept2, 1);
   }
 
   #[test]
@@ -172,6 +247,189 @@ mod test {
     assert_eq!(accept2, 1);
   }
 
+  #[test]
+  fn unsubscribe_source_after_last_subscriber() {
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let ref_count = Subject::<i32, _>::default()
+      .finalize(move || *c_finalized.rc_deref_mut() = true)
+      .share();
+    let s1 = ref_count.clone().subscribe(|_| {});
+    let s2 = ref_count.subscribe(|_| {});
+
+    s1.unsubscribe();
+    assert!(!*finalized.rc_deref());
+    s2.unsubscribe();
+    assert!(*finalized.rc_deref());
+  }
+
+  #[test]
+  fn reconnect_after_last_unsubscribe() {
+    let subscribed = MutRc::own(0);
+    let c_subscribed = subscribed.clone();
+    let subject = Subject::<i32, ()>::default();
+    let c_subject = subject.clone();
+    let shared = observable::defer(move || {
+      *c_subscribed.rc_deref_mut() += 1;
+      c_subject.clone()
+    })
+    .share();
+
+    let s1 = shared.clone().on_error(|_| {}).subscribe(|_| {});
+    let s2 = shared.clone().on_error(|_| {}).subscribe(|_| {});
+    assert_eq!(*subscribed.rc_deref(), 1);
+    s1.unsubscribe();
+    s2.unsubscribe();
+    assert!(subject.is_empty());
+
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    shared
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    assert_eq!(*subscribed.rc_deref(), 2);
+    subject.clone().next(1);
+    assert_eq!(&*values.rc_deref(), &[1]);
+  }
+
+  #[test]
+  fn single_execution_for_concurrent_subscribers() {
+    let subscribed = MutRc::own(0);
+    let c_subscribed = subscribed.clone();
+    let values = MutRc::own(vec![]);
+    let (c_values1, c_values2) = (values.clone(), values.clone());
+    let mut subject = Subject::<i32, ()>::default();
+    let c_subject = subject.clone();
+    let shared = observable::defer(move || {
+      *c_subscribed.rc_deref_mut() += 1;
+      c_subject.clone().map(|v| v * 10)
+    })
+    .share();
+
+    shared
+      .clone()
+      .on_error(|_| {})
+      .subscribe(move |v| c_values1.rc_deref_mut().push(v));
+    shared
+      .on_error(|_| {})
+      .subscribe(move |v| c_values2.rc_deref_mut().push(v));
+    subject.next(1);
+
+    assert_eq!(*subscribed.rc_deref(), 1);
+    assert_eq!(&*values.rc_deref(), &[10, 10]);
+  }
+
+  #[test]
+  fn share_replay_after_complete() {
+    let subscribed = MutRc::own(0);
+    let c_subscribed = subscribed.clone();
+    let shared = observable::defer(move || {
+      *c_subscribed.rc_deref_mut() += 1;
+      observable::from_iter(0..5)
+    })
+    .share_replay(3);
+    shared.clone().subscribe(|_| {});
+
+    let mut values = vec![];
+    let mut completed = false;
+    shared
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![2, 3, 4]);
+    assert!(completed);
+    assert_eq!(*subscribed.rc_deref(), 1);
+  }
+
+  #[test]
+  fn share_replay_error() {
+    let mut error = None;
+    let shared = observable::throw("fail").share_replay(1);
+    shared.clone().on_error(|_| {}).subscribe(|_| {});
+    shared.on_error(|e| error = Some(e)).subscribe(|_| {});
+
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn share_replay_reconnect() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut subject = Subject::<i32, ()>::default();
+    let shared = subject.clone().share_replay(1);
+
+    let s = shared.clone().on_error(|_| {}).subscribe(|_| {});
+    subject.next(1);
+    s.unsubscribe();
+    assert!(subject.is_empty());
+
+    // The new connection replays nothing from the previous one.
+    shared
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    assert!(values.rc_deref().is_empty());
+    subject.next(2);
+    assert_eq!(&*values.rc_deref(), &[2]);
+  }
+
+  #[test]
+  fn share_replay_within() {
+    use crate::observable::fake_timer::FakeClock;
+
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let clock = FakeClock::default();
+    let c_clock = clock.clone();
+    let mut subject = Subject::<i32, ()>::default();
+    let shared = subject.clone().share_replay_within(
+      10,
+      Duration::from_millis(20),
+      move || c_clock.current_time(),
+    );
+    let _guard = shared.clone().on_error(|_| {}).subscribe(|_| {});
+
+    subject.next(1);
+    clock.advance(Duration::from_millis(40));
+    subject.next(2);
+
+    shared
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    assert_eq!(&*values.rc_deref(), &[2]);
+  }
+
+  #[test]
+  fn share_replay_within_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut subject = SubjectThreads::<i32, ()>::default();
+    let shared = subject.clone().share_replay_within_threads(
+      10,
+      Duration::from_millis(20),
+      Instant::now,
+    );
+    let _guard = shared.clone().on_error(|_| {}).subscribe(|_| {});
+
+    subject.next(1);
+    subject.next(2);
+
+    shared
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+    assert_eq!(&*values.lock().unwrap(), &[1, 2]);
+  }
+
+  #[test]
+  fn share_replay_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let shared = observable::from_iter(0..3).share_replay_threads(2);
+    shared.clone().subscribe(|_| {});
+    shared.subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[1, 2]);
+  }
+
   #[test]
   fn bench() {
     do_bench();
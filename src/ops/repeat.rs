@@ -0,0 +1,351 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+/// An observable that resubscribes to the source when it completes.
+///
+/// This struct is created by the repeat method on
+/// [ObservableExt](ObservableExt::repeat). See its documentation for more.
+#[derive(Clone)]
+pub struct RepeatOp<'a, S> {
+  source: S,
+  count: usize,
+  _hint: TypeHint<&'a ()>,
+}
+
+#[derive(Clone)]
+pub struct RepeatOpThreads<S> {
+  source: S,
+  count: usize,
+}
+
+impl<'a, S> RepeatOp<'a, S> {
+  #[inline]
+  pub(crate) fn new(source: S, count: usize) -> Self {
+    Self { source, count, _hint: TypeHint::new() }
+  }
+}
+
+impl<S> RepeatOpThreads<S> {
+  #[inline]
+  pub(crate) fn new(source: S, count: usize) -> Self {
+    Self { source, count }
+  }
+}
+
+/// The subscription of the live round, replaced by every resubscription.
+pub struct RepeatState<O, U> {
+  unsub: Option<U>,
+  // Set while a round is being subscribed, a round completing meanwhile
+  // leaves the observer and the remaining rounds in `pending` for the loop of
+  // the running subscribe, so a synchronous source doesn't recurse.
+  subscribing: bool,
+  pending: Option<(O, usize)>,
+  closed: bool,
+}
+
+pub struct RepeatObserver<S, O, R> {
+  source: S,
+  observer: O,
+  remaining: usize,
+  state: R,
+  // Subscribes the next round, a function pointer avoids requiring the
+  // source to accept this observer in the `Observer` implementation.
+  resubscribe: fn(Self),
+}
+
+pub struct RepeatSubscription<R>(R);
+
+macro_rules! impl_repeat_op {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S> Observable<Item, Err, O>
+      for $name<$($lf,)? S>
+    where
+      O: Observer<Item, Err>,
+      S: Clone
+        + Observable<
+          Item,
+          Err,
+          RepeatObserver<S, O, $rc<RepeatState<O, $box_unsub>>>,
+        >,
+      S::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = RepeatSubscription<$rc<RepeatState<O, $box_unsub>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(RepeatState {
+          unsub: None,
+          subscribing: false,
+          pending: None,
+          closed: false,
+        });
+        if self.count == 0 {
+          observer.complete();
+        } else {
+          RepeatObserver {
+            source: self.source,
+            observer,
+            remaining: self.count,
+            state: state.clone(),
+            resubscribe: RepeatObserver::<
+              S,
+              O,
+              $rc<RepeatState<O, $box_unsub>>,
+            >::subscribe,
+          }
+          .subscribe();
+        }
+        RepeatSubscription(state)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S> ObservableExt<Item, Err> for $name<$($lf,)? S>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+
+    impl<$($lf,)? S, O> RepeatObserver<S, O, $rc<RepeatState<O, $box_unsub>>> {
+      fn subscribe<Item, Err>(self)
+      where
+        O: Observer<Item, Err>,
+        S: Clone + Observable<Item, Err, Self>,
+        S::Unsub: $($lf)? $($send + 'static)?,
+      {
+        let Self { source, mut observer, mut remaining, state, resubscribe } =
+          self;
+        loop {
+          state.rc_deref_mut().subscribing = true;
+          let unsub = source.clone().actual_subscribe(RepeatObserver {
+            source: source.clone(),
+            observer,
+            remaining,
+            state: state.clone(),
+            resubscribe,
+          });
+
+          let mut inner = state.rc_deref_mut();
+          inner.subscribing = false;
+          let pending = inner.pending.take();
+          if pending.is_none() && !inner.closed {
+            inner.unsub = Some(<$box_unsub>::new(unsub));
+            return;
+          }
+          let closed = inner.closed;
+          drop(inner);
+          match pending {
+            Some((o, r)) if !closed => (observer, remaining) = (o, r),
+            _ => {
+              unsub.unsubscribe();
+              return;
+            }
+          }
+        }
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S, O> Observer<Item, Err>
+      for RepeatObserver<S, O, $rc<RepeatState<O, $box_unsub>>>
+    where
+      O: Observer<Item, Err>,
+    {
+      #[inline]
+      fn next(&mut self, value: Item) {
+        self.observer.next(value);
+      }
+
+      #[inline]
+      fn error(self, err: Err) {
+        self.observer.error(err);
+      }
+
+      fn complete(self) {
+        let Self { source, observer, remaining, state, resubscribe } = self;
+        if remaining == 1 {
+          observer.complete();
+          return;
+        }
+        if observer.is_finished() {
+          return;
+        }
+        let remaining = if remaining == usize::MAX {
+          remaining
+        } else {
+          remaining - 1
+        };
+        {
+          let mut inner = state.rc_deref_mut();
+          if inner.closed {
+            return;
+          }
+          if inner.subscribing {
+            inner.pending = Some((observer, remaining));
+            return;
+          }
+          // The round is over, no need to unsubscribe it.
+          inner.unsub = None;
+        }
+        resubscribe(RepeatObserver {
+          source,
+          observer,
+          remaining,
+          state,
+          resubscribe,
+        });
+      }
+
+      #[inline]
+      fn is_finished(&self) -> bool {
+        self.observer.is_finished()
+      }
+    }
+  };
+}
+
+impl_repeat_op!(RepeatOp, MutRc, BoxSubscription<'a>, 'a);
+impl_repeat_op!(RepeatOpThreads, MutArc, BoxSubscriptionThreads, Send);
+
+impl<R, O, U> Subscription for RepeatSubscription<R>
+where
+  R: RcDerefMut<Target = RepeatState<O, U>>
+    + RcDeref<Target = RepeatState<O, U>>,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let unsub = {
+      let mut state = self.0.rc_deref_mut();
+      state.closed = true;
+      state.pending = None;
+      state.unsub.take()
+    };
+    unsub.unsubscribe();
+  }
+
+  fn is_closed(&self) -> bool {
+    let state = self.0.rc_deref();
+    state.closed || state.unsub.is_closed()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn repeat_count_times() {
+    let mut values = vec![];
+    let mut completed = 0;
+
+    observable::from_iter(0..3)
+      .repeat(3)
+      .on_complete(|| completed += 1)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1, 2, 0, 1, 2, 0, 1, 2]);
+    assert_eq!(completed, 1);
+  }
+
+  #[test]
+  fn repeat_zero_times() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::of(1)
+      .repeat(0)
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert!(values.is_empty());
+    assert!(completed);
+  }
+
+  #[test]
+  fn error_stop_repeat() {
+    let mut values = vec![];
+    let mut error = None;
+    {
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .repeat(3)
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      subject.next(0);
+      subject.error("fail");
+    }
+
+    assert_eq!(values, vec![0]);
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn synchronous_completion_not_recursive() {
+    let mut count = 0;
+    let mut completed = false;
+
+    observable::of(1)
+      .repeat(100_000)
+      .on_complete(|| completed = true)
+      .subscribe(|_| count += 1);
+
+    assert_eq!(count, 100_000);
+    assert!(completed);
+  }
+
+  #[test]
+  fn repeat_forever() {
+    let mut values = vec![];
+
+    observable::of(1)
+      .repeat_forever()
+      .take(5)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![1; 5]);
+  }
+
+  #[test]
+  fn unsubscribe_live_round() {
+    let subjects = MutRc::own(vec![]);
+    let c_subjects = subjects.clone();
+
+    let subscription = observable::defer(move || {
+      let subject = Subject::<i32, ()>::default();
+      c_subjects.rc_deref_mut().push(subject.clone());
+      subject
+    })
+    .repeat(3)
+    .on_error(|_| {})
+    .subscribe(|_| {});
+
+    let first = subjects.rc_deref()[0].clone();
+    first.complete();
+    assert_eq!(subjects.rc_deref().len(), 2);
+    let second = subjects.rc_deref()[1].clone();
+    assert!(!second.is_empty());
+
+    subscription.unsubscribe();
+    assert!(second.is_empty());
+  }
+
+  #[test]
+  fn repeat_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    observable::from_iter(0..2)
+      .repeat_threads(2)
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[0, 1, 0, 1]);
+  }
+}
@@ -0,0 +1,288 @@
+use crate::{
+  ops::retry_when::{
+    NotifiedObserver, RetryWhenNotifierObserver, RetryWhenState,
+    RetryWhenSubscription,
+  },
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+/// An observable that resubscribes to the source when the notifier returned
+/// by a handler emits.
+///
+/// This struct is created by the repeat_when method on
+/// [ObservableExt](ObservableExt::repeat_when). See its documentation for
+/// more.
+#[derive(Clone)]
+pub struct RepeatWhenOp<'a, S, F, NotifyItem> {
+  source: S,
+  handler: F,
+  _hint: TypeHint<&'a NotifyItem>,
+}
+
+#[derive(Clone)]
+pub struct RepeatWhenOpThreads<S, F, NotifyItem> {
+  source: S,
+  handler: F,
+  _hint: TypeHint<NotifyItem>,
+}
+
+impl<'a, S, F, NotifyItem> RepeatWhenOp<'a, S, F, NotifyItem> {
+  #[inline]
+  pub(crate) fn new(source: S, handler: F) -> Self {
+    Self { source, handler, _hint: TypeHint::new() }
+  }
+}
+
+impl<S, F, NotifyItem> RepeatWhenOpThreads<S, F, NotifyItem> {
+  #[inline]
+  pub(crate) fn new(source: S, handler: F) -> Self {
+    Self { source, handler, _hint: TypeHint::new() }
+  }
+}
+
+macro_rules! impl_repeat_when_op {
+  (
+    $name: ident, $rc: ident, $subject: ty, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S, F, N, NotifyItem> Observable<Item, Err, O>
+      for $name<$($lf,)? S, F, NotifyItem>
+    where
+      O: Observer<Item, Err>,
+      Err: Clone,
+      S: Clone
+        + Observable<
+          Item,
+          Err,
+          RepeatWhenObserver<$rc<Option<O>>, $subject, $rc<Option<$box_unsub>>>,
+        >,
+      F: FnOnce($subject) -> N,
+      N: Observable<
+        NotifyItem,
+        Err,
+        RetryWhenNotifierObserver<
+          S,
+          $rc<Option<O>>,
+          $subject,
+          $rc<Option<$box_unsub>>,
+          $rc<RetryWhenState<S::Unsub, Err>>,
+          Item,
+          RepeatWhenObserver<$rc<Option<O>>, $subject, $rc<Option<$box_unsub>>>,
+        >,
+      >,
+      N::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = RetryWhenSubscription<
+        $rc<RetryWhenState<S::Unsub, Err>>,
+        $rc<Option<$box_unsub>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let observer = $rc::own(Some(observer));
+        let completions = <$subject>::default();
+        let state = $rc::own(RetryWhenState::default());
+        let notifier_unsub = $rc::own(None);
+        let notifier_observer = RetryWhenNotifierObserver::new(
+          self.source,
+          observer.clone(),
+          completions.clone(),
+          notifier_unsub.clone(),
+          state.clone(),
+        );
+        let trigger = notifier_observer.clone();
+
+        let unsub =
+          (self.handler)(completions).actual_subscribe(notifier_observer);
+        if observer.is_finished() {
+          unsub.unsubscribe();
+        } else {
+          *notifier_unsub.rc_deref_mut() = Some(<$box_unsub>::new(unsub));
+          // The notifier may have already subscribed the source.
+          if !state.rc_deref().is_subscribed() {
+            trigger.resubscribe();
+          }
+        }
+
+        RetryWhenSubscription::new(state, notifier_unsub)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S, F, NotifyItem> ObservableExt<Item, Err>
+      for $name<$($lf,)? S, F, NotifyItem>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_repeat_when_op!(
+  RepeatWhenOp,
+  MutRc,
+  Subject<'a, (), Err>,
+  BoxSubscription<'a>,
+  'a
+);
+impl_repeat_when_op!(
+  RepeatWhenOpThreads,
+  MutArc,
+  SubjectThreads<(), Err>,
+  BoxSubscriptionThreads,
+  Send
+);
+
+pub struct RepeatWhenObserver<O, E, U> {
+  observer: O,
+  completions: E,
+  notifier_unsub: U,
+}
+
+impl<O, E, U> NotifiedObserver<O, E, U> for RepeatWhenObserver<O, E, U> {
+  #[inline]
+  fn new(observer: O, completions: E, notifier_unsub: U) -> Self {
+    Self { observer, completions, notifier_unsub }
+  }
+}
+
+impl<Item, Err, O, E, U> Observer<Item, Err> for RepeatWhenObserver<O, E, U>
+where
+  O: Observer<Item, Err>,
+  E: Observer<(), Err>,
+  U: Subscription,
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    self.observer.next(value);
+  }
+
+  fn error(self, err: Err) {
+    self.notifier_unsub.unsubscribe();
+    self.observer.error(err);
+  }
+
+  #[inline]
+  fn complete(mut self) {
+    self.completions.next(());
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use futures::executor::LocalPool;
+  use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
+
+  #[test]
+  fn repeat_on_notify() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::from_iter(0..2)
+      .repeat_when(|completions| completions.take(2))
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1, 0, 1, 0, 1]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn source_error_terminate() {
+    let mut values = vec![];
+    let mut error = None;
+    {
+      let mut notifier = Subject::<(), _>::default();
+      let c_notifier = notifier.clone();
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .repeat_when(move |_| c_notifier)
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      subject.next(1);
+      subject.error("fail");
+      assert!(notifier.is_empty());
+      notifier.next(());
+    }
+
+    assert_eq!(values, vec![1]);
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn notifier_error_terminate() {
+    let mut values = vec![];
+    let mut error = None;
+
+    observable::of_result(Ok(1))
+      .repeat_when(|completions| {
+        completions
+          .scan_initial(0, |count, _| count + 1)
+          .flat_map(|count| {
+            observable::of_result(if count < 2 { Ok(()) } else { Err("stop") })
+          })
+      })
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![1, 1]);
+    assert_eq!(error, Some("stop"));
+  }
+
+  #[test]
+  fn synchronous_completion_not_recursive() {
+    let mut count = 0;
+    let mut completed = false;
+
+    observable::of(1)
+      .repeat_when(|completions| completions.take(100_000))
+      .on_complete(|| completed = true)
+      .subscribe(|_| count += 1);
+
+    assert_eq!(count, 100_001);
+    assert!(completed);
+  }
+
+  #[test]
+  fn delayed_repeat() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+
+    observable::of(1)
+      .repeat_when(move |completions| {
+        completions.take(2).delay(Duration::from_millis(1), spawner)
+      })
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    assert_eq!(&*values.rc_deref(), &[1]);
+
+    pool.run();
+    assert_eq!(&*values.rc_deref(), &[1, 1, 1]);
+  }
+
+  #[test]
+  fn repeat_when_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    observable::from_iter(0..2)
+      .repeat_when_threads(|completions| completions.take(1))
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[0, 1, 0, 1]);
+  }
+}
@@ -0,0 +1,356 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+/// An observable that resubscribes to the source when it errors.
+///
+/// This struct is created by the retry method on
+/// [ObservableExt](ObservableExt::retry). See its documentation for more.
+#[derive(Clone)]
+pub struct RetryOp<'a, S> {
+  source: S,
+  count: usize,
+  _hint: TypeHint<&'a ()>,
+}
+
+#[derive(Clone)]
+pub struct RetryOpThreads<S> {
+  source: S,
+  count: usize,
+}
+
+impl<'a, S> RetryOp<'a, S> {
+  #[inline]
+  pub(crate) fn new(source: S, count: usize) -> Self {
+    Self { source, count, _hint: TypeHint::new() }
+  }
+}
+
+impl<S> RetryOpThreads<S> {
+  #[inline]
+  pub(crate) fn new(source: S, count: usize) -> Self {
+    Self { source, count }
+  }
+}
+
+/// The subscription of the live attempt, replaced by every resubscription.
+pub struct RetryState<O, U> {
+  unsub: Option<U>,
+  // Set while an attempt is being subscribed, an attempt erroring meanwhile
+  // leaves the observer and the remaining attempts in `pending` for the loop
+  // of the running subscribe, so a synchronous source doesn't recurse.
+  subscribing: bool,
+  pending: Option<(O, usize)>,
+  closed: bool,
+}
+
+pub struct RetryObserver<S, O, R> {
+  source: S,
+  observer: O,
+  remaining: usize,
+  state: R,
+  // Subscribes the next attempt, a function pointer avoids requiring the
+  // source to accept this observer in the `Observer` implementation.
+  resubscribe: fn(Self),
+}
+
+pub struct RetrySubscription<R>(R);
+
+macro_rules! impl_retry_op {
+  (
+    $name: ident, $rc: ident, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S> Observable<Item, Err, O>
+      for $name<$($lf,)? S>
+    where
+      O: Observer<Item, Err>,
+      S: Clone
+        + Observable<
+          Item,
+          Err,
+          RetryObserver<S, O, $rc<RetryState<O, $box_unsub>>>,
+        >,
+      S::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = RetrySubscription<$rc<RetryState<O, $box_unsub>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(RetryState {
+          unsub: None,
+          subscribing: false,
+          pending: None,
+          closed: false,
+        });
+        RetryObserver {
+          source: self.source,
+          observer,
+          remaining: self.count,
+          state: state.clone(),
+          resubscribe:
+            RetryObserver::<S, O, $rc<RetryState<O, $box_unsub>>>::subscribe,
+        }
+        .subscribe();
+        RetrySubscription(state)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S> ObservableExt<Item, Err> for $name<$($lf,)? S>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+
+    impl<$($lf,)? S, O> RetryObserver<S, O, $rc<RetryState<O, $box_unsub>>> {
+      fn subscribe<Item, Err>(self)
+      where
+        O: Observer<Item, Err>,
+        S: Clone + Observable<Item, Err, Self>,
+        S::Unsub: $($lf)? $($send + 'static)?,
+      {
+        let Self { source, mut observer, mut remaining, state, resubscribe } =
+          self;
+        loop {
+          state.rc_deref_mut().subscribing = true;
+          let unsub = source.clone().actual_subscribe(RetryObserver {
+            source: source.clone(),
+            observer,
+            remaining,
+            state: state.clone(),
+            resubscribe,
+          });
+
+          let mut inner = state.rc_deref_mut();
+          inner.subscribing = false;
+          let pending = inner.pending.take();
+          if pending.is_none() && !inner.closed {
+            inner.unsub = Some(<$box_unsub>::new(unsub));
+            return;
+          }
+          let closed = inner.closed;
+          drop(inner);
+          match pending {
+            Some((o, r)) if !closed => (observer, remaining) = (o, r),
+            _ => {
+              unsub.unsubscribe();
+              return;
+            }
+          }
+        }
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S, O> Observer<Item, Err>
+      for RetryObserver<S, O, $rc<RetryState<O, $box_unsub>>>
+    where
+      O: Observer<Item, Err>,
+    {
+      #[inline]
+      fn next(&mut self, value: Item) {
+        self.observer.next(value);
+      }
+
+      fn error(self, err: Err) {
+        let Self { source, observer, remaining, state, resubscribe } = self;
+        if remaining == 0 {
+          observer.error(err);
+          return;
+        }
+        let remaining = if remaining == usize::MAX {
+          remaining
+        } else {
+          remaining - 1
+        };
+        {
+          let mut inner = state.rc_deref_mut();
+          if inner.closed {
+            return;
+          }
+          if inner.subscribing {
+            inner.pending = Some((observer, remaining));
+            return;
+          }
+          // The attempt is over, no need to unsubscribe it.
+          inner.unsub = None;
+        }
+        resubscribe(RetryObserver {
+          source,
+          observer,
+          remaining,
+          state,
+          resubscribe,
+        });
+      }
+
+      #[inline]
+      fn complete(self) {
+        self.observer.complete();
+      }
+
+      #[inline]
+      fn is_finished(&self) -> bool {
+        self.observer.is_finished()
+      }
+    }
+  };
+}
+
+impl_retry_op!(RetryOp, MutRc, BoxSubscription<'a>, 'a);
+impl_retry_op!(RetryOpThreads, MutArc, BoxSubscriptionThreads, Send);
+
+impl<R, O, U> Subscription for RetrySubscription<R>
+where
+  R: RcDerefMut<Target = RetryState<O, U>> + RcDeref<Target = RetryState<O, U>>,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let unsub = {
+      let mut state = self.0.rc_deref_mut();
+      state.closed = true;
+      state.pending = None;
+      state.unsub.take()
+    };
+    unsub.unsubscribe();
+  }
+
+  fn is_closed(&self) -> bool {
+    let state = self.0.rc_deref();
+    state.closed || state.unsub.is_closed()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    ops::box_it::BoxOp,
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  // A cold source emitting two values per attempt and erroring the `times`
+  // first attempts.
+  fn fail_times<'a>(
+    times: i32,
+  ) -> ObservableDeref<impl FnOnce() -> BoxOp<'a, i32, &'static str> + Clone>
+  {
+    let attempts = MutRc::own(0);
+    observable::defer(move || {
+      let attempt = *attempts.rc_deref();
+      *attempts.rc_deref_mut() += 1;
+      observable::create(move |mut subscriber: Subscriber<_>| {
+        subscriber.next(attempt * 10);
+        subscriber.next(attempt * 10 + 1);
+        if attempt < times {
+          subscriber.error("fail");
+        } else {
+          subscriber.complete();
+        }
+      })
+      .box_it()
+    })
+  }
+
+  #[test]
+  fn fail_twice_then_succeed() {
+    let mut values = vec![];
+    let mut error = None;
+    let mut completed = false;
+
+    fail_times(2)
+      .retry(2)
+      .on_complete(|| completed = true)
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1, 10, 11, 20, 21]);
+    assert!(error.is_none());
+    assert!(completed);
+  }
+
+  #[test]
+  fn error_after_attempts_exhausted() {
+    let mut values = vec![];
+    let mut error = None;
+
+    fail_times(3)
+      .retry(2)
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1, 10, 11, 20, 21]);
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn retry_forever() {
+    let mut values = vec![];
+
+    fail_times(5)
+      .retry_forever()
+      .on_error(|_| unreachable!())
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values.len(), 12);
+  }
+
+  #[test]
+  fn synchronous_error_not_recursive() {
+    let mut error = None;
+
+    observable::throw("error")
+      .retry(100_000)
+      .on_error(|e| error = Some(e))
+      .subscribe(|_| {});
+
+    assert_eq!(error, Some("error"));
+  }
+
+  #[test]
+  fn unsubscribe_live_attempt() {
+    let subjects = MutRc::own(vec![]);
+    let c_subjects = subjects.clone();
+
+    let subscription = observable::defer(move || {
+      let subject = Subject::<i32, ()>::default();
+      c_subjects.rc_deref_mut().push(subject.clone());
+      subject
+    })
+    .retry(3)
+    .on_error(|_| {})
+    .subscribe(|_| {});
+
+    let first = subjects.rc_deref()[0].clone();
+    first.error(());
+    assert_eq!(subjects.rc_deref().len(), 2);
+    let second = subjects.rc_deref()[1].clone();
+    assert!(!second.is_empty());
+
+    subscription.unsubscribe();
+    assert!(second.is_empty());
+  }
+
+  #[test]
+  fn retry_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let attempts = Arc::new(Mutex::new(0));
+
+    observable::defer(move || {
+      let mut attempt = attempts.lock().unwrap();
+      *attempt += 1;
+      let result = if *attempt < 3 {
+        Err(*attempt)
+      } else {
+        Ok(*attempt)
+      };
+      observable::of_result(result)
+    })
+    .retry_threads(5)
+    .on_error(|_| unreachable!())
+    .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[3]);
+  }
+}
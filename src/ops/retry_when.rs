@@ -0,0 +1,582 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+/// An observable that resubscribes to the source when the notifier returned
+/// by a handler emits.
+///
+/// This struct is created by the retry_when method on
+/// [ObservableExt](ObservableExt::retry_when). See its documentation for more.
+#[derive(Clone)]
+pub struct RetryWhenOp<'a, S, F, NotifyItem> {
+  source: S,
+  handler: F,
+  _hint: TypeHint<&'a NotifyItem>,
+}
+
+#[derive(Clone)]
+pub struct RetryWhenOpThreads<S, F, NotifyItem> {
+  source: S,
+  handler: F,
+  _hint: TypeHint<NotifyItem>,
+}
+
+impl<'a, S, F, NotifyItem> RetryWhenOp<'a, S, F, NotifyItem> {
+  #[inline]
+  pub(crate) fn new(source: S, handler: F) -> Self {
+    Self { source, handler, _hint: TypeHint::new() }
+  }
+}
+
+impl<S, F, NotifyItem> RetryWhenOpThreads<S, F, NotifyItem> {
+  #[inline]
+  pub(crate) fn new(source: S, handler: F) -> Self {
+    Self { source, handler, _hint: TypeHint::new() }
+  }
+}
+
+/// The subscription of the live attempt.
+pub struct RetryWhenState<U, Err> {
+  unsub: Option<U>,
+  // Set while an attempt is being subscribed, a retry requested meanwhile is
+  // recorded in `pending` and done by the loop of the running subscribe, so a
+  // synchronously failing source doesn't recurse.
+  subscribing: bool,
+  pending: bool,
+  // The termination of the notifier received while subscribing, `Ok` for a
+  // completion, delivered once the pending retries are done.
+  terminal: Option<Result<(), Err>>,
+  closed: bool,
+}
+
+impl<U, Err> RetryWhenState<U, Err> {
+  /// Whether an attempt of the source has been subscribed.
+  #[inline]
+  pub(crate) fn is_subscribed(&self) -> bool {
+    self.unsub.is_some()
+  }
+}
+
+impl<U, Err> Default for RetryWhenState<U, Err> {
+  fn default() -> Self {
+    Self {
+      unsub: None,
+      subscribing: false,
+      pending: false,
+      terminal: None,
+      closed: false,
+    }
+  }
+}
+
+macro_rules! impl_retry_when_op {
+  (
+    $name: ident, $rc: ident, $subject: ty, $box_unsub: ty
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err, O, S, F, N, NotifyItem> Observable<Item, Err, O>
+      for $name<$($lf,)? S, F, NotifyItem>
+    where
+      O: Observer<Item, Err>,
+      Err: Clone,
+      S: Clone
+        + Observable<
+          Item,
+          Err,
+          RetryWhenObserver<$rc<Option<O>>, $subject, $rc<Option<$box_unsub>>>,
+        >,
+      F: FnOnce($subject) -> N,
+      N: Observable<
+        NotifyItem,
+        Err,
+        RetryWhenNotifierObserver<
+          S,
+          $rc<Option<O>>,
+          $subject,
+          $rc<Option<$box_unsub>>,
+          $rc<RetryWhenState<S::Unsub, Err>>,
+          Item,
+          RetryWhenObserver<$rc<Option<O>>, $subject, $rc<Option<$box_unsub>>>,
+        >,
+      >,
+      N::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub = RetryWhenSubscription<
+        $rc<RetryWhenState<S::Unsub, Err>>,
+        $rc<Option<$box_unsub>>,
+      >;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let observer = $rc::own(Some(observer));
+        let errors = <$subject>::default();
+        let state = $rc::own(RetryWhenState::default());
+        let notifier_unsub = $rc::own(None);
+        let notifier_observer = RetryWhenNotifierObserver::new(
+          self.source,
+          observer.clone(),
+          errors.clone(),
+          notifier_unsub.clone(),
+          state.clone(),
+        );
+        let trigger = notifier_observer.clone();
+
+        let unsub = (self.handler)(errors).actual_subscribe(notifier_observer);
+        if observer.is_finished() {
+          unsub.unsubscribe();
+        } else {
+          *notifier_unsub.rc_deref_mut() = Some(<$box_unsub>::new(unsub));
+          // The notifier may have already subscribed the source.
+          if !state.rc_deref().is_subscribed() {
+            trigger.resubscribe();
+          }
+        }
+
+        RetryWhenSubscription::new(state, notifier_unsub)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, S, F, NotifyItem> ObservableExt<Item, Err>
+      for $name<$($lf,)? S, F, NotifyItem>
+    where
+      S: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_retry_when_op!(
+  RetryWhenOp,
+  MutRc,
+  Subject<'a, Err, Err>,
+  BoxSubscription<'a>,
+  'a
+);
+impl_retry_when_op!(
+  RetryWhenOpThreads,
+  MutArc,
+  SubjectThreads<Err, Err>,
+  BoxSubscriptionThreads,
+  Send
+);
+
+/// Builds the observer subscribed to the source on every attempt, from the
+/// output observer, the subject feeding the handler and the notifier
+/// subscription.
+pub trait NotifiedObserver<O, E, U> {
+  fn new(observer: O, notifications: E, notifier_unsub: U) -> Self;
+}
+
+pub struct RetryWhenObserver<O, E, U> {
+  observer: O,
+  errors: E,
+  notifier_unsub: U,
+}
+
+impl<O, E, U> NotifiedObserver<O, E, U> for RetryWhenObserver<O, E, U> {
+  #[inline]
+  fn new(observer: O, errors: E, notifier_unsub: U) -> Self {
+    Self { observer, errors, notifier_unsub }
+  }
+}
+
+impl<Item, Err, O, E, U> Observer<Item, Err> for RetryWhenObserver<O, E, U>
+where
+  O: Observer<Item, Err>,
+  E: Observer<Err, Err>,
+  U: Subscription,
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    self.observer.next(value);
+  }
+
+  #[inline]
+  fn error(mut self, err: Err) {
+    self.errors.next(err);
+  }
+
+  fn complete(self) {
+    self.notifier_unsub.unsubscribe();
+    self.observer.complete();
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+/// Observes the notifier, resubscribes the source with a `SO` observer when
+/// it emits and terminates the output when it does.
+pub struct RetryWhenNotifierObserver<S, O, E, U, R, Item, SO> {
+  source: S,
+  observer: O,
+  notifications: E,
+  notifier_unsub: U,
+  state: R,
+  _hint: TypeHint<(Item, SO)>,
+}
+
+impl<S, O, E, U, R, Item, SO> Clone
+  for RetryWhenNotifierObserver<S, O, E, U, R, Item, SO>
+where
+  S: Clone,
+  O: Clone,
+  E: Clone,
+  U: Clone,
+  R: Clone,
+{
+  fn clone(&self) -> Self {
+    Self {
+      source: self.source.clone(),
+      observer: self.observer.clone(),
+      notifications: self.notifications.clone(),
+      notifier_unsub: self.notifier_unsub.clone(),
+      state: self.state.clone(),
+      _hint: TypeHint::new(),
+    }
+  }
+}
+
+impl<S, O, E, U, R, Item, SO>
+  RetryWhenNotifierObserver<S, O, E, U, R, Item, SO>
+{
+  pub(crate) fn new(
+    source: S,
+    observer: O,
+    notifications: E,
+    notifier_unsub: U,
+    state: R,
+  ) -> Self {
+    Self {
+      source,
+      observer,
+      notifications,
+      notifier_unsub,
+      state,
+      _hint: TypeHint::new(),
+    }
+  }
+
+  /// Unsubscribes the live attempt and subscribes the source again.
+  pub(crate) fn resubscribe<Err>(&self)
+  where
+    O: Observer<Item, Err> + Clone,
+    E: Clone,
+    U: Subscription + Clone,
+    S: Clone + Observable<Item, Err, SO>,
+    SO: NotifiedObserver<O, E, U> + Observer<Item, Err>,
+    R: RcDerefMut<Target = RetryWhenState<S::Unsub, Err>>,
+  {
+    {
+      let mut state = self.state.rc_deref_mut();
+      if state.closed || self.observer.is_finished() {
+        return;
+      }
+      if state.subscribing {
+        state.pending = true;
+        return;
+      }
+      state.subscribing = true;
+    }
+
+    loop {
+      let prev = self.state.rc_deref_mut().unsub.take();
+      prev.unsubscribe();
+      let unsub = self.source.clone().actual_subscribe(SO::new(
+        self.observer.clone(),
+        self.notifications.clone(),
+        self.notifier_unsub.clone(),
+      ));
+
+      let mut state = self.state.rc_deref_mut();
+      state.unsub = Some(unsub);
+      if state.pending && !state.closed && !self.observer.is_finished() {
+        state.pending = false;
+        continue;
+      }
+      state.pending = false;
+      state.subscribing = false;
+      let terminal = state.terminal.take();
+      if state.closed || terminal.is_some() {
+        let unsub = state.unsub.take();
+        drop(state);
+        unsub.unsubscribe();
+      }
+      match terminal {
+        Some(Ok(())) => self.observer.clone().complete(),
+        Some(Err(err)) => self.observer.clone().error(err),
+        None => {}
+      }
+      break;
+    }
+  }
+
+  /// Unsubscribes the live attempt and terminates the output, or leaves it to
+  /// the running subscribe loop.
+  fn terminate<Err, SU>(self, terminal: Result<(), Err>)
+  where
+    O: Observer<Item, Err>,
+    R: RcDerefMut<Target = RetryWhenState<SU, Err>>,
+    SU: Subscription,
+  {
+    let unsub = {
+      let mut state = self.state.rc_deref_mut();
+      if state.subscribing {
+        state.terminal = Some(terminal);
+        return;
+      }
+      state.unsub.take()
+    };
+    unsub.unsubscribe();
+    match terminal {
+      Ok(()) => self.observer.complete(),
+      Err(err) => self.observer.error(err),
+    }
+  }
+}
+
+impl<Item, Err, NotifyItem, S, O, E, U, R, SO> Observer<NotifyItem, Err>
+  for RetryWhenNotifierObserver<S, O, E, U, R, Item, SO>
+where
+  O: Observer<Item, Err> + Clone,
+  E: Clone,
+  U: Subscription + Clone,
+  S: Clone + Observable<Item, Err, SO>,
+  SO: NotifiedObserver<O, E, U> + Observer<Item, Err>,
+  R: RcDerefMut<Target = RetryWhenState<S::Unsub, Err>>,
+{
+  #[inline]
+  fn next(&mut self, _: NotifyItem) {
+    self.resubscribe();
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    self.terminate(Err(err));
+  }
+
+  #[inline]
+  fn complete(self) {
+    self.terminate(Ok(()));
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+pub struct RetryWhenSubscription<R, U> {
+  state: R,
+  notifier: U,
+}
+
+impl<R, U> RetryWhenSubscription<R, U> {
+  #[inline]
+  pub(crate) fn new(state: R, notifier: U) -> Self {
+    Self { state, notifier }
+  }
+}
+
+impl<R, U, SU, Err> Subscription for RetryWhenSubscription<R, U>
+where
+  R: RcDerefMut<Target = RetryWhenState<SU, Err>>
+    + RcDeref<Target = RetryWhenState<SU, Err>>,
+  SU: Subscription,
+  U: Subscription,
+{
+  fn unsubscribe(self) {
+    let unsub = {
+      let mut state = self.state.rc_deref_mut();
+      state.closed = true;
+      state.unsub.take()
+    };
+    unsub.unsubscribe();
+    self.notifier.unsubscribe();
+  }
+
+  fn is_closed(&self) -> bool {
+    self.state.rc_deref().closed
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    ops::box_it::BoxOp,
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use futures::executor::LocalPool;
+  use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
+
+  // A cold source emitting two values per attempt and erroring the `times`
+  // first attempts.
+  fn fail_times<'a>(
+    times: i32,
+  ) -> ObservableDeref<impl FnOnce() -> BoxOp<'a, i32, &'static str> + Clone>
+  {
+    let attempts = MutRc::own(0);
+    observable::defer(move || {
+      let attempt = *attempts.rc_deref();
+      *attempts.rc_deref_mut() += 1;
+      observable::create(move |mut subscriber: Subscriber<_>| {
+        subscriber.next(attempt * 10);
+        subscriber.next(attempt * 10 + 1);
+        if attempt < times {
+          subscriber.error("fail");
+        } else {
+          subscriber.complete();
+        }
+      })
+      .box_it()
+    })
+  }
+
+  #[test]
+  fn retry_on_notify() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    fail_times(2)
+      .retry_when(|errors| errors.take(2))
+      .on_complete(|| completed = true)
+      .on_error(|_| unreachable!())
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1, 10, 11, 20, 21]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn notifier_complete_terminate() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    fail_times(5)
+      .retry_when(|errors| errors.take(2))
+      .on_complete(|| completed = true)
+      .on_error(|_| unreachable!())
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1, 10, 11, 20, 21]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn notifier_error_terminate() {
+    let mut values = vec![];
+    let mut error = None;
+
+    fail_times(5)
+      .retry_when(|errors| {
+        errors
+          .scan_initial(0, |count, _| count + 1)
+          .flat_map(|count| {
+            observable::of_result(if count < 2 {
+              Ok(())
+            } else {
+              Err("give up")
+            })
+          })
+      })
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1, 10, 11]);
+    assert_eq!(error, Some("give up"));
+  }
+
+  #[test]
+  fn synchronous_failure_not_recursive() {
+    let attempts = MutRc::own(0);
+    let c_attempts = attempts.clone();
+    let mut completed = false;
+
+    observable::defer(move || {
+      *c_attempts.rc_deref_mut() += 1;
+      observable::of_result(Err::<(), _>(()))
+    })
+    .retry_when(|errors| errors.take(100_000))
+    .on_complete(|| completed = true)
+    .on_error(|_| unreachable!())
+    .subscribe(|_| {});
+
+    assert_eq!(*attempts.rc_deref(), 100_001);
+    assert!(completed);
+  }
+
+  #[test]
+  fn delayed_retry() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+
+    fail_times(2)
+      .retry_when(move |errors| errors.delay(Duration::from_millis(1), spawner))
+      .on_error(|_| unreachable!())
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    assert_eq!(&*values.rc_deref(), &[0, 1]);
+
+    pool.run();
+    assert_eq!(&*values.rc_deref(), &[0, 1, 10, 11, 20, 21]);
+  }
+
+  #[test]
+  fn unsubscribe_live_attempt_and_notifier() {
+    let subjects = MutRc::own(vec![]);
+    let c_subjects = subjects.clone();
+    let mut notifier = Subject::<(), ()>::default();
+    let c_notifier = notifier.clone();
+
+    let subscription = observable::defer(move || {
+      let subject = Subject::<i32, ()>::default();
+      c_subjects.rc_deref_mut().push(subject.clone());
+      subject
+    })
+    .retry_when(move |_| c_notifier)
+    .on_error(|_| {})
+    .subscribe(|_| {});
+    assert!(!notifier.is_empty());
+
+    let first = subjects.rc_deref()[0].clone();
+    first.error(());
+    notifier.next(());
+    assert_eq!(subjects.rc_deref().len(), 2);
+    let second = subjects.rc_deref()[1].clone();
+    assert!(!second.is_empty());
+
+    subscription.unsubscribe();
+    assert!(second.is_empty());
+    assert!(notifier.is_empty());
+  }
+
+  #[test]
+  fn retry_when_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let attempts = Arc::new(Mutex::new(0));
+
+    observable::defer(move || {
+      let mut attempt = attempts.lock().unwrap();
+      *attempt += 1;
+      let result = if *attempt < 3 {
+        Err(*attempt)
+      } else {
+        Ok(*attempt)
+      };
+      observable::of_result(result)
+    })
+    .retry_when_threads(|errors| errors.take(5))
+    .on_error(|_| unreachable!())
+    .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    assert_eq!(&*values.lock().unwrap(), &[3]);
+  }
+}
@@ -141,7 +141,11 @@ where
 
 #[cfg(test)]
 mod test {
-  use crate::{observable::fake_timer::FakeClock, prelude::*};
+  use crate::{
+    observable::fake_timer::FakeClock,
+    prelude::*,
+    rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+  };
   use std::{cell::RefCell, rc::Rc, time::Duration};
 
   #[test]
@@ -166,8 +170,6 @@ mod test {
   #[cfg(not(target_arch = "wasm32"))]
   #[test]
   fn sample_by_subject() {
-    use crate::rc::{MutArc, RcDeref, RcDerefMut};
-
     let mut subject = SubjectThreads::default();
     let mut notifier = SubjectThreads::default();
     let test_code = MutArc::own(0);
@@ -196,4 +198,89 @@ mod test {
     notifier.complete();
     assert_eq!(*test_code.rc_deref(), 4);
   }
+
+  #[test]
+  fn error_from_either_side() {
+    let mut source = Subject::default();
+    let notifier = Subject::<(), _>::default();
+    let err = MutRc::own(None);
+    let c_err = err.clone();
+    source
+      .clone()
+      .sample(notifier.clone())
+      .on_error(move |e| *c_err.rc_deref_mut() = Some(e))
+      .subscribe(|_: i32| {});
+    source.next(1);
+    notifier.clone().error("notifier");
+    assert_eq!(*err.rc_deref(), Some("notifier"));
+
+    let source = Subject::default();
+    let notifier = Subject::<(), _>::default();
+    let c_err = err.clone();
+    source
+      .clone()
+      .sample(notifier)
+      .on_error(move |e| *c_err.rc_deref_mut() = Some(e))
+      .subscribe(|_: i32| {});
+    source.error("source");
+    assert_eq!(*err.rc_deref(), Some("source"));
+  }
+
+  #[test]
+  fn source_complete_complete_output() {
+    let mut source = Subject::default();
+    let mut notifier = Subject::<(), ()>::default();
+    let completed = MutRc::own(false);
+    let values = MutRc::own(vec![]);
+    let c_completed = completed.clone();
+    let c_values = values.clone();
+    source
+      .clone()
+      .sample(notifier.clone())
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    source.next(1);
+    notifier.next(());
+    source.next(2);
+    source.complete();
+    notifier.next(());
+    assert!(*completed.rc_deref());
+    assert_eq!(*values.rc_deref(), vec![1]);
+  }
+
+  #[test]
+  fn unsubscribe_both() {
+    let mut source = Subject::<i32, ()>::default();
+    let mut notifier = Subject::<(), ()>::default();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    source
+      .clone()
+      .sample(notifier.clone())
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v))
+      .unsubscribe();
+    source.next(1);
+    notifier.next(());
+    assert!(values.rc_deref().is_empty());
+  }
+
+  #[test]
+  fn sample_time() {
+    use futures::executor::LocalPool;
+
+    let mut pool = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    observable::interval(Duration::from_millis(1), pool.spawner())
+      .take(10)
+      .sample_time(Duration::from_millis(5), pool.spawner())
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    pool.run();
+
+    let values = values.rc_deref();
+    assert!(values.len() < 10);
+    assert!(values.windows(2).all(|w| w[0] < w[1]));
+  }
 }
@@ -5,6 +5,8 @@ pub struct ScanOp<Source, BinaryOp, OutputItem, InputItem> {
   source: Source,
   binary_op: BinaryOp,
   initial_value: OutputItem,
+  // Whether the initial value is emitted before any source item.
+  emit_initial: bool,
   _m: TypeHint<InputItem>,
 }
 
@@ -20,15 +22,22 @@ impl<Source, BinaryOp, OutputItem, InputItem>
       source,
       binary_op,
       initial_value,
+      emit_initial: false,
       _m: TypeHint::default(),
     }
   }
+
+  pub(crate) fn emit_initial(mut self) -> Self {
+    self.emit_initial = true;
+    self
+  }
 }
 
 pub struct ScanObserver<Observer, BinaryOp, OutputItem> {
   target_observer: Observer,
   binary_op: BinaryOp,
-  acc: OutputItem,
+  // Only empty while `binary_op` folds an item into it.
+  acc: Option<OutputItem>,
 }
 
 impl<InputItem, OutputItem, Err, O, S, BinaryOp> Observable<OutputItem, Err, O>
@@ -40,11 +49,14 @@ where
   OutputItem: Clone,
 {
   type Unsub = S::Unsub;
-  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+  fn actual_subscribe(self, mut observer: O) -> Self::Unsub {
+    if self.emit_initial {
+      observer.next(self.initial_value.clone());
+    }
     self.source.actual_subscribe(ScanObserver {
       target_observer: observer,
       binary_op: self.binary_op,
-      acc: self.initial_value,
+      acc: Some(self.initial_value),
     })
   }
 }
@@ -68,8 +80,11 @@ where
 {
   fn next(&mut self, value: InputItem) {
     // accumulating each item with a current value
-    self.acc = (self.binary_op)(self.acc.clone(), value);
-    self.target_observer.next(self.acc.clone())
+    if let Some(acc) = self.acc.take() {
+      let acc = (self.binary_op)(acc, value);
+      self.target_observer.next(acc.clone());
+      self.acc = Some(acc);
+    }
   }
 
   #[inline]
@@ -90,7 +105,10 @@ where
 
 #[cfg(test)]
 mod test {
-  use crate::prelude::*;
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
 
   #[test]
   fn scan_initial() {
@@ -144,6 +162,53 @@ mod test {
     m.scan(|_acc, v| v as f32).subscribe(|_| {});
   }
 
+  #[test]
+  fn scan_emit_initial() {
+    let mut emitted = Vec::<i32>::new();
+    observable::from_iter(vec![1, 1])
+      .scan_emit_initial(100, |acc, v| acc + v)
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(vec!(100, 101, 102), emitted);
+  }
+
+  #[test]
+  fn scan_emit_initial_before_source() {
+    let emitted = MutRc::own(vec![]);
+    let c_emitted = emitted.clone();
+    let mut subject = Subject::<i32, ()>::default();
+    subject
+      .clone()
+      .scan_emit_initial(vec![], |mut acc, v| {
+        acc.push(v);
+        acc
+      })
+      .on_error(|_| {})
+      .subscribe(move |v| c_emitted.rc_deref_mut().push(v));
+
+    assert_eq!(&*emitted.rc_deref(), &[vec![]]);
+    subject.next(1);
+    assert_eq!(&*emitted.rc_deref(), &[vec![], vec![1]]);
+  }
+
+  #[test]
+  fn clone_once_per_emission() {
+    struct Counted(MutRc<usize>);
+    impl Clone for Counted {
+      fn clone(&self) -> Self {
+        *self.0.rc_deref_mut() += 1;
+        Counted(self.0.clone())
+      }
+    }
+
+    let clones = MutRc::own(0);
+    observable::from_iter(0..3)
+      .scan_emit_initial(Counted(clones.clone()), |acc, _| acc)
+      .subscribe(|_| {});
+
+    assert_eq!(*clones.rc_deref(), 4);
+  }
+
   #[test]
   fn bench() {
     do_bench();
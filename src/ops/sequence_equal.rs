@@ -0,0 +1,319 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+use std::collections::VecDeque;
+
+/// An observable that tells whether two observables emit equal items in the
+/// same order.
+///
+/// This struct is created by the sequence_equal method on
+/// [ObservableExt](ObservableExt::sequence_equal). See its documentation for
+/// more.
+#[derive(Clone)]
+pub struct SequenceEqualOp<'a, A, B, Item> {
+  a: A,
+  b: B,
+  _hint: TypeHint<&'a Item>,
+}
+
+#[derive(Clone)]
+pub struct SequenceEqualOpThreads<A, B, Item> {
+  a: A,
+  b: B,
+  _hint: TypeHint<Item>,
+}
+
+macro_rules! impl_sequence_equal_op {
+  (
+    $name:ident, $rc: ident, $box_unsub: ty
+    $(,$lf:lifetime)? $(,$send:ident)?
+  ) => {
+    impl<$($lf,)? A, B, Item> $name<$($lf,)? A, B, Item> {
+      #[inline]
+      pub(crate) fn new(a: A, b: B) -> Self {
+        Self { a, b, _hint: TypeHint::new() }
+      }
+    }
+
+    impl<$($lf,)? A, B, Item, Err, O> Observable<bool, Err, O>
+      for $name<$($lf,)? A, B, Item>
+    where
+      O: Observer<bool, Err>,
+      Item: PartialEq,
+      A: Observable<
+        Item,
+        Err,
+        SequenceEqualObserver<
+          $rc<SequenceEqualState<O, Item>>,
+          $rc<Option<B::Unsub>>,
+        >,
+      >,
+      B: Observable<
+        Item,
+        Err,
+        SequenceEqualObserver<
+          $rc<SequenceEqualState<O, Item>>,
+          $rc<Option<$box_unsub>>,
+        >,
+      >,
+      A::Unsub: $($lf)? $($send + 'static)?,
+    {
+      type Unsub =
+        ZipSubscription<$rc<Option<$box_unsub>>, $rc<Option<B::Unsub>>>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(SequenceEqualState {
+          observer: Some(observer),
+          queues: [VecDeque::default(), VecDeque::default()],
+          completed: [false, false],
+        });
+        let a_unsub = $rc::own(None);
+        let b_unsub = $rc::own(None);
+        let finished = || state.rc_deref().is_finished();
+
+        let a = self.a.actual_subscribe(SequenceEqualObserver {
+          state: state.clone(),
+          other_unsub: b_unsub.clone(),
+          side: 0,
+        });
+        if finished() {
+          a.unsubscribe();
+          return ZipSubscription::new(a_unsub, b_unsub);
+        }
+        *a_unsub.rc_deref_mut() = Some(<$box_unsub>::new(a));
+
+        let b = self.b.actual_subscribe(SequenceEqualObserver {
+          state: state.clone(),
+          other_unsub: a_unsub.clone(),
+          side: 1,
+        });
+        if finished() {
+          b.unsubscribe();
+        } else {
+          *b_unsub.rc_deref_mut() = Some(b);
+        }
+        ZipSubscription::new(a_unsub, b_unsub)
+      }
+    }
+
+    impl<$($lf,)? A, B, Item, Err> ObservableExt<bool, Err>
+      for $name<$($lf,)? A, B, Item>
+    where
+      A: ObservableExt<Item, Err>,
+      B: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_sequence_equal_op!(SequenceEqualOp, MutRc, BoxSubscription<'a>, 'a);
+impl_sequence_equal_op!(
+  SequenceEqualOpThreads,
+  MutArc,
+  BoxSubscriptionThreads,
+  Send
+);
+
+/// The state shared by both observables, only the one running ahead has
+/// items queued.
+pub struct SequenceEqualState<O, Item> {
+  observer: Option<O>,
+  queues: [VecDeque<Item>; 2],
+  completed: [bool; 2],
+}
+
+impl<O, Item> SequenceEqualState<O, Item> {
+  fn is_finished<Err>(&self) -> bool
+  where
+    O: Observer<bool, Err>,
+  {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+
+  /// Returns the observer and the result once the result is known.
+  fn on_next(&mut self, side: usize, value: Item) -> Option<(O, bool)>
+  where
+    Item: PartialEq,
+  {
+    self.observer.as_ref()?;
+    let other = 1 - side;
+    let equal = match self.queues[other].pop_front() {
+      Some(v) => v == value,
+      // The other observable has completed with fewer items.
+      None if self.completed[other] => false,
+      None => {
+        self.queues[side].push_back(value);
+        true
+      }
+    };
+    if equal {
+      None
+    } else {
+      self.observer.take().map(|o| (o, false))
+    }
+  }
+
+  fn on_complete(&mut self, side: usize) -> Option<(O, bool)> {
+    self.completed[side] = true;
+    let other = 1 - side;
+    if !self.queues[other].is_empty() {
+      self.observer.take().map(|o| (o, false))
+    } else if self.completed[other] {
+      let equal = self.queues[side].is_empty();
+      self.observer.take().map(|o| (o, equal))
+    } else {
+      None
+    }
+  }
+}
+
+/// Observes one side of a sequence_equal, and holds the subscription of the
+/// other side to unsubscribe it when the result is known early.
+pub struct SequenceEqualObserver<S, U> {
+  state: S,
+  other_unsub: U,
+  side: usize,
+}
+
+impl<S, U, O, Item, Err> Observer<Item, Err> for SequenceEqualObserver<S, U>
+where
+  S: RcDeref<Target = SequenceEqualState<O, Item>>
+    + RcDerefMut<Target = SequenceEqualState<O, Item>>,
+  U: Subscription + Clone,
+  O: Observer<bool, Err>,
+  Item: PartialEq,
+{
+  fn next(&mut self, value: Item) {
+    let result = self.state.rc_deref_mut().on_next(self.side, value);
+    if let Some((mut observer, equal)) = result {
+      self.other_unsub.clone().unsubscribe();
+      observer.next(equal);
+      observer.complete();
+    }
+  }
+
+  fn error(self, err: Err) {
+    let observer = self.state.rc_deref_mut().observer.take();
+    if let Some(observer) = observer {
+      self.other_unsub.unsubscribe();
+      observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    let result = self.state.rc_deref_mut().on_complete(self.side);
+    if let Some((mut observer, equal)) = result {
+      self.other_unsub.unsubscribe();
+      observer.next(equal);
+      observer.complete();
+    }
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.state.rc_deref().is_finished()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn equal_sequences() {
+    let mut values = vec![];
+
+    observable::from_iter(0..5)
+      .sequence_equal(observable::from_iter(0..5))
+      .subscribe(|b| values.push(b));
+    observable::empty::<i32>()
+      .sequence_equal(observable::empty())
+      .subscribe(|b| values.push(b));
+
+    assert_eq!(values, vec![true, true]);
+  }
+
+  #[test]
+  fn different_lengths() {
+    let mut values = vec![];
+
+    observable::from_iter(0..5)
+      .sequence_equal(observable::from_iter(0..4))
+      .subscribe(|b| values.push(b));
+    observable::from_iter(0..4)
+      .sequence_equal(observable::from_iter(0..5))
+      .subscribe(|b| values.push(b));
+
+    assert_eq!(values, vec![false, false]);
+  }
+
+  #[test]
+  fn mismatch_unsubscribe_both() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut a = Subject::<i32, ()>::default();
+    let mut b = Subject::<i32, ()>::default();
+    a.clone()
+      .sequence_equal(b.clone())
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+
+    a.next(1);
+    a.next(2);
+    b.next(1);
+    assert!(values.rc_deref().is_empty());
+    b.next(3);
+    assert_eq!(&*values.rc_deref(), &[false]);
+    assert!(a.is_empty());
+    assert!(b.is_empty());
+  }
+
+  #[test]
+  fn surplus_after_complete() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut a = Subject::<i32, ()>::default();
+    let mut b = Subject::<i32, ()>::default();
+    a.clone()
+      .sequence_equal(b.clone())
+      .on_error(|_| {})
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+
+    a.next(1);
+    a.complete();
+    b.next(1);
+    assert!(values.rc_deref().is_empty());
+    b.next(2);
+    assert_eq!(&*values.rc_deref(), &[false]);
+    assert!(b.is_empty());
+  }
+
+  #[test]
+  fn error_propagate() {
+    let mut error = None;
+
+    observable::of_result(Err::<i32, _>("fail"))
+      .sequence_equal(observable::of(1).on_error_map(|e| match e {}))
+      .on_error(|e| error = Some(e))
+      .subscribe(|_| unreachable!());
+
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn sequence_equal_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+
+    observable::from_iter(0..3)
+      .sequence_equal_threads(observable::from_iter(0..3))
+      .subscribe(move |b| c_values.lock().unwrap().push(b));
+
+    assert_eq!(&*values.lock().unwrap(), &[true]);
+  }
+}
@@ -0,0 +1,198 @@
+use crate::prelude::*;
+use std::fmt::Display;
+
+/// The error emitted by [`single`](ObservableExt::single) when the source
+/// does not emit exactly one matching item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SingleError {
+  /// The source completed without a matching item.
+  Empty,
+
+  /// The source emitted a second matching item.
+  MultipleValues,
+}
+
+impl Display for SingleError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      SingleError::Empty => write!(f, "the observable has no matching value"),
+      SingleError::MultipleValues => {
+        write!(f, "the observable emitted more than one matching value")
+      }
+    }
+  }
+}
+
+impl std::error::Error for SingleError {}
+
+#[derive(Clone)]
+pub struct SingleOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) predicate: F,
+}
+
+impl<Item, Err, O, S, F> Observable<Item, Err, O> for SingleOp<S, F>
+where
+  O: Observer<Item, Err>,
+  S: Observable<Item, Err, SingleObserver<O, F, Item>>,
+  F: Fn(&Item) -> bool,
+  Err: From<SingleError>,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self.source.actual_subscribe(SingleObserver {
+      observer: Some(observer),
+      predicate: self.predicate,
+      value: None,
+    })
+  }
+}
+
+impl<Item, Err, S, F> ObservableExt<Item, Err> for SingleOp<S, F> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct SingleObserver<O, F, Item> {
+  // Taken by the second matching item, the source stops once it's gone.
+  observer: Option<O>,
+  predicate: F,
+  value: Option<Item>,
+}
+
+impl<Item, Err, O, F> Observer<Item, Err> for SingleObserver<O, F, Item>
+where
+  O: Observer<Item, Err>,
+  F: Fn(&Item) -> bool,
+  Err: From<SingleError>,
+{
+  fn next(&mut self, value: Item) {
+    if self.observer.is_none() || !(self.predicate)(&value) {
+      return;
+    }
+    if self.value.is_none() {
+      self.value = Some(value);
+    } else if let Some(observer) = self.observer.take() {
+      self.value = None;
+      observer.error(SingleError::MultipleValues.into());
+    }
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    if let Some(observer) = self.observer {
+      observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    if let Some(mut observer) = self.observer {
+      match self.value {
+        Some(value) => {
+          observer.next(value);
+          observer.complete();
+        }
+        None => observer.error(SingleError::Empty.into()),
+      }
+    }
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::SingleError;
+  use crate::prelude::*;
+
+  #[test]
+  fn single_item() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::of(1)
+      .on_error_map(|e| match e {})
+      .single()
+      .on_complete(|| completed = true)
+      .on_error(|_: SingleError| unreachable!())
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![1]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn empty_error() {
+    let mut error = None;
+
+    observable::empty()
+      .on_error_map(|e| match e {})
+      .single()
+      .on_error(|e: SingleError| error = Some(e))
+      .subscribe(|_: i32| unreachable!());
+
+    assert_eq!(error, Some(SingleError::Empty));
+  }
+
+  #[test]
+  fn multiple_error_eagerly() {
+    let mut values = vec![];
+    let mut error = None;
+    {
+      let mut subject = Subject::<i32, SingleError>::default();
+      subject
+        .clone()
+        .single()
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      subject.next(1);
+      assert!(!subject.is_empty());
+      subject.next(2);
+      assert!(subject.is_empty());
+    }
+
+    assert!(values.is_empty());
+    assert_eq!(error, Some(SingleError::MultipleValues));
+  }
+
+  #[test]
+  fn stop_infinite_source() {
+    let mut produced = 0;
+    let mut error = None;
+
+    observable::repeat(1, usize::MAX)
+      .on_error_map(|e| match e {})
+      .tap(|_| produced += 1)
+      .single()
+      .on_error(|e: SingleError| error = Some(e))
+      .subscribe(|_| {});
+
+    assert_eq!(produced, 2);
+    assert_eq!(error, Some(SingleError::MultipleValues));
+  }
+
+  #[test]
+  fn single_with() {
+    let mut values = vec![];
+
+    observable::from_iter(0..10)
+      .on_error_map(|e| match e {})
+      .single_with(|v| *v == 3)
+      .on_error(|_: SingleError| unreachable!())
+      .subscribe(|v| values.push(v));
+    assert_eq!(values, vec![3]);
+
+    let mut error = None;
+    observable::from_iter(0..10)
+      .on_error_map(|e| match e {})
+      .single_with(|v| v % 5 == 0)
+      .on_error(|e: SingleError| error = Some(e))
+      .subscribe(|_| {});
+    assert_eq!(error, Some(SingleError::MultipleValues));
+  }
+}
@@ -95,6 +95,44 @@ mod test {
     assert!(completed);
   }
 
+  #[test]
+  fn emit_before_source_complete() {
+    let mut ticks = vec![];
+    {
+      let mut subject = Subject::default();
+      subject.clone().skip_last(2).subscribe(|v| ticks.push(v));
+
+      subject.next(0);
+      subject.next(1);
+      subject.next(2);
+      subject.next(3);
+    }
+
+    assert_eq!(ticks, vec![0, 1]);
+  }
+
+  #[test]
+  fn error_drop_queue() {
+    let mut ticks = vec![];
+    let mut error = None;
+    {
+      let mut subject = Subject::default();
+      subject
+        .clone()
+        .skip_last(2)
+        .on_error(|e| error = Some(e))
+        .subscribe(|v: i32| ticks.push(v));
+
+      subject.next(0);
+      subject.next(1);
+      subject.next(2);
+      subject.error("err");
+    }
+
+    assert_eq!(ticks, vec![0]);
+    assert_eq!(error, Some("err"));
+  }
+
   #[test]
   fn skip_last_support_fork() {
     let mut nc1 = 0;
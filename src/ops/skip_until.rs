@@ -9,7 +9,7 @@ use std::{
 
 use crate::{
   prelude::*,
-  rc::{MutArc, MutRc},
+  rc::{MutArc, MutRc, RcDeref},
 };
 
 #[derive(Clone)]
@@ -136,14 +136,14 @@ macro_rules! impl_observer {
       #[inline]
       fn error(self, _: Err) {}
 
+      // A notifier completed without emitting keeps the source suppressed.
       #[inline]
-      fn complete(self) {
-        self.0.stop_skipping()
-      }
+      fn complete(self) {}
 
+      // Once the gate is open the notifier is no longer needed.
       #[inline]
       fn is_finished(&self) -> bool {
-        false
+        !self.0.is_skipping() || self.0.observer.rc_deref().is_none()
       }
     }
   };
@@ -188,7 +188,7 @@ impl<O> ShareObserverThreads<O> {
 
 #[cfg(test)]
 mod test {
-  use std::vec;
+  use std::{rc::Rc, vec};
 
   use crate::prelude::*;
 
@@ -215,6 +215,70 @@ mod test {
     assert!(completed);
   }
 
+  #[test]
+  fn notifier_complete_without_value() {
+    let mut completed = false;
+    let mut items = vec![];
+    let mut source = Subject::default();
+    let notifier = Subject::<(), ()>::default();
+
+    source
+      .clone()
+      .skip_until(notifier.clone())
+      .on_complete(|| completed = true)
+      .subscribe(|v| items.push(v));
+
+    source.next(1);
+    notifier.complete();
+    source.next(2);
+    source.complete();
+
+    assert!(items.is_empty());
+    assert!(completed);
+  }
+
+  #[test]
+  fn drop_notifier_after_first_value() {
+    let mut items = vec![];
+    {
+      let mut source = Subject::default();
+      let mut notifier = Subject::<(), ()>::default();
+
+      source
+        .clone()
+        .skip_until(notifier.clone())
+        .subscribe(|v| items.push(v));
+
+      source.next(1);
+      notifier.next(());
+      notifier.retain();
+      assert!(notifier.is_empty());
+      source.next(2);
+    }
+
+    assert_eq!(items, [2]);
+  }
+
+  #[test]
+  fn unsubscribe_cancel_notifier() {
+    let source = Subject::<i32, _>::default();
+    let notifier = Subject::<(), ()>::default();
+    let alive = Rc::new(());
+    let c_alive = alive.clone();
+
+    let subscription =
+      source
+        .clone()
+        .skip_until(notifier.clone())
+        .subscribe(move |_| {
+          let _ = &c_alive;
+        });
+    assert_eq!(Rc::strong_count(&alive), 2);
+
+    subscription.unsubscribe();
+    assert_eq!(Rc::strong_count(&alive), 1);
+  }
+
   #[test]
   fn skip_until_support_fork() {
     let mut items1 = vec![];
@@ -81,6 +81,39 @@ mod test {
     assert!(completed);
   }
 
+  #[test]
+  fn not_re_suppress_after_gate_opened() {
+    let mut items = vec![];
+    let mut completed = false;
+
+    observable::from_iter([1, 2, 5, 1, 2, 6])
+      .skip_while(|v| v < &3)
+      .on_complete(|| completed = true)
+      .subscribe(|v| items.push(v));
+
+    assert_eq!(items, [5, 1, 2, 6]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn error_while_skipping() {
+    let mut next_count = 0;
+    let mut error = None;
+    let mut subject = Subject::default();
+
+    subject
+      .clone()
+      .skip_while(|v| v < &3)
+      .on_error(|e| error = Some(e))
+      .subscribe(|_: i32| next_count += 1);
+
+    subject.next(1);
+    subject.error("err");
+
+    assert_eq!(next_count, 0);
+    assert_eq!(error, Some("err"));
+  }
+
   #[test]
   fn skip_while_support_fork() {
     let mut nc1 = 0;
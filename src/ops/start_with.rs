@@ -1,29 +1,41 @@
 use crate::prelude::*;
 
 #[derive(Clone)]
-pub struct StartWithOp<S, B> {
+pub struct StartWithOp<S, I> {
   pub(crate) source: S,
-  pub(crate) values: Vec<B>,
+  pub(crate) values: I,
 }
 
-impl<Item, Err, O, S> Observable<Item, Err, O> for StartWithOp<S, Item>
+impl<Item, Err, O, S, I> Observable<Item, Err, O> for StartWithOp<S, I>
 where
   S: Observable<Item, Err, O>,
   O: Observer<Item, Err>,
+  I: IntoIterator<Item = Item>,
 {
-  type Unsub = S::Unsub;
+  type Unsub = Option<S::Unsub>;
 
   fn actual_subscribe(self, mut observer: O) -> Self::Unsub {
     for val in self.values {
+      if observer.is_finished() {
+        break;
+      }
       observer.next(val);
     }
 
-    self.source.actual_subscribe(observer)
+    // The observer may be finished by the prepended values, then the source
+    // is never subscribed.
+    if observer.is_finished() {
+      None
+    } else {
+      Some(self.source.actual_subscribe(observer))
+    }
   }
 }
 
-impl<Item, Err, S> ObservableExt<Item, Err> for StartWithOp<S, Item> where
-  S: ObservableExt<Item, Err>
+impl<Item, Err, S, I> ObservableExt<Item, Err> for StartWithOp<S, I>
+where
+  S: ObservableExt<Item, Err>,
+  I: IntoIterator<Item = Item>,
 {
 }
 
@@ -84,6 +96,46 @@ mod test {
     assert_eq!(values.borrow().as_ref(), vec![0]);
   }
 
+  #[test]
+  fn single_value() {
+    let mut values = vec![];
+    observable::of(1)
+      .start_with([0])
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1]);
+  }
+
+  #[test]
+  fn emit_for_every_subscription() {
+    let mut first = vec![];
+    let mut second = vec![];
+    let source = observable::from_iter(1..3).start_with(0..1);
+    source.clone().subscribe(|v| first.push(v));
+    source.subscribe(|v| second.push(v));
+
+    assert_eq!(first, vec![0, 1, 2]);
+    assert_eq!(second, vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn finished_by_prepended_values() {
+    let subscribed = Rc::new(RefCell::new(false));
+    let c_subscribed = subscribed.clone();
+    let mut values = vec![];
+
+    observable::defer(move || {
+      *c_subscribed.borrow_mut() = true;
+      observable::of(3)
+    })
+    .start_with(vec![0, 1, 2])
+    .take(2)
+    .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![0, 1]);
+    assert!(!*subscribed.borrow());
+  }
+
   #[test]
   fn bench() {
     do_bench();
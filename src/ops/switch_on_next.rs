@@ -0,0 +1,420 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+#[derive(Clone)]
+pub struct SwitchOnNextOp<'a, S, ObservableItem> {
+  source: S,
+  _marker: TypeHint<&'a ObservableItem>,
+}
+
+#[derive(Clone)]
+pub struct SwitchOnNextOpThreads<S, ObservableItem> {
+  source: S,
+  _marker: TypeHint<ObservableItem>,
+}
+
+macro_rules! impl_new_method {
+  ($name: ident $(,$lf:lifetime)?) => {
+    impl<$($lf,)? S, ObservableItem> $name<$($lf,)? S, ObservableItem> {
+      #[inline]
+      pub(crate) fn new(source: S) -> Self {
+        Self { source, _marker: TypeHint::default() }
+      }
+    }
+  };
+}
+
+impl_new_method!(SwitchOnNextOp, 'a);
+impl_new_method!(SwitchOnNextOpThreads);
+
+macro_rules! impl_observable_method {
+  ($box_unsub: ty, $outside_observer: ident, $rc: ident) => {
+    type Unsub =
+      ZipSubscription<$rc<Option<$box_unsub>>, $rc<Option<$box_unsub>>>;
+
+    fn actual_subscribe(self, observer: O) -> Self::Unsub {
+      let observer = $rc::own(Some(observer));
+      let outer_unsub = $rc::own(None);
+      let inner_unsub = $rc::own(None);
+      let outside_observer = $outside_observer {
+        observer: observer.clone(),
+        data: $rc::own(SwitchData {
+          active: None,
+          next_id: 0,
+          outside_completed: false,
+          outside_emitting: false,
+        }),
+        outer_unsub: outer_unsub.clone(),
+        inner_unsub: inner_unsub.clone(),
+        _hint: TypeHint::new(),
+      };
+      let unsub = self.source.actual_subscribe(outside_observer);
+      if observer.is_finished() {
+        // an inner observable errored during the subscription.
+        unsub.unsubscribe();
+      } else {
+        *outer_unsub.rc_deref_mut() = Some(<$box_unsub>::new(unsub));
+      }
+      ZipSubscription::new(outer_unsub, inner_unsub)
+    }
+  };
+}
+
+impl<'a, ObservableItem, Item, Err, O, S> Observable<Item, Err, O>
+  for SwitchOnNextOp<'a, S, ObservableItem>
+where
+  O: Observer<Item, Err> + 'a,
+  S: Observable<ObservableItem, Err, OutsideObserver<'a, O, Item>>,
+  S::Unsub: 'a,
+  ObservableItem: Observable<Item, Err, InnerObserver<'a, O>>,
+  ObservableItem::Unsub: 'a,
+{
+  impl_observable_method!(BoxSubscription<'a>, OutsideObserver, MutRc);
+}
+
+impl<'a, ObservableItem, Item, Err, S> ObservableExt<Item, Err>
+  for SwitchOnNextOp<'a, S, ObservableItem>
+where
+  S: ObservableExt<ObservableItem, Err>,
+  ObservableItem: ObservableExt<Item, Err>,
+{
+}
+
+impl<ObservableItem, Item, Err, O, S> Observable<Item, Err, O>
+  for SwitchOnNextOpThreads<S, ObservableItem>
+where
+  O: Observer<Item, Err> + Send + 'static,
+  S: Observable<ObservableItem, Err, OutsideObserverThreads<O, Item>>,
+  S::Unsub: Send + 'static,
+  ObservableItem: Observable<Item, Err, InnerObserverThreads<O>>,
+  ObservableItem::Unsub: Send + 'static,
+{
+  impl_observable_method!(
+    BoxSubscriptionThreads,
+    OutsideObserverThreads,
+    MutArc
+  );
+}
+
+impl<ObservableItem, Item, Err, S> ObservableExt<Item, Err>
+  for SwitchOnNextOpThreads<S, ObservableItem>
+where
+  S: ObservableExt<ObservableItem, Err>,
+  ObservableItem: ObservableExt<Item, Err>,
+{
+}
+
+pub struct SwitchData {
+  // The id of the inner observable currently mirrored, `None` if it completed.
+  active: Option<usize>,
+  next_id: usize,
+  outside_completed: bool,
+  // The outer subscription can't be unsubscribed during its own emission, an
+  // inner observable erroring meanwhile leaves it to the finished observer.
+  outside_emitting: bool,
+}
+
+pub struct OutsideObserver<'a, O, Item> {
+  observer: MutRc<Option<O>>,
+  data: MutRc<SwitchData>,
+  outer_unsub: MutRc<Option<BoxSubscription<'a>>>,
+  inner_unsub: MutRc<Option<BoxSubscription<'a>>>,
+  _hint: TypeHint<Item>,
+}
+
+pub struct OutsideObserverThreads<O, Item> {
+  observer: MutArc<Option<O>>,
+  data: MutArc<SwitchData>,
+  outer_unsub: MutArc<Option<BoxSubscriptionThreads>>,
+  inner_unsub: MutArc<Option<BoxSubscriptionThreads>>,
+  _hint: TypeHint<Item>,
+}
+
+pub struct InnerObserver<'a, O> {
+  observer: MutRc<Option<O>>,
+  data: MutRc<SwitchData>,
+  outer_unsub: MutRc<Option<BoxSubscription<'a>>>,
+  id: usize,
+}
+
+pub struct InnerObserverThreads<O> {
+  observer: MutArc<Option<O>>,
+  data: MutArc<SwitchData>,
+  outer_unsub: MutArc<Option<BoxSubscriptionThreads>>,
+  id: usize,
+}
+
+macro_rules! impl_inner_observer {
+  ($ty: ty $(, $lf: lifetime)?) => {
+    impl<$($lf,)? Item, Err, O> Observer<Item, Err> for $ty
+    where
+      O: Observer<Item, Err>,
+    {
+      fn next(&mut self, value: Item) {
+        if self.data.rc_deref().active == Some(self.id) {
+          self.observer.next(value)
+        }
+      }
+
+      fn error(self, err: Err) {
+        let outside_emitting = {
+          let mut data = self.data.rc_deref_mut();
+          if data.active != Some(self.id) {
+            return;
+          }
+          data.active = None;
+          data.outside_emitting
+        };
+        if !outside_emitting {
+          self.outer_unsub.unsubscribe();
+        }
+        self.observer.error(err)
+      }
+
+      fn complete(self) {
+        let completed = {
+          let mut data = self.data.rc_deref_mut();
+          if data.active != Some(self.id) {
+            return;
+          }
+          data.active = None;
+          data.outside_completed
+        };
+        if completed {
+          self.observer.complete();
+        }
+      }
+
+      fn is_finished(&self) -> bool {
+        self.data.rc_deref().active != Some(self.id)
+          || self.observer.is_finished()
+      }
+    }
+  };
+}
+
+impl_inner_observer!(InnerObserver<'a, O>, 'a);
+impl_inner_observer!(InnerObserverThreads<O>);
+
+macro_rules! impl_outside_observer {
+  ($outside_ty: ty, $inner: ident, $box_unsub: ty, $($lf:lifetime)? $($send:ident)?) => {
+    impl<$($lf,)? Item, Err, O, ObservableItem> Observer<ObservableItem, Err>
+      for $outside_ty
+    where
+      O: Observer<Item, Err>,
+      ObservableItem: Observable<Item, Err, $inner<$($lf,)? O>>,
+      ObservableItem::Unsub: $($lf)? $($send + 'static)?,
+    {
+      fn next(&mut self, value: ObservableItem) {
+        let id = {
+          let mut data = self.data.rc_deref_mut();
+          let id = data.next_id;
+          data.next_id += 1;
+          data.active = Some(id);
+          data.outside_emitting = true;
+          id
+        };
+        // Unsubscribe the previous inner observable before switching to the
+        // new one.
+        self.inner_unsub.clone().unsubscribe();
+        let unsub = value.actual_subscribe($inner {
+          observer: self.observer.clone(),
+          data: self.data.clone(),
+          outer_unsub: self.outer_unsub.clone(),
+          id,
+        });
+        *self.inner_unsub.rc_deref_mut() = Some(<$box_unsub>::new(unsub));
+        self.data.rc_deref_mut().outside_emitting = false;
+      }
+
+      fn error(self, err: Err) {
+        self.data.rc_deref_mut().active = None;
+        self.inner_unsub.unsubscribe();
+        self.observer.error(err);
+      }
+
+      fn complete(self) {
+        let completed = {
+          let mut data = self.data.rc_deref_mut();
+          data.outside_completed = true;
+          data.active.is_none()
+        };
+        if completed {
+          self.observer.complete();
+        }
+      }
+
+      #[inline]
+      fn is_finished(&self) -> bool {
+        self.observer.is_finished()
+      }
+    }
+  };
+}
+
+impl_outside_observer!(OutsideObserver<'a, O, Item>, InnerObserver, BoxSubscription<'a>, 'a);
+impl_outside_observer!(OutsideObserverThreads<O, Item>, InnerObserverThreads, BoxSubscriptionThreads, Send);
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    observable::fake_timer::FakeClock,
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+  };
+
+  #[test]
+  fn switch_to_latest_inner() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let mut outer = Subject::default();
+    let mut a = Subject::default();
+    let mut b = Subject::default();
+
+    outer
+      .clone()
+      .switch_on_next()
+      .subscribe(move |v: i32| values.borrow_mut().push(v));
+
+    outer.next(a.clone());
+    a.next(1);
+    outer.next(b.clone());
+    a.next(2);
+    b.next(3);
+
+    assert_eq!(&*c_values.borrow(), &[1, 3]);
+  }
+
+  #[test]
+  fn unsubscribe_previous_inner() {
+    let unsubscribed = MutRc::own(vec![]);
+    let c_unsubscribed = unsubscribed.clone();
+    let mut outer = Subject::default();
+
+    outer.clone().switch_on_next().subscribe(|_: i32| {});
+
+    for i in 0..3 {
+      let c_unsubscribed = c_unsubscribed.clone();
+      outer.next(
+        Subject::default()
+          .finalize(move || c_unsubscribed.rc_deref_mut().push(i)),
+      );
+    }
+    assert_eq!(&*unsubscribed.rc_deref(), &[0, 1]);
+  }
+
+  #[test]
+  fn complete_after_outer_and_last_inner() {
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let outer = Subject::default();
+    let a = Subject::<i32, _>::default();
+    let b = Subject::default();
+
+    outer
+      .clone()
+      .switch_on_next()
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(|_| {});
+
+    let mut c_outer = outer.clone();
+    c_outer.next(a.clone());
+    c_outer.next(b.clone());
+    a.complete();
+    outer.complete();
+    assert!(!*completed.rc_deref());
+
+    b.complete();
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn unsubscribe_cancel_inner() {
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let mut outer = Subject::default();
+
+    let subscription = outer.clone().switch_on_next().subscribe(|_: i32| {});
+    outer.next(
+      Subject::default().finalize(move || *c_finalized.rc_deref_mut() = true),
+    );
+    assert!(!*finalized.rc_deref());
+
+    subscription.unsubscribe();
+    assert!(*finalized.rc_deref());
+  }
+
+  #[test]
+  fn inner_error_unsubscribe_outer() {
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let errors = MutRc::own(vec![]);
+    let c_errors = errors.clone();
+    let mut outer = Subject::default();
+    let inner = Subject::<i32, _>::default();
+
+    outer
+      .clone()
+      .finalize(move || *c_finalized.rc_deref_mut() = true)
+      .switch_on_next()
+      .on_error(move |e| c_errors.rc_deref_mut().push(e))
+      .subscribe(|_| {});
+    outer.next(inner.clone());
+    inner.error("error");
+
+    assert_eq!(&*errors.rc_deref(), &["error"]);
+    assert!(*finalized.rc_deref());
+  }
+
+  #[test]
+  fn switch_on_next_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut outer = SubjectThreads::default();
+    let mut a = SubjectThreads::default();
+    let mut b = SubjectThreads::default();
+
+    outer
+      .clone()
+      .switch_on_next_threads()
+      .subscribe(move |v: i32| c_values.lock().unwrap().push(v));
+
+    outer.next(a.clone());
+    a.next(1);
+    outer.next(b.clone());
+    a.next(2);
+    b.next(3);
+
+    assert_eq!(&*values.lock().unwrap(), &[1, 3]);
+  }
+
+  #[test]
+  fn switch_map_cancel_previous_request() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let clock = FakeClock::default();
+    let c_clock = clock.clone();
+
+    clock
+      .interval(Duration::from_millis(3))
+      .take(3)
+      .switch_map(move |i| {
+        c_clock
+          .interval(Duration::from_millis(2))
+          .take(2)
+          .map(move |j| (i, j))
+      })
+      .subscribe(move |v| values.borrow_mut().push(v));
+    clock.advance(Duration::from_millis(20));
+
+    assert_eq!(&*c_values.borrow(), &[(0, 0), (1, 0), (2, 0), (2, 1)]);
+  }
+}
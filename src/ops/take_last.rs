@@ -31,7 +31,7 @@ impl<Item, Err, S> ObservableExt<Item, Err> for TakeLastOp<S> where
 pub struct TakeLastObserver<O, Item> {
   observer: O,
   count: usize,
-  queue: VecDeque<Item>, // TODO: replace VecDeque with RingBuf
+  queue: VecDeque<Item>,
 }
 
 impl<Item, Err, O> Observer<Item, Err> for TakeLastObserver<O, Item>
@@ -39,10 +39,13 @@ where
   O: Observer<Item, Err>,
 {
   fn next(&mut self, value: Item) {
-    self.queue.push_back(value);
-    while self.queue.len() > self.count {
+    if self.count == 0 {
+      return;
+    }
+    if self.queue.len() == self.count {
       self.queue.pop_front();
     }
+    self.queue.push_back(value);
   }
 
   #[inline]
@@ -81,6 +84,54 @@ mod test {
     assert!(completed);
   }
 
+  #[test]
+  fn source_shorter_than_count() {
+    let mut completed = false;
+    let mut ticks = vec![];
+
+    observable::from_iter(0..3)
+      .take_last(5)
+      .on_complete(|| completed = true)
+      .subscribe(|v| ticks.push(v));
+
+    assert_eq!(ticks, vec![0, 1, 2]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn zero_count() {
+    let mut completed = false;
+    let mut next_count = 0;
+
+    observable::from_iter(0..100)
+      .take_last(0)
+      .on_complete(|| completed = true)
+      .subscribe(|_| next_count += 1);
+
+    assert_eq!(next_count, 0);
+    assert!(completed);
+  }
+
+  #[test]
+  fn discard_on_error() {
+    let mut next_count = 0;
+    let mut error = None;
+    let mut subject = Subject::default();
+
+    subject
+      .clone()
+      .take_last(2)
+      .on_error(|e| error = Some(e))
+      .subscribe(|_: i32| next_count += 1);
+
+    subject.next(1);
+    subject.next(2);
+    subject.error("err");
+
+    assert_eq!(next_count, 0);
+    assert_eq!(error, Some("err"));
+  }
+
   #[test]
   fn take_last_support_fork() {
     let mut nc1 = 0;
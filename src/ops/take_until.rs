@@ -1,6 +1,6 @@
 use crate::{
   prelude::*,
-  rc::{MutArc, MutRc},
+  rc::{MutArc, MutRc, RcDerefMut},
 };
 
 #[derive(Clone)]
@@ -38,22 +38,39 @@ macro_rules! impl_take_until {
       N: Observable<
         NotifyItem,
         NotifyErr,
-        TakeUntilNotifierObserver<Item, Err, $rc<Option<O>>>,
+        TakeUntilNotifierObserver<
+          Item,
+          Err,
+          $rc<Option<O>>,
+          $rc<Option<S::Unsub>>,
+        >,
       >,
     {
-      type Unsub = ZipSubscription<S::Unsub, N::Unsub>;
+      type Unsub = ZipSubscription<$rc<Option<S::Unsub>>, N::Unsub>;
 
       fn actual_subscribe(self, observer: O) -> Self::Unsub {
         // We need to keep a reference to the observer from two places
         let main_observer = $rc::own(Some(observer));
+        let source_unsub = $rc::own(None);
 
-        let a = self.source.actual_subscribe(main_observer.clone());
+        // Subscribe the notifier first, so a notifier that fires immediately
+        // completes the output before the source is ever subscribed.
         let notify_observer = TakeUntilNotifierObserver {
-          main_observer,
+          main_observer: main_observer.clone(),
+          source_unsub: source_unsub.clone(),
           _hint: TypeHint::default(),
         };
         let b = self.notifier.actual_subscribe(notify_observer);
-        ZipSubscription::new(a, b)
+
+        if !main_observer.is_finished() {
+          let a = self.source.actual_subscribe(main_observer.clone());
+          if main_observer.is_finished() {
+            a.unsubscribe();
+          } else {
+            *source_unsub.rc_deref_mut() = Some(a);
+          }
+        }
+        ZipSubscription::new(source_unsub, b)
       }
     }
 
@@ -70,19 +87,23 @@ macro_rules! impl_take_until {
 impl_take_until!(TakeUntilOp, MutRc);
 impl_take_until!(TakeUntilOpThreads, MutArc);
 
-pub struct TakeUntilNotifierObserver<Item, Err, O> {
+pub struct TakeUntilNotifierObserver<Item, Err, O, U> {
   // We need access to main observer in order to call `complete` on it as soon
   // as notifier fired
   main_observer: O,
+  // The source subscription, unsubscribed as soon as notifier fired
+  source_unsub: U,
   _hint: TypeHint<(Item, Err)>,
 }
 
-impl<Item, Err, NotifyItem, NotifyErr, O> Observer<NotifyItem, NotifyErr>
-  for TakeUntilNotifierObserver<Item, Err, O>
+impl<Item, Err, NotifyItem, NotifyErr, O, U> Observer<NotifyItem, NotifyErr>
+  for TakeUntilNotifierObserver<Item, Err, O, U>
 where
   O: Observer<Item, Err> + Clone,
+  U: Subscription + Clone,
 {
   fn next(&mut self, _: NotifyItem) {
+    self.source_unsub.clone().unsubscribe();
     self.main_observer.clone().complete();
   }
 
@@ -134,6 +155,44 @@ mod test {
     assert_eq!(completed_count, 1);
   }
 
+  #[test]
+  fn notifier_fire_before_first_value() {
+    let mut next_count = 0;
+    let mut completed = false;
+    {
+      let source = Subject::<i32, _>::default();
+
+      source
+        .clone()
+        .take_until(observable::of(()))
+        .on_complete(|| completed = true)
+        .subscribe(|_| next_count += 1);
+
+      assert!(source.is_empty());
+    }
+    assert_eq!(next_count, 0);
+    assert!(completed);
+  }
+
+  #[test]
+  fn unsubscribe_source_when_notifier_fire() {
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let mut source = Subject::default();
+    let mut notifier = Subject::<(), ()>::default();
+
+    source
+      .clone()
+      .finalize(move || *c_finalized.rc_deref_mut() = true)
+      .take_until(notifier.clone())
+      .subscribe(|_: i32| {});
+
+    source.next(1);
+    assert!(!*finalized.rc_deref());
+    notifier.next(());
+    assert!(*finalized.rc_deref());
+  }
+
   #[test]
   fn circular() {
     let last_next_arg = MutRc::own(None);
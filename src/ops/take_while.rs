@@ -69,7 +69,7 @@ where
   }
 
   fn is_finished(&self) -> bool {
-    self.observer.as_ref().map_or(true, |o| o.is_finished())
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
   }
 }
 
@@ -105,6 +105,40 @@ mod test {
     assert!(completed);
   }
 
+  #[test]
+  fn inclusive_sentinel_from_infinite_source() {
+    let mut items = vec![];
+    let mut completed = false;
+
+    observable::from_iter(0..)
+      .take_while_inclusive(|v| v < &3)
+      .on_complete(|| completed = true)
+      .subscribe(|v| items.push(v));
+
+    assert_eq!(items, [0, 1, 2, 3]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn inclusive_unsubscribe_subject_source() {
+    let mut items = vec![];
+    {
+      let mut source = Subject::default();
+      source
+        .clone()
+        .take_while_inclusive(|v| v != &0)
+        .subscribe(|v| items.push(v));
+
+      source.next(1);
+      source.next(0);
+      source.retain();
+      assert!(source.is_empty());
+      source.next(2);
+    }
+
+    assert_eq!(items, [1, 0]);
+  }
+
   #[test]
   fn take_while_support_fork() {
     let mut nc1 = 0;
@@ -1,49 +1,111 @@
 use crate::prelude::*;
 
+/// The side effects run by [`tap_with`](ObservableExt::tap_with), every
+/// method receives a reference to the signal before it is forwarded and does
+/// nothing by default.
+pub trait Tap<Item, Err> {
+  #[inline]
+  fn tap_next(&mut self, _value: &Item) {}
+
+  #[inline]
+  fn tap_error(&mut self, _err: &Err) {}
+
+  #[inline]
+  fn tap_complete(&mut self) {}
+}
+
+/// Taps the items with a closure, created by `tap` and `tap_next`.
+#[derive(Clone)]
+pub struct TapNext<F>(pub(crate) F);
+
+/// Taps the error with a closure, created by `tap_error`.
+#[derive(Clone)]
+pub struct TapError<F>(pub(crate) Option<F>);
+
+/// Taps the completion with a closure, created by `tap_complete`.
+#[derive(Clone)]
+pub struct TapComplete<F>(pub(crate) Option<F>);
+
+impl<Item, Err, F> Tap<Item, Err> for TapNext<F>
+where
+  F: FnMut(&Item),
+{
+  #[inline]
+  fn tap_next(&mut self, value: &Item) {
+    (self.0)(value)
+  }
+}
+
+impl<Item, Err, F> Tap<Item, Err> for TapError<F>
+where
+  F: FnOnce(&Err),
+{
+  #[inline]
+  fn tap_error(&mut self, err: &Err) {
+    if let Some(f) = self.0.take() {
+      f(err)
+    }
+  }
+}
+
+impl<Item, Err, F> Tap<Item, Err> for TapComplete<F>
+where
+  F: FnOnce(),
+{
+  #[inline]
+  fn tap_complete(&mut self) {
+    if let Some(f) = self.0.take() {
+      f()
+    }
+  }
+}
+
 #[derive(Clone)]
-pub struct TapOp<S, M> {
+pub struct TapOp<S, T> {
   pub(crate) source: S,
-  pub(crate) func: M,
+  pub(crate) tap: T,
 }
 
-impl<Item, Err, S, M, O> Observable<Item, Err, O> for TapOp<S, M>
+impl<Item, Err, S, T, O> Observable<Item, Err, O> for TapOp<S, T>
 where
-  S: Observable<Item, Err, TapObserver<O, M>>,
-  M: FnMut(&Item),
+  S: Observable<Item, Err, TapObserver<O, T>>,
+  T: Tap<Item, Err>,
   O: Observer<Item, Err>,
 {
   type Unsub = S::Unsub;
   fn actual_subscribe(self, observer: O) -> Self::Unsub {
-    let func = self.func;
-    self.source.actual_subscribe(TapObserver { observer, func })
+    let tap = self.tap;
+    self.source.actual_subscribe(TapObserver { observer, tap })
   }
 }
 
-impl<Item, Err, S, M> ObservableExt<Item, Err> for TapOp<S, M> where
+impl<Item, Err, S, T> ObservableExt<Item, Err> for TapOp<S, T> where
   S: ObservableExt<Item, Err>
 {
 }
 #[derive(Clone)]
-pub struct TapObserver<O, F> {
+pub struct TapObserver<O, T> {
   observer: O,
-  func: F,
+  tap: T,
 }
 
-impl<Item, Err, O, F> Observer<Item, Err> for TapObserver<O, F>
+impl<Item, Err, O, T> Observer<Item, Err> for TapObserver<O, T>
 where
   O: Observer<Item, Err>,
-  F: FnMut(&Item),
+  T: Tap<Item, Err>,
 {
   fn next(&mut self, value: Item) {
-    (self.func)(&value);
+    self.tap.tap_next(&value);
     self.observer.next(value)
   }
 
-  fn error(self, err: Err) {
+  fn error(mut self, err: Err) {
+    self.tap.tap_error(&err);
     self.observer.error(err)
   }
 
-  fn complete(self) {
+  fn complete(mut self) {
+    self.tap.tap_complete();
     self.observer.complete()
   }
 
@@ -55,6 +117,7 @@ where
 
 #[cfg(test)]
 mod test {
+  use super::Tap;
   use crate::prelude::*;
 
   #[test]
@@ -68,6 +131,52 @@ mod test {
     assert_eq!(v, 100);
   }
 
+  #[test]
+  fn tap_terminations() {
+    let mut error = None;
+    let mut completed = false;
+
+    observable::of_result(Err::<i32, _>("fail"))
+      .tap_error(|e| error = Some(*e))
+      .on_error(|_| {})
+      .subscribe(|_| {});
+    observable::of(1)
+      .tap_complete(|| completed = true)
+      .subscribe(|_| {});
+
+    assert_eq!(error, Some("fail"));
+    assert!(completed);
+  }
+
+  #[test]
+  fn tap_with_hooks() {
+    #[derive(Default)]
+    struct Log(Vec<&'static str>);
+
+    impl<Item, Err> Tap<Item, Err> for &mut Log {
+      fn tap_next(&mut self, _: &Item) {
+        self.0.push("next");
+      }
+
+      fn tap_complete(&mut self) {
+        self.0.push("complete");
+      }
+    }
+
+    // Only a reference of the items is needed, they don't need to be `Clone`.
+    struct NotClone(i32);
+
+    let mut log = Log::default();
+    let mut sum = 0;
+    observable::from_iter([1, 2])
+      .map(NotClone)
+      .tap_with(&mut log)
+      .subscribe(|v| sum += v.0);
+
+    assert_eq!(log.0, vec!["next", "next", "complete"]);
+    assert_eq!(sum, 3);
+  }
+
   #[test]
   fn benchmark() {
     do_bench();
@@ -1,6 +1,6 @@
 use crate::{
   prelude::*,
-  rc::{MutArc, RcDerefMut},
+  rc::{MutArc, RcDeref, RcDerefMut},
 };
 use std::time::Duration;
 
@@ -36,15 +36,16 @@ impl ThrottleEdge {
   }
 }
 
+type RcHandler = MutArc<Option<TaskHandle<NormalReturn<()>>>>;
+
 impl<Item, Err, O, S, SD, F> Observable<Item, Err, O> for ThrottleOp<S, SD, F>
 where
-  Item: Clone,
   O: Observer<Item, Err>,
   S: Observable<Item, Err, ThrottleObserver<O, SD, Item, F>>,
   F: FnMut(&Item) -> Duration,
   ThrottleObserver<O, SD, Item, F>: Observer<Item, Err>,
 {
-  type Unsub = S::Unsub;
+  type Unsub = ZipSubscription<S::Unsub, RcHandler>;
 
   fn actual_subscribe(self, observer: O) -> Self::Unsub {
     let Self {
@@ -54,14 +55,16 @@ where
       edge,
     } = self;
 
-    source.actual_subscribe(ThrottleObserver {
+    let task_handler = MutArc::own(None);
+    let u = source.actual_subscribe(ThrottleObserver {
       observer: MutArc::own(Some(observer)),
       edge,
       duration_selector,
       trailing_value: MutArc::own(None),
-      task_handler: TaskHandle::value_handle(NormalReturn::new(())),
+      task_handler: task_handler.clone(),
       scheduler,
-    })
+    });
+    ZipSubscription::new(u, task_handler)
   }
 }
 
@@ -76,13 +79,28 @@ pub struct ThrottleObserver<O, SD, Item, F> {
   edge: ThrottleEdge,
   duration_selector: F,
   trailing_value: MutArc<Option<Item>>,
-  task_handler: TaskHandle<NormalReturn<()>>,
+  task_handler: RcHandler,
+}
+
+impl<O, SD, Item, F> ThrottleObserver<O, SD, Item, F> {
+  fn in_window(&self) -> bool {
+    self
+      .task_handler
+      .rc_deref()
+      .as_ref()
+      .is_some_and(|h| !h.is_closed())
+  }
+
+  fn cancel_window(&self) {
+    if let Some(handler) = self.task_handler.rc_deref_mut().take() {
+      handler.unsubscribe()
+    }
+  }
 }
 
 impl<Item, Err, O, SD, F> Observer<Item, Err>
   for ThrottleObserver<O, SD, Item, F>
 where
-  Item: Clone,
   O: Observer<Item, Err>,
   F: FnMut(&Item) -> Duration,
   SD: Scheduler<
@@ -90,34 +108,41 @@ where
   >,
 {
   fn next(&mut self, value: Item) {
-    if self.edge.leading || self.edge.tailing {
+    if self.in_window() {
       if self.edge.tailing {
-        *self.trailing_value.rc_deref_mut() = Some(value.clone());
+        *self.trailing_value.rc_deref_mut() = Some(value);
+      }
+    } else if self.edge.leading || self.edge.tailing {
+      let delay = (self.duration_selector)(&value);
+      // No window to throttle the next items, emit the item right away.
+      if delay.is_zero() {
+        self.observer.next(value);
+        return;
       }
-      if self.task_handler.is_closed() {
-        let delay = (self.duration_selector)(&value);
-        if self.edge.leading {
-          self.observer.next(value)
-        }
-        let task = OnceTask::new(
-          throttle_task,
-          (self.observer.clone(), self.trailing_value.clone()),
-        );
-        self.task_handler = self.scheduler.schedule(task, Some(delay));
+      let task = OnceTask::new(
+        throttle_task,
+        (self.observer.clone(), self.trailing_value.clone()),
+      );
+      let handler = self.scheduler.schedule(task, Some(delay));
+      *self.task_handler.rc_deref_mut() = Some(handler);
+      if self.edge.leading {
+        self.observer.next(value)
+      } else {
+        *self.trailing_value.rc_deref_mut() = Some(value);
       }
     }
   }
 
   fn error(self, err: Err) {
+    self.cancel_window();
     self.observer.error(err);
-    self.task_handler.unsubscribe();
   }
 
   fn complete(mut self) {
     if let Some(value) = self.trailing_value.rc_deref_mut().take() {
       self.observer.next(value);
     }
-    self.task_handler.unsubscribe();
+    self.cancel_window();
     self.observer.complete();
   }
 
@@ -142,7 +167,7 @@ where
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::rc::{MutRc, RcDeref};
+  use crate::rc::MutRc;
 
   #[test]
   fn smoke() {
@@ -210,4 +235,110 @@ mod tests {
 
     assert_eq!(&*x_c.rc_deref(), &[0, 3]);
   }
+
+  #[test]
+  fn source_faster_than_window() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = FuturesLocalSchedulerPool::new();
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .throttle_time(
+        Duration::from_millis(5),
+        ThrottleEdge::leading(),
+        pool.spawner(),
+      )
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    subject.next(1);
+    subject.next(2);
+    subject.next(3);
+    pool.run();
+    subject.next(4);
+    assert_eq!(&*x.rc_deref(), &[1, 4]);
+  }
+
+  #[test]
+  fn source_slower_than_window() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = FuturesLocalSchedulerPool::new();
+    let scheduler = pool.spawner();
+    observable::interval(Duration::from_millis(5), scheduler.clone())
+      .take(4)
+      .throttle_time(Duration::from_millis(1), ThrottleEdge::all(), scheduler)
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    pool.run();
+    assert_eq!(&*x.rc_deref(), &[0, 1, 2, 3]);
+  }
+
+  #[test]
+  fn zero_duration_not_throttle() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = FuturesLocalSchedulerPool::new();
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .throttle(
+        |v: &i32| {
+          if *v < 3 {
+            Duration::ZERO
+          } else {
+            Duration::from_millis(5)
+          }
+        },
+        ThrottleEdge::leading(),
+        pool.spawner(),
+      )
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    subject.next(1);
+    subject.next(2);
+    subject.next(3);
+    subject.next(4);
+    pool.run();
+    subject.next(1);
+    assert_eq!(&*x.rc_deref(), &[1, 2, 3, 1]);
+  }
+
+  #[test]
+  fn zero_duration_tailing_emit_immediately() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let pool = FuturesLocalSchedulerPool::new();
+    let mut subject = Subject::default();
+    subject
+      .clone()
+      .throttle_time(Duration::ZERO, ThrottleEdge::tailing(), pool.spawner())
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    subject.next(1);
+    subject.next(2);
+    assert_eq!(&*x.rc_deref(), &[1, 2]);
+  }
+
+  #[test]
+  fn unsubscribe_cancel_trailing_emission() {
+    let x = MutRc::own(vec![]);
+    let x_c = x.clone();
+    let mut pool = FuturesLocalSchedulerPool::new();
+    let mut subject = Subject::default();
+    let subscription = subject
+      .clone()
+      .throttle_time(
+        Duration::from_millis(5),
+        ThrottleEdge::all(),
+        pool.spawner(),
+      )
+      .subscribe(move |v| x_c.rc_deref_mut().push(v));
+
+    subject.next(1);
+    subject.next(2);
+    subscription.unsubscribe();
+    pool.run();
+    assert_eq!(&*x.rc_deref(), &[1]);
+  }
 }
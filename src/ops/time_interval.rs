@@ -0,0 +1,111 @@
+use crate::prelude::*;
+use std::time::{Duration, Instant};
+
+/// An item of the source with the time elapsed since the previous one, emitted
+/// by [`time_interval`](ObservableExt::time_interval).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeInterval<Item> {
+  pub value: Item,
+  pub interval: Duration,
+}
+
+#[derive(Clone)]
+pub struct TimeIntervalOp<S> {
+  pub(crate) source: S,
+}
+
+impl<Item, Err, O, S> Observable<TimeInterval<Item>, Err, O>
+  for TimeIntervalOp<S>
+where
+  O: Observer<TimeInterval<Item>, Err>,
+  S: Observable<Item, Err, TimeIntervalObserver<O>>,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self
+      .source
+      .actual_subscribe(TimeIntervalObserver { observer, last: Instant::now() })
+  }
+}
+
+impl<Item, Err, S> ObservableExt<TimeInterval<Item>, Err> for TimeIntervalOp<S> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct TimeIntervalObserver<O> {
+  observer: O,
+  last: Instant,
+}
+
+impl<Item, Err, O> Observer<Item, Err> for TimeIntervalObserver<O>
+where
+  O: Observer<TimeInterval<Item>, Err>,
+{
+  fn next(&mut self, value: Item) {
+    let now = Instant::now();
+    let interval = now - self.last;
+    self.last = now;
+    self.observer.next(TimeInterval { value, interval });
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    self.observer.error(err)
+  }
+
+  #[inline]
+  fn complete(self) {
+    self.observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use futures::executor::LocalPool;
+  use std::time::Duration;
+
+  #[test]
+  fn measure_intervals() {
+    let mut pool = LocalPool::new();
+    let intervals = MutRc::own(vec![]);
+    let c_intervals = intervals.clone();
+
+    observable::interval(Duration::from_millis(10), pool.spawner())
+      .take(3)
+      .time_interval()
+      .subscribe(move |v| c_intervals.rc_deref_mut().push(v));
+    pool.run();
+
+    let intervals = intervals.rc_deref();
+    let values: Vec<_> = intervals.iter().map(|t| t.value).collect();
+    assert_eq!(values, vec![0, 1, 2]);
+    assert!(intervals
+      .iter()
+      .all(|t| t.interval >= Duration::from_millis(10)));
+  }
+
+  #[test]
+  fn pass_error_through() {
+    let mut values = vec![];
+    let mut error = None;
+
+    observable::of_result(Err::<i32, _>("fail"))
+      .time_interval()
+      .on_error(|e| error = Some(e))
+      .subscribe(|v| values.push(v.value));
+
+    assert!(values.is_empty());
+    assert_eq!(error, Some("fail"));
+  }
+}
@@ -0,0 +1,394 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, RcDeref, RcDerefMut},
+};
+use std::{
+  fmt::Display,
+  time::{Duration, Instant},
+};
+
+/// The error emitted by [`timeout`](ObservableExt::timeout) when the source
+/// does not emit in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutError;
+
+impl Display for TimeoutError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "the observable did not emit in time")
+  }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// The fallback of `timeout`, an observable that errors with
+/// [`TimeoutError`] right after being subscribed.
+#[derive(Clone)]
+pub struct ThrowTimeout;
+
+impl<Item, Err, O> Observable<Item, Err, O> for ThrowTimeout
+where
+  O: Observer<Item, Err>,
+  Err: From<TimeoutError>,
+{
+  type Unsub = ();
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    observer.error(TimeoutError.into());
+  }
+}
+
+impl<Item, Err> ObservableExt<Item, Err> for ThrowTimeout {}
+
+#[derive(Clone)]
+pub struct TimeoutOp<S, F, SD> {
+  pub(crate) source: S,
+  pub(crate) duration: Duration,
+  pub(crate) fallback: F,
+  pub(crate) scheduler: SD,
+}
+
+type RcHandler = MutArc<Option<TaskHandle<NormalReturn<()>>>>;
+
+pub type TimeoutTask<O, F, FU, SU, SD> =
+  OnceTask<(TimeoutSubscription<O, F, FU, SU>, SD), NormalReturn<()>>;
+
+impl<Item, Err, O, S, F, SD> Observable<Item, Err, O> for TimeoutOp<S, F, SD>
+where
+  O: Observer<Item, Err>,
+  F: Observable<Item, Err, O>,
+  S: Observable<Item, Err, TimeoutObserver<O, F, F::Unsub>>,
+  SD: Scheduler<TimeoutTask<O, F, F::Unsub, S::Unsub, SD>>,
+{
+  type Unsub = TimeoutSubscription<O, F, F::Unsub, S::Unsub>;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    let Self { source, duration, fallback, scheduler } = self;
+    let observer = MutArc::own(Some(observer));
+    let state = MutArc::own(TimeoutState {
+      fallback: Some(fallback),
+      fallback_unsub: None,
+      duration,
+      deadline: Instant::now() + duration,
+      unsubscribed: false,
+    });
+    let subscription = TimeoutSubscription {
+      observer: observer.clone(),
+      state: state.clone(),
+      source: MutArc::own(None),
+      handle: MutArc::own(None),
+    };
+
+    // Only one task is scheduled at a time, it reschedules itself to the
+    // latest deadline instead of a new task for every item.
+    let task = OnceTask::new(
+      timeout_task::<Item, Err, O, F, S::Unsub, SD>,
+      (subscription.clone(), scheduler.clone()),
+    );
+    let handle = scheduler.schedule(task, Some(duration));
+    *subscription.handle.rc_deref_mut() = Some(handle);
+
+    let unsub = source.actual_subscribe(TimeoutObserver {
+      observer,
+      state,
+      handle: subscription.handle.clone(),
+    });
+    let terminated = subscription.observer.rc_deref().is_none();
+    if terminated {
+      unsub.unsubscribe();
+    } else {
+      *subscription.source.rc_deref_mut() = Some(unsub);
+    }
+    subscription
+  }
+}
+
+impl<Item, Err, S, F, SD> ObservableExt<Item, Err> for TimeoutOp<S, F, SD> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct TimeoutState<F, FU> {
+  fallback: Option<F>,
+  fallback_unsub: Option<FU>,
+  duration: Duration,
+  deadline: Instant,
+  unsubscribed: bool,
+}
+
+// The observer isn't a part of the state, so the state is never locked while
+// emitting and the observer can unsubscribe from its callbacks.
+pub struct TimeoutObserver<O, F, FU> {
+  observer: MutArc<Option<O>>,
+  state: MutArc<TimeoutState<F, FU>>,
+  handle: RcHandler,
+}
+
+impl<O, F, FU> TimeoutObserver<O, F, FU> {
+  fn cancel_timer(&self) {
+    if let Some(handle) = self.handle.rc_deref_mut().take() {
+      handle.unsubscribe()
+    }
+  }
+}
+
+impl<Item, Err, O, F, FU> Observer<Item, Err> for TimeoutObserver<O, F, FU>
+where
+  O: Observer<Item, Err>,
+{
+  fn next(&mut self, value: Item) {
+    {
+      let mut state = self.state.rc_deref_mut();
+      if state.unsubscribed {
+        return;
+      }
+      state.deadline = Instant::now() + state.duration;
+    }
+    self.observer.next(value);
+  }
+
+  fn error(self, err: Err) {
+    self.cancel_timer();
+    if !self.state.rc_deref().unsubscribed {
+      self.observer.error(err);
+    }
+  }
+
+  fn complete(self) {
+    self.cancel_timer();
+    if !self.state.rc_deref().unsubscribed {
+      self.observer.complete();
+    }
+  }
+
+  fn is_finished(&self) -> bool {
+    self.state.rc_deref().unsubscribed || self.observer.is_finished()
+  }
+}
+
+fn timeout_task<Item, Err, O, F, SU, SD>(
+  (subscription, scheduler): (TimeoutSubscription<O, F, F::Unsub, SU>, SD),
+) -> NormalReturn<()>
+where
+  O: Observer<Item, Err>,
+  F: Observable<Item, Err, O>,
+  SU: Subscription,
+  SD: Scheduler<TimeoutTask<O, F, F::Unsub, SU, SD>>,
+{
+  if subscription.observer.is_finished() {
+    return NormalReturn::new(());
+  }
+  let mut state = subscription.state.rc_deref_mut();
+  if state.unsubscribed {
+    return NormalReturn::new(());
+  }
+
+  let now = Instant::now();
+  if now < state.deadline {
+    let delay = state.deadline - now;
+    drop(state);
+    let handle = subscription.handle.clone();
+    let task = OnceTask::new(
+      timeout_task::<Item, Err, O, F, SU, SD>,
+      (subscription, scheduler.clone()),
+    );
+    *handle.rc_deref_mut() = Some(scheduler.schedule(task, Some(delay)));
+  } else {
+    let fallback = state.fallback.take();
+    drop(state);
+    let observer = subscription.observer.rc_deref_mut().take();
+    let (Some(observer), Some(fallback)) = (observer, fallback) else {
+      return NormalReturn::new(());
+    };
+    subscription.source.unsubscribe();
+    let unsub = fallback.actual_subscribe(observer);
+    let mut state = subscription.state.rc_deref_mut();
+    if state.unsubscribed {
+      drop(state);
+      unsub.unsubscribe();
+    } else {
+      state.fallback_unsub = Some(unsub);
+    }
+  }
+  NormalReturn::new(())
+}
+
+pub struct TimeoutSubscription<O, F, FU, SU> {
+  observer: MutArc<Option<O>>,
+  state: MutArc<TimeoutState<F, FU>>,
+  source: MutArc<Option<SU>>,
+  handle: RcHandler,
+}
+
+impl<O, F, FU, SU> Clone for TimeoutSubscription<O, F, FU, SU> {
+  fn clone(&self) -> Self {
+    Self {
+      observer: self.observer.clone(),
+      state: self.state.clone(),
+      source: self.source.clone(),
+      handle: self.handle.clone(),
+    }
+  }
+}
+
+impl<O, F, FU, SU> Subscription for TimeoutSubscription<O, F, FU, SU>
+where
+  FU: Subscription,
+  SU: Subscription,
+{
+  fn unsubscribe(self) {
+    self.handle.unsubscribe();
+    self.source.unsubscribe();
+    let fallback_unsub = {
+      let mut state = self.state.rc_deref_mut();
+      // The observer isn't released here, it may be emitting right now.
+      state.unsubscribed = true;
+      state.fallback = None;
+      state.fallback_unsub.take()
+    };
+    fallback_unsub.unsubscribe();
+  }
+
+  fn is_closed(&self) -> bool {
+    self.state.rc_deref().unsubscribed
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rc::MutRc;
+  use futures::executor::LocalPool;
+
+  #[test]
+  fn error_if_source_too_slow() {
+    let mut pool = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let error = MutRc::own(None);
+    let c_error = error.clone();
+
+    observable::interval(Duration::from_millis(20), pool.spawner())
+      .on_error_map(|e| match e {})
+      .timeout(Duration::from_millis(5), pool.spawner())
+      .on_error(move |e: TimeoutError| *c_error.rc_deref_mut() = Some(e))
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    pool.run();
+
+    assert!(values.rc_deref().is_empty());
+    assert_eq!(*error.rc_deref(), Some(TimeoutError));
+  }
+
+  #[test]
+  fn reset_timer_on_emission() {
+    let mut pool = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+
+    observable::interval(Duration::from_millis(2), pool.spawner())
+      .take(5)
+      .on_error_map(|e| match e {})
+      .timeout(Duration::from_millis(8), pool.spawner())
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .on_error(|_: TimeoutError| panic!("should not timeout"))
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    pool.run();
+
+    assert_eq!(&*values.rc_deref(), &[0, 1, 2, 3, 4]);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn cancel_timer_on_complete() {
+    let mut pool = LocalPool::new();
+    let mut subject = Subject::<i32, TimeoutError>::default();
+    let error = MutRc::own(false);
+    let c_error = error.clone();
+
+    subject
+      .clone()
+      .timeout(Duration::from_millis(1), pool.spawner())
+      .on_error(move |_| *c_error.rc_deref_mut() = true)
+      .subscribe(|_| {});
+    subject.next(1);
+    subject.complete();
+    pool.run();
+
+    assert!(!*error.rc_deref());
+  }
+
+  #[test]
+  fn unsubscribe_source_on_timeout() {
+    let mut pool = LocalPool::new();
+    let subject = Subject::<i32, TimeoutError>::default();
+
+    subject
+      .clone()
+      .timeout(Duration::from_millis(1), pool.spawner())
+      .on_error(|_| {})
+      .subscribe(|_| {});
+    assert!(!subject.is_empty());
+    pool.run();
+
+    assert!(subject.is_empty());
+  }
+
+  #[test]
+  fn switch_to_fallback() {
+    let mut pool = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+
+    observable::timer(1, Duration::from_millis(20), pool.spawner())
+      .timeout_with(
+        Duration::from_millis(5),
+        observable::from_iter([7, 8]),
+        pool.spawner(),
+      )
+      .subscribe(move |v| c_values.rc_deref_mut().push(v));
+    pool.run();
+
+    assert_eq!(&*values.rc_deref(), &[7, 8]);
+  }
+
+  #[test]
+  fn unsubscribe_cancel_timer() {
+    let mut pool = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+
+    observable::of(1)
+      .delay(Duration::from_millis(10), pool.spawner())
+      .timeout_with(Duration::from_millis(5), observable::of(2), pool.spawner())
+      .subscribe(move |v| c_values.rc_deref_mut().push(v))
+      .unsubscribe();
+    pool.run();
+
+    assert!(values.rc_deref().is_empty());
+  }
+
+  #[test]
+  fn unsubscribe_in_callback() {
+    let mut pool = LocalPool::new();
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let subscription = MutRc::own(None);
+    let c_subscription = subscription.clone();
+
+    let unsub = observable::interval(Duration::from_millis(1), pool.spawner())
+      .on_error_map(|e| match e {})
+      .timeout(Duration::from_millis(50), pool.spawner())
+      .on_error(|_: TimeoutError| {})
+      .subscribe(move |v| {
+        c_values.rc_deref_mut().push(v);
+        if v == 2 {
+          c_subscription.clone().unsubscribe();
+        }
+      });
+    *subscription.rc_deref_mut() = Some(BoxSubscription::new(unsub));
+    pool.run();
+
+    assert_eq!(&*values.rc_deref(), &[0, 1, 2]);
+  }
+}
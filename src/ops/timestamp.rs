@@ -0,0 +1,98 @@
+use crate::prelude::*;
+use std::time::Instant;
+
+/// An item of the source with the instant it was emitted at, emitted by
+/// [`timestamp`](ObservableExt::timestamp).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timestamped<Item> {
+  pub value: Item,
+  pub timestamp: Instant,
+}
+
+#[derive(Clone)]
+pub struct TimestampOp<S> {
+  pub(crate) source: S,
+}
+
+impl<Item, Err, O, S> Observable<Timestamped<Item>, Err, O> for TimestampOp<S>
+where
+  O: Observer<Timestamped<Item>, Err>,
+  S: Observable<Item, Err, TimestampObserver<O>>,
+{
+  type Unsub = S::Unsub;
+
+  fn actual_subscribe(self, observer: O) -> Self::Unsub {
+    self.source.actual_subscribe(TimestampObserver { observer })
+  }
+}
+
+impl<Item, Err, S> ObservableExt<Timestamped<Item>, Err> for TimestampOp<S> where
+  S: ObservableExt<Item, Err>
+{
+}
+
+pub struct TimestampObserver<O> {
+  observer: O,
+}
+
+impl<Item, Err, O> Observer<Item, Err> for TimestampObserver<O>
+where
+  O: Observer<Timestamped<Item>, Err>,
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    let timestamp = Instant::now();
+    self.observer.next(Timestamped { value, timestamp });
+  }
+
+  #[inline]
+  fn error(self, err: Err) {
+    self.observer.error(err)
+  }
+
+  #[inline]
+  fn complete(self) {
+    self.observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{ops::timestamp::Timestamped, prelude::*};
+  use std::time::Instant;
+
+  #[test]
+  fn stamp_items() {
+    let start = Instant::now();
+    let mut stamped = vec![];
+
+    observable::from_iter(0..3)
+      .timestamp()
+      .subscribe(|v| stamped.push(v));
+    let end = Instant::now();
+
+    let values: Vec<_> = stamped.iter().map(|t| t.value).collect();
+    assert_eq!(values, vec![0, 1, 2]);
+    assert!(stamped.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+    assert!(stamped
+      .iter()
+      .all(|t| start <= t.timestamp && t.timestamp <= end));
+  }
+
+  #[test]
+  fn destructure_in_map() {
+    let mut values = vec![];
+
+    observable::of(1)
+      .timestamp()
+      .map(|Timestamped { value, .. }| value)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![1]);
+  }
+}
@@ -0,0 +1,475 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, RcDerefMut},
+};
+use std::time::Duration;
+
+/// Observable returned by the `window_with_count` method, `Subject` is the
+/// type of the emitted windows.
+#[derive(Clone)]
+pub struct WindowWithCountOp<Source, Subject> {
+  pub(crate) source: Source,
+  pub(crate) count: usize,
+  _hint: TypeHint<Subject>,
+}
+
+impl<Source, Subject> WindowWithCountOp<Source, Subject> {
+  #[inline]
+  pub(crate) fn new(source: Source, count: usize) -> Self {
+    Self {
+      source,
+      count,
+      _hint: TypeHint::default(),
+    }
+  }
+}
+
+macro_rules! impl_observable_for_window_with_count {
+  ($ty: ty $(,$lf:lifetime)?) => {
+    impl<$($lf,)? Source, Item, Err, O> Observable<$ty, Err, O>
+      for WindowWithCountOp<Source, $ty>
+    where
+      O: Observer<$ty, Err>,
+      Source: Observable<Item, Err, WindowWithCountObserver<O, $ty>>,
+      Item: Clone,
+      Err: Clone,
+    {
+      type Unsub = Source::Unsub;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        self.source.actual_subscribe(WindowWithCountObserver {
+          observer,
+          count: self.count,
+          window: None,
+          hits: 0,
+        })
+      }
+    }
+
+    impl<$($lf,)? Source, Item, Err> ObservableExt<$ty, Err>
+      for WindowWithCountOp<Source, $ty>
+    where
+      Source: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_observable_for_window_with_count!(Subject<'a, Item, Err>, 'a);
+impl_observable_for_window_with_count!(SubjectThreads<Item, Err>);
+
+pub struct WindowWithCountObserver<O, Subject> {
+  observer: O,
+  count: usize,
+  window: Option<Subject>,
+  hits: usize,
+}
+
+impl<O, Subject, Item, Err> Observer<Item, Err>
+  for WindowWithCountObserver<O, Subject>
+where
+  O: Observer<Subject, Err>,
+  Subject: Clone + Default + Observer<Item, Err>,
+  Err: Clone,
+{
+  fn next(&mut self, value: Item) {
+    let observer = &mut self.observer;
+    let window = self.window.get_or_insert_with(|| {
+      let subject = Subject::default();
+      observer.next(subject.clone());
+      subject
+    });
+    window.next(value);
+
+    self.hits += 1;
+    if self.hits == self.count {
+      self.hits = 0;
+      if let Some(window) = self.window.take() {
+        window.complete();
+      }
+    }
+  }
+
+  fn error(self, err: Err) {
+    if let Some(window) = self.window {
+      window.error(err.clone());
+    }
+    self.observer.error(err)
+  }
+
+  fn complete(self) {
+    if let Some(window) = self.window {
+      window.complete();
+    }
+    self.observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+/// Observable returned by the `window_with_time` method, `Subject` is the
+/// type of the emitted windows.
+#[derive(Clone)]
+pub struct WindowWithTimeOp<Source, Scheduler, Subject> {
+  pub(crate) source: Source,
+  pub(crate) time: Duration,
+  pub(crate) scheduler: Scheduler,
+  _hint: TypeHint<Subject>,
+}
+
+impl<Source, Scheduler, Subject> WindowWithTimeOp<Source, Scheduler, Subject> {
+  #[inline]
+  pub(crate) fn new(
+    source: Source,
+    time: Duration,
+    scheduler: Scheduler,
+  ) -> Self {
+    Self {
+      source,
+      time,
+      scheduler,
+      _hint: TypeHint::default(),
+    }
+  }
+}
+
+pub struct WindowObserver<O, Subject> {
+  observer: O,
+  window: Subject,
+}
+
+type RcWindowObserver<O, Subject> = MutArc<Option<WindowObserver<O, Subject>>>;
+
+impl<O, Subject> WindowObserver<O, Subject> {
+  fn open<Err>(mut observer: O) -> Self
+  where
+    O: Observer<Subject, Err>,
+    Subject: Clone + Default,
+  {
+    let window = Subject::default();
+    observer.next(window.clone());
+    WindowObserver { observer, window }
+  }
+
+  fn reopen<Item, Err>(&mut self)
+  where
+    O: Observer<Subject, Err>,
+    Subject: Clone + Default + Observer<Item, Err>,
+  {
+    let window = std::mem::take(&mut self.window);
+    window.complete();
+    self.observer.next(self.window.clone());
+  }
+}
+
+impl<O, Subject, Item, Err> Observer<Item, Err> for WindowObserver<O, Subject>
+where
+  O: Observer<Subject, Err>,
+  Subject: Observer<Item, Err>,
+  Err: Clone,
+{
+  #[inline]
+  fn next(&mut self, value: Item) {
+    self.window.next(value)
+  }
+
+  fn error(self, err: Err) {
+    self.window.error(err.clone());
+    self.observer.error(err)
+  }
+
+  fn complete(self) {
+    self.window.complete();
+    self.observer.complete()
+  }
+
+  #[inline]
+  fn is_finished(&self) -> bool {
+    self.observer.is_finished()
+  }
+}
+
+fn reopen_window<O, Subject, Item, Err>(
+  observer: &mut RcWindowObserver<O, Subject>,
+  _seq: usize,
+) -> bool
+where
+  O: Observer<Subject, Err>,
+  Subject: Clone + Default + Observer<Item, Err>,
+  Err: Clone,
+{
+  if !observer.is_finished() {
+    if let Some(v) = observer.rc_deref_mut().as_mut() {
+      v.reopen()
+    }
+    true
+  } else {
+    // the downstream is finished, no one will complete the open window.
+    if let Some(v) = observer.rc_deref_mut().take() {
+      v.window.complete()
+    }
+    false
+  }
+}
+
+macro_rules! impl_observable_for_window_with_time {
+  ($ty: ty $(,$lf:lifetime)?) => {
+    impl<$($lf,)? Source, SD, Item, Err, O> Observable<$ty, Err, O>
+      for WindowWithTimeOp<Source, SD, $ty>
+    where
+      O: Observer<$ty, Err>,
+      Source: Observable<Item, Err, RcWindowObserver<O, $ty>>,
+      SD: Scheduler<RepeatTask<RcWindowObserver<O, $ty>>>,
+      Item: Clone,
+      Err: Clone,
+    {
+      type Unsub = ZipSubscription<TaskHandle<NormalReturn<()>>, Source::Unsub>;
+
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let Self { source, time, scheduler, .. } = self;
+        let observer = MutArc::own(Some(WindowObserver::open(observer)));
+
+        let handler = scheduler
+          .schedule(RepeatTask::new(time, reopen_window, observer.clone()), None);
+
+        let subscription = source.actual_subscribe(observer);
+        ZipSubscription::new(handler, subscription)
+      }
+    }
+
+    impl<$($lf,)? Source, SD, Item, Err> ObservableExt<$ty, Err>
+      for WindowWithTimeOp<Source, SD, $ty>
+    where
+      Source: ObservableExt<Item, Err>,
+    {
+    }
+  };
+}
+
+impl_observable_for_window_with_time!(Subject<'a, Item, Err>, 'a);
+impl_observable_for_window_with_time!(SubjectThreads<Item, Err>);
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use crate::rc::{MutRc, RcDeref, RcDerefMut};
+  use futures::executor::LocalPool;
+  use std::time::Duration;
+
+  #[test]
+  fn window_with_count() {
+    let windows = MutRc::own(vec![]);
+    let c_windows = windows.clone();
+    observable::from_iter(0..7)
+      .window_with_count::<Subject<_, _>>(3)
+      .subscribe(move |window| {
+        let idx = c_windows.rc_deref().len();
+        c_windows.rc_deref_mut().push(vec![]);
+        let c_windows = c_windows.clone();
+        window.subscribe(move |v| c_windows.rc_deref_mut()[idx].push(v));
+      });
+
+    assert_eq!(
+      &*windows.rc_deref(),
+      &[vec![0, 1, 2], vec![3, 4, 5], vec![6]]
+    );
+  }
+
+  #[test]
+  fn aggregate_per_window() {
+    let mut sums = vec![];
+    observable::from_iter(0..7)
+      .window_with_count::<Subject<_, _>>(3)
+      .flat_map(|window| window.sum())
+      .subscribe(|v| sums.push(v));
+
+    assert_eq!(sums, [3, 12, 6]);
+  }
+
+  #[test]
+  fn merge_all_windows() {
+    let mut values = vec![];
+    observable::from_iter(0..5)
+      .window_with_count::<Subject<_, _>>(2)
+      .merge_all()
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, [0, 1, 2, 3, 4]);
+  }
+
+  #[test]
+  fn complete_window_on_close() {
+    let completed = MutRc::own(0);
+    let c_completed = completed.clone();
+    let mut source = Subject::default();
+
+    source
+      .clone()
+      .window_with_count::<Subject<_, _>>(2)
+      .subscribe(move |window| {
+        let c_completed = c_completed.clone();
+        window
+          .on_complete(move || *c_completed.rc_deref_mut() += 1)
+          .subscribe(|_: i32| {});
+      });
+
+    source.next(0);
+    source.next(1);
+    assert_eq!(*completed.rc_deref(), 1);
+    source.next(2);
+    assert_eq!(*completed.rc_deref(), 1);
+    source.complete();
+    assert_eq!(*completed.rc_deref(), 2);
+  }
+
+  #[test]
+  fn error_window_and_observer() {
+    let errors = MutRc::own(0);
+    let c_errors = errors.clone();
+    let c_errors2 = errors.clone();
+    let mut source = Subject::default();
+
+    source
+      .clone()
+      .window_with_count::<Subject<_, _>>(2)
+      .on_error(move |_| *c_errors.rc_deref_mut() += 1)
+      .subscribe(move |window| {
+        let c_errors = c_errors2.clone();
+        window
+          .on_error(move |_: &str| *c_errors.rc_deref_mut() += 1)
+          .subscribe(|_: i32| {});
+      });
+
+    source.next(0);
+    source.error("error");
+    assert_eq!(*errors.rc_deref(), 2);
+  }
+
+  #[test]
+  fn window_with_count_threads() {
+    use crate::rc::MutArc;
+
+    let sums = MutArc::own(vec![]);
+    let c_sums = sums.clone();
+    observable::from_iter(0..7)
+      .window_with_count::<SubjectThreads<_, _>>(3)
+      .flat_map_threads(|window| window.sum())
+      .subscribe(move |v| c_sums.rc_deref_mut().push(v));
+
+    assert_eq!(*sums.rc_deref(), [3, 12, 6]);
+  }
+
+  #[test]
+  #[should_panic]
+  fn reject_zero_count() {
+    let _ = observable::from_iter(0..10).window_with_count::<Subject<_, _>>(0);
+  }
+
+  #[test]
+  fn window_with_time_emit_empty_windows() {
+    let mut pool = LocalPool::new();
+    let completed = MutRc::own(0);
+    let c_completed = completed.clone();
+    let values = MutRc::own(0);
+    let c_values = values.clone();
+
+    Subject::<i32, _>::default()
+      .window_with_time::<Subject<_, _>, _>(
+        Duration::from_millis(1),
+        pool.spawner(),
+      )
+      .take(3)
+      .subscribe(move |window| {
+        let c_completed = c_completed.clone();
+        let c_values = c_values.clone();
+        window
+          .on_complete(move || *c_completed.rc_deref_mut() += 1)
+          .subscribe(move |_| *c_values.rc_deref_mut() += 1);
+      });
+
+    // returns only if the periodic task stopped after `take` finished.
+    pool.run();
+
+    assert_eq!(*completed.rc_deref(), 3);
+    assert_eq!(*values.rc_deref(), 0);
+  }
+
+  #[test]
+  fn window_with_time_complete_open_window() {
+    let mut pool = LocalPool::new();
+    let windows = MutRc::own(vec![]);
+    let c_windows = windows.clone();
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+
+    observable::from_iter(0..3)
+      .window_with_time::<Subject<_, _>, _>(
+        Duration::from_millis(500),
+        pool.spawner(),
+      )
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(move |window| {
+        let idx = c_windows.rc_deref().len();
+        c_windows.rc_deref_mut().push(vec![]);
+        let c_windows = c_windows.clone();
+        window.subscribe(move |v| c_windows.rc_deref_mut()[idx].push(v));
+      });
+
+    // returns only if the periodic task stopped after the source completed.
+    pool.run();
+
+    assert_eq!(&*windows.rc_deref(), &[vec![0, 1, 2]]);
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn window_with_time_unsubscribe() {
+    let mut pool = LocalPool::new();
+    let mut source = Subject::<i32, _>::default();
+
+    let subscription = source
+      .clone()
+      .window_with_time::<Subject<_, _>, _>(
+        Duration::from_millis(1),
+        pool.spawner(),
+      )
+      .subscribe(|_| {});
+    source.next(1);
+    subscription.unsubscribe();
+
+    // blocks forever if the periodic task is still alive.
+    pool.run();
+  }
+
+  #[test]
+  fn window_with_time_error() {
+    let mut pool = LocalPool::new();
+    let errors = MutRc::own(0);
+    let c_errors = errors.clone();
+    let c_errors2 = errors.clone();
+    let mut source = Subject::default();
+
+    source
+      .clone()
+      .window_with_time::<Subject<_, _>, _>(
+        Duration::from_millis(500),
+        pool.spawner(),
+      )
+      .on_error(move |_| *c_errors.rc_deref_mut() += 1)
+      .subscribe(move |window| {
+        let c_errors = c_errors2.clone();
+        window
+          .on_error(move |_: &str| *c_errors.rc_deref_mut() += 1)
+          .subscribe(|_: i32| {});
+      });
+
+    source.next(0);
+    source.error("error");
+    pool.run();
+
+    assert_eq!(*errors.rc_deref(), 2);
+  }
+}
@@ -1,5 +1,3 @@
-use std::marker::PhantomData;
-
 use crate::{
   prelude::*,
   rc::{MutArc, MutRc, RcDeref, RcDerefMut},
@@ -10,35 +8,49 @@ use crate::{
 /// This struct is created by the with_latest_from method on
 /// [Observable](Observable::with_latest_from). See its documentation for more.
 #[derive(Clone)]
-pub struct WithLatestFromOp<S, FS> {
-  pub(crate) source: S,
-  pub(crate) from: FS,
+pub struct WithLatestFromOp<S, FS, BinaryOp, ItemA, ItemB> {
+  source: S,
+  from: FS,
+  binary_op: BinaryOp,
+  _hint: TypeHint<(ItemA, ItemB)>,
 }
 
 #[derive(Clone)]
-pub struct WithLatestFromOpThreads<S, FS> {
-  pub(crate) source: S,
-  pub(crate) from: FS,
+pub struct WithLatestFromOpThreads<S, FS, BinaryOp, ItemA, ItemB> {
+  source: S,
+  from: FS,
+  binary_op: BinaryOp,
+  _hint: TypeHint<(ItemA, ItemB)>,
 }
 
 macro_rules! impl_with_last_from_op {
   ($name: ident, $rc: ident) => {
-    impl<S, FS> $name<S, FS> {
-      pub(crate) fn new(source: S, from: FS) -> Self {
-        Self { source, from }
+    impl<S, FS, BinaryOp, ItemA, ItemB> $name<S, FS, BinaryOp, ItemA, ItemB> {
+      pub(crate) fn new(source: S, from: FS, binary_op: BinaryOp) -> Self {
+        Self {
+          source,
+          from,
+          binary_op,
+          _hint: TypeHint::new(),
+        }
       }
     }
 
-    impl<Source, From, O, ItemA, ItemB, Err> Observable<(ItemA, ItemB), Err, O>
-      for $name<Source, From>
+    impl<Source, From, BinaryOp, O, ItemA, ItemB, OutputItem, Err>
+      Observable<OutputItem, Err, O>
+      for $name<Source, From, BinaryOp, ItemA, ItemB>
     where
-      O: Observer<(ItemA, ItemB), Err>,
-      Source:
-        Observable<ItemA, Err, AObserver<$rc<Option<O>>, $rc<Option<ItemB>>>>,
+      O: Observer<OutputItem, Err>,
+      BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
+      Source: Observable<
+        ItemA,
+        Err,
+        AObserver<$rc<Option<O>>, $rc<Option<ItemB>>, BinaryOp>,
+      >,
       From: Observable<
         ItemB,
         Err,
-        BObserver<$rc<Option<O>>, $rc<Option<ItemB>>, ItemA>,
+        BObserver<$rc<Option<O>>, $rc<Option<ItemB>>, OutputItem>,
       >,
       ItemB: Clone,
     {
@@ -49,23 +61,26 @@ macro_rules! impl_with_last_from_op {
         let from_observer = BObserver {
           observer: source_observer.clone(),
           value: item.clone(),
-          _marker: std::marker::PhantomData::<ItemA>,
+          _marker: TypeHint::new(),
         };
         let from_unsub = self.from.actual_subscribe(from_observer);
         let source_unsub = self.source.actual_subscribe(AObserver {
           observer: source_observer,
           value: item,
+          binary_op: self.binary_op,
         });
 
         ZipSubscription::new(source_unsub, from_unsub)
       }
     }
 
-    impl<Source, From, ItemA, ItemB, Err> ObservableExt<(ItemA, ItemB), Err>
-      for $name<Source, From>
+    impl<Source, From, BinaryOp, ItemA, ItemB, OutputItem, Err>
+      ObservableExt<OutputItem, Err>
+      for $name<Source, From, BinaryOp, ItemA, ItemB>
     where
       Source: ObservableExt<ItemA, Err>,
       From: ObservableExt<ItemB, Err>,
+      BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
     {
     }
   };
@@ -74,16 +89,18 @@ macro_rules! impl_with_last_from_op {
 impl_with_last_from_op!(WithLatestFromOp, MutRc);
 impl_with_last_from_op!(WithLatestFromOpThreads, MutArc);
 
-#[derive(Clone)]
-pub struct BObserver<O, V, ItemA> {
+/// The observer of the secondary observable, it only caches the latest value
+/// and never completes the downstream.
+pub struct BObserver<O, V, OutputItem> {
   observer: O,
   value: V,
-  _marker: PhantomData<ItemA>,
+  _marker: TypeHint<OutputItem>,
 }
 
-impl<O, ItemA, ItemB, V, Err> Observer<ItemB, Err> for BObserver<O, V, ItemA>
+impl<O, ItemB, OutputItem, V, Err> Observer<ItemB, Err>
+  for BObserver<O, V, OutputItem>
 where
-  O: Observer<(ItemA, ItemB), Err>,
+  O: Observer<OutputItem, Err>,
   V: RcDerefMut<Target = Option<ItemB>>,
 {
   #[inline]
@@ -105,15 +122,17 @@ where
   }
 }
 
-#[derive(Clone)]
-pub struct AObserver<O, V> {
+pub struct AObserver<O, V, BinaryOp> {
   observer: O,
   value: V,
+  binary_op: BinaryOp,
 }
 
-impl<ItemA, ItemB, Err, V, O> Observer<ItemA, Err> for AObserver<O, V>
+impl<ItemA, ItemB, OutputItem, Err, V, O, BinaryOp> Observer<ItemA, Err>
+  for AObserver<O, V, BinaryOp>
 where
-  O: Observer<(ItemA, ItemB), Err>,
+  O: Observer<OutputItem, Err>,
+  BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
   ItemB: Clone,
   V: RcDeref<Target = Option<ItemB>>,
 {
@@ -121,7 +140,7 @@ where
     // should not write in one line early end value borrow.
     let v = self.value.rc_deref().clone();
     if let Some(v) = v {
-      self.observer.next((item, v));
+      self.observer.next((self.binary_op)(item, v));
     }
   }
 
@@ -153,10 +172,12 @@ mod test {
       let mut s1 = Subject::default();
       let mut s2 = Subject::default();
 
-      s1.clone().with_latest_from(s2.clone()).subscribe(|(a, b)| {
-        ret.push(a);
-        ret.push(b);
-      });
+      s1.clone()
+        .with_latest_from(s2.clone(), |a, b| (a, b))
+        .subscribe(|(a, b)| {
+          ret.push(a);
+          ret.push(b);
+        });
 
       s1.next('1');
       s2.next('A');
@@ -183,8 +204,9 @@ mod test {
       let primary = numbers.clone().filter(|v| *v % 3 == 0);
       let secondary = numbers.clone().filter(|v| *v % 3 != 0);
 
-      let with_latest_from =
-        primary.clone().with_latest_from(secondary.clone());
+      let with_latest_from = primary
+        .clone()
+        .with_latest_from(secondary.clone(), |a, b| (a, b));
 
       //  attach observers
       with_latest_from.subscribe(|v| numbers_store.push(v));
@@ -207,7 +229,7 @@ mod test {
     {
       let s1 = Subject::default();
       s1.clone()
-        .with_latest_from(Subject::default())
+        .with_latest_from(Subject::default(), |a, b| (a, b))
         .on_complete(|| complete = true)
         .subscribe(|((), ())| {});
 
@@ -220,7 +242,7 @@ mod test {
       let s1 = Subject::default();
       let s2 = Subject::default();
       s1.clone()
-        .with_latest_from(s2.clone())
+        .with_latest_from(s2.clone(), |a, b| (a, b))
         .on_complete(|| complete = true)
         .subscribe(|((), ())| {});
 
@@ -229,6 +251,47 @@ mod test {
     assert!(!complete);
   }
 
+  #[test]
+  fn combine_by_binary_op() {
+    let mut values = vec![];
+    {
+      let mut clicks = Subject::default();
+      let mut form = Subject::default();
+      clicks
+        .clone()
+        .with_latest_from(form.clone(), |click: i32, state: i32| click * state)
+        .subscribe(|v| values.push(v));
+
+      form.next(1);
+      form.next(10);
+      clicks.next(2);
+      form.next(100);
+      clicks.next(3);
+    }
+    assert_eq!(values, vec![20, 300]);
+  }
+
+  #[test]
+  fn secondary_error() {
+    let mut error = None;
+    let mut values = vec![];
+    {
+      let mut s1 = Subject::default();
+      let mut s2 = Subject::default();
+      s1.clone()
+        .with_latest_from(s2.clone(), |a: i32, b: i32| a + b)
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+
+      s2.next(1);
+      s1.next(1);
+      s2.clone().error("secondary");
+      s1.next(2);
+    }
+    assert_eq!(values, vec![2]);
+    assert_eq!(error, Some("secondary"));
+  }
+
   #[test]
   fn circular() {
     let mut subject_a = Subject::default();
@@ -237,7 +300,7 @@ mod test {
 
     subject_a
       .clone()
-      .with_latest_from(subject_b.clone())
+      .with_latest_from(subject_b.clone(), |a: (), b: ()| (a, b))
       .subscribe(move |_| {
         cloned_subject_b.next(());
       });
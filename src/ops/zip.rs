@@ -9,206 +9,278 @@ use std::collections::VecDeque;
 /// This struct is created by the zip method on [Observable](Observable::zip).
 /// See its documentation for more.
 #[derive(Clone)]
-pub struct ZipOp<A, B> {
+pub struct ZipOp<'a, A, B, BinaryOp, ItemA, ItemB> {
   a: A,
   b: B,
+  binary_op: BinaryOp,
+  _hint: TypeHint<&'a (ItemA, ItemB)>,
 }
 
 #[derive(Clone)]
-pub struct ZipOpThreads<A, B> {
+pub struct ZipOpThreads<A, B, BinaryOp, ItemA, ItemB> {
   a: A,
   b: B,
+  binary_op: BinaryOp,
+  _hint: TypeHint<(ItemA, ItemB)>,
 }
 
+/// The [`ZipOp`] emitting the pairs of items as tuples.
+pub type ZipPairOp<'a, A, B, ItemA, ItemB> =
+  ZipOp<'a, A, B, fn(ItemA, ItemB) -> (ItemA, ItemB), ItemA, ItemB>;
+pub type ZipPairOpThreads<A, B, ItemA, ItemB> =
+  ZipOpThreads<A, B, fn(ItemA, ItemB) -> (ItemA, ItemB), ItemA, ItemB>;
+
 macro_rules! impl_zip_op {
-  ($name:ident, $rc: ident) => {
-    impl<A, B> $name<A, B> {
-      pub fn new(a: A, b: B) -> Self {
-        Self { a, b }
+  (
+    $name:ident, $rc: ident, $box_unsub: ty
+    $(,$lf:lifetime)? $(,$send:ident)?
+  ) => {
+    impl<$($lf,)? A, B, BinaryOp, ItemA, ItemB>
+      $name<$($lf,)? A, B, BinaryOp, ItemA, ItemB>
+    {
+      pub fn new(a: A, b: B, binary_op: BinaryOp) -> Self {
+        Self { a, b, binary_op, _hint: TypeHint::new() }
       }
     }
 
-    impl<A, B, ItemA, ItemB, Err, O> Observable<(ItemA, ItemB), Err, O>
-      for $name<A, B>
+    impl<$($lf,)? A, B, BinaryOp, ItemA, ItemB, OutputItem, Err, O>
+      Observable<OutputItem, Err, O>
+      for $name<$($lf,)? A, B, BinaryOp, ItemA, ItemB>
     where
-      O: Observer<(ItemA, ItemB), Err>,
+      O: Observer<OutputItem, Err>,
+      BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
       A: Observable<
         ItemA,
         Err,
-        AObserver<$rc<ZipObserver<O, ItemA, ItemB>>, ItemB>,
+        AObserver<
+          $rc<ZipState<O, ItemA, ItemB, BinaryOp>>,
+          $rc<Option<B::Unsub>>,
+        >,
       >,
       B: Observable<
         ItemB,
         Err,
-        BObserver<$rc<ZipObserver<O, ItemA, ItemB>>, ItemA>,
+        BObserver<
+          $rc<ZipState<O, ItemA, ItemB, BinaryOp>>,
+          $rc<Option<$box_unsub>>,
+        >,
       >,
+      A::Unsub: $($lf)? $($send + 'static)?,
     {
-      type Unsub = ZipSubscription<A::Unsub, B::Unsub>;
-      fn actual_subscribe(self, observer: O) -> Self::Unsub {
-        let o_zip = ZipObserver::new(observer);
-        let o_zip = $rc::own(o_zip);
-        let a_unsub = self
-          .a
-          .actual_subscribe(AObserver(o_zip.clone(), TypeHint::new()));
-        let b_unsub =
-          self.b.actual_subscribe(BObserver(o_zip, TypeHint::new()));
+      type Unsub =
+        ZipSubscription<$rc<Option<$box_unsub>>, $rc<Option<B::Unsub>>>;
 
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = $rc::own(ZipState {
+          observer: Some(observer),
+          a: VecDeque::default(),
+          b: VecDeque::default(),
+          a_completed: false,
+          b_completed: false,
+          binary_op: self.binary_op,
+        });
+        let a_unsub = $rc::own(None);
+        let b_unsub = $rc::own(None);
+        let finished = || state.rc_deref().is_finished();
+
+        let a = self.a.actual_subscribe(AObserver {
+          state: state.clone(),
+          other_unsub: b_unsub.clone(),
+        });
+        if finished() {
+          a.unsubscribe();
+          return ZipSubscription::new(a_unsub, b_unsub);
+        }
+        *a_unsub.rc_deref_mut() = Some(<$box_unsub>::new(a));
+
+        let b = self.b.actual_subscribe(BObserver {
+          state: state.clone(),
+          other_unsub: a_unsub.clone(),
+        });
+        if finished() {
+          b.unsubscribe();
+        } else {
+          *b_unsub.rc_deref_mut() = Some(b);
+        }
         ZipSubscription::new(a_unsub, b_unsub)
       }
     }
 
-    impl<A, B, ItemA, ItemB, Err> ObservableExt<(ItemA, ItemB), Err>
-      for $name<A, B>
+    impl<$($lf,)? A, B, BinaryOp, ItemA, ItemB, OutputItem, Err>
+      ObservableExt<OutputItem, Err>
+      for $name<$($lf,)? A, B, BinaryOp, ItemA, ItemB>
     where
       A: ObservableExt<ItemA, Err>,
       B: ObservableExt<ItemB, Err>,
+      BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
     {
     }
 
-    impl<O, ItemA, ItemB, Err> Observer<ZipItem<ItemA, ItemB>, Err>
-      for $rc<ZipObserver<O, ItemA, ItemB>>
+    impl<O, ItemA, ItemB, OutputItem, BinaryOp, Err, U> Observer<ItemA, Err>
+      for AObserver<$rc<ZipState<O, ItemA, ItemB, BinaryOp>>, U>
     where
-      O: Observer<(ItemA, ItemB), Err>,
+      O: Observer<OutputItem, Err>,
+      BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
+      U: Subscription,
     {
-      fn next(&mut self, value: ZipItem<ItemA, ItemB>) {
-        let mut inner = self.rc_deref_mut();
-        let mut zip_value = None;
-        match value {
-          ZipItem::ItemA(v) => {
-            if !inner.b.is_empty() {
-              zip_value = Some((v, inner.b.pop_front().unwrap()));
-            } else {
-              inner.a.push_back(v);
+      fn next(&mut self, value: ItemA) {
+        let mut state = self.state.rc_deref_mut();
+        let ZipState { observer, a, b, b_completed, binary_op, .. } =
+          &mut *state;
+        if observer.is_none() {
+          return;
+        }
+        match b.pop_front() {
+          Some(v) => {
+            observer.as_mut().unwrap().next(binary_op(value, v));
+            if *b_completed && b.is_empty() {
+              if let Some(observer) = observer.take() {
+                observer.complete();
+              }
             }
           }
-          ZipItem::ItemB(v) => {
-            if !inner.a.is_empty() {
-              zip_value = Some((inner.a.pop_front().unwrap(), v));
-            } else {
-              inner.b.push_back(v)
-            }
+          None => a.push_back(value),
+        }
+      }
+
+      fn error(self, err: Err) {
+        let observer = self.state.rc_deref_mut().observer.take();
+        if let Some(observer) = observer {
+          self.other_unsub.unsubscribe();
+          observer.error(err);
+        }
+      }
+
+      fn complete(self) {
+        let observer = {
+          let mut state = self.state.rc_deref_mut();
+          state.a_completed = true;
+          if state.a.is_empty() {
+            state.observer.take()
+          } else {
+            None
           }
+        };
+        // No more pairs can be made, the other side is no longer needed.
+        if let Some(observer) = observer {
+          self.other_unsub.unsubscribe();
+          observer.complete();
         }
-        if let (Some(v), Some(observer)) = (zip_value, inner.observer.as_mut())
-        {
-          observer.next(v)
+      }
+
+      #[inline]
+      fn is_finished(&self) -> bool {
+        self.state.rc_deref().is_finished()
+      }
+    }
+
+    impl<O, ItemA, ItemB, OutputItem, BinaryOp, Err, U> Observer<ItemB, Err>
+      for BObserver<$rc<ZipState<O, ItemA, ItemB, BinaryOp>>, U>
+    where
+      O: Observer<OutputItem, Err>,
+      BinaryOp: FnMut(ItemA, ItemB) -> OutputItem,
+      U: Subscription,
+    {
+      fn next(&mut self, value: ItemB) {
+        let mut state = self.state.rc_deref_mut();
+        let ZipState { observer, a, b, a_completed, binary_op, .. } =
+          &mut *state;
+        if observer.is_none() {
+          return;
+        }
+        match a.pop_front() {
+          Some(v) => {
+            observer.as_mut().unwrap().next(binary_op(v, value));
+            if *a_completed && a.is_empty() {
+              if let Some(observer) = observer.take() {
+                observer.complete();
+              }
+            }
+          }
+          None => b.push_back(value),
         }
       }
 
       fn error(self, err: Err) {
-        if let Some(observer) = self.rc_deref_mut().observer.take() {
+        let observer = self.state.rc_deref_mut().observer.take();
+        if let Some(observer) = observer {
+          self.other_unsub.unsubscribe();
           observer.error(err);
         }
       }
 
       fn complete(self) {
-        let mut inner = self.rc_deref_mut();
-        if inner.completed_one {
-          if let Some(observer) = inner.observer.take() {
-            observer.complete();
+        let observer = {
+          let mut state = self.state.rc_deref_mut();
+          state.b_completed = true;
+          if state.b.is_empty() {
+            state.observer.take()
+          } else {
+            None
           }
-        } else {
-          inner.completed_one = true;
+        };
+        // No more pairs can be made, the other side is no longer needed.
+        if let Some(observer) = observer {
+          self.other_unsub.unsubscribe();
+          observer.complete();
         }
       }
 
       #[inline]
       fn is_finished(&self) -> bool {
-        self
-          .rc_deref()
-          .observer
-          .as_ref()
-          .map_or(true, |o| o.is_finished())
+        self.state.rc_deref().is_finished()
       }
     }
   };
 }
 
-impl_zip_op!(ZipOp, MutRc);
-impl_zip_op!(ZipOpThreads, MutArc);
+impl_zip_op!(ZipOp, MutRc, BoxSubscription<'a>, 'a);
+impl_zip_op!(ZipOpThreads, MutArc, BoxSubscriptionThreads, Send);
 
-enum ZipItem<A, B> {
-  ItemA(A),
-  ItemB(B),
-}
-
-pub struct ZipObserver<O, ItemA, ItemB> {
+/// The state shared by both sides of a zip, the two queues are kept together
+/// so the thread-safe version guards them with a single lock.
+pub struct ZipState<O, ItemA, ItemB, BinaryOp> {
   observer: Option<O>,
   a: VecDeque<ItemA>,
   b: VecDeque<ItemB>,
-  completed_one: bool,
+  a_completed: bool,
+  b_completed: bool,
+  binary_op: BinaryOp,
 }
 
-impl<O, ItemA, ItemB> ZipObserver<O, ItemA, ItemB> {
-  fn new(o: O) -> Self {
-    ZipObserver {
-      observer: Some(o),
-      a: VecDeque::default(),
-      b: VecDeque::default(),
-      completed_one: false,
-    }
+impl<O, ItemA, ItemB, BinaryOp> ZipState<O, ItemA, ItemB, BinaryOp> {
+  fn is_finished<Item, Err>(&self) -> bool
+  where
+    O: Observer<Item, Err>,
+  {
+    self.observer.as_ref().is_none_or(|o| o.is_finished())
   }
 }
 
-pub struct AObserver<O, ItemB>(O, TypeHint<ItemB>);
-
-impl<O, ItemA, ItemB, Err> Observer<ItemA, Err> for AObserver<O, ItemB>
-where
-  O: Observer<ZipItem<ItemA, ItemB>, Err>,
-{
-  #[inline]
-  fn next(&mut self, value: ItemA) {
-    self.0.next(ZipItem::ItemA(value));
-  }
-
-  #[inline]
-  fn error(self, err: Err) {
-    self.0.error(err)
-  }
-
-  #[inline]
-  fn complete(self) {
-    self.0.complete()
-  }
-
-  #[inline]
-  fn is_finished(&self) -> bool {
-    self.0.is_finished()
-  }
+/// Observes the first observable of a zip, and holds the subscription of the
+/// second one to unsubscribe it when the zip ends early.
+pub struct AObserver<S, U> {
+  state: S,
+  other_unsub: U,
 }
 
-pub struct BObserver<O, A>(O, TypeHint<A>);
-
-impl<O, ItemA, ItemB, Err> Observer<ItemB, Err> for BObserver<O, ItemA>
-where
-  O: Observer<ZipItem<ItemA, ItemB>, Err>,
-{
-  #[inline]
-  fn next(&mut self, value: ItemB) {
-    self.0.next(ZipItem::ItemB(value));
-  }
-
-  #[inline]
-  fn error(self, err: Err) {
-    self.0.error(err)
-  }
-
-  #[inline]
-  fn complete(self) {
-    self.0.complete()
-  }
-
-  #[inline]
-  fn is_finished(&self) -> bool {
-    self.0.is_finished()
-  }
+/// Observes the second observable of a zip, and holds the subscription of the
+/// first one to unsubscribe it when the zip ends early.
+pub struct BObserver<S, U> {
+  state: S,
+  other_unsub: U,
 }
 
 #[cfg(test)]
 mod test {
-  use crate::prelude::*;
+  use crate::{
+    observable::fake_timer::FakeClock,
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
   use std::sync::atomic::{AtomicUsize, Ordering};
-  use std::sync::Arc;
+  use std::sync::{Arc, Mutex};
+  use std::time::Duration;
 
   #[test]
   fn smoke() {
@@ -237,20 +309,133 @@ mod test {
 
       s1.complete();
     }
-    assert!(!complete);
+    assert!(complete);
+
+    let completed = MutRc::own(false);
+    let c_completed = completed.clone();
+    let mut s1 = Subject::default();
+    let mut s2 = Subject::default();
+    s1.clone()
+      .zip(s2.clone())
+      .on_complete(move || *c_completed.rc_deref_mut() = true)
+      .subscribe(|_: ((), ())| {});
+
+    s1.next(());
+    s1.complete();
+    assert!(!*completed.rc_deref());
+    s2.next(());
+    assert!(*completed.rc_deref());
+  }
+
+  #[test]
+  fn unequal_length() {
+    let mut values = vec![];
+    let mut completed = false;
+
+    observable::from_iter(0..3)
+      .zip(observable::from_iter(0..))
+      .on_complete(|| completed = true)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![(0, 0), (1, 1), (2, 2)]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn queue_the_faster_side() {
+    let mut values = vec![];
+    {
+      let mut s1 = Subject::default();
+      let mut s2 = Subject::default();
+      s1.clone()
+        .zip(s2.clone())
+        .subscribe(|v: (i32, char)| values.push(v));
+
+      s1.next(1);
+      s1.next(2);
+      s1.next(3);
+      s2.next('a');
+      s2.next('b');
+      s1.next(4);
+      s2.next('c');
+    }
+    assert_eq!(values, vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+  }
 
+  #[test]
+  fn unsubscribe_other_when_completed() {
+    let clock = FakeClock::default();
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let s = Subject::default();
+
+    s.clone()
+      .zip(
+        clock
+          .interval(Duration::from_millis(1))
+          .finalize(move || *c_finalized.rc_deref_mut() = true),
+      )
+      .subscribe(|_: ((), usize)| {});
+    clock.advance(Duration::from_millis(5));
+    assert!(!*finalized.rc_deref());
+
+    s.complete();
+    assert!(*finalized.rc_deref());
+
+    let finalized = MutRc::own(false);
+    let c_finalized = finalized.clone();
+    let s = Subject::<(), _>::default();
+    s.clone()
+      .finalize(move || *c_finalized.rc_deref_mut() = true)
+      .zip(observable::empty())
+      .subscribe(|_: ((), ())| {});
+    assert!(*finalized.rc_deref());
+  }
+
+  #[test]
+  fn error_from_either_side() {
+    let mut error = None;
     {
       let s1 = Subject::default();
-      let s2 = Subject::default();
+      let mut s2 = Subject::default();
       s1.clone()
         .zip(s2.clone())
-        .on_complete(|| complete = true)
-        .subscribe(|_: ((), ())| {});
+        .on_error(|e| error = Some(e))
+        .subscribe(|_: (i32, i32)| {});
 
-      s1.complete();
-      s2.complete();
+      s2.next(1);
+      s1.error("s1 error");
     }
-    assert!(complete);
+    assert_eq!(error, Some("s1 error"));
+  }
+
+  #[test]
+  fn zip_with_binary_op() {
+    let mut values = vec![];
+    observable::from_iter([1, 2, 3])
+      .zip_with(observable::from_iter([10, 20]), |a, b| a + b)
+      .subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![11, 22]);
+  }
+
+  #[test]
+  fn zip_threads() {
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut s1 = SubjectThreads::default();
+    let mut s2 = SubjectThreads::default();
+
+    s1.clone()
+      .zip_with_threads(s2.clone(), |a: i32, b: i32| a * b)
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    s1.next(2);
+    s1.next(3);
+    s2.next(5);
+    s2.next(7);
+
+    assert_eq!(&*values.lock().unwrap(), &[10, 21]);
   }
 
   #[test]
@@ -164,10 +164,21 @@ impl<T> TaskHandle<T> {
     }))
   }
 }
-trait TaskReturn {}
+trait TaskReturn {
+  /// Releases the return value of a task cancelled while it was running.
+  fn cancel(self);
+}
 
-impl<T: Subscription> TaskReturn for SubscribeReturn<T> {}
-impl<T> TaskReturn for NormalReturn<T> {}
+impl<T: Subscription> TaskReturn for SubscribeReturn<T> {
+  #[inline]
+  fn cancel(self) {
+    self.0.unsubscribe()
+  }
+}
+impl<T> TaskReturn for NormalReturn<T> {
+  #[inline]
+  fn cancel(self) {}
+}
 
 impl<T: 'static> Subscription for TaskHandle<NormalReturn<T>> {
   #[inline]
@@ -188,7 +199,6 @@ impl<T: Subscription + 'static> Subscription
 {
   fn unsubscribe(self) {
     let mut info = self.0.rc_deref_mut();
-    eprintln!("unsubscribe");
     info.keep_running = false;
     match info.value.take() {
       Some(Ok(v)) => v.0.unsubscribe(),
@@ -224,18 +234,30 @@ impl<Fut: Future + fmt::Debug> fmt::Debug for Remote<Fut> {
   }
 }
 
-impl<Fut: Future> Future for Remote<Fut> {
+impl<Fut: Future> Future for Remote<Fut>
+where
+  Fut::Output: TaskReturn,
+{
   type Output = ();
 
   fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
     let this = self.project();
 
-    let mut info = this.handle_info.rc_deref_mut();
-    if !info.keep_running {
+    if !this.handle_info.rc_deref().keep_running {
       // Cancelled, bail out
       return Poll::Ready(());
     }
-    info.value = Some(ready!(this.future.poll(cx)));
+    // The handle is not locked while polling, so the task can unsubscribe its
+    // own handle.
+    let value = ready!(this.future.poll(cx));
+    let mut info = this.handle_info.rc_deref_mut();
+    if info.keep_running {
+      info.value = Some(value);
+    } else if let Ok(value) = value {
+      // Cancelled while running.
+      drop(info);
+      value.cancel();
+    }
 
     Poll::Ready(())
   }
@@ -489,3 +511,75 @@ mod test {
     assert_eq!(container, vec![1, 2, 3, 4, 5]);
   }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod handle_test {
+  use super::*;
+  use futures::executor::{LocalPool, ThreadPool};
+  use std::sync::mpsc::channel;
+  use std::time::Duration;
+
+  type Slot<T> = MutArc<Option<TaskHandle<T>>>;
+
+  fn unsubscribe_own_handle(slot: Slot<NormalReturn<()>>) -> NormalReturn<()> {
+    if let Some(handle) = slot.rc_deref_mut().take() {
+      handle.unsubscribe();
+    }
+    NormalReturn::new(())
+  }
+
+  #[test]
+  fn task_unsubscribe_own_handle() {
+    let mut pool = LocalPool::new();
+    let slot = MutArc::own(None);
+    let task = OnceTask::new(unsubscribe_own_handle, slot.clone());
+    let handle = pool.spawner().schedule(task, None);
+    *slot.rc_deref_mut() = Some(handle);
+
+    pool.run();
+    assert!(slot.rc_deref().is_none());
+  }
+
+  #[test]
+  fn threads_task_unsubscribe_own_handle() {
+    let pool = ThreadPool::new().unwrap();
+    let (sender, receiver) = channel();
+    let slot = MutArc::own(None);
+    let c_slot = slot.clone();
+    let task = OnceTask::new(
+      |(slot, sender): (Slot<NormalReturn<()>>, _)| {
+        let r = unsubscribe_own_handle(slot);
+        sender.send(()).unwrap();
+        r
+      },
+      (c_slot, sender),
+    );
+    *slot.rc_deref_mut() =
+      Some(pool.schedule(task, Some(Duration::from_millis(10))));
+
+    receiver
+      .recv_timeout(Duration::from_secs(5))
+      .expect("the task deadlocked on its own handle");
+  }
+
+  #[test]
+  fn cancelled_running_task_unsubscribe_its_return() {
+    let mut pool = LocalPool::new();
+    let subject = Subject::<(), ()>::default();
+    let slot = MutArc::own(None);
+    let task = OnceTask::new(
+      |(slot, subject): (Slot<SubscribeReturn<_>>, Subject<(), ()>)| {
+        let subscription = subject.on_error(|_| {}).subscribe(|_| {});
+        if let Some(handle) = slot.rc_deref_mut().take() {
+          handle.unsubscribe();
+        }
+        SubscribeReturn::new(subscription)
+      },
+      (slot.clone(), subject.clone()),
+    );
+    *slot.rc_deref_mut() = Some(pool.spawner().schedule(task, None));
+
+    pool.run();
+    assert!(subject.is_empty());
+  }
+}
@@ -8,13 +8,50 @@ use crate::{
   subscriber::Subscriber,
 };
 
+pub mod async_subject;
+pub use async_subject::*;
 pub mod behavior_subject;
 pub use behavior_subject::*;
+pub mod replay_observer;
+pub mod replay_subject;
+pub use replay_subject::*;
 use smallvec::SmallVec;
 
 pub trait SubjectSize {
+  /// Returns `true` if the subject has no active subscriber.
   fn is_empty(&self) -> bool;
+  /// Returns the count of the subscribers not unsubscribed nor finished.
   fn len(&self) -> usize;
+
+  /// Returns the count of the observers still listening to the subject, the
+  /// same as [`len`](SubjectSize::len).
+  #[inline]
+  fn observer_count(&self) -> usize {
+    self.len()
+  }
+
+  /// Returns `true` if any observer is still listening to the subject, so a
+  /// producer can skip the work nobody would receive.
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut subject = Subject::<i32, ()>::default();
+  /// assert!(!subject.has_observers());
+  ///
+  /// let subscription = subject.clone().on_error(|_| {}).subscribe(|_| {});
+  /// assert!(subject.has_observers());
+  /// if subject.has_observers() {
+  ///   subject.next(1);
+  /// }
+  ///
+  /// subscription.unsubscribe();
+  /// assert_eq!(subject.observer_count(), 0);
+  /// ```
+  #[inline]
+  fn has_observers(&self) -> bool {
+    !self.is_empty()
+  }
 }
 
 type PublisherVec<'a, Item, Err> =
@@ -24,6 +61,7 @@ type PublisherVec<'a, Item, Err> =
 pub struct Subject<'a, Item, Err> {
   observers: PublisherVec<'a, Item, Err>,
   chamber: PublisherVec<'a, Item, Err>,
+  terminal: MutRc<Option<Result<(), Err>>>,
 }
 
 type PublisherVecThreads<Item, Err> =
@@ -33,6 +71,7 @@ type PublisherVecThreads<Item, Err> =
 pub struct SubjectThreads<Item, Err> {
   observers: PublisherVecThreads<Item, Err>,
   chamber: PublisherVecThreads<Item, Err>,
+  terminal: MutArc<Option<Result<(), Err>>>,
 }
 
 type PublisherMutRefValueVec<'a, Item, Err> = MutRc<
@@ -43,6 +82,7 @@ type PublisherMutRefValueVec<'a, Item, Err> = MutRc<
 pub struct MutRefItemSubject<'a, Item, Err> {
   observers: PublisherMutRefValueVec<'a, Item, Err>,
   chamber: PublisherMutRefValueVec<'a, Item, Err>,
+  terminal: MutRc<Option<Result<(), Err>>>,
 }
 
 type PublisherMutRefErrVec<'a, Item, Err> = MutRc<
@@ -53,6 +93,7 @@ type PublisherMutRefErrVec<'a, Item, Err> = MutRc<
 pub struct MutRefErrSubject<'a, Item, Err> {
   observers: PublisherMutRefErrVec<'a, Item, Err>,
   chamber: PublisherMutRefErrVec<'a, Item, Err>,
+  terminal: MutRc<Option<Result<(), Err>>>,
 }
 
 type PublisherMutRefValueErrVec<'a, Item, Err> = MutRc<
@@ -65,6 +106,7 @@ type PublisherMutRefValueErrVec<'a, Item, Err> = MutRc<
 pub struct MutRefItemErrSubject<'a, Item, Err> {
   observers: PublisherMutRefValueErrVec<'a, Item, Err>,
   chamber: PublisherMutRefValueErrVec<'a, Item, Err>,
+  terminal: MutRc<Option<Result<(), Err>>>,
 }
 
 macro_rules! impl_subject_trivial {
@@ -82,19 +124,21 @@ macro_rules! impl_subject_trivial {
 
     impl<$($lf,)? Item, Err>  SubjectSize for $ty {
       fn is_empty(&self) -> bool{
-        self
-        .observers
-        .rc_deref().as_ref().map_or(true, |observers| {
-          observers.is_empty()
-            && self.chamber.rc_deref().as_ref().unwrap().is_empty()
-        })
+        self.len() == 0
       }
 
       fn len(&self) -> usize {
+        // Subscribers unsubscribed or finished are only removed on the next
+        // emission, so they are not counted.
         self
           .observers
           .rc_deref().as_ref().map_or(0, |observers| {
-            observers.len() + self.chamber.rc_deref().as_ref().unwrap().len()
+            let chamber = self.chamber.rc_deref();
+            observers
+              .iter()
+              .chain(chamber.as_ref().unwrap().iter())
+              .filter(|p| !p.p_is_closed())
+              .count()
           })
       }
     }
@@ -104,7 +148,8 @@ macro_rules! impl_subject_trivial {
       fn clone(&self) -> Self {
         Self {
           observers: self.observers.clone(),
-          chamber: self.chamber.clone()
+          chamber: self.chamber.clone(),
+          terminal: self.terminal.clone(),
         }
       }
     }
@@ -113,7 +158,8 @@ macro_rules! impl_subject_trivial {
       fn default() -> Self {
         Self {
           observers: $rc::own(Some(<_>::default())) ,
-          chamber: $rc::own(Some(<_>::default()))
+          chamber: $rc::own(Some(<_>::default())),
+          terminal: $rc::own(None),
         }
       }
     }
@@ -157,19 +203,44 @@ impl<'a, Item, Err> ObservableExt<&mut Item, &mut Err>
 }
 
 macro_rules! impl_observer_methods {
+  (@err_terminal $err: ident, $err_clone: ident) => {
+    Some(Err($err.$err_clone()))
+  };
+  (@err_terminal $err: ident) => {
+    // The error is only borrowed, so it can't be replayed.
+    None
+  };
+  (@terminate $this: ident, $terminal: expr) => {{
+    // The terminal is recorded before loading the chamber, so the observer
+    // subscribing meanwhile either is in the chamber or gets the terminal.
+    let observers = $this.observers.rc_deref_mut().take();
+    observers.map(|mut observers| {
+      if let Some(terminal) = $terminal {
+        *$this.terminal.rc_deref_mut() = Some(terminal);
+      }
+      if let Some(chamber) = $this.chamber.rc_deref_mut().as_mut() {
+        observers.append(chamber);
+      }
+      observers
+    })
+  }};
   ($item: ty$({ $item_clone: ident})?, $err: ty$({$err_clone: ident})?) => {
     fn next(&mut self, value: $item) {
       self.load();
       if let Some(observers) = self.observers.rc_deref_mut().as_mut() {
+        // Drops the subscribers unsubscribed since the last emission.
+        observers.retain(|p| !p.p_is_unsubscribed());
         observers.iter_mut().for_each(|p| {
           p.p_next(value$(.$item_clone())?);
         });
       }
     }
 
-    fn error(mut self, err: $err) {
-      self.load();
-      if let Some(observers) = self.observers.rc_deref_mut().take() {
+    fn error(self, err: $err) {
+      let terminal =
+        impl_observer_methods!(@err_terminal err $(, $err_clone)?);
+      let observers = impl_observer_methods!(@terminate self, terminal);
+      if let Some(observers) = observers {
         observers
           .into_iter()
           .filter(|o| !o.p_is_closed())
@@ -177,9 +248,9 @@ macro_rules! impl_observer_methods {
       }
     }
 
-    fn complete(mut self) {
-      self.load();
-      if let Some(observers) = self.observers.rc_deref_mut().take() {
+    fn complete(self) {
+      let observers = impl_observer_methods!(@terminate self, Some(Ok(())));
+      if let Some(observers) = observers {
         observers
           .into_iter()
           .filter(|o| !o.p_is_closed())
@@ -225,11 +296,34 @@ impl<'a, Item, Err> Observer<&mut Item, &mut Err>
 }
 
 macro_rules! impl_observable_for_subject {
-  ($subscriber:ident) => {
+  (@terminal $this: ident, $observer: ident, $err_clone: ident) => {{
+    let terminal = $this.terminal.rc_deref().clone();
+    match terminal {
+      Some(Ok(())) => $observer.complete(),
+      Some(Err(err)) => $observer.error(err),
+      None => {}
+    }
+  }};
+  (@terminal $this: ident, $observer: ident) => {{
+    // Only the completion is recorded when the error is borrowed.
+    let completed = $this.terminal.rc_deref().is_some();
+    if completed {
+      $observer.complete();
+    }
+  }};
+  ($subscriber:ident $(, $err_clone: ident)?) => {
     type Unsub = $subscriber<O>;
 
     fn actual_subscribe(self, observer: O) -> Self::Unsub {
-      if let Some(chamber) = self.chamber.rc_deref_mut().as_mut() {
+      let mut chamber = self.chamber.rc_deref_mut();
+      if self.terminal.rc_deref().is_some() {
+        // A terminated subject emits its termination to the late observers.
+        drop(chamber);
+        impl_observable_for_subject!(@terminal self, observer $(, $err_clone)?);
+        $subscriber::new(None)
+      } else if let Some(chamber) = chamber.as_mut() {
+        // Drops the subscribers unsubscribed before any emission.
+        chamber.retain(|p| !p.p_is_unsubscribed());
         let subscriber = $subscriber::new(Some(observer));
         chamber.push(Box::new(subscriber.clone()));
         subscriber
@@ -243,23 +337,26 @@ macro_rules! impl_observable_for_subject {
 impl<'a, Item, Err, O> Observable<Item, Err, O> for Subject<'a, Item, Err>
 where
   O: Observer<Item, Err> + 'a,
+  Err: Clone,
 {
-  impl_observable_for_subject!(Subscriber);
+  impl_observable_for_subject!(Subscriber, clone);
 }
 
 impl<Item, Err, O> Observable<Item, Err, O> for SubjectThreads<Item, Err>
 where
   O: Observer<Item, Err> + Send + 'static,
+  Err: Clone,
 {
-  impl_observable_for_subject!(SubscriberThreads);
+  impl_observable_for_subject!(SubscriberThreads, clone);
 }
 
 impl<'a, Item, Err, O> Observable<&mut Item, Err, O>
   for MutRefItemSubject<'a, Item, Err>
 where
   O: for<'r> Observer<&'r mut Item, Err> + 'a,
+  Err: Clone,
 {
-  impl_observable_for_subject!(Subscriber);
+  impl_observable_for_subject!(Subscriber, clone);
 }
 
 impl<'a, Item, Err, O> Observable<Item, &mut Err, O>
@@ -281,6 +378,7 @@ where
 #[cfg(test)]
 mod test {
   use super::*;
+  use std::convert::Infallible;
 
   #[test]
   fn smoke() {
@@ -296,6 +394,18 @@ mod test {
     assert_eq!(value, 2);
   }
 
+  #[test]
+  fn len_ignore_closed_subscribers() {
+    let subject = Subject::<i32, _>::default();
+    let s1 = subject.clone().subscribe(|_| {});
+    subject.clone().subscribe(|_| {});
+    assert_eq!(subject.len(), 2);
+
+    s1.unsubscribe();
+    assert_eq!(subject.len(), 1);
+    assert!(!subject.is_empty());
+  }
+
   #[test]
   fn mut_ref_item() {
     let mut value = 0;
@@ -389,4 +499,113 @@ mod test {
     local.next(1);
     local.error(2);
   }
+
+  #[test]
+  fn late_subscriber_get_termination() {
+    let mut completed = false;
+    let mut error = None;
+    let mut values = vec![];
+    {
+      let mut subject = Subject::<i32, &str>::default();
+      subject.clone().complete();
+      subject.next(1);
+      subject.clone().error("ignored");
+
+      subject
+        .clone()
+        .on_complete(|| completed = true)
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+    }
+    assert!(completed);
+    assert_eq!(error, None);
+    assert!(values.is_empty());
+
+    let subject = SubjectThreads::<i32, &str>::default();
+    subject.clone().error("fail");
+    let error = MutArc::own(None);
+    let c_error = error.clone();
+    subject
+      .on_error(move |e| *c_error.rc_deref_mut() = Some(e))
+      .subscribe(|_| {});
+    assert_eq!(*error.rc_deref(), Some("fail"));
+  }
+
+  #[test]
+  fn mut_ref_error_late_complete() {
+    let mut completed = false;
+    {
+      let subject = MutRefErrSubject::<i32, i32>::default();
+      subject.clone().complete();
+      subject
+        .on_complete(|| completed = true)
+        .on_error((|_: &mut i32| {}) as for<'r> fn(&'r mut i32))
+        .subscribe(|_| {});
+    }
+    assert!(completed);
+  }
+
+  #[test]
+  fn drop_unsubscribed_observers() {
+    use std::{cell::Cell, rc::Rc};
+
+    // Counts its clones, one per observer emitted to.
+    struct Counted(Rc<Cell<usize>>);
+    impl Clone for Counted {
+      fn clone(&self) -> Self {
+        self.0.set(self.0.get() + 1);
+        Self(self.0.clone())
+      }
+    }
+
+    let clones = Rc::new(Cell::new(0));
+    let mut subject = Subject::<Counted, Infallible>::default();
+    let mut subscriptions: Vec<_> = (0..1000)
+      .map(|_| subject.clone().subscribe(|_| {}))
+      .collect();
+    subject.next(Counted(clones.clone()));
+    let stored = |subject: &Subject<Counted, Infallible>| {
+      subject.observers.rc_deref().as_ref().map_or(0, |o| o.len())
+    };
+    assert_eq!(stored(&subject), 1000);
+    assert_eq!(clones.get(), 1000);
+
+    let last = subscriptions.pop().unwrap();
+    subscriptions.into_iter().for_each(|s| s.unsubscribe());
+    clones.set(0);
+    subject.next(Counted(clones.clone()));
+    assert_eq!(stored(&subject), 1);
+    assert_eq!(clones.get(), 1);
+    assert!(!last.is_closed());
+
+    for _ in 0..1000 {
+      subject.clone().subscribe(|_| {}).unsubscribe();
+    }
+    assert!(subject.chamber.rc_deref().as_ref().unwrap().len() <= 1);
+  }
+
+  #[test]
+  fn observer_count() {
+    let subject = SubjectThreads::<i32, Infallible>::default();
+    assert!(!subject.has_observers());
+
+    let s1 = subject.clone().subscribe(|_| {});
+    let s2 = subject.clone().subscribe(|_| {});
+    assert_eq!(subject.observer_count(), 2);
+
+    s1.unsubscribe();
+    assert_eq!(subject.observer_count(), 1);
+    assert!(subject.has_observers());
+
+    s2.unsubscribe();
+    assert_eq!(subject.observer_count(), 0);
+    assert!(!subject.has_observers());
+
+    let mut behavior = BehaviorSubject::<_, Infallible>::new(0);
+    behavior.clone().take(1).subscribe(|_| {});
+    assert!(!behavior.has_observers());
+    behavior.clone().subscribe(|_| {});
+    behavior.next(1);
+    assert_eq!(behavior.observer_count(), 1);
+  }
 }
@@ -0,0 +1,280 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+
+/// A not threads safe subject that emits only the last item it received, and
+/// only once it completed, to all its observers, including the ones
+/// subscribing after the completion.
+///
+/// If it receives an error, the observers get only the error, and the last
+/// item is dropped.
+pub struct AsyncSubject<'a, Item, Err> {
+  subject: Subject<'a, Item, Err>,
+  state: MutRc<AsyncState<Item, Err>>,
+}
+
+/// A threads safe subject that emits only the last item it received, and
+/// only once it completed, to all its observers, including the ones
+/// subscribing after the completion.
+///
+/// If it receives an error, the observers get only the error, and the last
+/// item is dropped.
+pub struct AsyncSubjectThreads<Item, Err> {
+  subject: SubjectThreads<Item, Err>,
+  state: MutArc<AsyncState<Item, Err>>,
+}
+
+pub struct AsyncState<Item, Err> {
+  value: Option<Item>,
+  // `Ok` once completed, `Err` once errored.
+  terminal: Option<Result<(), Err>>,
+}
+
+impl<Item, Err> Default for AsyncState<Item, Err> {
+  fn default() -> Self {
+    Self { value: None, terminal: None }
+  }
+}
+
+macro_rules! impl_async_subject {
+  (
+    $ty: ty, $rc: ident, $subscriber: ident
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err> Default for $ty {
+      fn default() -> Self {
+        Self { subject: <_>::default(), state: <_>::default() }
+      }
+    }
+
+    impl<$($lf,)? Item, Err> Clone for $ty {
+      fn clone(&self) -> Self {
+        Self { subject: self.subject.clone(), state: self.state.clone() }
+      }
+    }
+
+    impl<$($lf,)? Item, Err> Observer<Item, Err> for $ty
+    where
+      Item: Clone,
+      Err: Clone,
+    {
+      fn next(&mut self, value: Item) {
+        let mut state = self.state.rc_deref_mut();
+        if state.terminal.is_none() {
+          state.value = Some(value);
+        }
+      }
+
+      fn error(self, err: Err) {
+        {
+          let mut state = self.state.rc_deref_mut();
+          if state.terminal.is_some() {
+            return;
+          }
+          state.value = None;
+          state.terminal = Some(Err(err.clone()));
+        }
+        self.subject.error(err);
+      }
+
+      fn complete(mut self) {
+        let value = {
+          let mut state = self.state.rc_deref_mut();
+          if state.terminal.is_some() {
+            return;
+          }
+          state.terminal = Some(Ok(()));
+          state.value.clone()
+        };
+        if let Some(value) = value {
+          self.subject.next(value);
+        }
+        self.subject.complete();
+      }
+
+      #[inline]
+      fn is_finished(&self) -> bool {
+        self.subject.is_finished()
+      }
+    }
+
+    impl<$($lf,)? Item, Err, O> Observable<Item, Err, O> for $ty
+    where
+      O: Observer<Item, Err> $(+ $lf)? $(+ $send + 'static)?,
+      Item: Clone,
+      Err: Clone,
+    {
+      type Unsub = $subscriber<O>;
+
+      fn actual_subscribe(self, mut observer: O) -> Self::Unsub {
+        let state = self.state.rc_deref();
+        match state.terminal.clone() {
+          // Subscribes while the state is held, so the observer can't miss
+          // the termination.
+          None => self.subject.actual_subscribe(observer),
+          Some(Ok(())) => {
+            let value = state.value.clone();
+            drop(state);
+            if let Some(value) = value {
+              observer.next(value);
+            }
+            observer.complete();
+            $subscriber::new(None)
+          }
+          Some(Err(err)) => {
+            drop(state);
+            observer.error(err);
+            $subscriber::new(None)
+          }
+        }
+      }
+    }
+
+    impl<$($lf,)? Item, Err> ObservableExt<Item, Err> for $ty {}
+
+    impl<$($lf,)? Item, Err> Subscription for $ty {
+      #[inline]
+      fn unsubscribe(self) {
+        self.subject.unsubscribe();
+      }
+
+      /// A terminated subject is not closed, it still emits its result to the
+      /// new observers.
+      fn is_closed(&self) -> bool {
+        self.state.rc_deref().terminal.is_none() && self.subject.is_closed()
+      }
+    }
+
+    impl<$($lf,)? Item, Err> SubjectSize for $ty {
+      #[inline]
+      fn is_empty(&self) -> bool {
+        self.subject.is_empty()
+      }
+
+      #[inline]
+      fn len(&self) -> usize {
+        self.subject.len()
+      }
+    }
+  };
+}
+
+impl_async_subject!(AsyncSubject<'a, Item, Err>, MutRc, Subscriber, 'a);
+impl_async_subject!(
+  AsyncSubjectThreads<Item, Err>,
+  MutArc,
+  SubscriberThreads,
+  Send
+);
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn emit_last_on_complete() {
+    let mut values = vec![];
+    let mut completed = 0;
+    {
+      let mut subject = AsyncSubject::<i32, ()>::default();
+      subject
+        .clone()
+        .on_complete(|| completed += 1)
+        .on_error(|_| {})
+        .subscribe(|v| values.push(v));
+      subject.next(1);
+      subject.next(2);
+      assert_eq!(subject.len(), 1);
+
+      subject.clone().complete();
+    }
+
+    assert_eq!(values, vec![2]);
+    assert_eq!(completed, 1);
+  }
+
+  #[test]
+  fn late_subscriber_after_complete() {
+    let mut values = vec![];
+    let mut completed = false;
+    {
+      let mut subject = AsyncSubject::<i32, ()>::default();
+      subject.next(1);
+      subject.clone().complete();
+      subject.next(2);
+
+      subject
+        .on_complete(|| completed = true)
+        .on_error(|_| {})
+        .subscribe(|v| values.push(v));
+    }
+
+    assert_eq!(values, vec![1]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn complete_without_item() {
+    let mut values = vec![];
+    let mut completed = false;
+    {
+      let subject = AsyncSubject::<i32, ()>::default();
+      subject.clone().complete();
+      subject
+        .on_complete(|| completed = true)
+        .on_error(|_| {})
+        .subscribe(|v| values.push(v));
+    }
+
+    assert!(values.is_empty());
+    assert!(completed);
+  }
+
+  #[test]
+  fn error_drop_value() {
+    let (mut values, mut late_values) = (vec![], vec![]);
+    let (mut error, mut late_error) = (None, None);
+    {
+      let mut subject = AsyncSubject::<i32, &str>::default();
+      subject
+        .clone()
+        .on_error(|e| error = Some(e))
+        .subscribe(|v| values.push(v));
+      subject.next(1);
+      subject.clone().error("fail");
+      subject.clone().complete();
+
+      subject
+        .on_error(|e| late_error = Some(e))
+        .subscribe(|v| late_values.push(v));
+    }
+
+    assert!(values.is_empty() && late_values.is_empty());
+    assert_eq!(error, Some("fail"));
+    assert_eq!(late_error, Some("fail"));
+  }
+
+  #[test]
+  fn threads_late_subscriber() {
+    use std::sync::{Arc, Mutex};
+
+    let mut subject = AsyncSubjectThreads::<i32, ()>::default();
+    let c_subject = subject.clone();
+    std::thread::spawn(move || {
+      let mut c_subject = c_subject;
+      c_subject.next(42);
+      c_subject.complete();
+    })
+    .join()
+    .unwrap();
+    subject.next(1);
+
+    let value = Arc::new(Mutex::new(None));
+    let c_value = value.clone();
+    subject
+      .on_error(|_| {})
+      .subscribe(move |v| *c_value.lock().unwrap() = Some(v));
+    assert_eq!(*value.lock().unwrap(), Some(42));
+  }
+}
@@ -1,123 +1,197 @@
-use crate::prelude::*;
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+  subject::replay_observer::ReplayObserver,
+};
 
-#[derive(Clone)]
-pub struct BehaviorSubject<Item, Subject> {
-  pub(crate) subject: Subject,
-  pub(crate) value: Item,
+/// A not threads safe subject that holds its latest item, and emits it to
+/// every observer subscribing, before forwarding the later items.
+///
+/// The clones of the subject share the same item. Once the subject completed
+/// or emitted an error, the observers subscribing get only that termination.
+pub struct BehaviorSubject<'a, Item, Err> {
+  subject: Subject<'a, Item, Err>,
+  state: MutRc<BehaviorState<Item, Err>>,
 }
 
-impl<Item, Subject: Default> BehaviorSubject<Item, Subject> {
-  pub fn new(value: Item) -> Self {
-    Self { subject: <_>::default(), value }
-  }
+/// A threads safe subject that holds its latest item, and emits it to every
+/// observer subscribing, before forwarding the later items.
+///
+/// The clones of the subject share the same item. Once the subject completed
+/// or emitted an error, the observers subscribing get only that termination.
+pub struct BehaviorSubjectThreads<Item, Err> {
+  subject: SubjectThreads<Item, Err>,
+  state: MutArc<BehaviorState<Item, Err>>,
 }
 
-impl<Item, Err, Subject> Observer<Item, Err> for BehaviorSubject<Item, Subject>
-where
-  Subject: Observer<Item, Err>,
-  Item: Clone,
-{
-  #[inline]
-  fn next(&mut self, value: Item) {
-    self.value = value;
-    Observer::next(&mut self.subject, self.value.clone());
-  }
+/// The state shared by the clones of a behavior subject: its latest item, and
+/// its termination once terminated.
+pub struct BehaviorState<Item, Err> {
+  value: Item,
+  // `Ok` once completed, `Err` once errored.
+  terminal: Option<Result<(), Err>>,
+}
 
-  #[inline]
-  fn error(self, err: Err) {
-    self.subject.error(err)
-  }
+macro_rules! impl_behavior_subject {
+  (
+    $ty: ty, $rc: ident, $subscriber: ident
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err> $ty {
+      /// Creates a subject holding `value` until it receives an item.
+      pub fn new(value: Item) -> Self {
+        Self {
+          subject: <_>::default(),
+          state: $rc::own(BehaviorState { value, terminal: None }),
+        }
+      }
 
-  #[inline]
-  fn complete(self) {
-    self.subject.complete()
-  }
+      /// Returns a clone of the latest item.
+      #[inline]
+      pub fn value(&self) -> Item
+      where
+        Item: Clone,
+      {
+        self.state.rc_deref().value.clone()
+      }
+    }
 
-  #[inline]
-  fn is_finished(&self) -> bool {
-    self.subject.is_finished()
-  }
-}
+    impl<$($lf,)? Item, Err> Clone for $ty {
+      fn clone(&self) -> Self {
+        Self { subject: self.subject.clone(), state: self.state.clone() }
+      }
+    }
 
-impl<Item, Subject> Subscription for BehaviorSubject<Item, Subject>
-where
-  Subject: Subscription,
-{
-  #[inline]
-  fn unsubscribe(self) {
-    self.subject.unsubscribe();
-  }
+    impl<$($lf,)? Item, Err> Observer<Item, Err> for $ty
+    where
+      Item: Clone,
+      Err: Clone,
+    {
+      fn next(&mut self, value: Item) {
+        {
+          let mut state = self.state.rc_deref_mut();
+          if state.terminal.is_some() {
+            return;
+          }
+          state.value = value.clone();
+        }
+        self.subject.next(value);
+      }
 
-  #[inline]
-  fn is_closed(&self) -> bool {
-    self.subject.is_closed()
-  }
-}
+      fn error(self, err: Err) {
+        self.state.rc_deref_mut().terminal = Some(Err(err.clone()));
+        self.subject.error(err);
+      }
 
-impl<Item, Subject> SubjectSize for BehaviorSubject<Item, Subject>
-where
-  Subject: SubjectSize,
-{
-  #[inline]
-  fn is_empty(&self) -> bool {
-    self.subject.is_empty()
-  }
+      fn complete(self) {
+        self.state.rc_deref_mut().terminal = Some(Ok(()));
+        self.subject.complete();
+      }
 
-  #[inline]
-  fn len(&self) -> usize {
-    self.subject.len()
-  }
-}
+      #[inline]
+      fn is_finished(&self) -> bool {
+        self.subject.is_finished()
+      }
+    }
 
-impl<Item, Err, O, Subject> Observable<Item, Err, O>
-  for BehaviorSubject<Item, Subject>
-where
-  Subject: Observable<Item, Err, O>,
-  O: Observer<Item, Err>,
-  Item: Clone,
-{
-  type Unsub = Subject::Unsub;
-
-  fn actual_subscribe(self, mut observer: O) -> Self::Unsub {
-    observer.next(self.value.clone());
-    self.subject.actual_subscribe(observer)
-  }
-}
+    impl<$($lf,)? Item, Err, O> Observable<Item, Err, O> for $ty
+    where
+      O: Observer<Item, Err> $(+ $lf)? $(+ $send + 'static)?,
+      Item: Clone $(+ $lf)? $(+ $send + 'static)?,
+      Err: Clone $(+ $lf)? $(+ $send + 'static)?,
+    {
+      type Unsub = $subscriber<$rc<ReplayObserver<O, Item, Err>>>;
 
-impl<Item, Err, Subject> ObservableExt<Item, Err>
-  for BehaviorSubject<Item, Subject>
-where
-  Subject: ObservableExt<Item, Err>,
-{
-}
+      fn actual_subscribe(self, observer: O) -> Self::Unsub {
+        let state = self.state.rc_deref();
+        match state.terminal.clone() {
+          None => {
+            let value = state.value.clone();
+            // Subscribed before the item can change, so the items emitted
+            // since are queued and forwarded only after this one.
+            let replay = $rc::own(ReplayObserver::replaying());
+            let subscriber = self.subject.actual_subscribe(replay.clone());
+            drop(state);
+            replay.replay(observer, Some(value), || subscriber.is_closed());
+            subscriber
+          }
+          Some(terminal) => {
+            drop(state);
+            match terminal {
+              Ok(()) => observer.complete(),
+              Err(err) => observer.error(err),
+            }
+            $subscriber::new(None)
+          }
+        }
+      }
+    }
 
-impl<Item, Err, Subject> Behavior<Item, Err> for BehaviorSubject<Item, Subject>
-where
-  Subject: Observer<Item, Err>,
-  Item: Clone,
-{
-  fn peek(&self) -> Item {
-    self.value.clone()
-  }
+    impl<$($lf,)? Item, Err> ObservableExt<Item, Err> for $ty {}
+
+    impl<$($lf,)? Item, Err> Behavior<Item, Err> for $ty
+    where
+      Item: Clone,
+      Err: Clone,
+    {
+      #[inline]
+      fn peek(&self) -> Item {
+        self.value()
+      }
+    }
+
+    impl<$($lf,)? Item, Err> Subscription for $ty {
+      #[inline]
+      fn unsubscribe(self) {
+        self.subject.unsubscribe();
+      }
+
+      #[inline]
+      fn is_closed(&self) -> bool {
+        self.subject.is_closed()
+      }
+    }
+
+    impl<$($lf,)? Item, Err> SubjectSize for $ty {
+      #[inline]
+      fn is_empty(&self) -> bool {
+        self.subject.is_empty()
+      }
+
+      #[inline]
+      fn len(&self) -> usize {
+        self.subject.len()
+      }
+    }
+  };
 }
 
+impl_behavior_subject!(BehaviorSubject<'a, Item, Err>, MutRc, Subscriber, 'a);
+impl_behavior_subject!(
+  BehaviorSubjectThreads<Item, Err>,
+  MutArc,
+  SubscriberThreads,
+  Send
+);
+
 #[cfg(test)]
 mod test {
   use crate::prelude::*;
+  use std::convert::Infallible;
 
   #[test]
   fn base_data_flow() {
     let mut i = 0;
 
     {
-      let broadcast = BehaviorSubject::<_, Subject<_, _>>::new(42);
+      let broadcast = BehaviorSubject::<_, Infallible>::new(42);
       broadcast.clone().subscribe(|v| i = v * 2);
     }
 
     assert_eq!(i, 84);
 
     {
-      let mut broadcast = BehaviorSubject::<_, Subject<_, _>>::new(42);
+      let mut broadcast = BehaviorSubject::<_, Infallible>::new(42);
       broadcast.clone().subscribe(|v| i = v * 2);
       broadcast.next(1);
     }
@@ -128,7 +202,7 @@ mod test {
   #[test]
   #[should_panic]
   fn error() {
-    let broadcast = BehaviorSubject::<_, Subject<_, _>>::new(42);
+    let broadcast = BehaviorSubject::new(42);
     broadcast
       .clone()
       .on_error(|err| panic!("{}", err))
@@ -142,14 +216,14 @@ mod test {
     let mut i = 0;
 
     {
-      let subject = BehaviorSubject::<_, Subject<_, _>>::new(42);
+      let subject = BehaviorSubject::<_, Infallible>::new(42);
       subject.clone().subscribe(|v| i = v).unsubscribe();
     }
 
     assert_eq!(i, 42);
 
     {
-      let mut subject = BehaviorSubject::<_, Subject<_, _>>::new(42);
+      let mut subject = BehaviorSubject::<_, Infallible>::new(42);
       subject.clone().subscribe(|v| i = v).unsubscribe();
       subject.next(100);
     }
@@ -159,10 +233,128 @@ mod test {
 
   #[test]
   fn subject_subscribe_subject() {
-    let mut local = BehaviorSubject::<_, Subject<_, _>>::new(42);
-    let local2 = BehaviorSubject::<_, Subject<_, _>>::new(42);
+    let mut local = BehaviorSubject::new(42);
+    let local2 = BehaviorSubject::new(42);
     local.clone().actual_subscribe(local2);
     local.next(1);
     local.error(2);
   }
+
+  #[test]
+  fn replay_latest_shared_by_clones() {
+    let mut values = vec![];
+    let subject = BehaviorSubject::<_, Infallible>::new(0);
+    subject.clone().next(1);
+    subject.clone().next(2);
+    assert_eq!(subject.value(), 2);
+
+    subject.clone().subscribe(|v| values.push(v));
+    subject.clone().next(3);
+    assert_eq!(subject.peek(), 3);
+    drop(subject);
+
+    assert_eq!(values, [2, 3]);
+  }
+
+  #[test]
+  fn terminal_only_after_termination() {
+    let mut values = vec![];
+    let mut completed = false;
+    let mut subject = BehaviorSubject::<_, &str>::new(0);
+    subject.next(1);
+    subject.clone().complete();
+    subject
+      .clone()
+      .on_complete(|| completed = true)
+      .on_error(|_| {})
+      .subscribe(|v| values.push(v));
+    drop(subject);
+    assert!(values.is_empty());
+    assert!(completed);
+
+    let mut error = None;
+    let subject = BehaviorSubject::<i32, _>::new(0);
+    subject.clone().error("fail");
+    subject.on_error(|e| error = Some(e)).subscribe(|_| {});
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn subscribe_in_callback() {
+    let subject = BehaviorSubject::<_, Infallible>::new(1);
+    let c_subject = subject.clone();
+    let inner = crate::rc::MutRc::own(vec![]);
+    let c_inner = inner.clone();
+    subject.clone().subscribe(move |v| {
+      let c_inner = c_inner.clone();
+      c_subject.clone().subscribe(move |i| {
+        crate::rc::RcDerefMut::rc_deref_mut(&c_inner).push((v, i))
+      });
+    });
+
+    assert_eq!(*crate::rc::RcDeref::rc_deref(&inner), [(1, 1)]);
+  }
+
+  #[test]
+  fn emit_into_subject_while_replaying() {
+    let values = crate::rc::MutRc::own(vec![]);
+    let c_values = values.clone();
+    let subject = BehaviorSubject::<_, Infallible>::new(1);
+    let mut c_subject = subject.clone();
+    subject.clone().subscribe(move |v| {
+      crate::rc::RcDerefMut::rc_deref_mut(&c_values).push(v);
+      if v < 3 {
+        c_subject.next(v + 1);
+      }
+    });
+
+    assert_eq!(*crate::rc::RcDeref::rc_deref(&values), [1, 2, 3]);
+    assert_eq!(subject.value(), 3);
+  }
+
+  #[test]
+  fn threads_emit_into_subject_while_replaying() {
+    use std::sync::{Arc, Mutex};
+
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let subject = BehaviorSubjectThreads::<_, Infallible>::new(1);
+    let mut c_subject = subject.clone();
+    subject.clone().subscribe(move |v| {
+      c_values.lock().unwrap().push(v);
+      if v < 3 {
+        c_subject.next(v + 1);
+      }
+    });
+
+    assert_eq!(*values.lock().unwrap(), [1, 2, 3]);
+  }
+
+  #[test]
+  fn threads_no_missed_item() {
+    use std::sync::{Arc, Mutex};
+
+    let mut subject = BehaviorSubjectThreads::<_, Infallible>::new(0);
+    let c_subject = subject.clone();
+    let handle = std::thread::spawn(move || {
+      let mut lasts = vec![];
+      for _ in 0..100 {
+        let last = Arc::new(Mutex::new(None));
+        let c_last = last.clone();
+        c_subject
+          .clone()
+          .subscribe(move |v| *c_last.lock().unwrap() = Some(v));
+        lasts.push(last);
+      }
+      lasts
+    });
+    for i in 1..=1000 {
+      subject.next(i);
+    }
+    let lasts = handle.join().unwrap();
+
+    for last in lasts {
+      assert_eq!(*last.lock().unwrap(), Some(1000));
+    }
+  }
 }
@@ -0,0 +1,124 @@
+use crate::{
+  ops::materialize::Notification,
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+};
+use std::collections::VecDeque;
+
+/// The observer a replaying subject subscribes for an observer, it queues the
+/// notifications received while the past items are replayed to the observer,
+/// so they are emitted after them.
+///
+/// The observer is not borrowed while the past items are replayed, so it can
+/// emit into the subject it's subscribing to.
+pub struct ReplayObserver<O, Item, Err> {
+  // `None` while replaying, or once terminated.
+  observer: Option<O>,
+  // `Some` while replaying.
+  pending: Option<VecDeque<Notification<Item, Err>>>,
+}
+
+impl<O, Item, Err> ReplayObserver<O, Item, Err> {
+  /// Creates an observer queuing its notifications until the replay is done.
+  pub(crate) fn replaying() -> Self {
+    Self {
+      observer: None,
+      pending: Some(VecDeque::new()),
+    }
+  }
+}
+
+macro_rules! impl_replay_observer {
+  ($rc: ident) => {
+    impl<O, Item, Err> $rc<ReplayObserver<O, Item, Err>>
+    where
+      O: Observer<Item, Err>,
+    {
+      /// Emits `items` to `observer`, then the notifications queued meanwhile,
+      /// and lets the later ones go straight to it. Stops as soon as
+      /// `is_closed` returns `true`.
+      pub(crate) fn replay(
+        &self,
+        mut observer: O,
+        items: impl IntoIterator<Item = Item>,
+        is_closed: impl Fn() -> bool,
+      ) {
+        let mut items = items.into_iter();
+        loop {
+          if is_closed() || observer.is_finished() {
+            self.rc_deref_mut().pending = None;
+            return;
+          }
+          let notification = match items.next() {
+            Some(value) => Notification::Next(value),
+            None => {
+              let mut inner = self.rc_deref_mut();
+              match inner.pending.as_mut().and_then(VecDeque::pop_front) {
+                Some(notification) => notification,
+                None => {
+                  inner.pending = None;
+                  inner.observer = Some(observer);
+                  return;
+                }
+              }
+            }
+          };
+          match notification.accept(observer) {
+            Some(o) => observer = o,
+            None => {
+              self.rc_deref_mut().pending = None;
+              return;
+            }
+          }
+        }
+      }
+
+      fn terminate(&self, notification: Notification<Item, Err>) {
+        let mut inner = self.rc_deref_mut();
+        if let Some(pending) = inner.pending.as_mut() {
+          pending.push_back(notification);
+          return;
+        }
+        let observer = inner.observer.take();
+        drop(inner);
+        if let Some(observer) = observer {
+          notification.accept(observer);
+        }
+      }
+    }
+
+    impl<O, Item, Err> Observer<Item, Err> for $rc<ReplayObserver<O, Item, Err>>
+    where
+      O: Observer<Item, Err>,
+    {
+      fn next(&mut self, value: Item) {
+        let mut inner = self.rc_deref_mut();
+        let ReplayObserver { observer, pending } = &mut *inner;
+        if let Some(pending) = pending {
+          pending.push_back(Notification::Next(value));
+        } else if let Some(observer) = observer {
+          observer.next(value);
+        }
+      }
+
+      #[inline]
+      fn error(self, err: Err) {
+        self.terminate(Notification::Error(err));
+      }
+
+      #[inline]
+      fn complete(self) {
+        self.terminate(Notification::Complete);
+      }
+
+      fn is_finished(&self) -> bool {
+        let inner = self.rc_deref();
+        inner.pending.is_none()
+          && inner.observer.as_ref().is_none_or(|o| o.is_finished())
+      }
+    }
+  };
+}
+
+impl_replay_observer!(MutRc);
+impl_replay_observer!(MutArc);
@@ -0,0 +1,434 @@
+use crate::{
+  prelude::*,
+  rc::{MutArc, MutRc, RcDeref, RcDerefMut},
+  subject::replay_observer::ReplayObserver,
+};
+use std::{
+  collections::VecDeque,
+  time::{Duration, Instant},
+};
+
+/// A not threads safe subject that replays the last items it received, and
+/// its termination, to the observers subscribing later.
+///
+/// The items are stamped by the clock `C` when the subject is created by
+/// [`with_time`](ReplaySubject::with_time), and by [`Instant::now`] otherwise.
+pub struct ReplaySubject<'a, Item, Err, C = fn() -> Instant> {
+  subject: Subject<'a, Item, Err>,
+  buffer: MutRc<ReplayBuffer<Item, Err, C>>,
+}
+
+/// A threads safe subject that replays the last items it received, and its
+/// termination, to the observers subscribing later.
+///
+/// The items are stamped by the clock `C` when the subject is created by
+/// [`with_time`](ReplaySubjectThreads::with_time), and by [`Instant::now`]
+/// otherwise.
+pub struct ReplaySubjectThreads<Item, Err, C = fn() -> Instant> {
+  subject: SubjectThreads<Item, Err>,
+  buffer: MutArc<ReplayBuffer<Item, Err, C>>,
+}
+
+/// The items kept by a replay subject, at most `size` of them and only the
+/// ones received within `window` if any.
+pub struct ReplayBuffer<Item, Err, C> {
+  items: VecDeque<(Instant, Item)>,
+  size: usize,
+  window: Option<Duration>,
+  clock: C,
+  // `Ok` once completed, `Err` once errored.
+  terminal: Option<Result<(), Err>>,
+}
+
+impl<Item, Err, C> ReplayBuffer<Item, Err, C>
+where
+  C: Fn() -> Instant,
+{
+  fn new(size: usize, window: Option<Duration>, clock: C) -> Self {
+    Self {
+      items: VecDeque::with_capacity(size.min(64)),
+      size,
+      window,
+      clock,
+      terminal: None,
+    }
+  }
+
+  fn push(&mut self, value: Item) {
+    if self.size == 0 {
+      return;
+    }
+    let now = (self.clock)();
+    self.drop_expired(now);
+    if self.items.len() == self.size {
+      self.items.pop_front();
+    }
+    self.items.push_back((now, value));
+  }
+
+  fn drop_expired(&mut self, now: Instant) {
+    if let Some(window) = self.window {
+      while self
+        .items
+        .front()
+        .is_some_and(|(at, _)| now.saturating_duration_since(*at) > window)
+      {
+        self.items.pop_front();
+      }
+    }
+  }
+}
+
+macro_rules! impl_replay_subject {
+  (
+    $name: ident, $rc: ident, $subscriber: ident
+    $(,$lf: lifetime)? $(,$send: ident)?
+  ) => {
+    impl<$($lf,)? Item, Err> $name<$($lf,)? Item, Err> {
+      /// Creates a subject replaying the last `size` items.
+      pub fn new(size: usize) -> Self {
+        Self {
+          subject: <_>::default(),
+          buffer: $rc::own(ReplayBuffer::new(size, None, Instant::now)),
+        }
+      }
+
+      /// Creates a subject replaying the last `size` items received within
+      /// `window` before the subscription.
+      pub fn with_window(size: usize, window: Duration) -> Self {
+        Self::with_time(size, window, Instant::now)
+      }
+    }
+
+    impl<$($lf,)? Item, Err, C> $name<$($lf,)? Item, Err, C>
+    where
+      C: Fn() -> Instant,
+    {
+      /// Creates a subject replaying the last `size` items received within
+      /// `window` before the subscription, as measured by `clock`.
+      ///
+      /// The expired items are dropped both when an item is received and when
+      /// an observer subscribes.
+      pub fn with_time(size: usize, window: Duration, clock: C) -> Self {
+        Self {
+          subject: <_>::default(),
+          buffer: $rc::own(ReplayBuffer::new(size, Some(window), clock)),
+        }
+      }
+
+      /// Creates an empty subject replaying like this one.
+      pub(crate) fn empty_copy(&self) -> Self
+      where
+        C: Clone,
+      {
+        let buffer = self.buffer.rc_deref();
+        let ReplayBuffer { size, window, ref clock, .. } = *buffer;
+        Self {
+          subject: <_>::default(),
+          buffer: $rc::own(ReplayBuffer::new(size, window, clock.clone())),
+        }
+      }
+    }
+
+    impl<$($lf,)? Item, Err, C> Clone for $name<$($lf,)? Item, Err, C> {
+      fn clone(&self) -> Self {
+        Self { subject: self.subject.clone(), buffer: self.buffer.clone() }
+      }
+    }
+
+    impl<$($lf,)? Item, Err, C> Observer<Item, Err>
+      for $name<$($lf,)? Item, Err, C>
+    where
+      Item: Clone,
+      Err: Clone,
+      C: Fn() -> Instant,
+    {
+      fn next(&mut self, value: Item) {
+        {
+          let mut buffer = self.buffer.rc_deref_mut();
+          if buffer.terminal.is_some() {
+            return;
+          }
+          buffer.push(value.clone());
+        }
+        self.subject.next(value);
+      }
+
+      fn error(self, err: Err) {
+        self.buffer.rc_deref_mut().terminal = Some(Err(err.clone()));
+        self.subject.error(err);
+      }
+
+      fn complete(self) {
+        self.buffer.rc_deref_mut().terminal = Some(Ok(()));
+        self.subject.complete();
+      }
+
+      #[inline]
+      fn is_finished(&self) -> bool {
+        self.subject.is_finished()
+      }
+    }
+
+    impl<$($lf,)? Item, Err, C, O> Observable<Item, Err, O>
+      for $name<$($lf,)? Item, Err, C>
+    where
+      O: Observer<Item, Err> $(+ $lf)? $(+ $send + 'static)?,
+      Item: Clone $(+ $lf)? $(+ $send + 'static)?,
+      Err: Clone $(+ $lf)? $(+ $send + 'static)?,
+      C: Fn() -> Instant,
+    {
+      type Unsub = $subscriber<$rc<ReplayObserver<O, Item, Err>>>;
+
+      fn actual_subscribe(self, mut observer: O) -> Self::Unsub {
+        let mut buffer = self.buffer.rc_deref_mut();
+        let now = (buffer.clock)();
+        buffer.drop_expired(now);
+        let items: Vec<_> =
+          buffer.items.iter().map(|(_, v)| v.clone()).collect();
+        match buffer.terminal.clone() {
+          None => {
+            // Subscribed before any item can be received, so the items
+            // received since are queued and forwarded only after the replayed
+            // ones.
+            let replay = $rc::own(ReplayObserver::replaying());
+            let subscriber = self.subject.actual_subscribe(replay.clone());
+            drop(buffer);
+            replay.replay(observer, items, || subscriber.is_closed());
+            subscriber
+          }
+          Some(terminal) => {
+            drop(buffer);
+            for item in items {
+              observer.next(item);
+            }
+            match terminal {
+              Ok(()) => observer.complete(),
+              Err(err) => observer.error(err),
+            }
+            $subscriber::new(None)
+          }
+        }
+      }
+    }
+
+    impl<$($lf,)? Item, Err, C> ObservableExt<Item, Err>
+      for $name<$($lf,)? Item, Err, C>
+    {
+    }
+
+    impl<$($lf,)? Item, Err, C> Subscription for $name<$($lf,)? Item, Err, C> {
+      #[inline]
+      fn unsubscribe(self) {
+        self.subject.unsubscribe();
+      }
+
+      /// A terminated subject is not closed, it still replays to the new
+      /// observers.
+      fn is_closed(&self) -> bool {
+        self.buffer.rc_deref().terminal.is_none() && self.subject.is_closed()
+      }
+    }
+
+    impl<$($lf,)? Item, Err, C> SubjectSize for $name<$($lf,)? Item, Err, C> {
+      #[inline]
+      fn is_empty(&self) -> bool {
+        self.subject.is_empty()
+      }
+
+      #[inline]
+      fn len(&self) -> usize {
+        self.subject.len()
+      }
+    }
+  };
+}
+
+impl_replay_subject!(ReplaySubject, MutRc, Subscriber, 'a);
+impl_replay_subject!(ReplaySubjectThreads, MutArc, SubscriberThreads, Send);
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    prelude::*,
+    rc::{MutRc, RcDeref, RcDerefMut},
+  };
+  use std::{thread::sleep, time::Duration};
+
+  #[test]
+  fn replay_last_items() {
+    let mut values = vec![];
+    {
+      let mut subject = ReplaySubject::<i32, ()>::new(2);
+      subject.next(1);
+      subject.next(2);
+      subject.next(3);
+
+      subject
+        .clone()
+        .on_error(|_| {})
+        .subscribe(|v| values.push(v));
+      subject.next(4);
+    }
+
+    assert_eq!(values, vec![2, 3, 4]);
+  }
+
+  #[test]
+  fn replay_termination() {
+    let mut values = vec![];
+    let mut completed = false;
+    {
+      let mut subject = ReplaySubject::<i32, ()>::new(1);
+      subject.next(1);
+      subject.clone().complete();
+
+      subject
+        .on_complete(|| completed = true)
+        .on_error(|_| {})
+        .subscribe(|v| values.push(v));
+    }
+
+    assert_eq!(values, vec![1]);
+    assert!(completed);
+
+    let mut error = None;
+    let subject = ReplaySubject::<i32, &str>::new(1);
+    subject.clone().error("fail");
+    subject.on_error(|e| error = Some(e)).subscribe(|_| {});
+    assert_eq!(error, Some("fail"));
+  }
+
+  #[test]
+  fn drop_expired_items() {
+    let mut values = vec![];
+    let mut subject =
+      ReplaySubject::<i32, ()>::with_window(10, Duration::from_millis(20));
+    subject.next(1);
+    sleep(Duration::from_millis(40));
+    subject.next(2);
+
+    subject.on_error(|_| {}).subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![2]);
+  }
+
+  #[test]
+  fn drop_expired_items_by_clock() {
+    use crate::observable::fake_timer::FakeClock;
+
+    let (mut first, mut second) = (vec![], vec![]);
+    let clock = FakeClock::default();
+    let c_clock = clock.clone();
+    let mut subject = ReplaySubject::<i32, (), _>::with_time(
+      2,
+      Duration::from_millis(100),
+      move || c_clock.current_time(),
+    );
+    subject.next(1);
+    clock.advance(Duration::from_millis(60));
+    subject.next(2);
+    clock.advance(Duration::from_millis(60));
+    subject.next(3);
+
+    subject
+      .clone()
+      .on_error(|_| {})
+      .subscribe(|v| first.push(v));
+    clock.advance(Duration::from_millis(50));
+    subject.on_error(|_| {}).subscribe(|v| second.push(v));
+
+    assert_eq!(first, vec![2, 3]);
+    assert_eq!(second, vec![3]);
+  }
+
+  #[test]
+  fn renew_keep_clock() {
+    use crate::observable::fake_timer::FakeClock;
+
+    let clock = FakeClock::default();
+    let c_clock = clock.clone();
+    let mut values = vec![];
+    let mut subject = ReplaySubject::<i32, (), _>::with_time(
+      4,
+      Duration::from_millis(100),
+      move || c_clock.current_time(),
+    )
+    .empty_copy();
+    subject.next(1);
+    clock.advance(Duration::from_millis(150));
+    subject.next(2);
+    subject.on_error(|_| {}).subscribe(|v| values.push(v));
+
+    assert_eq!(values, vec![2]);
+  }
+
+  #[test]
+  fn emit_into_subject_while_replaying() {
+    let values = MutRc::own(vec![]);
+    let c_values = values.clone();
+    let mut subject = ReplaySubject::<_, ()>::new(2);
+    subject.next(1);
+    subject.next(2);
+    let mut c_subject = subject.clone();
+    subject.on_error(|_| {}).subscribe(move |v| {
+      c_values.rc_deref_mut().push(v);
+      if v == 1 {
+        c_subject.next(3);
+      }
+    });
+
+    // The item received while replaying is emitted after the replayed ones.
+    assert_eq!(&*values.rc_deref(), &[1, 2, 3]);
+  }
+
+  #[test]
+  fn threads_emit_into_subject_while_replaying() {
+    use std::sync::{Arc, Mutex};
+
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut subject = ReplaySubjectThreads::<_, ()>::new(2);
+    subject.next(1);
+    subject.next(2);
+    let mut c_subject = subject.clone();
+    subject.on_error(|_| {}).subscribe(move |v| {
+      c_values.lock().unwrap().push(v);
+      if v == 1 {
+        c_subject.next(3);
+      }
+    });
+
+    assert_eq!(*values.lock().unwrap(), [1, 2, 3]);
+  }
+
+  #[test]
+  fn threads_replay_in_order() {
+    use std::sync::{Arc, Mutex};
+
+    let mut subject = ReplaySubjectThreads::<_, ()>::new(1000);
+    let c_subject = subject.clone();
+    let handle = std::thread::spawn(move || {
+      (0..50)
+        .map(|_| {
+          let values = Arc::new(Mutex::new(vec![]));
+          let c_values = values.clone();
+          c_subject
+            .clone()
+            .on_error(|_| {})
+            .subscribe(move |v| c_values.lock().unwrap().push(v));
+          values
+        })
+        .collect::<Vec<_>>()
+    });
+    for i in 0..1000 {
+      subject.next(i);
+    }
+    let all_values = handle.join().unwrap();
+
+    let expected: Vec<_> = (0..1000).collect();
+    for values in all_values {
+      assert_eq!(*values.lock().unwrap(), expected);
+    }
+  }
+}
@@ -35,6 +35,14 @@ pub trait Publisher<Item, Err> {
   fn p_complete(self: Box<Self>);
   fn p_unsubscribe(self: Box<Self>);
   fn p_is_closed(&self) -> bool;
+  /// Returns `true` once unsubscribed, so a subject can drop it before the
+  /// next emission; unlike `p_is_closed`, it doesn't ask the observer if it's
+  /// finished, so it can be called while the observer is in use. Publishers
+  /// not tracking it are kept until they're closed.
+  #[inline]
+  fn p_is_unsubscribed(&self) -> bool {
+    false
+  }
 }
 
 macro_rules! impl_subscriber {
@@ -99,6 +107,10 @@ macro_rules! impl_subscriber {
       fn p_is_closed(&self) -> bool {
         self.is_finished() || self.is_closed()
       }
+      #[inline]
+      fn p_is_unsubscribed(&self) -> bool {
+        self.is_closed()
+      }
     }
 
     impl<O> Clone for $subscriber<O> {
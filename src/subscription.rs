@@ -102,9 +102,16 @@ macro_rules! impl_multi_subscription {
           vec.push(Some(v));
         }
       }
+      /// Drops all the teardowns without unsubscribing them, for the owner
+      /// that knows they are all finished.
+      pub fn clear(&mut self) {
+        if let Some(vec) = self.0.rc_deref_mut().as_mut() {
+          vec.clear();
+        }
+      }
       pub fn retain(&mut self) {
         if let Some(vec) = self.0.rc_deref_mut().as_mut() {
-          vec.retain(|v| v.is_some());
+          vec.retain(|v| v.as_ref().is_some_and(|u| !u.is_closed()));
         }
       }
     }
@@ -121,6 +128,20 @@ impl Subscription for () {
   }
 }
 
+impl<T: Subscription> Subscription for Option<T> {
+  #[inline]
+  fn unsubscribe(self) {
+    if let Some(u) = self {
+      u.unsubscribe()
+    }
+  }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.as_ref().is_none_or(|u| u.is_closed())
+  }
+}
+
 impl_multi_subscription!(MultiSubscription<'a>, BoxSubscription<'a>, 'a);
 impl_multi_subscription!(MultiSubscriptionThreads, BoxSubscriptionThreads);
 
@@ -205,6 +226,11 @@ impl<'a> Subscription for BoxSubscription<'a> {
   fn unsubscribe(self) {
     self.0.boxed_unsubscribe()
   }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.0.boxed_is_closed()
+  }
 }
 
 impl Subscription for BoxSubscriptionThreads {
@@ -212,6 +238,11 @@ impl Subscription for BoxSubscriptionThreads {
   fn unsubscribe(self) {
     self.0.boxed_unsubscribe()
   }
+
+  #[inline]
+  fn is_closed(&self) -> bool {
+    self.0.boxed_is_closed()
+  }
 }
 
 #[cfg(test)]